@@ -58,6 +58,12 @@ async fn main() -> std::io::Result<()> {
 
 	temp_logger.push(vec![], Some("*CONSOLE_WHITESPACE*"));
 
+	if let Err(error) =
+		pontus_onyx::http_server::upgrade_workspace(&workspace_path, &mut temp_logger)
+	{
+		return Err(std::io::Error::other(error));
+	}
+
 	let mut settings_path = workspace_path.clone();
 	settings_path.push("settings.toml");
 	let settings =
@@ -80,7 +86,17 @@ async fn main() -> std::io::Result<()> {
 		.unwrap()
 		.push(vec![], Some("*CONSOLE_WHITESPACE*"));
 
-	let database = pontus_onyx::http_server::load_or_create_database(&settings, logger.clone());
+	let database = pontus_onyx::http_server::load_or_create_database(
+		&settings,
+		&workspace_path,
+		logger.clone(),
+	);
+
+	if let Some(command) = std::env::args().nth(2) {
+		let arguments: Vec<String> = std::env::args().skip(3).collect();
+
+		return run_command(&command, &arguments, &settings, &database);
+	}
 
 	logger
 		.lock()
@@ -110,6 +126,14 @@ async fn main() -> std::io::Result<()> {
 		.unwrap()
 		.push(vec![], Some("*CONSOLE_WHITESPACE*"));
 
+	pontus_onyx::http_server::spawn_scrubbing(
+		settings.clone(),
+		database.clone(),
+		program_state.clone(),
+		logger.clone(),
+	);
+	pontus_onyx::http_server::spawn_trash_purging(database.clone(), logger.clone());
+
 	let workspace_path_for_event_loop = workspace_path.clone();
 	let (history_sender, history_receiver) =
 		std::sync::mpsc::channel::<pontus_onyx::http_server::DbEvent>();
@@ -241,6 +265,8 @@ async fn main() -> std::io::Result<()> {
 			.wrap(pontus_onyx::http_server::middlewares::Auth {
 				logger: logger_for_server.clone(),
 			})
+			.wrap(pontus_onyx::http_server::middlewares::WebAuthoring)
+			.wrap(pontus_onyx::http_server::middlewares::Cors)
 			.wrap(pontus_onyx::http_server::middlewares::Logger {
 				logger: logger_for_server.clone(),
 			})
@@ -275,6 +301,126 @@ async fn main() -> std::io::Result<()> {
 	}
 }
 
+/// Runs `command` (given after the workspace path) on `database`, instead of starting servers.
+fn run_command(
+	command: &str,
+	arguments: &[String],
+	settings: &pontus_onyx::http_server::Settings,
+	database: &pontus_onyx::database::Database,
+) -> std::io::Result<()> {
+	let to_io_error =
+		|error: pontus_onyx::database::DataSourceError| std::io::Error::other(format!("{}", error));
+
+	match (command, arguments) {
+		("export", [username, archive_path]) => {
+			let mut archive = std::io::BufWriter::new(std::fs::File::create(archive_path)?);
+			let count = database
+				.export_user(username, &mut archive)
+				.map_err(to_io_error)?;
+			archive.flush()?;
+
+			println!("{count} documents of {username} exported in {archive_path}");
+
+			return Ok(());
+		}
+		("import", [username, archive_path, options @ ..])
+			if options.iter().all(|option| option == "--regenerate-etags") =>
+		{
+			let etags = if options.is_empty() {
+				pontus_onyx::database::ImportEtags::Preserve
+			} else {
+				pontus_onyx::database::ImportEtags::Regenerate
+			};
+
+			let archive = std::io::BufReader::new(std::fs::File::open(archive_path)?);
+			let count = database
+				.import_user(username, archive, etags)
+				.map_err(to_io_error)?;
+
+			println!("{count} documents imported from {archive_path} for {username}");
+
+			return Ok(());
+		}
+		("migrate", [data_path, options @ ..])
+			if options.iter().all(|option| option == "--delete-extra") =>
+		{
+			let mode = if options.is_empty() {
+				pontus_onyx::database::MigrationDestination::Empty
+			} else {
+				pontus_onyx::database::MigrationDestination::DeleteExtra
+			};
+
+			let mut destination = pontus_onyx::database::sources::FolderStorage {
+				root_folder_path: std::path::PathBuf::from(data_path),
+			};
+			let migration = database
+				.migrate_to(&mut destination, mode)
+				.map_err(to_io_error)?;
+
+			println!(
+				"{} documents copied in {data_path}, {} already there, {} removed, all checked",
+				migration.copied, migration.unchanged, migration.removed
+			);
+
+			return Ok(());
+		}
+		("check", []) | ("repair", []) => {
+			let mut storage = pontus_onyx::database::sources::FolderStorage {
+				root_folder_path: std::path::PathBuf::from(settings.data_path.clone()),
+			};
+			let inconsistencies = storage.check().map_err(to_io_error)?;
+
+			for inconsistency in &inconsistencies {
+				println!("{inconsistency}");
+			}
+
+			if command == "repair" {
+				let remaining = storage.repair(&inconsistencies).map_err(to_io_error)?;
+
+				println!(
+					"{} inconsistencies repaired, {} should be fixed by hand",
+					inconsistencies.len() - remaining.len(),
+					remaining.len()
+				);
+			} else {
+				println!("{} inconsistencies found", inconsistencies.len());
+			}
+
+			return Ok(());
+		}
+		("scrub", []) => {
+			let storage = pontus_onyx::database::sources::FolderStorage {
+				root_folder_path: std::path::PathBuf::from(settings.data_path.clone()),
+			};
+			let damaged = storage.scrub().map_err(to_io_error)?;
+
+			for corrupted in &damaged {
+				println!("{corrupted}");
+			}
+
+			println!(
+				"{} damaged documents found, they should be restored from a backup",
+				damaged.len()
+			);
+
+			return Ok(());
+		}
+		_ => {
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				"usage : <workspace> export <username> <archive.tar>\n\
+				\t<workspace> import <username> <archive.tar> [--regenerate-etags]\n\
+				\t<workspace> migrate <data folder> [--delete-extra]\n\
+				\t\t(refuses a non-empty data folder, unless --delete-extra is given :\n\
+				\t\tthen its documents which are not in the workspace are deleted)\n\
+				\t<workspace> check\n\
+				\t<workspace> repair\n\
+				\t<workspace> scrub",
+			));
+		}
+	}
+}
+
 /*
 TODO ?
 	Servers MAY support Content-Range headers [RANGE] on GET requests,
@@ -302,17 +448,6 @@ TODO :
 	All responses MUST carry CORS headers [CORS].
 */
 /*
-TODO :
-	A "http://remotestorage.io/spec/web-authoring" property has been
-	proposed with a string value of the fully qualified domain name to
-	which web authoring content is published if the server supports web
-	authoring as per [AUTHORING]. Note that this extension is a breaking
-	extension in the sense that it divides users into "haves", whose
-	remoteStorage accounts allow them to author web content, and
-	"have-nots", whose remoteStorage account does not support this
-	functionality.
-*/
-/*
 TODO :
 	The server MAY expire bearer tokens, and MAY require the user to
 	register applications as OAuth clients before first use; if no
@@ -321,22 +456,3 @@ TODO :
 	redirect_uri parameter for unique client identification. See section
 	4 of [ORIGIN] for computing the origin.
 */
-/*
-TODO :
-	11. Storage-first bearer token issuance
-
-	To request that the application connects to the user account
-	<account> ' ' <host>, providers MAY redirect to applications with a
-	'remotestorage' field in the URL fragment, with the user account as
-	value.
-
-	The appplication MUST make sure this request is intended by the
-	user. It SHOULD ask for confirmation from the user whether they want
-	to connect to the given provider account. After confirmation, it
-	SHOULD connect to the given provider account, as defined in Section
-	10.
-
-	If the 'remotestorage' field exists in the URL fragment, the
-	application SHOULD ignore any other parameters such as
-	'access_token' or 'state'
-*/
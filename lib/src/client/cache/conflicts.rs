@@ -0,0 +1,307 @@
+/// A local change which can not be sent as is, because the remote document changed since the last synchronization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+	pub path: crate::item::ItemPath,
+	/// The version of the last synchronization, which both `local` and `remote` are based on.
+	///
+	/// `None` if the document did not exist, or if it was unknown by the [`Cache`][`super::Cache`].
+	pub base: Option<crate::item::Item>,
+	/// `None` if the document has been deleted locally.
+	pub local: Option<crate::item::Item>,
+	/// `None` if the document has been deleted on the remote.
+	pub remote: Option<crate::item::Item>,
+}
+
+/// How a [`Conflict`] should be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+	/// The local version replaces the remote one.
+	KeepLocal,
+	/// The remote version replaces the local one, and the local change is lost.
+	KeepRemote,
+	/// The remote version replaces the local one, and the local version is kept as a new document at `copy`.
+	KeepBoth { copy: crate::item::ItemPath },
+	/// This document replaces both versions.
+	Merged(crate::item::Item),
+}
+
+/// Decides how to solve each [`Conflict`] found by [`Cache::sync`][`super::Cache::sync`].
+///
+/// Closures like `|conflict: &Conflict| -> Resolution` can be used as custom resolvers.
+pub trait ConflictResolver {
+	fn resolve(&mut self, conflict: &Conflict) -> Resolution;
+}
+impl<F: FnMut(&Conflict) -> Resolution> ConflictResolver for F {
+	fn resolve(&mut self, conflict: &Conflict) -> Resolution {
+		return self(conflict);
+	}
+}
+
+/// Keeps the version which has been modified last, or the local one when it is unknown.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastWriterWins;
+impl ConflictResolver for LastWriterWins {
+	fn resolve(&mut self, conflict: &Conflict) -> Resolution {
+		return match (
+			last_modified_of(conflict.local.as_ref()),
+			last_modified_of(conflict.remote.as_ref()),
+		) {
+			(Some(local), Some(remote)) if remote > local => Resolution::KeepRemote,
+			_ => Resolution::KeepLocal,
+		};
+	}
+}
+
+/// Keeps the remote version, and saves the local one next to it, like `notes.conflict-<etag>.txt`.
+///
+/// If one of them has been deleted, the other one is kept.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeepBoth;
+impl ConflictResolver for KeepBoth {
+	fn resolve(&mut self, conflict: &Conflict) -> Resolution {
+		return match (&conflict.local, &conflict.remote) {
+			(Some(local), Some(_)) => match copy_path(&conflict.path, local.get_etag()) {
+				Some(copy) => Resolution::KeepBoth { copy },
+				None => Resolution::KeepLocal,
+			},
+			(Some(_), None) => Resolution::KeepLocal,
+			(None, _) => Resolution::KeepRemote,
+		};
+	}
+}
+
+/// `a/notes.txt` -> `a/notes.conflict-<etag>.txt`
+fn copy_path(
+	path: &crate::item::ItemPath,
+	etag: &crate::item::Etag,
+) -> Option<crate::item::ItemPath> {
+	let name = path.file_name();
+	let tag: String = etag
+		.opaque_tag()
+		.chars()
+		.filter(|c| c.is_ascii_alphanumeric())
+		.take(8)
+		.collect();
+
+	let copy_name = match name.rsplit_once('.') {
+		Some((stem, extension)) if !stem.is_empty() => {
+			format!("{stem}.conflict-{tag}.{extension}")
+		}
+		_ => format!("{name}.conflict-{tag}"),
+	};
+
+	return path.parent()?.joined_doc(&copy_name).ok();
+}
+
+fn last_modified_of(item: Option<&crate::item::Item>) -> Option<time::OffsetDateTime> {
+	return match item {
+		Some(crate::item::Item::Document { last_modified, .. }) => *last_modified,
+		_ => None,
+	};
+}
+
+/// Merges JSON documents with [`merge_json`], and gives other conflicts to `fallback`.
+pub struct JsonMerge {
+	fallback: Box<dyn ConflictResolver>,
+}
+impl JsonMerge {
+	pub fn new(fallback: impl ConflictResolver + 'static) -> Self {
+		return Self {
+			fallback: Box::new(fallback),
+		};
+	}
+}
+impl ConflictResolver for JsonMerge {
+	fn resolve(&mut self, conflict: &Conflict) -> Resolution {
+		if let (Some(base), Some(local), Some(remote)) = (
+			json_of(conflict.base.as_ref()),
+			json_of(conflict.local.as_ref()),
+			json_of(conflict.remote.as_ref()),
+		) {
+			if let Some(merged) = merge_json(&base, &local, &remote) {
+				if let Some(crate::item::Item::Document { content_type, .. }) = &conflict.local {
+					let content = serde_json::to_vec(&merged).unwrap();
+
+					return Resolution::Merged(crate::item::Item::Document {
+						etag: crate::item::Etag::from(""),
+						content_length: content.len() as u64,
+						content: Some(content),
+						content_type: content_type.clone(),
+						last_modified: Some(time::OffsetDateTime::now_utc()),
+					});
+				}
+			}
+		}
+
+		return self.fallback.resolve(conflict);
+	}
+}
+
+fn json_of(item: Option<&crate::item::Item>) -> Option<serde_json::Value> {
+	return match item {
+		Some(crate::item::Item::Document {
+			content: Some(content),
+			content_type,
+			..
+		}) if format!("{}", content_type).contains("json") => serde_json::from_slice(content).ok(),
+		_ => None,
+	};
+}
+
+/// Three-way merge of JSON values : changes of `local` and `remote` from `base` are both applied.
+///
+/// Objects are merged key by key, other values are only merged if at most one side changed them.
+/// Returns `None` if both sides changed the same value differently.
+pub fn merge_json(
+	base: &serde_json::Value,
+	local: &serde_json::Value,
+	remote: &serde_json::Value,
+) -> Option<serde_json::Value> {
+	return merge_json_value(Some(base), Some(local), Some(remote)).flatten();
+}
+
+/// `None` values are missing keys, and the returned `Some(None)` is a removed key.
+fn merge_json_value(
+	base: Option<&serde_json::Value>,
+	local: Option<&serde_json::Value>,
+	remote: Option<&serde_json::Value>,
+) -> Option<Option<serde_json::Value>> {
+	if local == remote || remote == base {
+		return Some(local.cloned());
+	} else if local == base {
+		return Some(remote.cloned());
+	}
+
+	if let (Some(serde_json::Value::Object(local)), Some(serde_json::Value::Object(remote))) =
+		(local, remote)
+	{
+		let empty = serde_json::Map::new();
+		let base = match base {
+			Some(serde_json::Value::Object(base)) => base,
+			_ => &empty,
+		};
+
+		let keys: std::collections::BTreeSet<&String> = base
+			.keys()
+			.chain(local.keys())
+			.chain(remote.keys())
+			.collect();
+
+		let mut result = serde_json::Map::new();
+		for key in keys {
+			if let Some(value) = merge_json_value(base.get(key), local.get(key), remote.get(key))? {
+				result.insert(key.clone(), value);
+			}
+		}
+
+		return Some(Some(serde_json::Value::Object(result)));
+	}
+
+	return None;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		copy_path, merge_json, Conflict, ConflictResolver, JsonMerge, KeepBoth, LastWriterWins,
+		Resolution,
+	};
+	use crate::item::{Etag, Item, ItemPath};
+
+	fn doc(content: &str, content_type: &str, seconds: i64) -> Item {
+		return Item::Document {
+			etag: Etag::from(format!("etag-{seconds}")),
+			content: Some(content.as_bytes().to_vec()),
+			content_length: content.len() as u64,
+			content_type: crate::item::ContentType::from(content_type),
+			last_modified: Some(time::OffsetDateTime::from_unix_timestamp(seconds).unwrap()),
+		};
+	}
+
+	#[test]
+	fn t5kq0xw8hz3mbn1rcv() {
+		let merge = |base: &str, local: &str, remote: &str| {
+			merge_json(
+				&serde_json::from_str(base).unwrap(),
+				&serde_json::from_str(local).unwrap(),
+				&serde_json::from_str(remote).unwrap(),
+			)
+		};
+
+		assert_eq!(
+			merge(
+				r#"{"a":1,"b":{"c":2,"d":3},"e":4}"#,
+				r#"{"a":10,"b":{"c":2,"d":3}}"#,
+				r#"{"a":1,"b":{"c":2,"d":30},"e":4,"f":5}"#
+			),
+			Some(serde_json::json!({"a":10,"b":{"c":2,"d":30},"f":5}))
+		);
+		assert_eq!(merge(r#"{"a":1}"#, r#"{"a":2}"#, r#"{"a":3}"#), None);
+		assert_eq!(
+			merge(r#"{"a":1}"#, r#"{"a":2}"#, r#"{"a":2}"#),
+			Some(serde_json::json!({"a":2}))
+		);
+		assert_eq!(merge("[1]", "[1,2]", "[1,3]"), None);
+		assert_eq!(
+			merge("[1]", "[1]", "[1,3]"),
+			Some(serde_json::json!([1, 3]))
+		);
+	}
+
+	#[test]
+	fn a9mz4hv1cx7ktq2wpe() {
+		let conflict = Conflict {
+			path: ItemPath::from("a/notes.json"),
+			base: Some(doc(r#"{"a":1,"b":1}"#, "application/json", 1)),
+			local: Some(doc(r#"{"a":2,"b":1}"#, "application/json", 3)),
+			remote: Some(doc(r#"{"a":1,"b":2}"#, "application/json", 2)),
+		};
+
+		assert_eq!(LastWriterWins.resolve(&conflict), Resolution::KeepLocal);
+		assert_eq!(
+			LastWriterWins.resolve(&Conflict {
+				remote: Some(doc("{}", "application/json", 4)),
+				..conflict.clone()
+			}),
+			Resolution::KeepRemote
+		);
+
+		assert_eq!(
+			KeepBoth.resolve(&conflict),
+			Resolution::KeepBoth {
+				copy: ItemPath::from("a/notes.conflict-etag3.json")
+			}
+		);
+		assert_eq!(
+			KeepBoth.resolve(&Conflict {
+				local: None,
+				..conflict.clone()
+			}),
+			Resolution::KeepRemote
+		);
+
+		match JsonMerge::new(KeepBoth).resolve(&conflict) {
+			Resolution::Merged(Item::Document { content, .. }) => {
+				assert_eq!(
+					serde_json::from_slice::<serde_json::Value>(&content.unwrap()).unwrap(),
+					serde_json::json!({"a":2,"b":2})
+				);
+			}
+			resolution => panic!("unexpected resolution : {:?}", resolution),
+		}
+		assert_eq!(
+			JsonMerge::new(|_: &Conflict| Resolution::KeepRemote).resolve(&Conflict {
+				base: Some(doc("a", "text/plain", 1)),
+				local: Some(doc("b", "text/plain", 3)),
+				remote: Some(doc("c", "text/plain", 2)),
+				..conflict.clone()
+			}),
+			Resolution::KeepRemote
+		);
+
+		assert_eq!(
+			copy_path(&ItemPath::from("notes"), &Etag::from("W/\"01-AB\"")),
+			Some(ItemPath::from("notes.conflict-01AB"))
+		);
+	}
+}
@@ -0,0 +1,29 @@
+/// The error returned by [`Cache::sync`][`super::Cache::sync`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncError {
+	/// The remote can not be reached, or refused a request : local changes are kept for the next synchronization.
+	Remote { error: crate::client::ClientError },
+	/// The local replica can not be read or written.
+	Local {
+		error: crate::database::DataSourceError,
+	},
+}
+impl std::fmt::Display for SyncError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		match self {
+			Self::Remote { error } => f.write_fmt(format_args!("remote error : {}", error)),
+			Self::Local { error } => f.write_fmt(format_args!("local error : {}", error)),
+		}
+	}
+}
+impl std::error::Error for SyncError {}
+impl From<crate::client::ClientError> for SyncError {
+	fn from(error: crate::client::ClientError) -> Self {
+		Self::Remote { error }
+	}
+}
+impl From<crate::database::DataSourceError> for SyncError {
+	fn from(error: crate::database::DataSourceError) -> Self {
+		Self::Local { error }
+	}
+}
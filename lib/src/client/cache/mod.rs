@@ -0,0 +1,593 @@
+mod conflicts;
+mod error;
+
+#[cfg(test)]
+#[cfg(feature = "actix_server")]
+mod tests;
+
+pub use conflicts::{
+	merge_json, Conflict, ConflictResolver, JsonMerge, KeepBoth, LastWriterWins, Resolution,
+};
+pub use error::SyncError;
+
+/// A boxed future, as returned by [`Remote`].
+///
+/// It is not `Send`, because the futures of the wasm client can not be.
+pub type RemoteFuture<'a, T> = std::pin::Pin<
+	Box<dyn std::future::Future<Output = Result<T, crate::client::ClientError>> + 'a>,
+>;
+
+/// The server side of a [`Cache`].
+pub trait Remote {
+	/// The `content` of returned folders only contains metadata of their children.
+	fn get<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Item>;
+
+	/// The `etag` of `document` is ignored : `conditions` are sent instead.
+	///
+	/// Returns the new [`Etag`][`crate::item::Etag`] of the document.
+	fn put<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		document: &'a crate::item::Item,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag>;
+
+	/// Returns the [`Etag`][`crate::item::Etag`] of the deleted document.
+	fn delete<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag>;
+}
+
+#[cfg(feature = "client_native")]
+impl Remote for crate::client::native::Client {
+	fn get<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Item> {
+		return Box::pin(crate::client::native::Client::get(
+			self,
+			path,
+			conditions.clone(),
+		));
+	}
+
+	fn put<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		document: &'a crate::item::Item,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag> {
+		return Box::pin(async move {
+			let document = self
+				.put_with_conditions(path, document, conditions.clone())
+				.await?;
+
+			Ok(document.get_etag().clone())
+		});
+	}
+
+	fn delete<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag> {
+		return Box::pin(crate::client::native::Client::delete(
+			self,
+			path,
+			conditions.clone(),
+		));
+	}
+}
+
+#[cfg(feature = "client")]
+impl Remote for crate::client::Client {
+	fn get<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Item> {
+		return Box::pin(self.get_async(path, conditions));
+	}
+
+	fn put<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		document: &'a crate::item::Item,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag> {
+		return Box::pin(async move {
+			let document = self.put_async(path, document, conditions).await?;
+
+			Ok(document.get_etag().clone())
+		});
+	}
+
+	fn delete<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag> {
+		return Box::pin(self.delete_async(path, conditions));
+	}
+}
+
+#[cfg(feature = "client")]
+impl Remote for crate::client::ClientRemote {
+	fn get<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Item> {
+		return Box::pin(self.get_async(path, conditions));
+	}
+
+	fn put<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		document: &'a crate::item::Item,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag> {
+		return Box::pin(async move {
+			let document = self.put_async(path, document, conditions).await?;
+
+			Ok(document.get_etag().clone())
+		});
+	}
+
+	fn delete<'a>(
+		&'a self,
+		path: &'a crate::item::ItemPath,
+		conditions: &'a crate::client::Conditions,
+	) -> RemoteFuture<'a, crate::item::Etag> {
+		return Box::pin(self.delete_async(path, conditions));
+	}
+}
+
+/// Called by [`Cache::sync`] for each [`Conflict`].
+pub type ConflictCallback = Box<dyn FnMut(&Conflict)>;
+
+/// What a [`Cache`] has to remember between two synchronizations.
+///
+/// It can be serialized, in order to keep changes made offline when the application restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SyncState {
+	/// The remote [`Etag`][`crate::item::Etag`] of each item, as found during the last synchronization.
+	pub remote_etags: std::collections::BTreeMap<String, crate::item::Etag>,
+	/// Paths of the documents changed locally which are not sent to the remote yet, oldest first.
+	pub outbox: Vec<String>,
+	/// The version of the last synchronization of each document in `outbox`, see [`Conflict::base`].
+	#[serde(default)]
+	pub bases: std::collections::BTreeMap<String, crate::item::Item>,
+}
+impl SyncState {
+	fn enqueue(&mut self, path: &crate::item::ItemPath) {
+		let path = path.to_string();
+
+		if !self.outbox.contains(&path) {
+			self.outbox.push(path);
+		}
+	}
+
+	fn dequeue(&mut self, path: &crate::item::ItemPath) {
+		let path = path.to_string();
+
+		self.outbox.retain(|pending| pending != &path);
+		self.bases.remove(&path);
+	}
+}
+
+/// What [`Cache::sync`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+	/// Local changes sent to the remote.
+	pub sent: Vec<crate::item::ItemPath>,
+	/// Remote documents created or updated in the local replica.
+	pub received: Vec<crate::item::ItemPath>,
+	/// Remote deletions applied to the local replica.
+	pub removed: Vec<crate::item::ItemPath>,
+	/// Local changes which were in conflict with the remote, see [`Conflict`].
+	pub conflicts: Vec<crate::item::ItemPath>,
+}
+
+/// A local replica of a [`Remote`] storage, which keeps working offline.
+///
+/// Reads and writes only use the `local` [`DataSource`][`crate::database::sources::DataSource`]
+/// (a [`LocalStorage`][`crate::database::LocalStorage`] in browsers, for example), and local
+/// changes are queued until [`sync`][`Cache::sync`] sends them to the remote. Then, changes of
+/// the remote are fetched, skipping folders which [`Etag`][`crate::item::Etag`] did not change
+/// since the last synchronization.
+///
+/// When the remote document changed since the last synchronization, the [`Conflict`] is given to
+/// the callback of [`with_conflict_callback`][`Cache::with_conflict_callback`], then solved by the
+/// [`ConflictResolver`] of [`with_conflict_resolver`][`Cache::with_conflict_resolver`], which keeps
+/// the local version by default.
+pub struct Cache<R: Remote> {
+	local: Box<dyn crate::database::sources::DataSource>,
+	remote: R,
+	state: SyncState,
+	on_conflict: Option<ConflictCallback>,
+	resolver: Box<dyn ConflictResolver>,
+}
+impl<R: Remote> Cache<R> {
+	pub fn new(local: Box<dyn crate::database::sources::DataSource>, remote: R) -> Self {
+		return Self {
+			local,
+			remote,
+			state: SyncState::default(),
+			on_conflict: None,
+			resolver: Box::new(|_: &Conflict| Resolution::KeepLocal),
+		};
+	}
+
+	/// Restores a state saved from [`state`][`Cache::state`], with the pending changes of a previous session.
+	pub fn with_state(mut self, state: SyncState) -> Self {
+		self.state = state;
+
+		return self;
+	}
+
+	pub fn with_conflict_callback(mut self, callback: impl FnMut(&Conflict) + 'static) -> Self {
+		self.on_conflict = Some(Box::new(callback));
+
+		return self;
+	}
+
+	pub fn with_conflict_resolver(mut self, resolver: impl ConflictResolver + 'static) -> Self {
+		self.resolver = Box::new(resolver);
+
+		return self;
+	}
+
+	pub fn state(&self) -> &SyncState {
+		return &self.state;
+	}
+
+	pub fn remote(&self) -> &R {
+		return &self.remote;
+	}
+
+	pub fn has_pending_changes(&self) -> bool {
+		return !self.state.outbox.is_empty();
+	}
+
+	pub fn get(
+		&self,
+		path: &crate::item::ItemPath,
+		get_content: bool,
+	) -> Result<crate::item::Item, crate::database::DataSourceError> {
+		return self.local.get(path, &[], &[], get_content);
+	}
+
+	/// Writes in the local replica, and queues the change for the next synchronization.
+	pub fn put(
+		&mut self,
+		path: &crate::item::ItemPath,
+		new_item: crate::item::Item,
+	) -> crate::database::PutResult {
+		self.save_base(path);
+		let result = self.local.put(path, &[], &[], new_item);
+
+		if !matches!(result, crate::database::PutResult::Err(_)) {
+			self.state.enqueue(path);
+		}
+
+		return result;
+	}
+
+	/// Deletes from the local replica, and queues the change for the next synchronization.
+	pub fn delete(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+		self.save_base(path);
+		let result = self.local.delete(path, &[]);
+
+		if result.is_ok() {
+			self.state.enqueue(path);
+		}
+
+		return result;
+	}
+
+	/// Keeps the synchronized version of a document before its first local change.
+	fn save_base(&mut self, path: &crate::item::ItemPath) {
+		let key = path.to_string();
+
+		if !self.state.outbox.contains(&key) && self.state.remote_etags.contains_key(&key) {
+			if let Ok(item) = self.local.get(path, &[], &[], true) {
+				self.state.bases.insert(key, item);
+			}
+		}
+	}
+
+	/// Sends queued changes under the `path` folder to the remote, then fetches the changes of the remote.
+	///
+	/// If the remote can not be reached, queued changes are kept for the next call.
+	pub async fn sync(&mut self, path: &crate::item::ItemPath) -> Result<SyncReport, SyncError> {
+		let mut report = SyncReport::default();
+
+		self.push(path, &mut report).await?;
+		self.pull(path, &mut report).await?;
+
+		return Ok(report);
+	}
+
+	async fn push(
+		&mut self,
+		path: &crate::item::ItemPath,
+		report: &mut SyncReport,
+	) -> Result<(), SyncError> {
+		let prefix = path.to_string();
+		let mut pending: std::collections::VecDeque<crate::item::ItemPath> = self
+			.state
+			.outbox
+			.iter()
+			.filter(|pending| pending.starts_with(&prefix))
+			.map(|pending| crate::item::ItemPath::from(pending.as_str()))
+			.collect();
+
+		while let Some(path) = pending.pop_front() {
+			let local = match self.local.get(&path, &[], &[], true) {
+				Ok(item) => Some(item),
+				Err(crate::database::DataSourceError::NotFound { .. }) => None,
+				Err(error) => return Err(SyncError::from(error)),
+			};
+			let known = self.state.remote_etags.get(&path.to_string()).cloned();
+
+			let mut sent = true;
+			let result = match self.send(&path, local.as_ref(), known.as_ref()).await {
+				Err(crate::client::ClientError::PreconditionFailed { .. }) => {
+					let remote = match self
+						.remote
+						.get(&path, &crate::client::Conditions::default())
+						.await
+					{
+						Ok(item) => Some(item),
+						Err(crate::client::ClientError::NotFound { .. }) => None,
+						Err(error) => return Err(SyncError::from(error)),
+					};
+
+					let conflict = Conflict {
+						path: path.clone(),
+						base: self.state.bases.get(&path.to_string()).cloned(),
+						local,
+						remote,
+					};
+					if let Some(on_conflict) = &mut self.on_conflict {
+						on_conflict(&conflict);
+					}
+					report.conflicts.push(path.clone());
+
+					let found = conflict
+						.remote
+						.as_ref()
+						.map(|remote| remote.get_etag().clone());
+
+					match self.resolver.resolve(&conflict) {
+						Resolution::KeepLocal => {
+							self.send(&path, conflict.local.as_ref(), found.as_ref())
+								.await
+						}
+						Resolution::Merged(merged) => {
+							self.write_local(&path, Some(merged.clone()))?;
+							self.send(&path, Some(&merged), found.as_ref()).await
+						}
+						Resolution::KeepRemote => {
+							sent = false;
+							self.write_local(&path, conflict.remote)?;
+							report.received.push(path.clone());
+
+							Ok(found)
+						}
+						Resolution::KeepBoth { copy } => {
+							if let Some(local) = conflict.local {
+								self.write_local(&copy, Some(local))?;
+								self.state.enqueue(&copy);
+								pending.push_back(copy);
+							}
+
+							sent = false;
+							self.write_local(&path, conflict.remote)?;
+							report.received.push(path.clone());
+
+							Ok(found)
+						}
+					}
+				}
+				result => result,
+			};
+
+			match result {
+				Ok(Some(etag)) => {
+					self.state.remote_etags.insert(path.to_string(), etag);
+				}
+				Ok(None) | Err(crate::client::ClientError::NotFound { .. }) => {
+					self.state.remote_etags.remove(&path.to_string());
+				}
+				Err(crate::client::ClientError::PreconditionFailed { .. }) => {
+					// the remote changed again in the meantime : retried by the next synchronization
+					continue;
+				}
+				Err(error) => return Err(SyncError::from(error)),
+			}
+
+			self.state.dequeue(&path);
+			if sent {
+				report.sent.push(path);
+			}
+		}
+
+		return Ok(());
+	}
+
+	/// Replaces the document of the local replica, or deletes it.
+	fn write_local(
+		&mut self,
+		path: &crate::item::ItemPath,
+		item: Option<crate::item::Item>,
+	) -> Result<(), SyncError> {
+		match item {
+			Some(item) => match self.local.put(path, &[], &[], item) {
+				crate::database::PutResult::Created(_, _)
+				| crate::database::PutResult::Updated(_, _)
+				// already up to date, like after an interrupted synchronization
+				| crate::database::PutResult::Err(crate::database::DataSourceError::ContentNotChanged) => {}
+				crate::database::PutResult::Err(error) => return Err(SyncError::from(error)),
+			},
+			None => match self.local.delete(path, &[]) {
+				Ok(_) | Err(crate::database::DataSourceError::NotFound { .. }) => {}
+				Err(error) => return Err(SyncError::from(error)),
+			},
+		}
+
+		return Ok(());
+	}
+
+	/// Sends the `local` version of a document, if the remote one is still `known`.
+	///
+	/// Returns the new remote ETag, or `None` if the document does not exists anymore.
+	async fn send(
+		&self,
+		path: &crate::item::ItemPath,
+		local: Option<&crate::item::Item>,
+		known: Option<&crate::item::Etag>,
+	) -> Result<Option<crate::item::Etag>, crate::client::ClientError> {
+		let conditions = match known {
+			Some(etag) => crate::client::Conditions::if_match(etag.clone()),
+			None => crate::client::Conditions::if_none_match(crate::item::Etag::from("*")),
+		};
+
+		return match (local, known) {
+			(Some(local), _) => self.remote.put(path, local, &conditions).await.map(Some),
+			(None, Some(_)) => self.remote.delete(path, &conditions).await.map(|_| None),
+			(None, None) => Ok(None),
+		};
+	}
+
+	async fn pull(
+		&mut self,
+		path: &crate::item::ItemPath,
+		report: &mut SyncReport,
+	) -> Result<(), SyncError> {
+		// folder ETags are only saved when the whole tree has been fetched
+		let mut folder_etags = vec![];
+		let mut listed_folders = vec![];
+		let mut found = std::collections::HashSet::new();
+
+		let mut folders = vec![path.folder_clone()];
+		while let Some(folder) = folders.pop() {
+			let known = self.state.remote_etags.get(&folder.to_string()).cloned();
+			let conditions = match &known {
+				Some(etag) => crate::client::Conditions::if_none_match(etag.clone()),
+				None => crate::client::Conditions::default(),
+			};
+
+			let (etag, content) = match self.remote.get(&folder, &conditions).await {
+				Ok(crate::item::Item::Folder { etag, content }) => {
+					(etag, content.unwrap_or_default())
+				}
+				Ok(crate::item::Item::Document { .. }) => continue,
+				Err(crate::client::ClientError::NotModified { .. }) => continue,
+				Err(crate::client::ClientError::NotFound { .. }) => (
+					crate::item::Etag::from(""),
+					std::collections::HashMap::new(),
+				),
+				Err(error) => return Err(SyncError::from(error)),
+			};
+
+			for (name, child) in content {
+				let name = name.trim_end_matches('/');
+
+				match *child {
+					crate::item::Item::Folder { etag, .. } => {
+						let child_path = folder.joined_folder(name).map_err(|error| {
+							crate::client::ClientError::InvalidResponse { error }
+						})?;
+
+						found.insert(child_path.to_string());
+						if self.state.remote_etags.get(&child_path.to_string()) != Some(&etag) {
+							folders.push(child_path);
+						}
+					}
+					crate::item::Item::Document { etag, .. } => {
+						let child_path = folder.joined_doc(name).map_err(|error| {
+							crate::client::ClientError::InvalidResponse { error }
+						})?;
+
+						found.insert(child_path.to_string());
+						if self.state.outbox.contains(&child_path.to_string())
+							|| self.state.remote_etags.get(&child_path.to_string()) == Some(&etag)
+						{
+							continue;
+						}
+
+						let document = self
+							.remote
+							.get(&child_path, &crate::client::Conditions::default())
+							.await?;
+						self.write_local(&child_path, Some(document))?;
+
+						self.state.remote_etags.insert(child_path.to_string(), etag);
+						report.received.push(child_path);
+					}
+				}
+			}
+
+			if etag.is_empty() {
+				self.state.remote_etags.remove(&folder.to_string());
+			} else {
+				folder_etags.push((folder.to_string(), etag));
+			}
+			listed_folders.push(folder.to_string());
+		}
+
+		// known children of listed folders which are not found anymore, with their own children
+		let removed: Vec<String> = self
+			.state
+			.remote_etags
+			.keys()
+			.filter(|known| {
+				!found.contains(*known)
+					&& !listed_folders.contains(known)
+					&& crate::item::ItemPath::from(known.as_str())
+						.parent()
+						.map(|parent| listed_folders.contains(&parent.to_string()))
+						.unwrap_or_default()
+			})
+			.cloned()
+			.collect();
+		let removed: Vec<String> = self
+			.state
+			.remote_etags
+			.keys()
+			.filter(|known| {
+				removed.iter().any(|removed| {
+					known == &removed
+						|| (removed.ends_with('/') && known.starts_with(removed.as_str()))
+				}) && !self.state.outbox.contains(known)
+			})
+			.cloned()
+			.collect();
+
+		for known in removed {
+			self.state.remote_etags.remove(&known);
+
+			let known = crate::item::ItemPath::from(known.as_str());
+			if known.is_document() {
+				self.write_local(&known, None)?;
+				report.removed.push(known);
+			}
+		}
+
+		self.state.remote_etags.extend(folder_etags);
+
+		return Ok(());
+	}
+}
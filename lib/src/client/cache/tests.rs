@@ -0,0 +1,445 @@
+use super::{Cache, Conflict, JsonMerge, KeepBoth, Remote, RemoteFuture, SyncError, SyncReport};
+use crate::client::{ClientError, Conditions};
+use crate::database::{DataSourceError, Database, MemoryStorage, PutResult};
+use crate::item::{Etag, Item, ItemPath};
+use std::{cell::RefCell, rc::Rc};
+
+/// A [`Remote`] which directly uses a [`Database`], and which can be put offline.
+struct TestRemote {
+	database: Database,
+	online: std::cell::Cell<bool>,
+}
+impl TestRemote {
+	fn new() -> Self {
+		return Self {
+			database: Database::new(Box::new(MemoryStorage {
+				root_item: Item::new_folder(vec![]),
+			})),
+			online: std::cell::Cell::new(true),
+		};
+	}
+
+	fn check_online(&self) -> Result<(), ClientError> {
+		if self.online.get() {
+			return Ok(());
+		} else {
+			return Err(ClientError::Transport {
+				error: String::from("offline"),
+			});
+		}
+	}
+}
+impl Remote for TestRemote {
+	fn get<'a>(&'a self, path: &'a ItemPath, conditions: &'a Conditions) -> RemoteFuture<'a, Item> {
+		let result = self.check_online().and_then(|_| {
+			self.database
+				.get(
+					path,
+					&conditions.if_match.iter().collect::<Vec<&Etag>>(),
+					&conditions.if_none_match.iter().collect::<Vec<&Etag>>(),
+				)
+				.map_err(|error| to_client_error("GET", path, error))
+		});
+
+		return Box::pin(async move { result });
+	}
+
+	fn put<'a>(
+		&'a self,
+		path: &'a ItemPath,
+		document: &'a Item,
+		conditions: &'a Conditions,
+	) -> RemoteFuture<'a, Etag> {
+		let result = self.check_online().and_then(|_| {
+			match self.database.put(
+				path,
+				document.clone(),
+				&conditions.if_match.iter().collect::<Vec<&Etag>>(),
+				&conditions.if_none_match.iter().collect::<Vec<&Etag>>(),
+			) {
+				PutResult::Created(etag, _) | PutResult::Updated(etag, _) => Ok(etag),
+				PutResult::Err(error) => Err(to_client_error("PUT", path, error)),
+			}
+		});
+
+		return Box::pin(async move { result });
+	}
+
+	fn delete<'a>(
+		&'a self,
+		path: &'a ItemPath,
+		conditions: &'a Conditions,
+	) -> RemoteFuture<'a, Etag> {
+		let result = self.check_online().and_then(|_| {
+			self.database
+				.delete(path, &conditions.if_match.iter().collect::<Vec<&Etag>>())
+				.map_err(|error| to_client_error("DELETE", path, error))
+		});
+
+		return Box::pin(async move { result });
+	}
+}
+
+/// Converts errors like the HTTP server does.
+fn to_client_error(method: &str, path: &ItemPath, error: DataSourceError) -> ClientError {
+	let status = match error {
+		DataSourceError::IfNoneMatch { .. } if method == "GET" => 304,
+		error => error.status_code().as_u16(),
+	};
+
+	return ClientError::check_status(method, &path.to_string(), status).unwrap_err();
+}
+
+fn new_cache() -> Cache<TestRemote> {
+	return Cache::new(
+		Box::new(MemoryStorage {
+			root_item: Item::new_folder(vec![]),
+		}),
+		TestRemote::new(),
+	);
+}
+
+fn content_of(item: Item) -> Vec<u8> {
+	match item {
+		Item::Document {
+			content: Some(content),
+			..
+		} => content,
+		item => panic!("unexpected item : {:?}", item),
+	}
+}
+
+#[actix_rt::test]
+async fn w7bq2kx9mz4tcv0nhe() {
+	let mut cache = new_cache();
+	cache
+		.remote()
+		.database
+		.put(
+			&ItemPath::from("a/b.txt"),
+			Item::new_doc(b"B", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap();
+
+	assert_eq!(
+		cache.sync(&ItemPath::from("")).await.unwrap().received,
+		vec![ItemPath::from("a/b.txt")]
+	);
+	assert_eq!(
+		content_of(cache.get(&ItemPath::from("a/b.txt"), true).unwrap()),
+		b"B".to_vec()
+	);
+
+	// nothing changed, so folders are not listed again
+	assert_eq!(
+		cache.sync(&ItemPath::from("")).await.unwrap(),
+		SyncReport::default()
+	);
+
+	// offline changes are kept until the remote can be reached
+	cache.remote().online.set(false);
+	cache
+		.put(
+			&ItemPath::from("a/c.txt"),
+			Item::new_doc(b"C", "text/plain"),
+		)
+		.unwrap();
+	assert!(matches!(
+		cache.sync(&ItemPath::from("")).await,
+		Err(SyncError::Remote {
+			error: ClientError::Transport { .. }
+		})
+	));
+	assert!(cache.has_pending_changes());
+
+	cache.remote().online.set(true);
+	assert_eq!(
+		cache.sync(&ItemPath::from("")).await.unwrap().sent,
+		vec![ItemPath::from("a/c.txt")]
+	);
+	assert!(!cache.has_pending_changes());
+	assert_eq!(
+		content_of(
+			cache
+				.remote()
+				.database
+				.get(&ItemPath::from("a/c.txt"), &[], &[])
+				.unwrap()
+		),
+		b"C".to_vec()
+	);
+
+	// changes of the remote
+	cache
+		.remote()
+		.database
+		.put(
+			&ItemPath::from("a/b.txt"),
+			Item::new_doc(b"B2", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap();
+	cache
+		.remote()
+		.database
+		.delete(&ItemPath::from("a/c.txt"), &[])
+		.unwrap();
+
+	let report = cache.sync(&ItemPath::from("")).await.unwrap();
+	assert_eq!(report.received, vec![ItemPath::from("a/b.txt")]);
+	assert_eq!(report.removed, vec![ItemPath::from("a/c.txt")]);
+	assert_eq!(
+		content_of(cache.get(&ItemPath::from("a/b.txt"), true).unwrap()),
+		b"B2".to_vec()
+	);
+	assert!(matches!(
+		cache.get(&ItemPath::from("a/c.txt"), true),
+		Err(DataSourceError::NotFound { .. })
+	));
+
+	// local deletion
+	cache.delete(&ItemPath::from("a/b.txt")).unwrap();
+	assert_eq!(
+		cache.sync(&ItemPath::from("")).await.unwrap().sent,
+		vec![ItemPath::from("a/b.txt")]
+	);
+	assert!(matches!(
+		cache
+			.remote()
+			.database
+			.get(&ItemPath::from("a/b.txt"), &[], &[]),
+		Err(DataSourceError::NotFound { .. })
+	));
+	assert!(!cache.state().remote_etags.contains_key("a/b.txt"));
+}
+
+#[actix_rt::test]
+async fn f1hx6rq3vn8yzk2mpw() {
+	let conflicts: Rc<RefCell<Vec<Conflict>>> = Rc::new(RefCell::new(vec![]));
+	let conflicts_for_callback = conflicts.clone();
+
+	let mut cache = new_cache().with_conflict_callback(move |conflict| {
+		conflicts_for_callback.borrow_mut().push(conflict.clone());
+	});
+
+	cache
+		.put(
+			&ItemPath::from("a/b.txt"),
+			Item::new_doc(b"first", "text/plain"),
+		)
+		.unwrap();
+	cache.sync(&ItemPath::from("")).await.unwrap();
+
+	cache
+		.remote()
+		.database
+		.put(
+			&ItemPath::from("a/b.txt"),
+			Item::new_doc(b"remote", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap();
+	cache
+		.put(
+			&ItemPath::from("a/b.txt"),
+			Item::new_doc(b"local", "text/plain"),
+		)
+		.unwrap();
+
+	let report = cache.sync(&ItemPath::from("")).await.unwrap();
+	assert_eq!(report.conflicts, vec![ItemPath::from("a/b.txt")]);
+	assert_eq!(report.sent, vec![ItemPath::from("a/b.txt")]);
+
+	let conflicts = conflicts.borrow();
+	assert_eq!(conflicts.len(), 1);
+	assert_eq!(conflicts[0].path, ItemPath::from("a/b.txt"));
+	assert_eq!(
+		content_of(conflicts[0].local.clone().unwrap()),
+		b"local".to_vec()
+	);
+	assert_eq!(
+		content_of(conflicts[0].remote.clone().unwrap()),
+		b"remote".to_vec()
+	);
+
+	// the local version wins
+	assert_eq!(
+		content_of(
+			cache
+				.remote()
+				.database
+				.get(&ItemPath::from("a/b.txt"), &[], &[])
+				.unwrap()
+		),
+		b"local".to_vec()
+	);
+}
+
+#[actix_rt::test]
+async fn k8rz3wq6mh1xvd0bny() {
+	let mut cache = new_cache().with_conflict_resolver(JsonMerge::new(KeepBoth));
+
+	cache
+		.put(
+			&ItemPath::from("a/n.json"),
+			Item::new_doc(br#"{"a":1,"b":1}"#, "application/json"),
+		)
+		.unwrap();
+	cache
+		.put(
+			&ItemPath::from("a/t.txt"),
+			Item::new_doc(b"first", "text/plain"),
+		)
+		.unwrap();
+	cache.sync(&ItemPath::from("")).await.unwrap();
+
+	for (path, remote, local) in [
+		("a/n.json", r#"{"a":1,"b":2}"#, r#"{"a":2,"b":1}"#),
+		("a/t.txt", "remote", "local"),
+	] {
+		let content_type = if path.ends_with(".json") {
+			"application/json"
+		} else {
+			"text/plain"
+		};
+
+		cache
+			.remote()
+			.database
+			.put(
+				&ItemPath::from(path),
+				Item::new_doc(remote.as_bytes(), content_type),
+				&[],
+				&[],
+			)
+			.unwrap();
+		cache
+			.put(
+				&ItemPath::from(path),
+				Item::new_doc(local.as_bytes(), content_type),
+			)
+			.unwrap();
+	}
+
+	let report = cache.sync(&ItemPath::from("")).await.unwrap();
+	assert_eq!(
+		report.conflicts,
+		vec![ItemPath::from("a/n.json"), ItemPath::from("a/t.txt")]
+	);
+	assert!(!cache.has_pending_changes());
+
+	// JSON documents are merged
+	for item in [
+		cache.get(&ItemPath::from("a/n.json"), true).unwrap(),
+		cache
+			.remote()
+			.database
+			.get(&ItemPath::from("a/n.json"), &[], &[])
+			.unwrap(),
+	] {
+		assert_eq!(
+			serde_json::from_slice::<serde_json::Value>(&content_of(item)).unwrap(),
+			serde_json::json!({"a":2,"b":2})
+		);
+	}
+
+	// other documents are kept both
+	let copy = cache
+		.state()
+		.remote_etags
+		.keys()
+		.find(|path| path.starts_with("a/t.conflict-") && path.ends_with(".txt"))
+		.cloned()
+		.unwrap();
+	assert_eq!(
+		content_of(cache.get(&ItemPath::from("a/t.txt"), true).unwrap()),
+		b"remote".to_vec()
+	);
+	assert_eq!(
+		content_of(cache.get(&ItemPath::from(copy.as_str()), true).unwrap()),
+		b"local".to_vec()
+	);
+	assert_eq!(
+		content_of(
+			cache
+				.remote()
+				.database
+				.get(&ItemPath::from(copy.as_str()), &[], &[])
+				.unwrap()
+		),
+		b"local".to_vec()
+	);
+}
+
+#[actix_rt::test]
+async fn r2mw7xk4tz9qbn6hvd() {
+	let mut cache = new_cache();
+
+	for path in ["a/note", "a/notes", "a/notes.d/c.txt"] {
+		cache
+			.remote()
+			.database
+			.put(
+				&ItemPath::from(path),
+				Item::new_doc(b"remote", "text/plain"),
+				&[],
+				&[],
+			)
+			.unwrap();
+	}
+	cache.sync(&ItemPath::from("")).await.unwrap();
+
+	cache
+		.remote()
+		.database
+		.delete(&ItemPath::from("a/note"), &[])
+		.unwrap();
+
+	let report = cache.sync(&ItemPath::from("")).await.unwrap();
+	assert_eq!(report.removed, vec![ItemPath::from("a/note")]);
+
+	assert!(cache.get(&ItemPath::from("a/note"), true).is_err());
+	for path in ["a/notes", "a/notes.d/c.txt"] {
+		assert_eq!(
+			content_of(cache.get(&ItemPath::from(path), true).unwrap()),
+			b"remote".to_vec()
+		);
+	}
+}
+
+#[actix_rt::test]
+async fn j5tq1vz8nk3rxm7wcb() {
+	// like after an interrupted synchronization : the document was written locally, but not its remote ETag
+	let mut cache = Cache::new(
+		Box::new(MemoryStorage {
+			root_item: Item::new_folder(vec![(
+				"a",
+				Item::new_folder(vec![("b.txt", Item::new_doc(b"same", "text/plain"))]),
+			)]),
+		}),
+		TestRemote::new(),
+	);
+	let etag = cache
+		.remote()
+		.database
+		.put(
+			&ItemPath::from("a/b.txt"),
+			Item::new_doc(b"same", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap()
+		.0;
+
+	let report = cache.sync(&ItemPath::from("")).await.unwrap();
+	assert_eq!(report.received, vec![ItemPath::from("a/b.txt")]);
+	assert_eq!(cache.state().remote_etags.get("a/b.txt"), Some(&etag));
+	assert_eq!(
+		content_of(cache.get(&ItemPath::from("a/b.txt"), true).unwrap()),
+		b"same".to_vec()
+	);
+}
@@ -0,0 +1,95 @@
+/// The conditional headers of a request, evaluated by the server against the current
+/// [`Etag`][`crate::item::Etag`] of the item.
+///
+/// An empty list means that the header is not sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Conditions {
+	/// The request is only applied if the current ETag is one of these.
+	pub if_match: Vec<crate::item::Etag>,
+	/// The request is only applied if the current ETag is none of these.
+	pub if_none_match: Vec<crate::item::Etag>,
+}
+impl Conditions {
+	pub fn if_match(etag: crate::item::Etag) -> Self {
+		return Self {
+			if_match: vec![etag],
+			if_none_match: vec![],
+		};
+	}
+
+	pub fn if_none_match(etag: crate::item::Etag) -> Self {
+		return Self {
+			if_match: vec![],
+			if_none_match: vec![etag],
+		};
+	}
+
+	/// The `(name, value)` of each header to send.
+	pub(super) fn headers(&self) -> Vec<(&'static str, String)> {
+		let mut result = vec![];
+
+		for (name, etags) in [
+			("If-Match", &self.if_match),
+			("If-None-Match", &self.if_none_match),
+		] {
+			let etags: Vec<String> = etags
+				.iter()
+				.filter(|etag| !etag.is_empty())
+				.map(entity_tag)
+				.collect();
+
+			if !etags.is_empty() {
+				result.push((name, etags.join(", ")));
+			}
+		}
+
+		return result;
+	}
+}
+impl From<Option<crate::item::Etag>> for Conditions {
+	fn from(etag: Option<crate::item::Etag>) -> Self {
+		return match etag {
+			Some(etag) => Self::if_match(etag),
+			None => Self::default(),
+		};
+	}
+}
+
+/// Formats an [`Etag`][`crate::item::Etag`] as an entity-tag of conditional headers.
+fn entity_tag(etag: &crate::item::Etag) -> String {
+	if etag.is_wildcard() {
+		return String::from("*");
+	} else if etag.is_weak() {
+		return format!("W/\"{}\"", etag.opaque_tag());
+	} else {
+		return format!("\"{}\"", etag.opaque_tag());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Conditions;
+	use crate::item::Etag;
+
+	#[test]
+	fn p3vx8kqz1mw6hj0tcd() {
+		assert_eq!(Conditions::default().headers(), vec![]);
+		assert_eq!(Conditions::from(None).headers(), vec![]);
+		assert_eq!(Conditions::if_match(Etag::from("")).headers(), vec![]);
+		assert_eq!(
+			Conditions::from(Some(Etag::from("A"))).headers(),
+			vec![("If-Match", String::from("\"A\""))]
+		);
+		assert_eq!(
+			Conditions {
+				if_match: vec![Etag::from("A"), Etag::from("W/B")],
+				if_none_match: vec![Etag::from("*")],
+			}
+			.headers(),
+			vec![
+				("If-Match", String::from("\"A\", W/\"B\"")),
+				("If-None-Match", String::from("*"))
+			]
+		);
+	}
+}
@@ -0,0 +1,144 @@
+/// The error returned by the typed methods of both wasm and native clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+	/// The request can not be sent, or its response can not be received.
+	Transport {
+		error: String,
+	},
+	/// The server answered with an unexpected status code.
+	UnexpectedStatus {
+		method: String,
+		uri: String,
+		status: u16,
+	},
+	/// The document or folder did not change since the ETag of `If-None-Match` (status code 304).
+	NotModified {
+		uri: String,
+	},
+	/// The condition of `If-Match` or `If-None-Match` is not met, it has been changed in the meantime for example (status code 412).
+	PreconditionFailed {
+		method: String,
+		uri: String,
+	},
+	NotFound {
+		uri: String,
+	},
+	Unauthorized {
+		uri: String,
+	},
+	MissingHeader {
+		name: &'static str,
+	},
+	InvalidResponse {
+		error: String,
+	},
+	WebfingerLinkNotFound,
+	OauthFormTokenNotFound,
+	/// The OAuth dialog of the server did not give an access token, because of wrong credentials, for example.
+	AuthorizationRefused {
+		reason: String,
+	},
+	DoesNotWorksForFolders,
+	NoContentInside,
+	/// The `ClientRemote` has no access token yet.
+	NotConnected,
+}
+impl ClientError {
+	/// Converts the status code of a response, if it is not a success.
+	pub(super) fn check_status(method: &str, uri: &str, status: u16) -> Result<(), Self> {
+		return match status {
+			200..=299 => Ok(()),
+			304 => Err(Self::NotModified {
+				uri: String::from(uri),
+			}),
+			412 => Err(Self::PreconditionFailed {
+				method: String::from(method),
+				uri: String::from(uri),
+			}),
+			401 | 403 => Err(Self::Unauthorized {
+				uri: String::from(uri),
+			}),
+			404 => Err(Self::NotFound {
+				uri: String::from(uri),
+			}),
+			status => Err(Self::UnexpectedStatus {
+				method: String::from(method),
+				uri: String::from(uri),
+				status,
+			}),
+		};
+	}
+}
+impl std::fmt::Display for ClientError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		match self {
+			Self::Transport { error } => {
+				f.write_fmt(format_args!("can not reach the server : {}", error))
+			}
+			Self::UnexpectedStatus {
+				method,
+				uri,
+				status,
+			} => f.write_fmt(format_args!("error {} when {} `{}`", status, method, uri)),
+			Self::NotModified { uri } => {
+				f.write_fmt(format_args!("`{}` has not been modified", uri))
+			}
+			Self::PreconditionFailed { method, uri } => f.write_fmt(format_args!(
+				"the conditions of {} `{}` are not met, it may have been changed in the meantime",
+				method, uri
+			)),
+			Self::NotFound { uri } => {
+				f.write_fmt(format_args!("`{}` does not exists yet in database", uri))
+			}
+			Self::Unauthorized { uri } => f.write_fmt(format_args!(
+				"the access token is not allowed to access to `{}`",
+				uri
+			)),
+			Self::MissingHeader { name } => f.write_fmt(format_args!(
+				"missing `{}` header from server response",
+				name
+			)),
+			Self::InvalidResponse { error } => {
+				f.write_fmt(format_args!("can not read server response : {}", error))
+			}
+			Self::WebfingerLinkNotFound => {
+				f.write_str("can not find `links` content in webfinger response of the server")
+			}
+			Self::OauthFormTokenNotFound => {
+				f.write_str("can not find the form token in the OAuth dialog of the server")
+			}
+			Self::AuthorizationRefused { reason } => f.write_fmt(format_args!(
+				"the server refused to give an access token : {}",
+				reason
+			)),
+			Self::DoesNotWorksForFolders => {
+				f.write_str("we can only put Item::Document to servers")
+			}
+			Self::NoContentInside => f.write_str("content of document is empty"),
+			Self::NotConnected => f.write_str("client is not connected"),
+		}
+	}
+}
+impl std::error::Error for ClientError {}
+#[cfg(feature = "client_native")]
+impl From<attohttpc::Error> for ClientError {
+	fn from(error: attohttpc::Error) -> Self {
+		Self::Transport {
+			error: format!("{}", error),
+		}
+	}
+}
+#[cfg(feature = "client")]
+impl From<wasm_bindgen::JsValue> for ClientError {
+	fn from(error: wasm_bindgen::JsValue) -> Self {
+		Self::Transport {
+			error: error.as_string().unwrap_or_else(|| format!("{:?}", error)),
+		}
+	}
+}
+#[cfg(feature = "client")]
+impl From<ClientError> for wasm_bindgen::JsValue {
+	fn from(error: ClientError) -> Self {
+		Self::from_str(&format!("{}", error))
+	}
+}
@@ -0,0 +1,523 @@
+/*
+TODO :
+	Clients SHOULD also handle the case where a response takes too long
+	to arrive, or where no response is received at all.
+*/
+
+pub use super::ClientError;
+
+#[cfg(test)]
+#[cfg(feature = "actix_server")]
+mod tests;
+
+use super::responses::{FolderResponse, WebfingerResponse};
+
+/// Finds a remoteStorage server through webfinger, and gets an access token from its OAuth dialog.
+///
+/// This is the native (non-wasm) version of the `client` feature : requests are sent
+/// from a dedicated thread, so the returned futures can be awaited from any async runtime.
+#[derive(Debug, Clone)]
+pub struct ClientRemote {
+	webfinger_root_uri: String,
+	username: String,
+	scope: crate::scope::Scope,
+	client_id: String,
+	pub debug: bool,
+}
+impl ClientRemote {
+	/// If `webfinger_root_uri` does not start with a scheme, `https://` is tried first, then `http://`.
+	pub async fn new(
+		webfinger_root_uri: impl Into<String>,
+		username: impl Into<String>,
+		scope: crate::scope::Scope,
+		client_id: impl Into<String>,
+		debug: bool,
+	) -> Self {
+		let webfinger_root_uri: String = webfinger_root_uri.into();
+
+		let webfinger_root_uri = if !webfinger_root_uri.starts_with("https://")
+			&& !webfinger_root_uri.starts_with("http://")
+		{
+			let https_uri = format!("https://{webfinger_root_uri}");
+
+			let https_uri_for_job = https_uri.clone();
+			let https_works =
+				blocking(move || Ok(attohttpc::head(&https_uri_for_job).send().is_ok()))
+					.await
+					.unwrap_or_default();
+
+			if https_works {
+				https_uri
+			} else {
+				format!("http://{webfinger_root_uri}")
+			}
+		} else {
+			webfinger_root_uri
+		};
+
+		return Self {
+			webfinger_root_uri,
+			username: username.into(),
+			scope,
+			client_id: client_id.into(),
+			debug,
+		};
+	}
+
+	/// Asks the server where the storage of the user is, and where its OAuth dialog is.
+	pub async fn discover(&self) -> Result<Discovery, ClientError> {
+		let webfinger_uri = String::from(
+			self.webfinger_root_uri
+				.strip_suffix('/')
+				.unwrap_or(&self.webfinger_root_uri),
+		);
+		let resource = format!("acct:{}@{}", self.username, host(&self.webfinger_root_uri));
+		let debug = self.debug;
+
+		let webfinger: WebfingerResponse = blocking(move || {
+			let uri = format!("{webfinger_uri}/.well-known/webfinger");
+
+			if debug {
+				println!("pontus-onyx-client-debug: trying to fetch GET {uri}");
+			}
+
+			let response = check_status(
+				"GET",
+				&uri,
+				attohttpc::get(&uri).param("resource", resource).send()?,
+			)?;
+
+			response
+				.json()
+				.map_err(|error| ClientError::InvalidResponse {
+					error: format!("{}", error),
+				})
+		})
+		.await?;
+
+		let link = webfinger
+			.links
+			.into_iter()
+			.next()
+			.ok_or(ClientError::WebfingerLinkNotFound)?;
+
+		if self.debug {
+			println!(
+				"pontus-onyx-client-debug: found server path in webfinger response : {}",
+				link.href
+			);
+		}
+
+		return Ok(Discovery {
+			storage_root: link.href,
+			auth_dialog: link.properties.target,
+		});
+	}
+
+	/// Gets an access token by submitting the credentials of the user to the OAuth dialog of the server.
+	///
+	/// The `client_id` is used as redirect URI, like the web clients do with their origin.
+	pub async fn connect(&self, password: impl Into<String>) -> Result<Client, ClientError> {
+		let discovery = self.discover().await?;
+
+		let password = password.into();
+		let username = self.username.clone();
+		let scope = format!("{}", self.scope);
+		let client_id = self.client_id.clone();
+		let auth_dialog = discovery.auth_dialog.clone();
+		let debug = self.debug;
+
+		let access_token = blocking(move || {
+			if debug {
+				println!("pontus-onyx-client-debug: trying to fetch GET {auth_dialog}");
+			}
+
+			let dialog = check_status(
+				"GET",
+				&auth_dialog,
+				attohttpc::get(&auth_dialog)
+					.param("redirect_uri", &client_id)
+					.param("scope", &scope)
+					.param("client_id", &client_id)
+					.param("response_type", "token")
+					.send()?,
+			)?
+			.text()?;
+
+			let form_token = regex::Regex::new(r#"name="token" value="([^"]*)""#)
+				.unwrap()
+				.captures(&dialog)
+				.and_then(|captures| captures.get(1))
+				.map(|form_token| String::from(form_token.as_str()))
+				.ok_or(ClientError::OauthFormTokenNotFound)?;
+
+			let origin = origin(&auth_dialog);
+			let uri = format!("{origin}/oauth");
+
+			if debug {
+				println!("pontus-onyx-client-debug: trying to POST credentials to {uri}");
+			}
+
+			let response = attohttpc::post(&uri)
+				.header("Origin", origin)
+				.follow_redirects(false)
+				.form(&[
+					("redirect_uri", client_id.as_str()),
+					("scope", scope.as_str()),
+					("client_id", client_id.as_str()),
+					("response_type", "token"),
+					("username", username.as_str()),
+					("password", password.as_str()),
+					("allow", "Allow"),
+					("token", form_token.as_str()),
+				])?
+				.send()?;
+
+			let location = header(&response, "Location")
+				.ok_or(ClientError::MissingHeader { name: "Location" })?;
+
+			match location.split_once("#access_token=") {
+				Some((_, fragment)) => {
+					let access_token = fragment.split('&').next().unwrap_or_default();
+
+					pct_str::PctString::new(access_token)
+						.map(|access_token| access_token.decode())
+						.map_err(|error| ClientError::InvalidResponse {
+							error: format!("{:?}", error),
+						})
+				}
+				None => Err(ClientError::AuthorizationRefused {
+					reason: location
+						.split_once("auth_result=")
+						.map(|(_, reason)| {
+							String::from(reason.split('&').next().unwrap_or_default())
+						})
+						.unwrap_or(location),
+				}),
+			}
+		})
+		.await?;
+
+		let mut client = Client::new(discovery.storage_root, access_token);
+		client.debug = self.debug;
+
+		return Ok(client);
+	}
+}
+
+/// Where a server stores the data of an user, as found by [`ClientRemote::discover`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discovery {
+	/// The root URI of the storage of the user, like `https://example.com/storage/user`.
+	pub storage_root: String,
+	/// The URI of the OAuth dialog of the server.
+	pub auth_dialog: String,
+}
+
+/// Access to the storage of an user, with an access token.
+#[derive(Debug, Clone)]
+pub struct Client {
+	server_path: String,
+	access_token: String,
+	pub debug: bool,
+}
+impl Client {
+	pub fn new(server_path: impl Into<String>, access_token: impl Into<String>) -> Self {
+		let server_path: String = server_path.into();
+		let server_path = if !server_path.ends_with('/') {
+			format!("{server_path}/")
+		} else {
+			server_path
+		};
+
+		return Self {
+			server_path,
+			access_token: access_token.into(),
+			debug: false,
+		};
+	}
+
+	/// Only returns metadata : its `content` is always `None`.
+	pub async fn head(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<crate::item::Item, ClientError> {
+		let request = self.request(attohttpc::Method::HEAD, path, &conditions.into())?;
+		let is_folder = path.is_folder();
+
+		return blocking(move || {
+			let response = request.send()?;
+			let etag = etag_of(&response)?;
+
+			if is_folder {
+				Ok(crate::item::Item::Folder {
+					etag,
+					content: None,
+				})
+			} else {
+				Ok(crate::item::Item::Document {
+					etag,
+					content: None,
+					content_type: content_type_of(&response)?,
+					content_length: content_length_of(&response),
+					last_modified: last_modified_of(&response),
+				})
+			}
+		})
+		.await;
+	}
+
+	/// The `content` of returned folders only contains metadata of their children.
+	pub async fn get(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<crate::item::Item, ClientError> {
+		let request = self.request(attohttpc::Method::GET, path, &conditions.into())?;
+		let is_folder = path.is_folder();
+
+		return blocking(move || {
+			let response = request.send()?;
+			let etag = etag_of(&response)?;
+
+			if is_folder {
+				let content: FolderResponse =
+					response
+						.json()
+						.map_err(|error| ClientError::InvalidResponse {
+							error: format!("{}", error),
+						})?;
+
+				Ok(crate::item::Item::Folder {
+					etag,
+					content: Some(content.into()),
+				})
+			} else {
+				let content_type = content_type_of(&response)?;
+				let last_modified = last_modified_of(&response);
+				let content = response.bytes()?;
+
+				Ok(crate::item::Item::Document {
+					etag,
+					content_length: content.len() as u64,
+					content: Some(content),
+					content_type,
+					last_modified,
+				})
+			}
+		})
+		.await;
+	}
+
+	/// If the `etag` of `document` is not empty, it is sent as `If-Match`.
+	///
+	/// The returned document contains the new `etag`, but not the content.
+	pub async fn put(
+		&self,
+		path: &crate::item::ItemPath,
+		document: &crate::item::Item,
+	) -> Result<crate::item::Item, ClientError> {
+		let conditions = crate::client::Conditions::if_match(document.get_etag().clone());
+
+		return self.put_with_conditions(path, document, conditions).await;
+	}
+
+	/// Like [`put`][`Client::put`], but the `etag` of `document` is ignored : `conditions` are sent instead.
+	pub async fn put_with_conditions(
+		&self,
+		path: &crate::item::ItemPath,
+		document: &crate::item::Item,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<crate::item::Item, ClientError> {
+		if let crate::item::Item::Document {
+			content_type,
+			content,
+			..
+		} = document
+		{
+			if let Some(content) = content {
+				let request = self
+					.request(attohttpc::Method::PUT, path, &conditions.into())?
+					.header("Content-Type", format!("{}", content_type))
+					.bytes(content.clone());
+				let content_type = content_type.clone();
+				let content_length = content.len() as u64;
+
+				return blocking(move || {
+					let response = request.send()?;
+
+					Ok(crate::item::Item::Document {
+						etag: etag_of(&response)?,
+						content: None,
+						content_type,
+						content_length,
+						last_modified: last_modified_of(&response),
+					})
+				})
+				.await;
+			} else {
+				return Err(ClientError::NoContentInside);
+			}
+		} else {
+			return Err(ClientError::DoesNotWorksForFolders);
+		}
+	}
+
+	/// Returns the [`Etag`][`crate::item::Etag`] of the deleted document.
+	pub async fn delete(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<crate::item::Etag, ClientError> {
+		if path.is_folder() {
+			return Err(ClientError::DoesNotWorksForFolders);
+		}
+
+		let request = self.request(attohttpc::Method::DELETE, path, &conditions.into())?;
+
+		return blocking(move || etag_of(&request.send()?)).await;
+	}
+
+	fn request(
+		&self,
+		method: attohttpc::Method,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<SentRequest, ClientError> {
+		let uri = format!("{}{}", self.server_path, path);
+
+		if self.debug {
+			println!("pontus-onyx-client-debug: trying to fetch {method} {uri}");
+		}
+
+		let mut builder = attohttpc::RequestBuilder::try_new(method.clone(), &uri)?
+			.bearer_auth(&self.access_token);
+		for (name, value) in conditions.headers() {
+			builder = builder.header(name, value);
+		}
+
+		return Ok(SentRequest {
+			method,
+			uri,
+			builder,
+		});
+	}
+}
+
+/// A request which checks the status code of its response.
+struct SentRequest<B = attohttpc::body::Empty> {
+	method: attohttpc::Method,
+	uri: String,
+	builder: attohttpc::RequestBuilder<B>,
+}
+impl SentRequest {
+	fn header(mut self, name: &'static str, value: String) -> Self {
+		self.builder = self.builder.header(name, value);
+
+		return self;
+	}
+
+	fn bytes(self, content: Vec<u8>) -> SentRequest<attohttpc::body::Bytes<Vec<u8>>> {
+		return SentRequest {
+			method: self.method,
+			uri: self.uri,
+			builder: self.builder.bytes(content),
+		};
+	}
+}
+impl<B: attohttpc::body::Body> SentRequest<B> {
+	fn send(self) -> Result<attohttpc::Response, ClientError> {
+		let response = self.builder.send()?;
+
+		return check_status(self.method.as_str(), &self.uri, response);
+	}
+}
+
+fn check_status(
+	method: &str,
+	uri: &str,
+	response: attohttpc::Response,
+) -> Result<attohttpc::Response, ClientError> {
+	ClientError::check_status(method, uri, response.status().as_u16())?;
+
+	return Ok(response);
+}
+
+fn header(response: &attohttpc::Response, name: &str) -> Option<String> {
+	return response
+		.headers()
+		.get(name)
+		.and_then(|value| value.to_str().ok())
+		.map(String::from);
+}
+
+fn etag_of(response: &attohttpc::Response) -> Result<crate::item::Etag, ClientError> {
+	return header(response, "ETag")
+		.map(crate::item::Etag::from)
+		.ok_or(ClientError::MissingHeader { name: "ETag" });
+}
+
+fn content_type_of(
+	response: &attohttpc::Response,
+) -> Result<crate::item::ContentType, ClientError> {
+	return header(response, "Content-Type")
+		.map(crate::item::ContentType::from)
+		.ok_or(ClientError::MissingHeader {
+			name: "Content-Type",
+		});
+}
+
+fn content_length_of(response: &attohttpc::Response) -> u64 {
+	return header(response, "Content-Length")
+		.and_then(|content_length| content_length.parse().ok())
+		.unwrap_or_default();
+}
+
+fn last_modified_of(response: &attohttpc::Response) -> Option<time::OffsetDateTime> {
+	return header(response, "Last-Modified").and_then(|last_modified| {
+		time::OffsetDateTime::parse(
+			&last_modified,
+			&time::format_description::well_known::Rfc2822,
+		)
+		.ok()
+	});
+}
+
+/// `https://example.com:8080/path` -> `https://example.com:8080`
+fn origin(uri: &str) -> &str {
+	let scheme_end = uri.find("://").map(|index| index + 3).unwrap_or_default();
+
+	return match uri[scheme_end..].find('/') {
+		Some(path_start) => &uri[..scheme_end + path_start],
+		None => uri,
+	};
+}
+
+/// `https://example.com:8080/path` -> `example.com`
+fn host(uri: &str) -> &str {
+	let origin = origin(uri);
+	let authority = &origin[origin
+		.find("://")
+		.map(|index| index + 3)
+		.unwrap_or_default()..];
+
+	return authority.split(':').next().unwrap_or(authority);
+}
+
+/// Runs a blocking request in a dedicated thread, so it does not block the async runtime.
+async fn blocking<T: Send + 'static>(
+	job: impl FnOnce() -> Result<T, ClientError> + Send + 'static,
+) -> Result<T, ClientError> {
+	let (sender, receiver) = futures::channel::oneshot::channel();
+
+	std::thread::spawn(move || {
+		sender.send(job()).ok();
+	});
+
+	return receiver.await.unwrap_or_else(|_| {
+		Err(ClientError::Transport {
+			error: String::from("the request has been cancelled"),
+		})
+	});
+}
@@ -0,0 +1,184 @@
+use super::{ClientError, ClientRemote};
+use crate::item::{Etag, Item, ItemPath};
+use std::sync::{Arc, Mutex};
+
+/// Starts a pontus_onyx server on a random port of `127.0.0.1`, with an user `user`.
+fn start_server(workspace_path: &std::path::Path) -> (actix_web::dev::ServerHandle, u16) {
+	let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+
+	let mut settings = crate::http_server::Settings::new(workspace_path.to_path_buf());
+	settings.domain = Some(String::from("127.0.0.1"));
+	settings.port = usize::from(port);
+	settings.oauth_wait_seconds = Some(0);
+	let settings = Arc::new(Mutex::new(settings));
+
+	let mut users = crate::http_server::Users::new();
+	users.insert("user", &mut String::from("password")).unwrap();
+	let users = Arc::new(Mutex::new(users));
+
+	let database = crate::database::Database::new(Box::new(crate::database::MemoryStorage {
+		root_item: Item::new_folder(vec![]),
+	}));
+	let access_tokens = Arc::new(Mutex::new(vec![]));
+	let oauth_form_tokens = Arc::new(Mutex::new(vec![]));
+	let program_state = Arc::new(Mutex::new(crate::http_server::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	}));
+	let logger = Arc::new(Mutex::new(charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::new(|_| {})),
+		None,
+	)));
+	let workspace_path = workspace_path.to_path_buf();
+	let (dbevent_sender, dbevent_receiver) = std::sync::mpsc::channel();
+	std::thread::spawn(move || for _ in dbevent_receiver {});
+
+	let server = actix_web::HttpServer::new(move || {
+		actix_web::App::new()
+			.wrap(crate::http_server::middlewares::Auth {
+				logger: logger.clone(),
+			})
+			.wrap(crate::http_server::middlewares::Cors)
+			.configure(crate::http_server::configure_server(
+				settings.clone(),
+				database.clone(),
+				access_tokens.clone(),
+				oauth_form_tokens.clone(),
+				users.clone(),
+				program_state.clone(),
+				logger.clone(),
+				&workspace_path,
+				Some(dbevent_sender.clone()),
+			))
+	})
+	.workers(1)
+	.listen(listener)
+	.unwrap()
+	.run();
+
+	let handle = server.handle();
+	actix_rt::spawn(server);
+
+	return (handle, port);
+}
+
+#[actix_rt::test]
+async fn g4nq7zx2wv8kbt1rml() {
+	let workspace = tempfile::tempdir().unwrap();
+	let (server, port) = start_server(workspace.path());
+	let server_uri = format!("http://127.0.0.1:{port}");
+
+	let remote = ClientRemote::new(
+		server_uri.clone(),
+		"user",
+		crate::scope::Scope {
+			module: String::from("test"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		},
+		server_uri.clone(),
+		false,
+	)
+	.await;
+
+	let discovery = remote.discover().await.unwrap();
+	assert_eq!(discovery.storage_root, format!("{server_uri}/storage/user"));
+	assert_eq!(discovery.auth_dialog, format!("{server_uri}/oauth/user"));
+
+	assert_eq!(
+		remote.connect("wrong password").await.unwrap_err(),
+		ClientError::AuthorizationRefused {
+			reason: String::from("wrong_credentials")
+		}
+	);
+
+	let client = remote.connect("password").await.unwrap();
+
+	let path = ItemPath::from("test/a.txt");
+
+	let mut document = Item::new_doc(b"A", "text/plain");
+	if let Item::Document { etag, .. } = &mut document {
+		*etag = Etag::from("");
+	}
+
+	let put = client.put(&path, &document).await.unwrap();
+	let etag = put.get_etag().clone();
+	assert!(!etag.is_empty());
+
+	match client.get(&path, None).await.unwrap() {
+		Item::Document {
+			etag: found_etag,
+			content,
+			content_type,
+			..
+		} => {
+			assert_eq!(found_etag, etag);
+			assert_eq!(content, Some(b"A".to_vec()));
+			assert_eq!(content_type, crate::item::ContentType::from("text/plain"));
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	assert!(matches!(
+		client
+			.get(
+				&path,
+				crate::client::Conditions::if_none_match(etag.clone())
+			)
+			.await
+			.unwrap_err(),
+		ClientError::NotModified { .. }
+	));
+
+	match client.head(&path, Some(etag.clone())).await.unwrap() {
+		Item::Document {
+			etag: found_etag,
+			content,
+			content_type,
+			..
+		} => {
+			assert_eq!(found_etag, etag);
+			assert_eq!(content, None);
+			assert_eq!(content_type, crate::item::ContentType::from("text/plain"));
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	match client.get(&ItemPath::from("test/"), None).await.unwrap() {
+		Item::Folder {
+			content: Some(content),
+			..
+		} => {
+			assert_eq!(content.get("a.txt").unwrap().get_etag(), &etag);
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	let mut outdated = Item::new_doc(b"B", "text/plain");
+	if let Item::Document { etag, .. } = &mut outdated {
+		*etag = Etag::from("outdated");
+	}
+	assert!(matches!(
+		client.put(&path, &outdated).await.unwrap_err(),
+		ClientError::PreconditionFailed { .. }
+	));
+
+	assert_eq!(
+		client.delete(&path, Some(etag)).await.unwrap(),
+		put.get_etag().clone()
+	);
+	assert!(matches!(
+		client.get(&path, None).await.unwrap_err(),
+		ClientError::NotFound { .. }
+	));
+
+	assert!(matches!(
+		super::Client::new(format!("{server_uri}/storage/user"), "wrong token")
+			.get(&path, None)
+			.await
+			.unwrap_err(),
+		ClientError::Unauthorized { .. }
+	));
+
+	server.stop(true).await;
+}
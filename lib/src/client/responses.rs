@@ -0,0 +1,66 @@
+#[derive(Debug, serde::Deserialize)]
+pub struct WebfingerResponse {
+	pub links: Vec<Link>,
+}
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct Link {
+	pub href: String,
+	pub properties: LinkProperties,
+}
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct LinkProperties {
+	#[serde(rename = "http://tools.ietf.org/html/rfc6749#section-4.2")]
+	pub target: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct FolderResponse {
+	pub items: std::collections::HashMap<String, FolderResponseItem>,
+}
+impl From<FolderResponse> for std::collections::HashMap<String, Box<crate::item::Item>> {
+	fn from(input: FolderResponse) -> Self {
+		input
+			.items
+			.into_iter()
+			.map(|(name, value)| (name, Box::new(value.into())))
+			.collect()
+	}
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct FolderResponseItem {
+	#[serde(rename = "ETag")]
+	pub etag: String,
+	#[serde(rename = "Content-Type")]
+	pub content_type: Option<String>,
+	#[serde(rename = "Content-Length")]
+	pub content_length: Option<u64>,
+	#[serde(rename = "Last-Modified")]
+	pub last_modified: Option<String>,
+}
+impl From<FolderResponseItem> for crate::item::Item {
+	fn from(input: FolderResponseItem) -> Self {
+		if let Some(content_type) = input.content_type {
+			let last_modified = input.last_modified.and_then(|last_modified_string| {
+				time::OffsetDateTime::parse(
+					&last_modified_string,
+					&time::format_description::well_known::Rfc2822,
+				)
+				.ok()
+			});
+
+			return Self::Document {
+				etag: crate::item::Etag::from(input.etag),
+				content: None,
+				content_type: crate::item::ContentType::from(content_type),
+				content_length: input.content_length.unwrap_or_default(),
+				last_modified,
+			};
+		}
+
+		return Self::Folder {
+			etag: crate::item::Etag::from(input.etag),
+			content: None,
+		};
+	}
+}
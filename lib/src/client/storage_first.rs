@@ -0,0 +1,90 @@
+/// An account given by a provider in the `remotestorage` field of the URL fragment of the app,
+/// like `#remotestorage=user@example.com` (section 11 of the draft).
+///
+/// The app should ask the user to confirm before connecting to this account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageFirstRequest {
+	pub username: String,
+	/// The host of the provider, optionally with its port, like `example.com:8080`.
+	pub host: String,
+}
+impl StorageFirstRequest {
+	/// Finds the `remotestorage` field in `fragment` (with or without the leading `#`).
+	///
+	/// Other fields, like `access_token` or `state`, are ignored.
+	pub fn from_fragment(fragment: &str) -> Option<Self> {
+		let fragment = fragment.strip_prefix('#').unwrap_or(fragment);
+
+		let account = fragment.split('&').find_map(|field| {
+			let (name, value) = field.split_once('=')?;
+
+			if name.trim() == "remotestorage" {
+				Some(pct_str::PctString::new(value.trim()).ok()?.decode())
+			} else {
+				None
+			}
+		})?;
+
+		let (username, host) = account.rsplit_once('@')?;
+		if username.is_empty() || host.is_empty() || host.contains('/') {
+			return None;
+		}
+
+		return Some(Self {
+			username: String::from(username),
+			host: String::from(host),
+		});
+	}
+}
+impl std::fmt::Display for StorageFirstRequest {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		f.write_fmt(format_args!("{}@{}", self.username, self.host))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::StorageFirstRequest;
+
+	#[test]
+	fn n6wq2hz8kx0bmv4tcr() {
+		let expected = Some(StorageFirstRequest {
+			username: String::from("user"),
+			host: String::from("example.com"),
+		});
+
+		assert_eq!(
+			StorageFirstRequest::from_fragment("#remotestorage=user@example.com"),
+			expected
+		);
+		assert_eq!(
+			StorageFirstRequest::from_fragment("remotestorage=user%40example.com"),
+			expected
+		);
+		assert_eq!(
+			StorageFirstRequest::from_fragment(
+				"#access_token=abc&remotestorage=user%40example.com&state=xyz"
+			),
+			expected
+		);
+		assert_eq!(
+			StorageFirstRequest::from_fragment("#remotestorage=user@localhost:8080")
+				.map(|request| request.to_string()),
+			Some(String::from("user@localhost:8080"))
+		);
+
+		assert_eq!(
+			StorageFirstRequest::from_fragment("#access_token=abc&token_type=bearer"),
+			None
+		);
+		assert_eq!(StorageFirstRequest::from_fragment(""), None);
+		assert_eq!(
+			StorageFirstRequest::from_fragment("#remotestorage=example.com"),
+			None
+		);
+		assert_eq!(
+			StorageFirstRequest::from_fragment("#remotestorage=@example.com"),
+			None
+		);
+	}
+}
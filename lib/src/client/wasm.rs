@@ -0,0 +1,1011 @@
+/*
+TODO :
+	Clients SHOULD also handle the case where a response takes too long
+	to arrive, or where no response is received at all.
+*/
+/*
+TODO :
+* <storage_root> example : 'https://example.com:8080/path/to/storage' (host, port and
+	path prefix; note there is no trailing slash)
+* <access_token> as per [OAUTH]. The token SHOULD be hard to
+	guess and SHOULD NOT be reused from one client to another. It
+	can however be reused in subsequent interactions with the same
+	client, as long as that client is still trusted. Example:
+	'ofb24f1ac3973e70j6vts19qr9v2eei'
+* <storage_api>, always 'draft-dejong-remotestorage-17' for this
+	alternative version of the specification.
+
+client request = <storage_root> with
+	'/' plus one or more <folder> '/' strings indicating a path in the
+	folder tree, followed by zero or one <document> strings
+*/
+
+/*
+TODO : disabled functionalities ?
+	- (rs.js) modules
+*/
+
+use super::responses::{FolderResponse, WebfingerResponse};
+use js_sys::Promise;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+lazy_static::lazy_static! {
+	static ref ACCESS_TOKEN_REGEX: regex::Regex = regex::Regex::new("^#.*access_token=([^&]+).+$").unwrap();
+}
+
+pub struct ClientRemote {
+	webfinger_root_uri: String,
+	username: String,
+	scope: crate::scope::Scope,
+	client_id: String,
+	pub debug: bool,
+	client: Option<Client>,
+}
+impl ClientRemote {
+	pub async fn new(
+		webfinger_root_uri: impl Into<String>,
+		username: impl Into<String>,
+		scope: crate::scope::Scope,
+		client_id: impl Into<String>,
+		debug: bool,
+	) -> Result<Self, JsValue> {
+		let webfinger_root_uri: String = webfinger_root_uri.into();
+		let username = username.into();
+		let client_id = client_id.into();
+
+		let webfinger_root_uri = if !webfinger_root_uri.starts_with("https://")
+			&& !webfinger_root_uri.starts_with("http://")
+		{
+			let mut opts = web_sys::RequestInit::new();
+			opts.method("HEAD");
+			opts.mode(web_sys::RequestMode::Cors);
+
+			let request = web_sys::Request::new_with_str_and_init(
+				&format!("https://{webfinger_root_uri}"),
+				&opts,
+			)?;
+
+			let window = web_sys::window().ok_or("window not found")?;
+
+			let resp_value =
+				wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request)).await;
+
+			if resp_value.is_ok() {
+				format!("https://{webfinger_root_uri}")
+			} else {
+				format!("http://{webfinger_root_uri}")
+			}
+		} else {
+			webfinger_root_uri
+		};
+
+		////////////////////////
+
+		let mut result = Self {
+			webfinger_root_uri,
+			username,
+			scope,
+			client_id,
+			debug,
+			client: None,
+		};
+
+		result.try_mount_saved_client().await?;
+
+		Ok(result)
+	}
+}
+impl ClientRemote {
+	pub fn head(
+		&self,
+		path: &crate::item::ItemPath, // TODO : replace with `impl AsRef<crate::item::ItemPath>` ?
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		match &self.client {
+			Some(client) => client.head(path, conditions),
+			None => Err(JsValue::from_str("client is not connected")),
+		}
+	}
+	pub fn get(
+		&self,
+		path: &crate::item::ItemPath, // TODO : replace with `impl AsRef<crate::item::ItemPath>` ?
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		match &self.client {
+			Some(client) => client.get(path, conditions),
+			None => Err(JsValue::from_str("client is not connected")),
+		}
+	}
+	pub fn put(
+		&self,
+		path: &crate::item::ItemPath, // TODO : replace with `impl AsRef<crate::item::ItemPath>` ?
+		document: &crate::item::Item,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		match &self.client {
+			Some(client) => client.put(path, document, conditions),
+			None => Err(JsValue::from_str("client is not connected")),
+		}
+	}
+	pub fn delete(
+		&self,
+		path: &crate::item::ItemPath, // TODO : replace with `impl AsRef<crate::item::ItemPath>` ?
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		match &self.client {
+			Some(client) => client.delete(path, conditions),
+			None => Err(JsValue::from_str("client is not connected")),
+		}
+	}
+}
+impl ClientRemote {
+	pub async fn head_async(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Item, crate::client::ClientError> {
+		match &self.client {
+			Some(client) => client.head_async(path, conditions).await,
+			None => Err(crate::client::ClientError::NotConnected),
+		}
+	}
+	pub async fn get_async(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Item, crate::client::ClientError> {
+		match &self.client {
+			Some(client) => client.get_async(path, conditions).await,
+			None => Err(crate::client::ClientError::NotConnected),
+		}
+	}
+	pub async fn put_async(
+		&self,
+		path: &crate::item::ItemPath,
+		document: &crate::item::Item,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Item, crate::client::ClientError> {
+		match &self.client {
+			Some(client) => client.put_async(path, document, conditions).await,
+			None => Err(crate::client::ClientError::NotConnected),
+		}
+	}
+	pub async fn delete_async(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Etag, crate::client::ClientError> {
+		match &self.client {
+			Some(client) => client.delete_async(path, conditions).await,
+			None => Err(crate::client::ClientError::NotConnected),
+		}
+	}
+}
+impl ClientRemote {
+	fn generate_cookie_name_header(&self) -> String {
+		let webfinger_root_uri_obj = self.webfinger_root_uri.parse::<http::uri::Uri>().unwrap();
+		let client_id_uri_obj = self.client_id.parse::<http::uri::Uri>().unwrap();
+
+		let cookie_name_header = format!(
+			"{}|{}|{}|{}|",
+			match client_id_uri_obj.port() {
+				Some(port) => format!("{}:{}", client_id_uri_obj.host().unwrap(), port),
+				None => String::from(client_id_uri_obj.host().unwrap()),
+			},
+			self.username,
+			match webfinger_root_uri_obj.port() {
+				Some(port) => format!("{}:{}", webfinger_root_uri_obj.host().unwrap(), port),
+				None => String::from(webfinger_root_uri_obj.host().unwrap()),
+			},
+			self.scope
+		);
+		let cookie_name_header =
+			pct_str::PctString::encode(cookie_name_header.chars(), pct_str::URIReserved);
+
+		cookie_name_header.to_string()
+	}
+	async fn try_get_webfinger_data(&self) -> Result<WebfingerResponse, JsValue> {
+		let mut opts = web_sys::RequestInit::new();
+		opts.method("GET");
+		opts.mode(web_sys::RequestMode::Cors);
+
+		let webfinger_uri = self
+			.webfinger_root_uri
+			.strip_suffix('/')
+			.unwrap_or(&self.webfinger_root_uri);
+
+		let webfinger_root_uri_obj = self.webfinger_root_uri.parse::<http::uri::Uri>().unwrap();
+
+		let url = format!(
+			"{webfinger_uri}/.well-known/webfinger?resource=acct:{}@{}",
+			self.username,
+			webfinger_root_uri_obj.host().unwrap()
+		);
+
+		let request = web_sys::Request::new_with_str_and_init(&url, &opts)?;
+
+		let window = web_sys::window().ok_or_else(|| JsValue::from_str("window not found"))?;
+
+		if self.debug {
+			web_sys::console::log_1(
+				&format!("pontus-onyx-client-debug: trying to fetch GET {url}").into(),
+			);
+		}
+
+		let resp_value =
+			wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request)).await?;
+		let resp: web_sys::Response = resp_value.dyn_into()?;
+		let json = wasm_bindgen_futures::JsFuture::from(resp.json()?).await?;
+		let response: WebfingerResponse = json.into_serde().unwrap();
+
+		Ok(response)
+	}
+	async fn try_mount_saved_client(&mut self) -> Result<bool, JsValue> {
+		let window = web_sys::window().ok_or_else(|| JsValue::from_str("window not found"))?;
+		let document = window
+			.document()
+			.ok_or_else(|| JsValue::from_str("document not found"))?;
+		let document = document
+			.dyn_ref::<web_sys::HtmlDocument>()
+			.ok_or_else(|| JsValue::from_str("document can not be casted into HtmlDocument"))?;
+
+		let hash = window.location().hash()?;
+
+		// tokens are ignored in storage-first requests, see `from_storage_first`
+		let access_token = if crate::client::StorageFirstRequest::from_fragment(&hash).is_none()
+			&& hash.contains("token_type")
+			&& ACCESS_TOKEN_REGEX.is_match(&hash)
+		{
+			if let Some(matches) = ACCESS_TOKEN_REGEX.captures_iter(&hash).next() {
+				matches.get(1).map(|access_token| {
+					let access_token = access_token.as_str();
+
+					if self.debug {
+						web_sys::console::log_1(&format!("pontus-onyx-client-debug: found token in URL hash : {access_token}").into());
+					}
+
+					pct_str::PctString::new(access_token)
+						.unwrap()
+						.decode()
+				})
+			} else {
+				None
+			}
+		} else {
+			None
+		};
+
+		let access_token = if cfg!(feature = "client_cookies") {
+			match access_token {
+				Some(access_token) => {
+					// hide token from URL
+					window.history()?.replace_state_with_url(
+						&String::new().into(),
+						"",
+						Some("/"),
+					)?;
+
+					if self.debug {
+						web_sys::console::log_1(
+							&format!(
+								"pontus-onyx-client-debug: trying to set cookie {}access_token",
+								self.generate_cookie_name_header()
+							)
+							.into(),
+						);
+					}
+
+					document
+						.set_cookie(&format!(
+							"{}access_token={}; Secure",
+							self.generate_cookie_name_header(),
+							pct_str::PctString::encode(access_token.chars(), pct_str::URIReserved)
+						))
+						.unwrap();
+
+					Some(access_token)
+				}
+				None => {
+					let document = window
+						.document()
+						.ok_or_else(|| JsValue::from_str("document not found"))?;
+					let document = document.dyn_ref::<web_sys::HtmlDocument>().unwrap();
+
+					let all_cookies = document.cookie()?;
+
+					let mut access_token = None;
+					for cookie in all_cookies.split(';') {
+						let mut iter = cookie.split('=');
+						let name = iter.next().map(str::trim);
+						let value = iter
+							.next()
+							.map(|res| pct_str::PctString::new(res.trim()).unwrap().decode());
+
+						if let Some(name) = name {
+							if name == format!("{}access_token", self.generate_cookie_name_header())
+							{
+								if self.debug {
+									web_sys::console::log_1(
+										&format!(
+											"pontus-onyx-client-debug: found cookie {}access_token",
+											self.generate_cookie_name_header()
+										)
+										.into(),
+									);
+								}
+
+								access_token = value;
+								break;
+							}
+						}
+					}
+
+					access_token
+				}
+			}
+		} else {
+			if self.debug {
+				web_sys::console::log_1(
+					&"pontus-onyx-client-debug: cookies storage disabled".into(),
+				);
+			}
+
+			access_token
+		};
+
+		match access_token {
+			Some(access_token) => {
+				let webfinger = self.try_get_webfinger_data().await?;
+
+				let server_path = webfinger.links.get(0).map(|link| link.href.clone());
+
+				match server_path {
+					Some(server_path) => {
+						let server_path = if !server_path.ends_with('/') {
+							format!("{server_path}/")
+						} else {
+							server_path
+						};
+
+						if self.debug {
+							web_sys::console::log_1(&format!("pontus-onyx-client-debug: found server path in webfinger response : {server_path}").into());
+						}
+
+						let mut opts = web_sys::RequestInit::new();
+						opts.method("HEAD");
+						opts.mode(web_sys::RequestMode::Cors);
+
+						let full_path = format!("{}{}/", server_path, self.scope.module);
+
+						if self.debug {
+							web_sys::console::log_1(
+								&format!(
+									"pontus-onyx-client-debug: trying to fetch HEAD {full_path}"
+								)
+								.into(),
+							);
+						}
+
+						let request =
+							web_sys::Request::new_with_str_and_init(&full_path, &opts).unwrap();
+						request
+							.headers()
+							.set("Authorization", &format!("Bearer {}", access_token))?;
+
+						let window = web_sys::window().ok_or("window not found")?;
+
+						let root_head = wasm_bindgen_futures::JsFuture::from(Promise::new(
+							&mut |resolve, reject| {
+								let debug = self.debug;
+								let full_path_for_main = full_path.clone();
+								let process_callback =
+									Closure::once(Box::new(move |resp: JsValue| {
+										let resp: web_sys::Response = resp.dyn_into().unwrap();
+
+										if resp.ok() {
+											if debug {
+												web_sys::console::log_1(&format!("pontus-onyx-client-debug: HEAD {full_path_for_main} response is OK").into());
+											}
+
+											resolve
+												.call1(&JsValue::NULL, &JsValue::from_bool(true))
+												.unwrap();
+										} else if resp.status() == 401 {
+											if debug {
+												web_sys::console::log_1(&format!("pontus-onyx-client-debug: HEAD {full_path_for_main} response returns 401 UNAUTHORIZED").into());
+											}
+
+											resolve
+												.call1(&JsValue::NULL, &JsValue::from_bool(false))
+												.unwrap();
+										} else {
+											if debug {
+												web_sys::console::log_1(&format!("pontus-onyx-client-debug: HEAD {full_path_for_main} response is NOT OK").into());
+											}
+
+											resolve
+												.call1(&JsValue::NULL, &JsValue::from_bool(true))
+												.unwrap();
+										}
+									}) as Box<dyn FnOnce(JsValue)>);
+
+								let err_callback = Closure::wrap(Box::new(move |err: JsValue| {
+									reject
+										.call1(&JsValue::NULL, &format!("{:?}", err).into())
+										.unwrap();
+								}) as Box<dyn FnMut(JsValue)>);
+
+								window
+									.fetch_with_request(&request)
+									.then(&process_callback)
+									.catch(&err_callback);
+
+								process_callback.forget();
+								err_callback.forget();
+							},
+						))
+						.await?;
+
+						let root_head = root_head.as_bool();
+
+						if let Some(true) = root_head {
+							self.client = Some(Client {
+								access_token,
+								server_path,
+								debug: self.debug,
+							});
+
+							Ok(true)
+						} else {
+							Ok(false)
+						}
+					}
+					None => Err(JsValue::from_str(
+						"can not find `links` content in webfinger reponse of the server",
+					)),
+				}
+			}
+			None => Ok(false),
+		}
+	}
+}
+impl ClientRemote {
+	pub async fn show_connect_overlay(
+		&self,
+		absolute_uri_handle: impl AsRef<str>,
+	) -> Result<(), JsValue> {
+		let absolute_uri_handle = absolute_uri_handle.as_ref();
+		let webfinger = self.try_get_webfinger_data().await?;
+
+		match webfinger.links.get(0) {
+			Some(link) => {
+				let window =
+					web_sys::window().ok_or_else(|| JsValue::from_str("window not found"))?;
+				let document = window
+					.document()
+					.ok_or_else(|| JsValue::from_str("document not found"))?;
+
+				let res = if document
+					.get_element_by_id("pontus_onyx_oauth_next_window")
+					.is_none()
+				{
+					let oauth_origin = link.properties.target.clone();
+					let oauth_path = self.oauth_path(&oauth_origin, absolute_uri_handle)?;
+
+					// location.set_href(&oauth_path).unwrap();
+
+					let next_window = document.create_element("div")?;
+					next_window.set_attribute("id", "pontus_onyx_oauth_next_window")?;
+					let next_window = next_window.dyn_ref::<web_sys::HtmlElement>().unwrap();
+
+					next_window
+						.style()
+						.set_property("border", "5px solid #FF4B03")?;
+					next_window.style().set_property("background", "white")?;
+					next_window.style().set_property("color", "black")?;
+					next_window.style().set_property("padding", "1em")?;
+					next_window.style().set_property("position", "absolute")?;
+					next_window.style().set_property("width", "66%")?;
+					next_window.style().set_property("left", "17%")?;
+					next_window.style().set_property("top", "30px")?;
+					next_window.style().set_property("opacity", "0.8")?;
+					next_window.style().set_property("display", "flex")?;
+					next_window
+						.style()
+						.set_property("flex-direction", "column")?;
+					next_window.style().set_property("align-items", "stretch")?;
+					next_window
+						.style()
+						.set_property("align-content", "stretch")?;
+					next_window.style().set_property("gap", "1em")?;
+
+					let abort = document.create_element("button")?;
+					let abort = abort.dyn_ref::<web_sys::HtmlElement>().unwrap();
+					abort.style().set_property("border", "2px solid #FF4B03")?;
+					abort.style().set_property("background", "white")?;
+					abort.style().set_property("cursor", "pointer")?;
+					abort.style().set_property("font-weight", "bold")?;
+					abort.style().set_property("padding", "1em 0em")?;
+					abort.style().set_property("color", "black")?;
+					abort.set_inner_html("❌ Abort");
+					let close_next_window =
+						wasm_bindgen::closure::Closure::wrap(Box::new(move || {
+							if let Some(window) = web_sys::window() {
+								if let Some(document) = window.document() {
+									if let Some(body) = document.body() {
+										if let Some(node) = document
+											.get_element_by_id("pontus_onyx_oauth_next_window")
+										{
+											body.remove_child(&node).ok();
+										}
+									}
+								}
+							}
+						}) as Box<dyn FnMut()>);
+					abort.set_onclick(Some(close_next_window.as_ref().unchecked_ref()));
+					close_next_window.forget();
+
+					next_window.append_child(abort)?;
+
+					let dummy = document.create_element("div")?;
+					dummy.set_inner_html(
+						&String::from_utf8(crate::assets::REMOTE_STORAGE.to_vec()).unwrap(),
+					);
+					let dummy = dummy.dyn_ref::<web_sys::HtmlElement>().unwrap();
+
+					let svg = dummy.first_element_child();
+					if let Some(svg) = svg {
+						svg.set_attribute("width", "100%")?;
+						svg.set_attribute("height", "75")?;
+						next_window.append_child(&svg)?;
+					}
+
+					let mut html = String::from("You will be temporary redirected to<br>\n");
+					html += &format!(r#"<a href="{}">{}</a><br>"#, oauth_path, oauth_origin);
+					html += "\nin order to authenticate<br>\n";
+					html += "on the requested server,<br>\n";
+					html += "and then bring back to this app<br>\n";
+					html += "with some credentials";
+
+					let explain = document.create_element("p")?;
+					let explain = explain.dyn_ref::<web_sys::HtmlElement>().unwrap();
+					explain.style().set_property("text-align", "center")?;
+					explain.style().set_property("color", "black")?;
+					explain.set_inner_html(&html);
+
+					next_window.append_child(explain)?;
+
+					let a_next = document.create_element("a")?;
+					let a_next = a_next.dyn_ref::<web_sys::HtmlElement>().unwrap();
+					a_next.set_attribute("href", &oauth_path)?;
+					a_next.style().set_property("display", "block")?;
+
+					let button_next = document.create_element("button")?;
+					let button_next = button_next.dyn_ref::<web_sys::HtmlElement>().unwrap();
+					button_next.set_inner_html("Next ➡️");
+					button_next
+						.style()
+						.set_property("border", "2px solid black")?;
+					button_next.style().set_property("background", "#FF4B03")?;
+					button_next.style().set_property("color", "black")?;
+					button_next.style().set_property("cursor", "pointer")?;
+					button_next.style().set_property("font-weight", "bold")?;
+					button_next.style().set_property("width", "100%")?;
+					button_next.style().set_property("padding", "1em 0em")?;
+					a_next.append_child(button_next)?;
+
+					next_window.append_child(a_next)?;
+
+					document.body().unwrap().append_child(next_window)?;
+
+					// TODO : automatic redirection ?
+
+					Ok(())
+				} else {
+					Err(JsValue::from_str(
+						"the id `pontus_onyx_oauth_next_window` already exists in the window so the overlay should be probably already displayed",
+					))
+				};
+
+				window.scroll_to_with_x_and_y(0.0, 0.0);
+
+				res
+			}
+			None => Err(JsValue::from_str(
+				"can not find `links` content in webfinger response of the server",
+			)),
+		}
+	}
+}
+impl ClientRemote {
+	/// Storage-first bearer token issuance (section 11 of the draft) : if the URL fragment contains a
+	/// `remotestorage` field (see [`StorageFirstRequest`][`crate::client::StorageFirstRequest`]),
+	/// asks the user to confirm, and then redirects to the authorization dialog of this account.
+	///
+	/// Returns `None` if there is no such field, or if the user refused.
+	pub async fn from_storage_first(
+		scope: crate::scope::Scope,
+		client_id: impl Into<String>,
+		absolute_uri_handle: impl AsRef<str>,
+		debug: bool,
+	) -> Result<Option<Self>, JsValue> {
+		let window = web_sys::window().ok_or_else(|| JsValue::from_str("window not found"))?;
+		let location = window.location();
+
+		match crate::client::StorageFirstRequest::from_fragment(&location.hash()?) {
+			Some(request) => {
+				if debug {
+					web_sys::console::log_1(
+						&format!(
+							"pontus-onyx-client-debug: found storage-first request for {request}"
+						)
+						.into(),
+					);
+				}
+
+				// the request should not be handled again
+				window.history()?.replace_state_with_url(
+					&JsValue::NULL,
+					"",
+					Some(&format!("{}{}", location.pathname()?, location.search()?)),
+				)?;
+
+				if !window.confirm_with_message(&format!(
+					"Do you want to connect this app to the account {request} ?"
+				))? {
+					return Ok(None);
+				}
+
+				let remote =
+					Self::new(request.host, request.username, scope, client_id, debug).await?;
+
+				let webfinger = remote.try_get_webfinger_data().await?;
+				match webfinger.links.get(0) {
+					Some(link) => {
+						location.set_href(
+							&remote.oauth_path(
+								&link.properties.target,
+								absolute_uri_handle.as_ref(),
+							)?,
+						)?;
+
+						Ok(Some(remote))
+					}
+					None => Err(JsValue::from_str(
+						"can not find `links` content in webfinger response of the server",
+					)),
+				}
+			}
+			None => Ok(None),
+		}
+	}
+	fn oauth_path(&self, oauth_origin: &str, absolute_uri_handle: &str) -> Result<String, JsValue> {
+		let location = web_sys::window()
+			.ok_or_else(|| JsValue::from_str("window not found"))?
+			.location();
+
+		Ok(format!(
+			"{oauth_origin}?redirect_uri={}&scope={}&client_id={}&response_type={}",
+			pct_str::PctString::encode(
+				format!(
+					"{}//{}{}",
+					location.protocol()?,
+					location.host()?,
+					absolute_uri_handle
+				)
+				.chars(),
+				pct_str::URIReserved
+			), // TODO : change to base url (no page name, or its arguments)
+			pct_str::PctString::encode(format!("{}", self.scope).chars(), pct_str::URIReserved),
+			pct_str::PctString::encode(self.client_id.chars(), pct_str::URIReserved),
+			pct_str::PctString::encode("token".chars(), pct_str::URIReserved),
+		))
+	}
+}
+impl ClientRemote {
+	pub fn is_connected(&self) -> bool {
+		self.client.is_some()
+	}
+}
+
+#[derive(Clone)]
+pub struct Client {
+	server_path: String,
+	access_token: String,
+	pub debug: bool,
+}
+impl Client {
+	/// Resolves with the [`Item`][`crate::item::Item`] returned by [`head_async`][`Client::head_async`].
+	pub fn head(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		let client = self.clone();
+		let path = path.clone();
+		let conditions = conditions.into();
+
+		Ok(wasm_bindgen_futures::future_to_promise(async move {
+			let item = client.head_async(&path, &conditions).await?;
+
+			Ok(JsValue::from_serde(&item).unwrap())
+		}))
+	}
+	/// Resolves with the [`Item`][`crate::item::Item`] returned by [`get_async`][`Client::get_async`].
+	pub fn get(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		let client = self.clone();
+		let path = path.clone();
+		let conditions = conditions.into();
+
+		Ok(wasm_bindgen_futures::future_to_promise(async move {
+			let item = client.get_async(&path, &conditions).await?;
+
+			Ok(JsValue::from_serde(&item).unwrap())
+		}))
+	}
+	/// If `conditions` are empty and the `etag` of `document` is not, it is sent as `If-Match`.
+	/// Use [`Conditions::if_none_match`][`crate::client::Conditions::if_none_match`] with `*` to only create it.
+	///
+	/// Resolves with the [`Item`][`crate::item::Item`] returned by [`put_async`][`Client::put_async`].
+	pub fn put(
+		&self,
+		path: &crate::item::ItemPath,
+		document: &crate::item::Item,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		let conditions = match (document, conditions.into()) {
+			(
+				crate::item::Item::Document {
+					content: Some(_),
+					etag,
+					..
+				},
+				conditions,
+			) => {
+				if conditions == crate::client::Conditions::default() {
+					crate::client::Conditions::if_match(etag.clone())
+				} else {
+					conditions
+				}
+			}
+			(crate::item::Item::Document { content: None, .. }, _) => {
+				return Err(crate::client::ClientError::NoContentInside.into())
+			}
+			(crate::item::Item::Folder { .. }, _) => {
+				return Err(crate::client::ClientError::DoesNotWorksForFolders.into())
+			}
+		};
+
+		let client = self.clone();
+		let path = path.clone();
+		let document = document.clone();
+
+		Ok(wasm_bindgen_futures::future_to_promise(async move {
+			let item = client.put_async(&path, &document, &conditions).await?;
+
+			Ok(JsValue::from_serde(&item).unwrap())
+		}))
+	}
+	/// Resolves with the [`Etag`][`crate::item::Etag`] of the deleted document, as a string.
+	pub fn delete(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: impl Into<crate::client::Conditions>,
+	) -> Result<Promise, JsValue> {
+		let client = self.clone();
+		let path = path.clone();
+		let conditions = conditions.into();
+
+		Ok(wasm_bindgen_futures::future_to_promise(async move {
+			let etag: String = client.delete_async(&path, &conditions).await?.into();
+
+			Ok(JsValue::from_str(&etag))
+		}))
+	}
+}
+impl Client {
+	/// Only returns metadata : its `content` is always `None`.
+	pub async fn head_async(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Item, crate::client::ClientError> {
+		let response = self.fetch("HEAD", path, conditions, None).await?;
+		let etag = etag_of(&response)?;
+
+		if path.is_folder() {
+			Ok(crate::item::Item::Folder {
+				etag,
+				content: None,
+			})
+		} else {
+			Ok(crate::item::Item::Document {
+				etag,
+				content: None,
+				content_type: content_type_of(&response)?,
+				content_length: content_length_of(&response),
+				last_modified: last_modified_of(&response),
+			})
+		}
+	}
+	/// The `content` of returned folders only contains metadata of their children.
+	pub async fn get_async(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Item, crate::client::ClientError> {
+		let response = self.fetch("GET", path, conditions, None).await?;
+		let etag = etag_of(&response)?;
+
+		let body = wasm_bindgen_futures::JsFuture::from(response.array_buffer()?).await?;
+		let body = js_sys::Uint8Array::new(&body).to_vec();
+
+		if path.is_folder() {
+			let content = serde_json::from_slice::<FolderResponse>(&body).map_err(|error| {
+				crate::client::ClientError::InvalidResponse {
+					error: format!("{}", error),
+				}
+			})?;
+
+			Ok(crate::item::Item::Folder {
+				etag,
+				content: Some(content.into()),
+			})
+		} else {
+			Ok(crate::item::Item::Document {
+				etag,
+				content_length: body.len() as u64,
+				content: Some(body),
+				content_type: content_type_of(&response)?,
+				last_modified: last_modified_of(&response),
+			})
+		}
+	}
+	/// The `etag` of `document` is ignored : use `conditions` instead.
+	///
+	/// Returns metadata of the new version of the document.
+	pub async fn put_async(
+		&self,
+		path: &crate::item::ItemPath,
+		document: &crate::item::Item,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Item, crate::client::ClientError> {
+		match document {
+			crate::item::Item::Document {
+				content: Some(content),
+				content_type,
+				..
+			} => {
+				let response = self
+					.fetch("PUT", path, conditions, Some((content, content_type)))
+					.await?;
+
+				Ok(crate::item::Item::Document {
+					etag: etag_of(&response)?,
+					content: None,
+					content_type: content_type.clone(),
+					content_length: content.len() as u64,
+					last_modified: last_modified_of(&response),
+				})
+			}
+			crate::item::Item::Document { content: None, .. } => {
+				Err(crate::client::ClientError::NoContentInside)
+			}
+			crate::item::Item::Folder { .. } => {
+				Err(crate::client::ClientError::DoesNotWorksForFolders)
+			}
+		}
+	}
+	/// Returns the [`Etag`][`crate::item::Etag`] of the deleted document.
+	pub async fn delete_async(
+		&self,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+	) -> Result<crate::item::Etag, crate::client::ClientError> {
+		if path.is_folder() {
+			return Err(crate::client::ClientError::DoesNotWorksForFolders);
+		}
+
+		let response = self.fetch("DELETE", path, conditions, None).await?;
+
+		etag_of(&response)
+	}
+
+	async fn fetch(
+		&self,
+		method: &str,
+		path: &crate::item::ItemPath,
+		conditions: &crate::client::Conditions,
+		body: Option<(&[u8], &crate::item::ContentType)>,
+	) -> Result<web_sys::Response, crate::client::ClientError> {
+		let mut opts = web_sys::RequestInit::new();
+		opts.method(method);
+		opts.mode(web_sys::RequestMode::Cors);
+		if let Some((content, _)) = body {
+			opts.body(Some(&js_sys::Uint8Array::from(content)));
+		}
+
+		let full_path = format!("{}{}", self.server_path, path);
+
+		let request = web_sys::Request::new_with_str_and_init(&full_path, &opts)?;
+		request
+			.headers()
+			.set("Authorization", &format!("Bearer {}", self.access_token))?;
+		if let Some((_, content_type)) = body {
+			request
+				.headers()
+				.set("Content-Type", &format!("{}", content_type))?;
+		}
+		for (name, value) in conditions.headers() {
+			request.headers().set(name, &value)?;
+		}
+
+		let window = web_sys::window().ok_or(crate::client::ClientError::Transport {
+			error: String::from("window not found"),
+		})?;
+
+		if self.debug {
+			web_sys::console::log_1(
+				&format!("pontus-onyx-client-debug: trying to fetch {method} {full_path}").into(),
+			);
+		}
+
+		let response: web_sys::Response =
+			wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
+				.await?
+				.dyn_into()?;
+
+		crate::client::ClientError::check_status(method, &full_path, response.status())?;
+
+		if self.debug {
+			web_sys::console::log_1(
+				&format!(
+					"pontus-onyx-client-debug: server response for {method} {full_path} is OK"
+				)
+				.into(),
+			);
+		}
+
+		Ok(response)
+	}
+}
+
+fn header(response: &web_sys::Response, name: &'static str) -> Option<String> {
+	response.headers().get(name).ok().flatten()
+}
+
+fn etag_of(response: &web_sys::Response) -> Result<crate::item::Etag, crate::client::ClientError> {
+	header(response, "ETag")
+		.map(crate::item::Etag::from)
+		.ok_or(crate::client::ClientError::MissingHeader { name: "ETag" })
+}
+
+fn content_type_of(
+	response: &web_sys::Response,
+) -> Result<crate::item::ContentType, crate::client::ClientError> {
+	header(response, "Content-Type")
+		.map(crate::item::ContentType::from)
+		.ok_or(crate::client::ClientError::MissingHeader {
+			name: "Content-Type",
+		})
+}
+
+fn content_length_of(response: &web_sys::Response) -> u64 {
+	header(response, "Content-Length")
+		.and_then(|content_length| content_length.parse().ok())
+		.unwrap_or_default()
+}
+
+fn last_modified_of(response: &web_sys::Response) -> Option<time::OffsetDateTime> {
+	header(response, "Last-Modified").and_then(|last_modified| {
+		time::OffsetDateTime::parse(
+			&last_modified,
+			&time::format_description::well_known::Rfc2822,
+		)
+		.ok()
+	})
+}
@@ -0,0 +1,328 @@
+/// How [`Database::import_user`][`crate::database::Database::import_user`] gives ETags to imported documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportEtags {
+	/// Documents keep the ETags they had when they were exported, so clients see them as unchanged.
+	///
+	/// With [`EtagStrategy::ContentHash`][`crate::database::EtagStrategy::ContentHash`],
+	/// ETags are derived from content anyway.
+	#[default]
+	Preserve,
+	/// Documents get new ETags, like any other put, so clients download them again.
+	Regenerate,
+}
+
+/// The archive folder of documents in `/storage/<user>/`.
+const PRIVATE_FOLDER: &str = "storage";
+/// The archive folder of documents in `/storage/public/<user>/`.
+const PUBLIC_FOLDER: &str = "public";
+
+/// Writes all documents of `username` in `writer`, see [`Database::export_user`][`crate::database::Database::export_user`].
+pub(crate) fn export_user<S>(
+	source: &S,
+	username: &str,
+	writer: impl std::io::Write,
+) -> Result<usize, crate::database::DataSourceError>
+where
+	S: crate::database::sources::DataSource + ?Sized,
+{
+	check_username(username)?;
+
+	let mut builder = tar::Builder::new(writer);
+	let mut count = 0;
+
+	for (archive_folder, user_folder) in user_folders(username) {
+		let documents =
+			match source.documents_in(&crate::item::ItemPath::from(user_folder.as_str())) {
+				Ok(documents) => documents,
+				Err(crate::database::DataSourceError::NotFound { .. }) => vec![],
+				Err(error) => return Err(error),
+			};
+
+		for path in documents {
+			let (etag, content, content_type, last_modified) =
+				match source.get(&path, &[], &[], true)? {
+					crate::item::Item::Document {
+						etag,
+						content: Some(content),
+						content_type,
+						last_modified,
+						..
+					} => (etag, content, content_type, last_modified),
+					_ => {
+						return Err(crate::database::DataSourceError::NoContentInside {
+							item_path: path,
+						})
+					}
+				};
+
+			let archive_path = format!(
+				"{}/{}",
+				archive_folder,
+				path.to_string()
+					.strip_prefix(user_folder.as_str())
+					.unwrap_or_default()
+			);
+
+			let itemdata = crate::item::DataDocument {
+				datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
+				etag,
+				content_type,
+				content_length: Some(content.len() as u64),
+				checksum: Some(crate::item::DataDocument::checksum_of(&content)),
+				last_modified,
+			};
+			let itemdata = toml::to_vec(&itemdata).map_err(internal_error)?;
+
+			let mtime = last_modified
+				.map(|last_modified| last_modified.unix_timestamp().max(0) as u64)
+				.unwrap_or_default();
+
+			append(
+				&mut builder,
+				&itemdata_path(&archive_path),
+				&itemdata,
+				mtime,
+			)?;
+			append(&mut builder, &archive_path, &content, mtime)?;
+
+			count += 1;
+		}
+	}
+
+	builder.into_inner().map_err(internal_error)?;
+
+	return Ok(count);
+}
+
+/// Puts all documents of the archive read from `reader` in the storage of `username`,
+/// see [`Database::import_user`][`crate::database::Database::import_user`].
+pub(crate) fn import_user(
+	source: &mut dyn crate::database::sources::DataSource,
+	etag_strategy: crate::database::EtagStrategy,
+	username: &str,
+	reader: impl std::io::Read,
+	etags: ImportEtags,
+) -> Result<usize, crate::database::DataSourceError> {
+	check_username(username)?;
+
+	// a broken archive is refused before the first put, so it does not leave half of its documents
+	let documents = read_archive(username, reader)?;
+	let mut count = 0;
+
+	for (path, itemdata, content) in documents {
+		let item = crate::item::Item::Document {
+			etag: itemdata.etag.clone(),
+			content_length: content.len() as u64,
+			content: Some(content),
+			content_type: itemdata.content_type,
+			last_modified: itemdata.last_modified,
+		};
+
+		let new_etag = match super::put_with_strategy(source, etag_strategy, &path, &[], &[], item)
+		{
+			crate::database::PutResult::Created(etag, _)
+			| crate::database::PutResult::Updated(etag, _) => etag,
+			crate::database::PutResult::Err(error) => return Err(error),
+		};
+
+		let etag = if etags == ImportEtags::Preserve
+			&& etag_strategy != crate::database::EtagStrategy::ContentHash
+		{
+			itemdata.etag
+		} else {
+			new_etag
+		};
+		source.set_metadata(&path, &etag, itemdata.last_modified)?;
+
+		count += 1;
+	}
+
+	return Ok(count);
+}
+
+/// Reads all entries of the archive read from `reader`, and checks their paths, their metadata and
+/// the checksums of their content, before returning the documents to put in the storage of `username`.
+fn read_archive(
+	username: &str,
+	reader: impl std::io::Read,
+) -> Result<
+	Vec<(crate::item::ItemPath, crate::item::DataDocument, Vec<u8>)>,
+	crate::database::DataSourceError,
+> {
+	let mut archive = tar::Archive::new(reader);
+	let mut itemdatas: std::collections::HashMap<String, crate::item::DataDocument> =
+		std::collections::HashMap::new();
+	let mut contents = vec![];
+
+	for entry in archive.entries().map_err(internal_error)? {
+		let mut entry = entry.map_err(internal_error)?;
+		if !entry.header().entry_type().is_file() {
+			continue;
+		}
+
+		let archive_path = archive_path_of(&entry.path().map_err(internal_error)?)?;
+
+		let mut content = vec![];
+		std::io::Read::read_to_end(&mut entry, &mut content).map_err(internal_error)?;
+
+		if let Some(document_path) = document_of_itemdata(&archive_path) {
+			let itemdata: crate::item::DataDocument =
+				toml::from_slice(&content).map_err(|error| {
+					crate::database::DataSourceError::InternalError {
+						error: format!("can not parse `{}` : {}", archive_path, error),
+					}
+				})?;
+
+			itemdatas.insert(document_path, itemdata);
+		} else {
+			contents.push((archive_path, content));
+		}
+	}
+
+	let mut documents = vec![];
+	for (archive_path, content) in contents {
+		// documents exported without metadata get the default ones
+		let itemdata = itemdatas.remove(&archive_path).unwrap_or_default();
+
+		let path = match archive_path.split_once('/') {
+			Some((PRIVATE_FOLDER, rest)) => format!("{}/{}", username, rest),
+			Some((PUBLIC_FOLDER, rest)) => format!("public/{}/{}", username, rest),
+			_ => {
+				return Err(crate::database::DataSourceError::InternalError {
+					error: format!(
+						"`{}` should be in `{}/` or `{}/`",
+						archive_path, PRIVATE_FOLDER, PUBLIC_FOLDER
+					),
+				});
+			}
+		};
+		let path = crate::item::ItemPath::from(path.as_str());
+
+		if let Some(error) = path
+			.parts_iter()
+			.find_map(|part| part.check_validity(true).err())
+		{
+			return Err(crate::database::DataSourceError::IncorrectItemName {
+				item_path: path,
+				error,
+			});
+		}
+		if !path.is_document() {
+			return Err(crate::database::DataSourceError::IncorrectItemName {
+				item_path: path,
+				error: String::from("this is not a document"),
+			});
+		}
+
+		if !itemdata.is_intact(&content) {
+			return Err(crate::database::DataSourceError::InternalError {
+				error: format!(
+					"the content of `{}` does not match its checksum",
+					archive_path
+				),
+			});
+		}
+
+		documents.push((path, itemdata, content));
+	}
+
+	if let Some(document_path) = itemdatas.keys().next() {
+		return Err(crate::database::DataSourceError::InternalError {
+			error: format!(
+				"the archive has metadata of `{}`, but not its content",
+				document_path
+			),
+		});
+	}
+
+	return Ok(documents);
+}
+
+/// The archive folders and the matching folders in the storage of `username`.
+fn user_folders(username: &str) -> [(&'static str, String); 2] {
+	return [
+		(PRIVATE_FOLDER, format!("{}/", username)),
+		(PUBLIC_FOLDER, format!("public/{}/", username)),
+	];
+}
+
+fn check_username(username: &str) -> Result<(), crate::database::DataSourceError> {
+	let path = crate::item::ItemPath::from("");
+
+	return match path.joined_folder(username) {
+		Ok(user_path)
+			if !username.is_empty()
+				&& username != "public"
+				&& !username.contains(['/', '\\'])
+				&& user_path
+					.parts_iter()
+					.all(|part| part.check_validity(true).is_ok()) =>
+		{
+			Ok(())
+		}
+		_ => Err(crate::database::DataSourceError::IncorrectItemName {
+			item_path: crate::item::ItemPath::from(username),
+			error: String::from("this is not a correct username"),
+		}),
+	};
+}
+
+/// The metadata entry of the document at `archive_path`, like the ones of [`FolderStorage`][`crate::database::sources::FolderStorage`].
+fn itemdata_path(archive_path: &str) -> String {
+	return match archive_path.rsplit_once('/') {
+		Some((folder, name)) => format!("{}/.{}.itemdata.toml", folder, name),
+		None => format!(".{}.itemdata.toml", archive_path),
+	};
+}
+
+/// The document described by the metadata entry at `archive_path`, if it is one.
+fn document_of_itemdata(archive_path: &str) -> Option<String> {
+	let (folder, name) = archive_path.rsplit_once('/')?;
+	let name = name.strip_prefix('.')?.strip_suffix(".itemdata.toml")?;
+
+	return Some(format!("{}/{}", folder, name));
+}
+
+/// The path of an entry, which should not go out of the archive.
+fn archive_path_of(path: &std::path::Path) -> Result<String, crate::database::DataSourceError> {
+	let mut parts = vec![];
+
+	for component in path.components() {
+		match component {
+			std::path::Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+			std::path::Component::CurDir => {}
+			_ => {
+				return Err(crate::database::DataSourceError::InternalError {
+					error: format!(
+						"the entry `{}` is out of the archive",
+						path.to_string_lossy()
+					),
+				});
+			}
+		}
+	}
+
+	return Ok(parts.join("/"));
+}
+
+fn append<W: std::io::Write>(
+	builder: &mut tar::Builder<W>,
+	path: &str,
+	data: &[u8],
+	mtime: u64,
+) -> Result<(), crate::database::DataSourceError> {
+	let mut header = tar::Header::new_gnu();
+	header.set_size(data.len() as u64);
+	header.set_mode(0o644);
+	header.set_mtime(mtime);
+
+	return builder
+		.append_data(&mut header, path, data)
+		.map_err(internal_error);
+}
+
+fn internal_error(error: impl std::fmt::Display) -> crate::database::DataSourceError {
+	return crate::database::DataSourceError::InternalError {
+		error: format!("{}", error),
+	};
+}
@@ -0,0 +1,132 @@
+/// One operation of [`DataSource::batch`][`crate::database::sources::DataSource::batch`], with its own conditions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchOperation {
+	/// Puts `item` at `path`, like [`DataSource::put`][`crate::database::sources::DataSource::put`].
+	Put {
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		item: crate::item::Item,
+	},
+	/// Deletes the document at `path`, or the folder at `path` with all its content,
+	/// see [`DataSource::delete_folder`][`crate::database::sources::DataSource::delete_folder`].
+	Delete {
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+	},
+}
+impl BatchOperation {
+	pub fn path(&self) -> &crate::item::ItemPath {
+		match self {
+			Self::Put { path, .. } => path,
+			Self::Delete { path, .. } => path,
+		}
+	}
+}
+
+/// The result of a [`BatchOperation`].
+#[derive(Debug)]
+pub enum BatchResult {
+	Put(crate::database::PutResult),
+	/// The ETag of the deleted item.
+	Delete(Result<crate::item::Etag, crate::database::DataSourceError>),
+}
+impl BatchResult {
+	pub fn is_ok(&self) -> bool {
+		return matches!(
+			self,
+			Self::Put(crate::database::PutResult::Created(_, _))
+				| Self::Put(crate::database::PutResult::Updated(_, _))
+				| Self::Delete(Ok(_))
+		);
+	}
+}
+
+/// Applies `operation` to `source`, as [`DataSource::batch`][`crate::database::sources::DataSource::batch`] does by default.
+pub(crate) fn apply<S>(source: &mut S, operation: &BatchOperation) -> BatchResult
+where
+	S: crate::database::sources::DataSource + ?Sized,
+{
+	match operation {
+		BatchOperation::Put {
+			path,
+			if_match,
+			if_none_match,
+			item,
+		} => {
+			return BatchResult::Put(source.put(
+				path,
+				&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+				&if_none_match.iter().collect::<Vec<&crate::item::Etag>>(),
+				item.clone(),
+			));
+		}
+		BatchOperation::Delete { path, if_match } => {
+			let if_match = if_match.iter().collect::<Vec<&crate::item::Etag>>();
+
+			if path.is_folder() {
+				return BatchResult::Delete(source.delete_folder(path, &if_match));
+			} else {
+				return BatchResult::Delete(source.delete(path, &if_match));
+			}
+		}
+	}
+}
+
+/// Adds the paths of all documents inside `folder` (which is at `path`) to `documents`.
+///
+/// Children folders without content are fetched from `source`.
+pub(crate) fn collect_documents<S>(
+	source: &S,
+	path: &crate::item::ItemPath,
+	folder: &crate::item::Item,
+	documents: &mut Vec<crate::item::ItemPath>,
+) -> Result<(), crate::database::DataSourceError>
+where
+	S: crate::database::sources::DataSource + ?Sized,
+{
+	let content = match folder {
+		crate::item::Item::Folder {
+			content: Some(content),
+			..
+		} => content,
+		crate::item::Item::Folder { content: None, .. } => {
+			let folder = source.get(path, &[], &[], false)?;
+			return match folder {
+				crate::item::Item::Folder {
+					content: Some(_), ..
+				} => collect_documents(source, path, &folder, documents),
+				_ => Err(crate::database::DataSourceError::NoContentInside {
+					item_path: path.clone(),
+				}),
+			};
+		}
+		crate::item::Item::Document { .. } => {
+			return Err(crate::database::DataSourceError::Conflict {
+				item_path: path.clone(),
+			});
+		}
+	};
+
+	for (name, child) in content {
+		if child.is_folder() {
+			let child_path = path.joined_folder(name).map_err(|error| {
+				crate::database::DataSourceError::IncorrectItemName {
+					item_path: path.clone(),
+					error,
+				}
+			})?;
+
+			collect_documents(source, &child_path, child, documents)?;
+		} else {
+			documents.push(path.joined_doc(name).map_err(|error| {
+				crate::database::DataSourceError::IncorrectItemName {
+					item_path: path.clone(),
+					error,
+				}
+			})?);
+		}
+	}
+
+	return Ok(());
+}
@@ -0,0 +1,155 @@
+/// Why the conditional headers of a request prevent it to be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreconditionFailure {
+	/// None of the ETags of `If-Match` matches the current one.
+	NoIfMatch {
+		search: crate::item::Etag,
+		found: crate::item::Etag,
+	},
+	/// One of the ETags of `If-None-Match` matches the current one.
+	IfNoneMatch {
+		search: crate::item::Etag,
+		found: crate::item::Etag,
+	},
+}
+
+/// Evaluates `If-Match` then `If-None-Match`, following
+/// [RFC 7232, section 6](https://www.rfc-editor.org/rfc/rfc7232#section-6).
+///
+/// `found` is the ETag of the current item, or `None` if it does not exist.
+///
+/// `If-Match` uses the strong comparison, and `If-None-Match` the weak one.
+/// Empty ETags are ignored, so an empty list means that the header is absent.
+///
+/// Choosing between `304 Not Modified` and `412 Precondition Failed` for
+/// [`IfNoneMatch`][`PreconditionFailure::IfNoneMatch`] depends on the request method,
+/// see [`DataSourceError::to_response`][`crate::database::DataSourceError`].
+pub fn check_preconditions(
+	found: Option<&crate::item::Etag>,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+) -> Result<(), PreconditionFailure> {
+	let if_match: Vec<&crate::item::Etag> = if_match
+		.iter()
+		.copied()
+		.filter(|etag| !etag.trim().is_empty())
+		.collect();
+	let if_none_match: Vec<&crate::item::Etag> = if_none_match
+		.iter()
+		.copied()
+		.filter(|etag| !etag.trim().is_empty())
+		.collect();
+
+	if !if_match.is_empty() {
+		let matches = match found {
+			Some(found) => if_match
+				.iter()
+				.any(|search| search.is_wildcard() || search.strong_eq(found)),
+			None => false,
+		};
+
+		if !matches {
+			return Err(PreconditionFailure::NoIfMatch {
+				search: crate::item::Etag::from(
+					if_match
+						.iter()
+						.map(|etag| etag.trim().into())
+						.collect::<Vec<String>>()
+						.join(", "),
+				),
+				found: found
+					.cloned()
+					.unwrap_or_else(|| crate::item::Etag::from("")),
+			});
+		}
+	}
+
+	if let Some(found) = found {
+		if let Some(search) = if_none_match
+			.iter()
+			.find(|search| search.is_wildcard() || search.weak_eq(found))
+		{
+			return Err(PreconditionFailure::IfNoneMatch {
+				search: (*search).clone(),
+				found: found.clone(),
+			});
+		}
+	}
+
+	return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{check_preconditions, PreconditionFailure};
+	use crate::item::Etag;
+
+	#[test]
+	fn y0r5ohb9xk3s6yq2ogf() {
+		let found = Etag::from("A");
+
+		assert_eq!(check_preconditions(Some(&found), &[], &[]), Ok(()));
+		assert_eq!(
+			check_preconditions(Some(&found), &[&Etag::from("")], &[&Etag::from("")]),
+			Ok(())
+		);
+		assert_eq!(
+			check_preconditions(Some(&found), &[&Etag::from("B"), &Etag::from("A")], &[]),
+			Ok(())
+		);
+		assert_eq!(
+			check_preconditions(Some(&found), &[&Etag::from("*")], &[]),
+			Ok(())
+		);
+		assert_eq!(
+			check_preconditions(Some(&found), &[&Etag::from("B"), &Etag::from("C")], &[]),
+			Err(PreconditionFailure::NoIfMatch {
+				search: Etag::from("B, C"),
+				found: found.clone(),
+			})
+		);
+		assert_eq!(
+			check_preconditions(None, &[&Etag::from("*")], &[]),
+			Err(PreconditionFailure::NoIfMatch {
+				search: Etag::from("*"),
+				found: Etag::from(""),
+			})
+		);
+	}
+
+	#[test]
+	fn e2x9kyhm1zqvg6ajlp() {
+		let found = Etag::from("A");
+
+		// If-Match uses the strong comparison
+		assert!(check_preconditions(Some(&found), &[&Etag::from("W/A")], &[]).is_err());
+		assert!(check_preconditions(Some(&Etag::from("W/A")), &[&Etag::from("A")], &[]).is_err());
+
+		// If-None-Match uses the weak comparison
+		assert_eq!(
+			check_preconditions(Some(&found), &[], &[&Etag::from("B"), &Etag::from("W/A")]),
+			Err(PreconditionFailure::IfNoneMatch {
+				search: Etag::from("W/A"),
+				found: found.clone(),
+			})
+		);
+		assert_eq!(
+			check_preconditions(Some(&found), &[], &[&Etag::from("*")]),
+			Err(PreconditionFailure::IfNoneMatch {
+				search: Etag::from("*"),
+				found: found.clone(),
+			})
+		);
+		assert_eq!(
+			check_preconditions(Some(&found), &[], &[&Etag::from("B")]),
+			Ok(())
+		);
+		assert_eq!(check_preconditions(None, &[], &[&Etag::from("*")]), Ok(()));
+
+		// If-Match is evaluated before If-None-Match
+		assert!(matches!(
+			check_preconditions(Some(&found), &[&Etag::from("B")], &[&Etag::from("A")]),
+			Err(PreconditionFailure::NoIfMatch { .. })
+		));
+	}
+}
@@ -0,0 +1,271 @@
+/// The error returned by all [`DataSource`][`crate::database::DataSource`]s.
+///
+/// Each backend converts its own errors into this type, so the HTTP server
+/// can answer with the same status code, whatever the backend is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataSourceError {
+	Conflict {
+		item_path: crate::item::ItemPath,
+	},
+	NotFound {
+		item_path: crate::item::ItemPath,
+	},
+	NoContentInside {
+		item_path: crate::item::ItemPath,
+	},
+	IncorrectItemName {
+		item_path: crate::item::ItemPath,
+		error: String,
+	},
+	CanNotBeListed {
+		item_path: crate::item::ItemPath,
+	},
+	NoIfMatch {
+		item_path: crate::item::ItemPath,
+		search: crate::item::Etag,
+		found: crate::item::Etag,
+	},
+	IfNoneMatch {
+		item_path: crate::item::ItemPath,
+		search: crate::item::Etag,
+		found: crate::item::Etag,
+	},
+	DoesNotWorksForFolders,
+	ContentNotChanged,
+	IsSystemFile,
+	/// Returned by [`migrate`][`crate::database::migrate`] when the destination already contains `item_path`,
+	/// and should be empty.
+	DestinationNotEmpty {
+		item_path: crate::item::ItemPath,
+	},
+	InternalError {
+		error: String,
+	},
+}
+impl DataSourceError {
+	/// Converts an error boxed by the internals of a backend, which should be of type `E`.
+	///
+	/// Any other type of error ends up in [`InternalError`][`DataSourceError::InternalError`].
+	pub fn from_boxed<E>(error: Box<dyn std::error::Error>) -> Self
+	where
+		E: std::error::Error + 'static,
+		Self: From<E>,
+	{
+		return match error.downcast::<E>() {
+			Ok(error) => Self::from(*error),
+			Err(error) => match error.downcast::<Self>() {
+				Ok(error) => *error,
+				Err(error) => Self::InternalError {
+					error: format!("{}", error),
+				},
+			},
+		};
+	}
+}
+impl std::fmt::Display for DataSourceError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		match self {
+			Self::Conflict { item_path } => f.write_fmt(format_args!("name conflict between folder and file on the path `{}`", item_path)),
+			Self::NotFound { item_path } => f.write_fmt(format_args!("path not found : `{}`", item_path)),
+			Self::NoContentInside { item_path } => f.write_fmt(format_args!("no content found in `{}`", item_path)),
+			Self::IncorrectItemName { item_path, error } => f.write_fmt(format_args!("the path `{}` is incorrect, because {}", item_path, error)),
+			Self::CanNotBeListed { item_path } => f.write_fmt(format_args!("the folder `{}` can not be listed", item_path)),
+			Self::NoIfMatch { item_path, search, found } => f.write_fmt(format_args!("the requested `{}` etag (through `IfMatch`) for `{}` was not found, found `{}` instead", search, item_path, found)),
+			Self::IfNoneMatch { item_path, search, found } => f.write_fmt(format_args!("the unwanted etag `{}` (through `IfNoneMatch`) for `{}` was matches with `{}`", search, item_path, found)),
+			Self::DoesNotWorksForFolders => f.write_str("this method does not works on folders"),
+			Self::ContentNotChanged => f.write_str("content not changed"),
+			Self::IsSystemFile => f.write_str("this is a system file, that should not be server"),
+			Self::DestinationNotEmpty { item_path } => f.write_fmt(format_args!("the destination is not empty, it already contains `{}`", item_path)),
+			Self::InternalError { error } => f.write_fmt(format_args!("internal server error : {}", error)),
+		}
+	}
+}
+impl std::error::Error for DataSourceError {}
+#[cfg(feature = "actix_server")]
+impl DataSourceError {
+	/// The HTTP status code which should be used to answer this error.
+	///
+	/// Errors about items in `public/` answer [`NOT_FOUND`][`actix_web::http::StatusCode::NOT_FOUND`]
+	/// when they could reveal the content of a folder.
+	///
+	/// [`IfNoneMatch`][`DataSourceError::IfNoneMatch`] is answered with
+	/// [`NOT_MODIFIED`][`actix_web::http::StatusCode::NOT_MODIFIED`] instead by
+	/// [`to_response`][`DataSourceError::to_response`] for `GET` and `HEAD` requests.
+	pub fn status_code(&self) -> actix_web::http::StatusCode {
+		match self {
+			Self::Conflict { item_path } => {
+				if item_path.starts_with("public/") {
+					actix_web::http::StatusCode::NOT_FOUND
+				} else {
+					actix_web::http::StatusCode::CONFLICT
+				}
+			}
+			Self::NotFound { .. } => actix_web::http::StatusCode::NOT_FOUND,
+			Self::NoContentInside { item_path } => {
+				if item_path.starts_with("public/") {
+					actix_web::http::StatusCode::NOT_FOUND
+				} else {
+					actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+				}
+			}
+			Self::IncorrectItemName { .. } => actix_web::http::StatusCode::BAD_REQUEST,
+			Self::CanNotBeListed { .. } => actix_web::http::StatusCode::NOT_FOUND,
+			Self::NoIfMatch { .. } => actix_web::http::StatusCode::PRECONDITION_FAILED,
+			Self::IfNoneMatch { .. } => actix_web::http::StatusCode::PRECONDITION_FAILED,
+			Self::DoesNotWorksForFolders => actix_web::http::StatusCode::BAD_REQUEST,
+			Self::ContentNotChanged => actix_web::http::StatusCode::NOT_MODIFIED,
+			Self::IsSystemFile => actix_web::http::StatusCode::BAD_REQUEST,
+			Self::DestinationNotEmpty { .. } => actix_web::http::StatusCode::CONFLICT,
+			Self::InternalError { .. } => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+		}
+	}
+
+	pub fn to_response(
+		&self,
+		request_method: &actix_web::http::Method,
+		should_have_body: bool,
+	) -> actix_web::HttpResponse {
+		if let Self::IfNoneMatch { found, .. } = self {
+			// RFC 7232, section 3.2
+			if request_method == actix_web::http::Method::GET
+				|| request_method == actix_web::http::Method::HEAD
+			{
+				let found: String = found.clone().into();
+
+				return actix_web::HttpResponse::NotModified()
+					.insert_header((actix_web::http::header::ETAG, found))
+					.finish();
+			}
+		}
+
+		return crate::database::build_http_json_response(
+			request_method,
+			self.status_code(),
+			None,
+			None,
+			self.hint(),
+			should_have_body,
+		);
+	}
+
+	/// The explanation given to clients, which does not reveal what is hidden by the status code.
+	pub fn hint(&self) -> Option<String> {
+		return match self {
+			Self::Conflict { item_path }
+			| Self::NoContentInside { item_path }
+			| Self::CanNotBeListed { item_path }
+				if self.status_code() == actix_web::http::StatusCode::NOT_FOUND =>
+			{
+				Some(format!("path not found : `{}`", item_path))
+			}
+			Self::IsSystemFile | Self::InternalError { .. } => None,
+			_ => Some(format!("{}", self)),
+		};
+	}
+}
+
+#[cfg(test)]
+#[cfg(feature = "actix_server")]
+mod tests {
+	use super::DataSourceError;
+	use crate::item::{Etag, ItemPath};
+
+	#[test]
+	fn ucdy5s0ptkv3uy4cfq() {
+		let tests = vec![
+			(
+				DataSourceError::Conflict {
+					item_path: ItemPath::from("A/AA"),
+				},
+				actix_web::http::StatusCode::CONFLICT,
+			),
+			(
+				DataSourceError::Conflict {
+					item_path: ItemPath::from("public/A/AA"),
+				},
+				actix_web::http::StatusCode::NOT_FOUND,
+			),
+			(
+				DataSourceError::NotFound {
+					item_path: ItemPath::from("A/"),
+				},
+				actix_web::http::StatusCode::NOT_FOUND,
+			),
+			(
+				DataSourceError::NoIfMatch {
+					item_path: ItemPath::from("A/AA"),
+					search: Etag::from("A"),
+					found: Etag::from("B"),
+				},
+				actix_web::http::StatusCode::PRECONDITION_FAILED,
+			),
+			(
+				DataSourceError::ContentNotChanged,
+				actix_web::http::StatusCode::NOT_MODIFIED,
+			),
+			(
+				DataSourceError::InternalError {
+					error: String::from("disk is on fire"),
+				},
+				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+			),
+		];
+
+		for (error, status) in tests {
+			assert_eq!(error.status_code(), status);
+			assert_eq!(
+				error
+					.to_response(&actix_web::http::Method::GET, true)
+					.status(),
+				status
+			);
+		}
+
+		let if_none_match = DataSourceError::IfNoneMatch {
+			item_path: ItemPath::from("A/AA"),
+			search: Etag::from("*"),
+			found: Etag::from("B"),
+		};
+		let response = if_none_match.to_response(&actix_web::http::Method::GET, true);
+		assert_eq!(response.status(), actix_web::http::StatusCode::NOT_MODIFIED);
+		assert_eq!(
+			response
+				.headers()
+				.get(actix_web::http::header::ETAG)
+				.unwrap(),
+			"B"
+		);
+		assert_eq!(
+			if_none_match
+				.to_response(&actix_web::http::Method::HEAD, false)
+				.status(),
+			actix_web::http::StatusCode::NOT_MODIFIED
+		);
+		assert_eq!(
+			if_none_match
+				.to_response(&actix_web::http::Method::PUT, true)
+				.status(),
+			actix_web::http::StatusCode::PRECONDITION_FAILED
+		);
+	}
+
+	#[test]
+	fn d6lz9j1yszb6hqkcw8() {
+		let error: Box<dyn std::error::Error> =
+			Box::new(crate::database::sources::memory::GetError::NotFound {
+				item_path: ItemPath::from("A/"),
+			});
+		assert_eq!(
+			DataSourceError::from_boxed::<crate::database::sources::memory::GetError>(error),
+			DataSourceError::NotFound {
+				item_path: ItemPath::from("A/"),
+			}
+		);
+
+		let error: Box<dyn std::error::Error> = Box::new(std::fmt::Error);
+		assert!(matches!(
+			DataSourceError::from_boxed::<crate::database::sources::memory::GetError>(error),
+			DataSourceError::InternalError { .. }
+		));
+	}
+}
@@ -0,0 +1,161 @@
+/// Which children of a folder are listed by [`DataSource::list`][`crate::database::sources::DataSource::list`].
+///
+/// Children are ordered by their key in listings (their name, with a trailing `/` for folders),
+/// so [`after`][`ListingOptions::after`] can be used as a cursor between pages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListingOptions {
+	/// Only lists children whose name starts with it.
+	pub prefix: Option<String>,
+	/// Only lists documents modified since this date (included).
+	///
+	/// Folders are not listed when it is set, because they do not have a modification date.
+	pub modified_since: Option<time::OffsetDateTime>,
+	/// Only lists documents of this content type (parameters, like `charset`, are ignored).
+	///
+	/// Folders are not listed when it is set, because they do not have a content type.
+	pub content_type: Option<String>,
+	/// Only lists children whose key is after this one, usually the [`next`][`Listing::next`] of the previous page.
+	pub after: Option<String>,
+	/// The maximum count of listed children.
+	pub limit: Option<usize>,
+}
+impl ListingOptions {
+	/// Checks the filters which do not need the metadata of the child.
+	fn accepts_key(&self, name: &str, key: &str, is_folder: bool) -> bool {
+		if is_folder && (self.modified_since.is_some() || self.content_type.is_some()) {
+			return false;
+		}
+
+		if let Some(prefix) = &self.prefix {
+			if !name.starts_with(prefix.as_str()) {
+				return false;
+			}
+		}
+
+		if let Some(after) = &self.after {
+			if key <= after.as_str() {
+				return false;
+			}
+		}
+
+		return true;
+	}
+
+	fn accepts_item(&self, item: &crate::item::Item) -> bool {
+		if let crate::item::Item::Document {
+			content_type,
+			last_modified,
+			..
+		} = item
+		{
+			if let Some(modified_since) = self.modified_since {
+				match last_modified {
+					Some(last_modified) if *last_modified >= modified_since => {}
+					_ => return false,
+				}
+			}
+
+			if let Some(expected) = &self.content_type {
+				let content_type = content_type.to_string();
+				let essence = content_type.split(';').next().unwrap_or_default().trim();
+				let expected = expected.split(';').next().unwrap_or_default().trim();
+
+				if !essence.eq_ignore_ascii_case(expected) {
+					return false;
+				}
+			}
+		}
+
+		return true;
+	}
+}
+
+/// A page of the listing of a folder, see [`DataSource::list`][`crate::database::sources::DataSource::list`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+	/// The ETag of the listed folder.
+	pub etag: crate::item::Etag,
+	/// The listed children (without content), ordered by their key in listings.
+	pub items: Vec<(String, crate::item::Item)>,
+	/// The key of the last listed child, if there are more children to list after it.
+	pub next: Option<String>,
+}
+
+/// The key of a child in listings.
+pub fn listing_key(name: &str, is_folder: bool) -> String {
+	if is_folder {
+		return format!("{}/", name);
+	} else {
+		return String::from(name);
+	}
+}
+
+/// Lists the children of the folder of `etag`, given by their name and whether they are folders.
+///
+/// `load` gives the item of a child (its content is not needed), or `None` when it should not be listed.
+/// It is only called for the children which are not filtered out by their name, until the page is full.
+pub(crate) fn build_listing<E>(
+	etag: crate::item::Etag,
+	children: Vec<(String, bool)>,
+	options: &ListingOptions,
+	mut load: impl FnMut(&str, bool) -> Result<Option<crate::item::Item>, E>,
+) -> Result<Listing, E> {
+	let mut candidates: Vec<(String, String, bool)> = children
+		.into_iter()
+		.map(|(name, is_folder)| (listing_key(&name, is_folder), name, is_folder))
+		.filter(|(key, name, is_folder)| options.accepts_key(name, key, *is_folder))
+		.collect();
+	candidates.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+	let mut items = vec![];
+	let mut last_key = None;
+	let mut next = None;
+	for (key, name, is_folder) in candidates {
+		if let Some(item) = load(&name, is_folder)? {
+			if options.accepts_item(&item) {
+				if Some(items.len()) == options.limit {
+					next = last_key;
+					break;
+				}
+
+				items.push((name, item.empty_clone()));
+				last_key = Some(key);
+			}
+		}
+	}
+
+	return Ok(Listing { etag, items, next });
+}
+
+/// Lists children which are already in memory, like the content of a [`Folder`][`crate::item::Item::Folder`].
+///
+/// Empty folders are not listed.
+pub(crate) fn list_children(
+	etag: crate::item::Etag,
+	content: &std::collections::HashMap<String, Box<crate::item::Item>>,
+	options: &ListingOptions,
+) -> Listing {
+	let children = content
+		.iter()
+		.map(|(name, item)| (name.clone(), item.is_folder()))
+		.collect();
+
+	let result: Result<Listing, std::convert::Infallible> =
+		build_listing(etag, children, options, |name, _| {
+			return Ok(content
+				.get(name)
+				.filter(|item| match &***item {
+					crate::item::Item::Folder {
+						content: Some(content),
+						..
+					} => !content.is_empty(),
+					_ => true,
+				})
+				.map(|item| item.empty_clone()));
+		});
+
+	return match result {
+		Ok(listing) => listing,
+		Err(never) => match never {},
+	};
+}
@@ -0,0 +1,181 @@
+/// What [`migrate`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Migration {
+	/// Documents which were put in the destination.
+	pub copied: usize,
+	/// Documents which were already in the destination, with the same metadata.
+	pub unchanged: usize,
+	/// Documents of the destination which are not in the source anymore, so they were deleted.
+	pub removed: usize,
+}
+
+/// What [`migrate`] does when the destination already contains documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MigrationDestination {
+	/// The migration is refused with [`DestinationNotEmpty`][`crate::database::DataSourceError::DestinationNotEmpty`],
+	/// before writing anything, so no document of the destination is lost.
+	#[default]
+	Empty,
+	/// Documents of the destination are replaced by the ones of the source,
+	/// and the ones which are not in the source are deleted.
+	DeleteExtra,
+}
+
+/// Copies all documents of `from` in `to`, with their content type, ETag and last modification date,
+/// then checks that both contain the same documents.
+///
+/// With [`MigrationDestination::DeleteExtra`], documents which are already in `to` with the same metadata
+/// are not copied again, and documents of `to` which are not in `from` are deleted. So it can be run
+/// a first time while `from` is still in use, then a second time after stopping the server,
+/// which only copies the last changes.
+///
+/// `to` should support [`DataSource::set_metadata`][`crate::database::sources::DataSource::set_metadata`].
+/// ETags of folders are not preserved.
+pub fn migrate(
+	from: &dyn crate::database::sources::DataSource,
+	to: &mut dyn crate::database::sources::DataSource,
+	destination: MigrationDestination,
+) -> Result<Migration, crate::database::DataSourceError> {
+	let root = crate::item::ItemPath::from("");
+	let mut migration = Migration::default();
+
+	if destination == MigrationDestination::Empty {
+		if let Some(item_path) = all_documents(to, &root)?.into_iter().next() {
+			return Err(crate::database::DataSourceError::DestinationNotEmpty { item_path });
+		}
+	}
+
+	let documents = all_documents(from, &root)?;
+
+	for path in &documents {
+		let item = from.get(path, &[], &[], true)?;
+
+		let up_to_date = match to.get(path, &[], &[], false) {
+			Ok(existing) => same_metadata(&item, &existing),
+			Err(crate::database::DataSourceError::NotFound { .. }) => false,
+			Err(error) => return Err(error),
+		};
+		if up_to_date {
+			migration.unchanged += 1;
+			continue;
+		}
+
+		let (etag, last_modified) = match &item {
+			crate::item::Item::Document {
+				etag,
+				last_modified,
+				..
+			} => (etag.clone(), *last_modified),
+			crate::item::Item::Folder { .. } => {
+				return Err(crate::database::DataSourceError::Conflict {
+					item_path: path.clone(),
+				});
+			}
+		};
+
+		match to.put(path, &[], &[], item) {
+			crate::database::PutResult::Created(_, _)
+			| crate::database::PutResult::Updated(_, _)
+			// only the metadata differs
+			| crate::database::PutResult::Err(crate::database::DataSourceError::ContentNotChanged) => {}
+			crate::database::PutResult::Err(error) => return Err(error),
+		}
+		to.set_metadata(path, &etag, last_modified)?;
+
+		migration.copied += 1;
+	}
+
+	let copied: std::collections::HashSet<&crate::item::ItemPath> = documents.iter().collect();
+	for path in all_documents(to, &root)? {
+		if !copied.contains(&path) {
+			to.delete(&path, &[])?;
+
+			migration.removed += 1;
+		}
+	}
+
+	verify(from, to, &documents)?;
+
+	return Ok(migration);
+}
+
+/// Checks that `to` contains exactly `documents`, which are the same as in `from`.
+fn verify(
+	from: &dyn crate::database::sources::DataSource,
+	to: &dyn crate::database::sources::DataSource,
+	documents: &[crate::item::ItemPath],
+) -> Result<(), crate::database::DataSourceError> {
+	let migrated = all_documents(to, &crate::item::ItemPath::from(""))?;
+	if migrated.len() != documents.len() {
+		return Err(crate::database::DataSourceError::InternalError {
+			error: format!(
+				"{} documents were migrated, instead of {}",
+				migrated.len(),
+				documents.len()
+			),
+		});
+	}
+
+	for path in documents {
+		let expected = from.get(path, &[], &[], true)?;
+		let found = to.get(path, &[], &[], true)?;
+
+		let same_content = match (&expected, &found) {
+			(
+				crate::item::Item::Document {
+					content: expected_content,
+					..
+				},
+				crate::item::Item::Document {
+					content: found_content,
+					..
+				},
+			) => expected_content == found_content,
+			_ => false,
+		};
+
+		if !same_content || !same_metadata(&expected, &found) {
+			return Err(crate::database::DataSourceError::InternalError {
+				error: format!("`{}` is not the same after its migration", path),
+			});
+		}
+	}
+
+	return Ok(());
+}
+
+/// The paths of all documents of `source`, or none if its root does not exist yet.
+fn all_documents(
+	source: &dyn crate::database::sources::DataSource,
+	root: &crate::item::ItemPath,
+) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+	return match source.documents_in(root) {
+		Ok(documents) => Ok(documents),
+		Err(crate::database::DataSourceError::NotFound { .. }) => Ok(vec![]),
+		Err(error) => Err(error),
+	};
+}
+
+fn same_metadata(expected: &crate::item::Item, found: &crate::item::Item) -> bool {
+	return match (expected, found) {
+		(
+			crate::item::Item::Document {
+				etag,
+				content_type,
+				last_modified,
+				..
+			},
+			crate::item::Item::Document {
+				etag: found_etag,
+				content_type: found_content_type,
+				last_modified: found_last_modified,
+				..
+			},
+		) => {
+			etag == found_etag
+				&& content_type == found_content_type
+				&& last_modified == found_last_modified
+		}
+		_ => false,
+	};
+}
@@ -1,36 +1,114 @@
 use sources::DataSource;
+use std::sync::{Arc, RwLock};
 
+#[cfg(feature = "actix_server")]
+mod archive;
+mod batch;
+mod conditions;
+mod error;
+mod listing;
+mod migration;
 pub mod sources;
+mod trash;
+
+#[cfg(test)]
+#[cfg(feature = "actix_server")]
+mod tests;
+
+#[cfg(feature = "actix_server")]
+pub use archive::ImportEtags;
+pub use batch::{BatchOperation, BatchResult};
+pub use conditions::{check_preconditions, PreconditionFailure};
+pub use error::DataSourceError;
+pub use listing::{listing_key, Listing, ListingOptions};
+pub use migration::{migrate, Migration, MigrationDestination};
+pub use sources::{AsyncDataSource, DataSourceFuture};
+pub use trash::{Trash, TrashedItem};
 
 #[cfg(feature = "server_file_storage")]
 pub use sources::FolderStorage;
+#[cfg(feature = "server_indexed_db")]
+pub use sources::IndexedDbStorage;
 #[cfg(feature = "server_local_storage")]
 pub use sources::LocalStorage;
 pub use sources::MemoryStorage;
 
-#[derive(Debug)]
+/// How [`Database`] gives [`Etag`][`crate::item::Etag`]s to new versions of items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EtagStrategy {
+	/// Each new version gets a random ETag, see [`Etag::new`][`crate::item::Etag::new`].
+	#[default]
+	Random,
+	/// ETags of documents are derived from their content, and ETags of folders from the ones of their children.
+	///
+	/// Identical content gets the same ETag on all replicas, so they can detect equality without transferring data.
+	///
+	/// See [`Item::rehash_etags`][`crate::item::Item::rehash_etags`].
+	ContentHash,
+}
+
+/// The shared entry point to the data.
+///
+/// Cloning it is cheap, all clones share the same [`DataSource`].
+/// Reads can run in parallel, whereas writes are exclusive.
+#[derive(Debug, Clone)]
 pub struct Database {
-	source: Box<dyn DataSource>,
+	source: Arc<RwLock<Box<dyn DataSource>>>,
+	etag_strategy: EtagStrategy,
+	trash: Option<Arc<RwLock<Trash>>>,
 }
 impl Database {
 	pub fn new(source: Box<dyn DataSource>) -> Self {
-		Database { source }
+		Database {
+			source: Arc::new(RwLock::new(source)),
+			etag_strategy: EtagStrategy::default(),
+			trash: None,
+		}
+	}
+
+	/// Changes how ETags are given to the items which are put or deleted from now on.
+	///
+	/// Existing items keep their current ETags, see [`rehash_etags`][`Database::rehash_etags`] to update them.
+	pub fn with_etag_strategy(mut self, etag_strategy: EtagStrategy) -> Self {
+		self.etag_strategy = etag_strategy;
+
+		return self;
+	}
+
+	pub fn etag_strategy(&self) -> EtagStrategy {
+		return self.etag_strategy;
+	}
+
+	/// Keeps the documents deleted from now on in `trash`, instead of losing them.
+	///
+	/// Deleting a folder fails if its documents can not be fetched first, like in `public/`.
+	pub fn with_trash(mut self, trash: Trash) -> Self {
+		self.trash = Some(Arc::new(RwLock::new(trash)));
+
+		return self;
+	}
+
+	pub fn has_trash(&self) -> bool {
+		return self.trash.is_some();
 	}
 
 	pub fn get(
 		&self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
-	) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
-		self.source.get(path, if_match, if_none_match, true)
+	) -> Result<crate::item::Item, DataSourceError> {
+		self.source
+			.read()
+			.unwrap()
+			.get(path, if_match, if_none_match, true)
 	}
 
 	pub fn put(
-		&mut self,
+		&self,
 		path: &crate::item::ItemPath,
 		content: crate::item::Item,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 	) -> PutResult {
 		/*
@@ -40,21 +118,668 @@ impl Database {
 				section 7.2].
 		*/
 
-		self.source.put(path, if_match, if_none_match, content)
+		return put_with_strategy(
+			&mut **self.source.write().unwrap(),
+			self.etag_strategy,
+			path,
+			if_match,
+			if_none_match,
+			content,
+		);
 	}
 
 	pub fn delete(
-		&mut self,
+		&self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
-	) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
-		/*
-		TODO : option to keep old documents ?
-			A provider MAY offer version rollback functionality to its users,
-			but this specification does not define the interface for that.
-		*/
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, DataSourceError> {
+		return delete_with_strategy(
+			&mut **self.source.write().unwrap(),
+			self.etag_strategy,
+			self.trash.as_deref(),
+			path,
+			if_match,
+		);
+	}
+
+	/// Copies the document at `from` to `to`, in one operation.
+	///
+	/// `if_match` applies to `from`, and `if_none_match` to `to`.
+	pub fn copy_item(
+		&self,
+		from: &crate::item::ItemPath,
+		to: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+	) -> PutResult {
+		return transfer_with_strategy(
+			&mut **self.source.write().unwrap(),
+			self.etag_strategy,
+			from,
+			to,
+			if_match,
+			if_none_match,
+			false,
+		);
+	}
+
+	/// Moves the document at `from` to `to`, in one operation, instead of a `get`,
+	/// a `put` and a `delete` which could be interleaved with other changes.
+	///
+	/// `if_match` applies to `from`, and `if_none_match` to `to`.
+	pub fn move_item(
+		&self,
+		from: &crate::item::ItemPath,
+		to: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+	) -> PutResult {
+		return transfer_with_strategy(
+			&mut **self.source.write().unwrap(),
+			self.etag_strategy,
+			from,
+			to,
+			if_match,
+			if_none_match,
+			true,
+		);
+	}
 
-		self.source.delete(path, if_match)
+	/// Lists the children of the folder at `path`, see [`DataSource::list`].
+	pub fn list(
+		&self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+		options: &ListingOptions,
+	) -> Result<Listing, DataSourceError> {
+		return self
+			.source
+			.read()
+			.unwrap()
+			.list(path, if_match, if_none_match, options);
+	}
+
+	/// Applies `operations` in their order, in one exclusive write, see [`DataSource::batch`].
+	///
+	/// With [`EtagStrategy::ContentHash`], ETags are derived again after each successful operation.
+	pub fn batch(&self, operations: &[BatchOperation]) -> Vec<BatchResult> {
+		return batch_with_strategy(
+			&mut **self.source.write().unwrap(),
+			self.etag_strategy,
+			self.trash.as_deref(),
+			operations,
+		);
+	}
+
+	/// Writes all documents of `username` (in `<username>/` and `public/<username>/`) in `writer`,
+	/// as a tar archive which keeps their content type, ETag and last modification date.
+	///
+	/// Writes wait until the export is done, so it is consistent. Returns how many documents were exported.
+	#[cfg(feature = "actix_server")]
+	pub fn export_user(
+		&self,
+		username: &str,
+		writer: impl std::io::Write,
+	) -> Result<usize, DataSourceError> {
+		return archive::export_user(&**self.source.read().unwrap(), username, writer);
+	}
+
+	/// Puts all documents of the archive read from `reader` (see [`export_user`][`Database::export_user`])
+	/// in the storage of `username`, in place of existing ones.
+	///
+	/// The archive can come from another user or another server. Returns how many documents were imported.
+	#[cfg(feature = "actix_server")]
+	pub fn import_user(
+		&self,
+		username: &str,
+		reader: impl std::io::Read,
+		etags: ImportEtags,
+	) -> Result<usize, DataSourceError> {
+		return archive::import_user(
+			&mut **self.source.write().unwrap(),
+			self.etag_strategy,
+			username,
+			reader,
+			etags,
+		);
+	}
+
+	/// Copies all documents in `to`, then checks that they are the same, see [`migrate`].
+	///
+	/// Writes wait until the migration is done.
+	pub fn migrate_to(
+		&self,
+		to: &mut dyn DataSource,
+		destination: MigrationDestination,
+	) -> Result<Migration, DataSourceError> {
+		return migrate(&**self.source.read().unwrap(), to, destination);
+	}
+
+	/// The documents of `username` which are in the trash, without their content,
+	/// the most recently deleted first.
+	///
+	/// Expired ones are purged before.
+	pub fn trashed_items(&self, username: &str) -> Result<Vec<TrashedItem>, DataSourceError> {
+		return match &self.trash {
+			Some(trash) => {
+				let mut trash = trash.write().unwrap();
+				trash
+					.purge(time::OffsetDateTime::now_utc())
+					.map_err(|error| DataSourceError::InternalError { error })?;
+
+				Ok(trash.list(username))
+			}
+			None => Ok(vec![]),
+		};
+	}
+
+	/// The document with `id` in the trash, without its content.
+	pub fn trashed_item(&self, id: &str) -> Option<TrashedItem> {
+		return self.trash.as_ref().and_then(|trash| {
+			trash.read().unwrap().get(id).map(|trashed| TrashedItem {
+				item: trashed.item.empty_clone(),
+				..trashed.clone()
+			})
+		});
+	}
+
+	/// Puts the document with `id` back at its former path, then removes it from the trash.
+	///
+	/// It fails if there is an item at this path again.
+	pub fn restore_trashed(&self, id: &str) -> PutResult {
+		let mut source = self.source.write().unwrap();
+
+		let trash = match &self.trash {
+			Some(trash) => trash,
+			None => {
+				return PutResult::Err(DataSourceError::NotFound {
+					item_path: crate::item::ItemPath::from(id),
+				})
+			}
+		};
+		let mut trash = trash.write().unwrap();
+
+		let trashed = match trash.load(id) {
+			Ok(Some(trashed)) => trashed,
+			Ok(None) => {
+				return PutResult::Err(DataSourceError::NotFound {
+					item_path: crate::item::ItemPath::from(id),
+				})
+			}
+			Err(error) => return PutResult::Err(DataSourceError::InternalError { error }),
+		};
+
+		let result = put_with_strategy(
+			&mut **source,
+			self.etag_strategy,
+			&crate::item::ItemPath::from(trashed.path.as_str()),
+			&[],
+			&[&crate::item::Etag::from("*")],
+			trashed.item,
+		);
+
+		if let PutResult::Created(_, _) | PutResult::Updated(_, _) = result {
+			if let Err(error) = trash.remove(id) {
+				return PutResult::Err(DataSourceError::InternalError { error });
+			}
+		}
+
+		return result;
+	}
+
+	/// Removes the documents which are in the trash for longer than its retention, and gives how many they were.
+	pub fn purge_trash(&self) -> Result<usize, DataSourceError> {
+		return match &self.trash {
+			Some(trash) => trash
+				.write()
+				.unwrap()
+				.purge(time::OffsetDateTime::now_utc())
+				.map_err(|error| DataSourceError::InternalError { error }),
+			None => Ok(0),
+		};
+	}
+
+	/// Replaces the ETags of the item at `path` (and of all its children) and of
+	/// its ancestors by ones derived from their content, whatever the current
+	/// [`EtagStrategy`] is.
+	///
+	/// Use it with an empty path to update all existing items, after switching to
+	/// [`EtagStrategy::ContentHash`] or restoring a backup, for example.
+	pub fn rehash_etags(
+		&self,
+		path: &crate::item::ItemPath,
+	) -> Result<Option<crate::item::Etag>, DataSourceError> {
+		return self.source.write().unwrap().rehash_etags(path);
+	}
+}
+
+fn put_with_strategy(
+	source: &mut dyn DataSource,
+	etag_strategy: EtagStrategy,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+	new_item: crate::item::Item,
+) -> PutResult {
+	let result = source.put(path, if_match, if_none_match, new_item);
+
+	if etag_strategy == EtagStrategy::ContentHash {
+		if let PutResult::Created(_, _) | PutResult::Updated(_, _) = result {
+			return match source.rehash_etags(path) {
+				Ok(Some(etag)) => match result {
+					PutResult::Created(_, last_modified) => PutResult::Created(etag, last_modified),
+					PutResult::Updated(_, last_modified) => PutResult::Updated(etag, last_modified),
+					PutResult::Err(error) => PutResult::Err(error),
+				},
+				Ok(None) => result,
+				Err(error) => PutResult::Err(error),
+			};
+		}
+	}
+
+	return result;
+}
+
+fn transfer_with_strategy(
+	source: &mut dyn DataSource,
+	etag_strategy: EtagStrategy,
+	from: &crate::item::ItemPath,
+	to: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+	remove_source: bool,
+) -> PutResult {
+	let result = if remove_source {
+		source.move_item(from, to, if_match, if_none_match)
+	} else {
+		source.copy_item(from, to, if_match, if_none_match)
+	};
+
+	if etag_strategy == EtagStrategy::ContentHash {
+		if let PutResult::Created(_, _) | PutResult::Updated(_, _) = result {
+			if remove_source {
+				if let Err(error) = source.rehash_etags(from) {
+					return PutResult::Err(error);
+				}
+			}
+
+			return match source.rehash_etags(to) {
+				Ok(Some(etag)) => match result {
+					PutResult::Created(_, last_modified) => PutResult::Created(etag, last_modified),
+					PutResult::Updated(_, last_modified) => PutResult::Updated(etag, last_modified),
+					PutResult::Err(error) => PutResult::Err(error),
+				},
+				Ok(None) => result,
+				Err(error) => PutResult::Err(error),
+			};
+		}
+	}
+
+	return result;
+}
+
+fn batch_with_strategy(
+	source: &mut dyn DataSource,
+	etag_strategy: EtagStrategy,
+	trash: Option<&RwLock<Trash>>,
+	operations: &[BatchOperation],
+) -> Vec<BatchResult> {
+	let mut results = match trash {
+		Some(trash) => batch_to_trash(source, &mut trash.write().unwrap(), operations),
+		None => source.batch(operations),
+	};
+
+	if etag_strategy == EtagStrategy::ContentHash {
+		for (operation, result) in operations.iter().zip(results.iter_mut()) {
+			if result.is_ok() {
+				match (source.rehash_etags(operation.path()), &*result) {
+					(Ok(Some(etag)), BatchResult::Put(PutResult::Created(_, last_modified))) => {
+						*result = BatchResult::Put(PutResult::Created(etag, *last_modified));
+					}
+					(Ok(Some(etag)), BatchResult::Put(PutResult::Updated(_, last_modified))) => {
+						*result = BatchResult::Put(PutResult::Updated(etag, *last_modified));
+					}
+					(Ok(_), _) => {}
+					(Err(error), BatchResult::Put(_)) => {
+						*result = BatchResult::Put(PutResult::Err(error));
+					}
+					(Err(error), BatchResult::Delete(_)) => {
+						*result = BatchResult::Delete(Err(error));
+					}
+				}
+			}
+		}
+	}
+
+	return results;
+}
+
+/// Applies `operations` like [`DataSource::batch`], and puts the documents they delete in `trash`.
+fn batch_to_trash(
+	source: &mut dyn DataSource,
+	trash: &mut Trash,
+	operations: &[BatchOperation],
+) -> Vec<BatchResult> {
+	// fetched before applying any operation, so documents put earlier in the
+	// same batch are trashed in their previous version only
+	let trashables: Vec<_> = operations
+		.iter()
+		.map(|operation| match operation {
+			BatchOperation::Put { .. } => Ok(vec![]),
+			BatchOperation::Delete { path, .. } => trashable(source, path),
+		})
+		.collect();
+
+	let applied: Vec<BatchOperation> = operations
+		.iter()
+		.zip(trashables.iter())
+		.filter(|(_, trashable)| trashable.is_ok())
+		.map(|(operation, _)| operation.clone())
+		.collect();
+	let mut applied_results = source.batch(&applied).into_iter();
+
+	return trashables
+		.into_iter()
+		.map(|trashable| match trashable {
+			Ok(documents) => {
+				let result = applied_results.next().unwrap();
+
+				if result.is_ok() {
+					if let Err(error) = put_in_trash(trash, documents) {
+						return BatchResult::Delete(Err(error));
+					}
+				}
+
+				result
+			}
+			Err(error) => BatchResult::Delete(Err(error)),
+		})
+		.collect();
+}
+
+/// The documents (with their content) which deleting `path` would lose.
+fn trashable(
+	source: &dyn DataSource,
+	path: &crate::item::ItemPath,
+) -> Result<Vec<(crate::item::ItemPath, crate::item::Item)>, DataSourceError> {
+	if path.is_document() {
+		return match source.get(path, &[], &[], true) {
+			Ok(item) => Ok(vec![(path.clone(), item)]),
+			// the deletion fails the same way
+			Err(_) => Ok(vec![]),
+		};
+	}
+
+	let folder = match source.get(path, &[], &[], false) {
+		Ok(folder) => folder,
+		Err(DataSourceError::NotFound { .. }) => return Ok(vec![]),
+		Err(error) => return Err(error),
+	};
+
+	let mut documents = vec![];
+	batch::collect_documents(source, path, &folder, &mut documents)?;
+
+	return documents
+		.into_iter()
+		.map(|document| {
+			let item = source.get(&document, &[], &[], true)?;
+
+			Ok((document, item))
+		})
+		.collect();
+}
+
+/// Adds all `documents` to `trash`, or none of them, and gives their ids.
+fn put_in_trash(
+	trash: &mut Trash,
+	documents: Vec<(crate::item::ItemPath, crate::item::Item)>,
+) -> Result<Vec<String>, DataSourceError> {
+	let mut ids = vec![];
+
+	for (path, item) in documents {
+		match trash.push(&path, item) {
+			Ok(id) => ids.push(id),
+			Err(error) => {
+				take_out_of_trash(trash, &ids);
+
+				return Err(DataSourceError::InternalError { error });
+			}
+		}
+	}
+
+	return Ok(ids);
+}
+
+/// Removes items with `ids` from `trash`, as best as it can.
+fn take_out_of_trash(trash: &mut Trash, ids: &[String]) {
+	for id in ids {
+		trash.remove(id).ok();
+	}
+}
+
+fn delete_with_strategy(
+	source: &mut dyn DataSource,
+	etag_strategy: EtagStrategy,
+	trash: Option<&RwLock<Trash>>,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+) -> Result<crate::item::Etag, DataSourceError> {
+	// saved before the deletion, so documents are never lost
+	let trashed = match trash {
+		Some(trash) => put_in_trash(&mut trash.write().unwrap(), trashable(source, path)?)?,
+		None => vec![],
+	};
+
+	let etag = match source.delete(path, if_match) {
+		Ok(etag) => etag,
+		Err(error) => {
+			if let Some(trash) = trash {
+				take_out_of_trash(&mut trash.write().unwrap(), &trashed);
+			}
+
+			return Err(error);
+		}
+	};
+
+	if etag_strategy == EtagStrategy::ContentHash {
+		source.rehash_etags(path)?;
+	}
+
+	return Ok(etag);
+}
+
+#[cfg(feature = "actix_server")]
+impl AsyncDataSource for Database {
+	fn get(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		get_content: bool,
+	) -> DataSourceFuture<Result<crate::item::Item, DataSourceError>> {
+		let source = self.source.clone();
+
+		return Box::pin(async move {
+			actix_web::web::block(move || {
+				source.read().unwrap().get(
+					&path,
+					&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					&if_none_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					get_content,
+				)
+			})
+			.await
+			.unwrap_or_else(|error| {
+				Err(DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+		});
+	}
+
+	fn put(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		new_item: crate::item::Item,
+	) -> DataSourceFuture<PutResult> {
+		let source = self.source.clone();
+		let etag_strategy = self.etag_strategy;
+
+		return Box::pin(async move {
+			actix_web::web::block(move || {
+				put_with_strategy(
+					&mut **source.write().unwrap(),
+					etag_strategy,
+					&path,
+					&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					&if_none_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					new_item,
+				)
+			})
+			.await
+			.unwrap_or_else(|error| {
+				PutResult::Err(DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+		});
+	}
+
+	fn delete(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+	) -> DataSourceFuture<Result<crate::item::Etag, DataSourceError>> {
+		let source = self.source.clone();
+		let etag_strategy = self.etag_strategy;
+		let trash = self.trash.clone();
+
+		return Box::pin(async move {
+			actix_web::web::block(move || {
+				delete_with_strategy(
+					&mut **source.write().unwrap(),
+					etag_strategy,
+					trash.as_deref(),
+					&path,
+					&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+				)
+			})
+			.await
+			.unwrap_or_else(|error| {
+				Err(DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+		});
+	}
+
+	fn list(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		options: ListingOptions,
+	) -> DataSourceFuture<Result<Listing, DataSourceError>> {
+		let source = self.source.clone();
+
+		return Box::pin(async move {
+			actix_web::web::block(move || {
+				source.read().unwrap().list(
+					&path,
+					&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					&if_none_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					&options,
+				)
+			})
+			.await
+			.unwrap_or_else(|error| {
+				Err(DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+		});
+	}
+
+	fn copy_item(
+		&self,
+		from: crate::item::ItemPath,
+		to: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+	) -> DataSourceFuture<PutResult> {
+		return self.transfer(from, to, if_match, if_none_match, false);
+	}
+
+	fn move_item(
+		&self,
+		from: crate::item::ItemPath,
+		to: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+	) -> DataSourceFuture<PutResult> {
+		return self.transfer(from, to, if_match, if_none_match, true);
+	}
+
+	fn batch(
+		&self,
+		operations: Vec<BatchOperation>,
+	) -> DataSourceFuture<Result<Vec<BatchResult>, DataSourceError>> {
+		let source = self.source.clone();
+		let etag_strategy = self.etag_strategy;
+		let trash = self.trash.clone();
+
+		return Box::pin(async move {
+			actix_web::web::block(move || {
+				batch_with_strategy(
+					&mut **source.write().unwrap(),
+					etag_strategy,
+					trash.as_deref(),
+					&operations,
+				)
+			})
+			.await
+			.map_err(|error| DataSourceError::InternalError {
+				error: format!("{}", error),
+			})
+		});
+	}
+}
+
+#[cfg(feature = "actix_server")]
+impl Database {
+	fn transfer(
+		&self,
+		from: crate::item::ItemPath,
+		to: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		remove_source: bool,
+	) -> DataSourceFuture<PutResult> {
+		let source = self.source.clone();
+		let etag_strategy = self.etag_strategy;
+
+		return Box::pin(async move {
+			actix_web::web::block(move || {
+				transfer_with_strategy(
+					&mut **source.write().unwrap(),
+					etag_strategy,
+					&from,
+					&to,
+					&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					&if_none_match.iter().collect::<Vec<&crate::item::Etag>>(),
+					remove_source,
+				)
+			})
+			.await
+			.unwrap_or_else(|error| {
+				PutResult::Err(DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+		});
 	}
 }
 
@@ -63,7 +788,7 @@ impl Database {
 pub enum PutResult {
 	Created(crate::item::Etag, time::OffsetDateTime),
 	Updated(crate::item::Etag, time::OffsetDateTime),
-	Err(Box<dyn std::error::Error>),
+	Err(DataSourceError),
 }
 impl PutResult {
 	pub fn unwrap(self) -> (crate::item::Etag, time::OffsetDateTime) {
@@ -73,7 +798,7 @@ impl PutResult {
 			Self::Err(e) => panic!("{}", e),
 		}
 	}
-	pub fn unwrap_err(self) -> Box<dyn std::error::Error> {
+	pub fn unwrap_err(self) -> DataSourceError {
 		match self {
 			Self::Created(etag, last_modified) => panic!("found Created({etag}, {last_modified})"),
 			Self::Updated(etag, last_modified) => panic!("found Updated({etag}, {last_modified})"),
@@ -96,7 +821,6 @@ struct JsonResponse {
 
 #[cfg(feature = "actix_server")]
 pub fn build_http_json_response(
-	origin: &str,
 	request_method: &actix_web::http::Method,
 	code: actix_web::http::StatusCode,
 	etag: Option<crate::item::Etag>,
@@ -109,16 +833,6 @@ pub fn build_http_json_response(
 	if request_method == actix_web::http::Method::GET && code.is_success() {
 		response.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"));
 	}
-	response.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin));
-
-	if origin != "*" {
-		response.insert_header((actix_web::http::header::VARY, "Origin"));
-	}
-
-	response.insert_header((
-		actix_web::http::header::ACCESS_CONTROL_EXPOSE_HEADERS,
-		"Content-Length, Content-Type, ETag, Last-Modified",
-	));
 
 	if let Some(etag) = &etag {
 		let etag: String = (*etag).clone().into();
@@ -147,8 +861,3 @@ pub fn build_http_json_response(
 		response.finish()
 	};
 }
-
-#[cfg(feature = "server")]
-pub trait Error: std::fmt::Debug + std::error::Error {
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse;
-}
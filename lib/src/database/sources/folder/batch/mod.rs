@@ -0,0 +1,234 @@
+#[cfg(test)]
+pub mod tests;
+
+/// Applies `operations`, then renews only once the ETag of each folder touched by them.
+pub fn batch(
+	root_folder_path: &std::path::Path,
+	operations: &[crate::database::BatchOperation],
+) -> Vec<crate::database::BatchResult> {
+	let mut touched_folders = vec![];
+
+	let mut results: Vec<crate::database::BatchResult> = operations
+		.iter()
+		.map(|operation| match operation {
+			crate::database::BatchOperation::Put {
+				path,
+				if_match,
+				if_none_match,
+				item,
+			} => crate::database::BatchResult::Put(super::put::put_internal(
+				root_folder_path,
+				path,
+				&if_match.iter().collect::<Vec<&crate::item::Etag>>(),
+				&if_none_match.iter().collect::<Vec<&crate::item::Etag>>(),
+				item.clone(),
+				Some(&mut touched_folders),
+			)),
+			crate::database::BatchOperation::Delete { path, if_match } => {
+				let if_match = if_match.iter().collect::<Vec<&crate::item::Etag>>();
+
+				crate::database::BatchResult::Delete(if path.is_folder() {
+					delete_folder(
+						root_folder_path,
+						path,
+						&if_match,
+						Some(&mut touched_folders),
+					)
+				} else {
+					super::delete::delete_internal(
+						root_folder_path,
+						path,
+						&if_match,
+						Some(&mut touched_folders),
+					)
+					.map_err(crate::database::DataSourceError::from_boxed::<super::DeleteError>)
+				})
+			}
+		})
+		.collect();
+
+	if let Err(error) = renew_folder_etags(root_folder_path, &touched_folders) {
+		// operations are applied, but clients can not see it from the ETags of folders
+		for result in results.iter_mut().filter(|result| result.is_ok()) {
+			*result = match result {
+				crate::database::BatchResult::Put(_) => crate::database::BatchResult::Put(
+					crate::database::PutResult::Err(error.clone()),
+				),
+				crate::database::BatchResult::Delete(_) => {
+					crate::database::BatchResult::Delete(Err(error.clone()))
+				}
+			};
+		}
+	}
+
+	return results;
+}
+
+/// Deletes the folder at `path` with all the documents inside it, even in `public/`.
+///
+/// ETags of remaining ancestors are renewed only once, or added to `touched_folders` if it is given.
+pub fn delete_folder(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	touched_folders: Option<&mut Vec<crate::item::ItemPath>>,
+) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+	if !path.is_folder() {
+		return Err(crate::database::DataSourceError::Conflict {
+			item_path: path.clone(),
+		});
+	}
+
+	let mut cumulated_path = crate::item::ItemPath::from("");
+	for part in path.parts_iter() {
+		cumulated_path = cumulated_path.joined(part).unwrap();
+		if let Err(error) = part.check_validity(true) {
+			return Err(crate::database::DataSourceError::IncorrectItemName {
+				item_path: cumulated_path,
+				error,
+			});
+		}
+	}
+
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+	if !target.is_dir() {
+		return Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		});
+	}
+
+	let folderdata: crate::item::DataFolder =
+		super::rehash::read_itemdata(&target.join(".folder.itemdata.toml"))?;
+
+	if let Err(failure) =
+		crate::database::check_preconditions(Some(&folderdata.etag), if_match, &[])
+	{
+		return Err(crate::database::DataSourceError::from(
+			super::GetError::from_precondition(path.clone(), failure),
+		));
+	}
+
+	let mut documents = vec![];
+	collect_documents(root_folder_path, path, &mut documents)?;
+
+	let mut own_touched_folders = vec![];
+	let renew_here = touched_folders.is_none();
+	let touched_folders = touched_folders.unwrap_or(&mut own_touched_folders);
+
+	for document in documents {
+		super::delete::delete_internal(
+			root_folder_path,
+			&document,
+			&[],
+			Some(&mut *touched_folders),
+		)
+		.map_err(crate::database::DataSourceError::from_boxed::<super::DeleteError>)?;
+	}
+
+	// folders without documents are still there
+	if path != &crate::item::ItemPath::from("") && target.exists() {
+		std::fs::remove_dir_all(&target).map_err(|error| {
+			crate::database::DataSourceError::InternalError {
+				error: format!("can not delete `{}` : {}", target.to_string_lossy(), error),
+			}
+		})?;
+
+		for ancestor in path
+			.ancestors()
+			.into_iter()
+			.filter(|ancestor| ancestor != path)
+		{
+			touch(touched_folders, ancestor);
+		}
+	}
+
+	if renew_here {
+		renew_folder_etags(root_folder_path, touched_folders)?;
+	}
+
+	return Ok(folderdata.etag);
+}
+
+/// Adds `folder` to `touched_folders`, if it is not already inside.
+pub fn touch(touched_folders: &mut Vec<crate::item::ItemPath>, folder: crate::item::ItemPath) {
+	if !touched_folders.contains(&folder) {
+		touched_folders.push(folder);
+	}
+}
+
+/// Gives a new ETag to each folder of `folders` which still exists.
+pub fn renew_folder_etags(
+	root_folder_path: &std::path::Path,
+	folders: &[crate::item::ItemPath],
+) -> Result<(), crate::database::DataSourceError> {
+	for folder in folders {
+		let folderdata_path = root_folder_path
+			.join(std::path::PathBuf::from(folder))
+			.join(".folder.itemdata.toml");
+
+		if folderdata_path.exists() {
+			let mut folderdata: crate::item::DataFolder =
+				super::rehash::read_itemdata(&folderdata_path)?;
+			folderdata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+			folderdata.etag = crate::item::Etag::new();
+			super::rehash::write_itemdata(&folderdata_path, &folderdata)?;
+		}
+	}
+
+	return Ok(());
+}
+
+/// Lists the paths of all documents inside the folder at `path`, even in `public/`.
+pub fn documents_in(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	if !path.is_folder() {
+		return Err(crate::database::DataSourceError::Conflict {
+			item_path: path.clone(),
+		});
+	}
+	if !target.is_dir() {
+		return Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		});
+	}
+
+	let mut documents = vec![];
+	collect_documents(root_folder_path, path, &mut documents)?;
+
+	return Ok(documents);
+}
+
+/// Adds the paths of all documents inside the folder at `path` to `documents`.
+fn collect_documents(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	documents: &mut Vec<crate::item::ItemPath>,
+) -> Result<(), crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	let entries = std::fs::read_dir(&target).map_err(|error| {
+		crate::database::DataSourceError::InternalError {
+			error: format!("can not read `{}` : {}", target.to_string_lossy(), error),
+		}
+	})?;
+
+	for entry in entries.flatten() {
+		let name = entry.file_name().to_string_lossy().to_string();
+
+		if entry.path().is_dir() {
+			if let Ok(child_path) = path.joined_folder(&name) {
+				collect_documents(root_folder_path, &child_path, documents)?;
+			}
+		} else if !name.ends_with(".itemdata.toml") {
+			if let Ok(child_path) = path.joined_doc(&name) {
+				documents.push(child_path);
+			}
+		}
+	}
+
+	return Ok(());
+}
@@ -0,0 +1,126 @@
+use super::{batch, delete_folder};
+use crate::database::{BatchOperation, BatchResult, PutResult};
+use crate::item::{Etag, Item, ItemPath};
+
+#[test]
+fn q8mw3zt5hk1xcv7nrd() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	println!(
+		"folder dedicated to this test : {}",
+		tmp_folder.path().to_string_lossy()
+	);
+
+	for path in [
+		"user/notes/a.txt",
+		"user/notes/old/b.txt",
+		"user/notes/old/c/d.txt",
+		"user/other/e.txt",
+		"public/user/notes/f.txt",
+	] {
+		super::super::put::put(
+			tmp_folder.path(),
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(b"CONTENT", "text/plain"),
+		)
+		.unwrap();
+	}
+
+	let etag_of = |path: &str| -> Etag {
+		let folderdata: crate::item::DataFolder = toml::from_slice(
+			&std::fs::read(tmp_folder.path().join(path).join(".folder.itemdata.toml")).unwrap(),
+		)
+		.unwrap();
+
+		folderdata.etag
+	};
+
+	let root_etag = etag_of("");
+	let user_etag = etag_of("user");
+	let notes_etag = etag_of("user/notes");
+	let other_etag = etag_of("user/other");
+
+	let results = batch(
+		tmp_folder.path(),
+		&[
+			BatchOperation::Put {
+				path: ItemPath::from("user/notes/new.txt"),
+				if_match: vec![],
+				if_none_match: vec![Etag::from("*")],
+				item: Item::new_doc(b"NEW", "text/plain"),
+			},
+			BatchOperation::Put {
+				path: ItemPath::from("user/notes/a.txt"),
+				if_match: vec![Etag::from("wrong")],
+				if_none_match: vec![],
+				item: Item::new_doc(b"A", "text/plain"),
+			},
+			BatchOperation::Delete {
+				path: ItemPath::from("user/notes/old/"),
+				if_match: vec![],
+			},
+			BatchOperation::Delete {
+				path: ItemPath::from("user/notes/a.txt"),
+				if_match: vec![],
+			},
+			BatchOperation::Delete {
+				path: ItemPath::from("user/missing/"),
+				if_match: vec![],
+			},
+		],
+	);
+
+	assert_eq!(results.len(), 5);
+	assert!(matches!(
+		results[0],
+		BatchResult::Put(PutResult::Created(_, _)) | BatchResult::Put(PutResult::Updated(_, _))
+	));
+	assert!(matches!(
+		results[1],
+		BatchResult::Put(PutResult::Err(
+			crate::database::DataSourceError::NoIfMatch { .. }
+		))
+	));
+	assert!(results[2].is_ok());
+	assert!(results[3].is_ok());
+	assert!(matches!(
+		results[4],
+		BatchResult::Delete(Err(crate::database::DataSourceError::NotFound { .. }))
+	));
+
+	assert!(tmp_folder.path().join("user/notes/new.txt").exists());
+	assert!(!tmp_folder.path().join("user/notes/a.txt").exists());
+	assert!(!tmp_folder.path().join("user/notes/old").exists());
+
+	assert_ne!(etag_of(""), root_etag);
+	assert_ne!(etag_of("user"), user_etag);
+	assert_ne!(etag_of("user/notes"), notes_etag);
+	assert_eq!(etag_of("user/other"), other_etag);
+
+	let public_etag = etag_of("public/user/notes");
+	assert_eq!(
+		delete_folder(
+			tmp_folder.path(),
+			&ItemPath::from("public/user/notes/"),
+			&[&Etag::from("wrong")],
+			None,
+		),
+		Err(crate::database::DataSourceError::NoIfMatch {
+			item_path: ItemPath::from("public/user/notes/"),
+			search: Etag::from("wrong"),
+			found: public_etag.clone(),
+		})
+	);
+	assert_eq!(
+		delete_folder(
+			tmp_folder.path(),
+			&ItemPath::from("public/user/notes/"),
+			&[&public_etag],
+			None,
+		),
+		Ok(public_etag)
+	);
+	assert!(!tmp_folder.path().join("public").exists());
+	assert!(tmp_folder.path().join("user/other/e.txt").exists());
+}
@@ -0,0 +1,51 @@
+/// A problem found by [`FolderStorage::check`][`crate::database::sources::FolderStorage::check`] in a data folder,
+/// because of hand edits or crashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+	/// The metadata of a document which does not exist.
+	OrphanItemdata { os_path: std::path::PathBuf },
+	/// A document without metadata, so it can not be read.
+	MissingItemdata {
+		item_path: crate::item::ItemPath,
+		os_path: std::path::PathBuf,
+	},
+	/// A folder without metadata, so it can not be read.
+	MissingFolderdata {
+		item_path: crate::item::ItemPath,
+		os_path: std::path::PathBuf,
+	},
+	/// The metadata of the item at `item_path` can not be deserialized, so it can not be read.
+	CanNotDeserializeFile {
+		item_path: crate::item::ItemPath,
+		os_path: std::path::PathBuf,
+		error: String,
+	},
+	/// The content of the document at `item_path` does not match the checksum of its metadata,
+	/// see [`FolderStorage::scrub`][`crate::database::sources::FolderStorage::scrub`].
+	///
+	/// It can not be repaired, because its content should be restored from a backup.
+	CorruptedContent {
+		item_path: crate::item::ItemPath,
+		os_path: std::path::PathBuf,
+	},
+	/// A file or a folder whose name is not allowed, see [`ItemPathPart::check_validity`][`crate::item::ItemPathPart::check_validity`].
+	///
+	/// It can not be repaired, because it would need to be renamed.
+	IncorrectItemName {
+		os_path: std::path::PathBuf,
+		error: String,
+	},
+}
+impl std::fmt::Display for Inconsistency {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		match self {
+			Self::OrphanItemdata { os_path } => f.write_fmt(format_args!("the metadata file `{:?}` does not describe any document", os_path)),
+			Self::MissingItemdata { item_path, os_path } => f.write_fmt(format_args!("the document `{}` can not be read, because its metadata file `{:?}` is missing", item_path, os_path)),
+			Self::MissingFolderdata { item_path, os_path } => f.write_fmt(format_args!("the folder `{}` can not be read, because its metadata file `{:?}` is missing", item_path, os_path)),
+			Self::CanNotDeserializeFile { item_path, os_path, error } => f.write_fmt(format_args!("the item `{}` can not be read, because its metadata file `{:?}` can not be deserialized : {}", item_path, os_path, error)),
+			Self::CorruptedContent { item_path, os_path } => f.write_fmt(format_args!("the content of the document `{}` in file `{:?}` is corrupted, because it does not match its checksum", item_path, os_path)),
+			Self::IncorrectItemName { os_path, error } => f.write_fmt(format_args!("the name of `{:?}` is incorrect, because {}", os_path, error)),
+		}
+	}
+}
+impl std::error::Error for Inconsistency {}
@@ -0,0 +1,267 @@
+mod inconsistency;
+pub use inconsistency::*;
+
+#[cfg(test)]
+pub mod tests;
+
+/// Scans the whole data folder at `root_folder_path`, and returns all the inconsistencies inside it.
+pub fn check(
+	root_folder_path: &std::path::Path,
+) -> Result<Vec<Inconsistency>, crate::database::DataSourceError> {
+	let mut inconsistencies = vec![];
+
+	if root_folder_path.is_dir() {
+		check_folder(
+			root_folder_path,
+			&crate::item::ItemPath::from(""),
+			&mut inconsistencies,
+		)?;
+	}
+
+	return Ok(inconsistencies);
+}
+
+/// Reads the content of all documents of the data folder at `root_folder_path`, and returns the ones
+/// which do not match the checksum of their metadata, as [`Inconsistency::CorruptedContent`].
+///
+/// Documents without checksum (written by older versions) or without readable metadata
+/// (reported by [`check`]) are skipped.
+pub fn scrub(
+	root_folder_path: &std::path::Path,
+) -> Result<Vec<Inconsistency>, crate::database::DataSourceError> {
+	let mut damaged = vec![];
+
+	if root_folder_path.is_dir() {
+		scrub_folder(
+			root_folder_path,
+			&crate::item::ItemPath::from(""),
+			&mut damaged,
+		)?;
+	}
+
+	return Ok(damaged);
+}
+
+/// Fixes `inconsistencies` found by [`check`], and returns the ones which can not be fixed.
+///
+/// Missing or broken metadata is written again with a new ETag (so a default content type for documents),
+/// and orphan metadata is deleted. ETags of the ancestors of fixed items are renewed.
+pub fn repair(
+	root_folder_path: &std::path::Path,
+	inconsistencies: &[Inconsistency],
+) -> Result<Vec<Inconsistency>, crate::database::DataSourceError> {
+	let mut remaining = vec![];
+	let mut touched_folders = vec![];
+
+	for inconsistency in inconsistencies {
+		match inconsistency {
+			Inconsistency::OrphanItemdata { os_path } => {
+				if os_path.exists() {
+					std::fs::remove_file(os_path)
+						.map_err(|error| super::rehash::io_error(os_path, error))?;
+				}
+			}
+			Inconsistency::MissingItemdata { item_path, os_path }
+			| Inconsistency::CanNotDeserializeFile {
+				item_path, os_path, ..
+			} => {
+				if item_path.is_folder() {
+					super::rehash::write_itemdata(os_path, &crate::item::DataFolder::default())?;
+				} else {
+					let target = root_folder_path.join(std::path::PathBuf::from(item_path));
+					let metadata = std::fs::metadata(&target)
+						.map_err(|error| super::rehash::io_error(&target, error))?;
+					let content = std::fs::read(&target)
+						.map_err(|error| super::rehash::io_error(&target, error))?;
+
+					let itemdata = crate::item::DataDocument {
+						content_length: Some(metadata.len()),
+						checksum: Some(crate::item::DataDocument::checksum_of(&content)),
+						last_modified: Some(
+							metadata
+								.modified()
+								.map(time::OffsetDateTime::from)
+								.unwrap_or_else(|_| time::OffsetDateTime::now_utc()),
+						),
+						..Default::default()
+					};
+					super::rehash::write_itemdata(os_path, &itemdata)?;
+				}
+
+				for ancestor in item_path
+					.ancestors()
+					.into_iter()
+					.filter(|ancestor| ancestor != item_path)
+				{
+					super::batch::touch(&mut touched_folders, ancestor);
+				}
+			}
+			Inconsistency::MissingFolderdata { item_path, os_path } => {
+				super::rehash::write_itemdata(os_path, &crate::item::DataFolder::default())?;
+
+				for ancestor in item_path
+					.ancestors()
+					.into_iter()
+					.filter(|ancestor| ancestor != item_path)
+				{
+					super::batch::touch(&mut touched_folders, ancestor);
+				}
+			}
+			Inconsistency::CorruptedContent { .. } | Inconsistency::IncorrectItemName { .. } => {
+				remaining.push(inconsistency.clone());
+			}
+		}
+	}
+
+	super::batch::renew_folder_etags(root_folder_path, &touched_folders)?;
+
+	return Ok(remaining);
+}
+
+fn check_folder(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	inconsistencies: &mut Vec<Inconsistency>,
+) -> Result<(), crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	let folderdata_path = target.join(".folder.itemdata.toml");
+	if folderdata_path.is_file() {
+		check_itemdata::<crate::item::DataFolder>(path, &folderdata_path, inconsistencies);
+	} else {
+		inconsistencies.push(Inconsistency::MissingFolderdata {
+			item_path: path.clone(),
+			os_path: folderdata_path,
+		});
+	}
+
+	let mut entries = std::fs::read_dir(&target)
+		.map_err(|error| super::rehash::io_error(&target, error))?
+		.flatten()
+		.collect::<Vec<std::fs::DirEntry>>();
+	entries.sort_by_key(|entry| entry.file_name());
+
+	for entry in entries {
+		let name = entry.file_name().to_string_lossy().to_string();
+		let os_path = entry.path();
+
+		if os_path.is_dir() {
+			let part = crate::item::ItemPathPart::Folder(name);
+
+			match part.check_validity(true).and_then(|_| path.joined(&part)) {
+				Ok(child_path) => check_folder(root_folder_path, &child_path, inconsistencies)?,
+				Err(error) => {
+					inconsistencies.push(Inconsistency::IncorrectItemName { os_path, error });
+				}
+			}
+		} else if name == ".folder.itemdata.toml" {
+			// already checked
+		} else if let Some(document_name) = name
+			.strip_prefix('.')
+			.and_then(|name| name.strip_suffix(".itemdata.toml"))
+		{
+			if !target.join(document_name).is_file() {
+				inconsistencies.push(Inconsistency::OrphanItemdata { os_path });
+			}
+		} else {
+			let part = crate::item::ItemPathPart::Document(name.clone());
+
+			match part.check_validity(true).and_then(|_| path.joined(&part)) {
+				Ok(document_path) => {
+					let itemdata_path = target.join(format!(".{}.itemdata.toml", name));
+
+					if itemdata_path.is_file() {
+						check_itemdata::<crate::item::DataDocument>(
+							&document_path,
+							&itemdata_path,
+							inconsistencies,
+						);
+					} else {
+						inconsistencies.push(Inconsistency::MissingItemdata {
+							item_path: document_path,
+							os_path: itemdata_path,
+						});
+					}
+				}
+				Err(error) => {
+					inconsistencies.push(Inconsistency::IncorrectItemName { os_path, error });
+				}
+			}
+		}
+	}
+
+	return Ok(());
+}
+
+fn scrub_folder(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	damaged: &mut Vec<Inconsistency>,
+) -> Result<(), crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	let mut entries = std::fs::read_dir(&target)
+		.map_err(|error| super::rehash::io_error(&target, error))?
+		.flatten()
+		.collect::<Vec<std::fs::DirEntry>>();
+	entries.sort_by_key(|entry| entry.file_name());
+
+	for entry in entries {
+		let name = entry.file_name().to_string_lossy().to_string();
+		let os_path = entry.path();
+
+		if name.ends_with(".itemdata.toml") {
+			continue;
+		}
+
+		let part = if os_path.is_dir() {
+			crate::item::ItemPathPart::Folder(name.clone())
+		} else {
+			crate::item::ItemPathPart::Document(name.clone())
+		};
+		// incorrect names are reported by `check`
+		let item_path = match part.check_validity(true).and_then(|_| path.joined(&part)) {
+			Ok(item_path) => item_path,
+			Err(_) => continue,
+		};
+
+		if os_path.is_dir() {
+			scrub_folder(root_folder_path, &item_path, damaged)?;
+		} else {
+			let itemdata = match std::fs::read(target.join(format!(".{}.itemdata.toml", name)))
+				.ok()
+				.and_then(|content| toml::from_slice::<crate::item::DataDocument>(&content).ok())
+			{
+				Some(itemdata) => itemdata,
+				None => continue,
+			};
+
+			let content = std::fs::read(&os_path)
+				.map_err(|error| super::rehash::io_error(&os_path, error))?;
+
+			if !itemdata.is_intact(&content) {
+				damaged.push(Inconsistency::CorruptedContent { item_path, os_path });
+			}
+		}
+	}
+
+	return Ok(());
+}
+
+/// Checks that the metadata of the item at `path` can be deserialized as a `T`.
+fn check_itemdata<T: serde::de::DeserializeOwned>(
+	path: &crate::item::ItemPath,
+	itemdata_path: &std::path::Path,
+	inconsistencies: &mut Vec<Inconsistency>,
+) {
+	if let Err(error) = std::fs::read(itemdata_path)
+		.map_err(|error| format!("{}", error))
+		.and_then(|content| toml::from_slice::<T>(&content).map_err(|error| format!("{}", error)))
+	{
+		inconsistencies.push(Inconsistency::CanNotDeserializeFile {
+			item_path: path.clone(),
+			os_path: itemdata_path.to_path_buf(),
+			error,
+		});
+	}
+}
@@ -0,0 +1,160 @@
+use super::{check, repair, scrub, Inconsistency};
+use crate::item::{Item, ItemPath};
+
+#[test]
+fn p8dk3wz6rm1qvt9nhx() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	println!(
+		"folder dedicated to this test : {}",
+		tmp_folder.path().to_string_lossy()
+	);
+	let root = tmp_folder.path();
+
+	for path in ["A/AA", "A/AB", "B/BA", "C/CA", "D"] {
+		super::super::put::put(
+			root,
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(b"content", "text/plain"),
+		)
+		.unwrap();
+	}
+
+	assert_eq!(check(root).unwrap(), vec![]);
+
+	std::fs::remove_file(root.join("A").join(".AA.itemdata.toml")).unwrap();
+	std::fs::remove_file(root.join("A").join("AB")).unwrap();
+	std::fs::remove_file(root.join("B").join(".folder.itemdata.toml")).unwrap();
+	std::fs::write(root.join("C").join(".CA.itemdata.toml"), b"not toml").unwrap();
+	std::fs::create_dir(root.join("folder")).unwrap();
+
+	let inconsistencies = check(root).unwrap();
+	assert_eq!(inconsistencies.len(), 5);
+	assert_eq!(
+		inconsistencies[0],
+		Inconsistency::OrphanItemdata {
+			os_path: root.join("A").join(".AB.itemdata.toml"),
+		}
+	);
+	assert_eq!(
+		inconsistencies[1],
+		Inconsistency::MissingItemdata {
+			item_path: ItemPath::from("A/AA"),
+			os_path: root.join("A").join(".AA.itemdata.toml"),
+		}
+	);
+	assert_eq!(
+		inconsistencies[2],
+		Inconsistency::MissingFolderdata {
+			item_path: ItemPath::from("B/"),
+			os_path: root.join("B").join(".folder.itemdata.toml"),
+		}
+	);
+	assert!(matches!(
+		&inconsistencies[3],
+		Inconsistency::CanNotDeserializeFile { item_path, .. } if item_path == &ItemPath::from("C/CA")
+	));
+	assert!(matches!(
+		&inconsistencies[4],
+		Inconsistency::IncorrectItemName { os_path, .. } if os_path == &root.join("folder")
+	));
+
+	let root_etag = super::super::get::get(root, &ItemPath::from(""), &[], &[], false)
+		.unwrap()
+		.get_etag()
+		.clone();
+
+	assert_eq!(
+		repair(root, &inconsistencies).unwrap(),
+		vec![inconsistencies[4].clone()]
+	);
+	assert_eq!(check(root).unwrap(), vec![inconsistencies[4].clone()]);
+
+	assert!(!root.join("A").join(".AB.itemdata.toml").exists());
+	assert!(super::super::get::get(root, &ItemPath::from("B/"), &[], &[], true).is_ok());
+	for path in ["A/AA", "C/CA"] {
+		match super::super::get::get(root, &ItemPath::from(path), &[], &[], true).unwrap() {
+			Item::Document {
+				content,
+				content_type,
+				..
+			} => {
+				assert_eq!(content, Some(b"content".to_vec()));
+				assert_eq!(content_type, "application/octet-stream");
+			}
+			item => panic!("unexpected item : {:?}", item),
+		}
+	}
+	assert_ne!(
+		super::super::get::get(root, &ItemPath::from(""), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		&root_etag
+	);
+}
+
+#[test]
+fn f3nz8wq5vt1kmr6xdb() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	println!(
+		"folder dedicated to this test : {}",
+		tmp_folder.path().to_string_lossy()
+	);
+	let root = tmp_folder.path();
+
+	for path in ["A/AA", "A/AB", "B/BA"] {
+		super::super::put::put(
+			root,
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(b"content", "text/plain"),
+		)
+		.unwrap();
+	}
+
+	assert_eq!(scrub(root).unwrap(), vec![]);
+
+	std::fs::write(root.join("A").join("AB"), b"damaged").unwrap();
+	// written by an older version, so it can not be verified
+	let itemdata_path = root.join("B").join(".BA.itemdata.toml");
+	let mut itemdata: crate::item::DataDocument =
+		toml::from_slice(&std::fs::read(&itemdata_path).unwrap()).unwrap();
+	itemdata.checksum = None;
+	std::fs::write(&itemdata_path, toml::to_vec(&itemdata).unwrap()).unwrap();
+	std::fs::write(root.join("B").join("BA"), b"changed").unwrap();
+
+	let damaged = scrub(root).unwrap();
+	assert_eq!(
+		damaged,
+		vec![Inconsistency::CorruptedContent {
+			item_path: ItemPath::from("A/AB"),
+			os_path: root.join("A").join("AB"),
+		}]
+	);
+	assert_eq!(repair(root, &damaged).unwrap(), damaged);
+
+	assert_eq!(
+		*super::super::get::get(root, &ItemPath::from("A/AB"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<super::super::GetError>()
+			.unwrap(),
+		super::super::GetError::CorruptedContent {
+			item_path: ItemPath::from("A/AB"),
+			os_path: root.join("A").join("AB"),
+		}
+	);
+	assert!(super::super::get::get(root, &ItemPath::from("A/AB"), &[], &[], false).is_ok());
+	assert!(super::super::get::get(root, &ItemPath::from("B/BA"), &[], &[], true).is_ok());
+
+	super::super::put::put(
+		root,
+		&ItemPath::from("A/AB"),
+		&[],
+		&[],
+		Item::new_doc(b"restored", "text/plain"),
+	)
+	.unwrap();
+	assert_eq!(scrub(root).unwrap(), vec![]);
+}
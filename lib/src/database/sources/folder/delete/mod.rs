@@ -7,7 +7,18 @@ pub mod tests;
 pub fn delete(
 	root_folder_path: &std::path::Path,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
+) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
+	return delete_internal(root_folder_path, path, if_match, None);
+}
+
+/// Same as [`delete`], but if `touched_folders` is given, ETags of remaining ancestors are not renewed :
+/// their paths are added to it instead, see [`renew_folder_etags`][`super::batch::renew_folder_etags`].
+pub fn delete_internal(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	mut touched_folders: Option<&mut Vec<crate::item::ItemPath>>,
 ) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
 	if path.is_folder() {
 		return Err(Box::new(DeleteError::DoesNotWorksForFolders));
@@ -78,6 +89,11 @@ pub fn delete(
 								}
 							}
 						} else {
+							if let Some(touched_folders) = touched_folders.as_mut() {
+								super::batch::touch(touched_folders, parent);
+								continue;
+							}
+
 							let mut folderdata = match std::fs::read(
 								root_folder_path
 									.join(std::path::PathBuf::from(&parent))
@@ -189,14 +189,10 @@ fn simple_delete_on_not_existing() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*delete(
-			&tmp_folder_path,
-			&ItemPath::from("A/AA/AAA"),
-			&Etag::from(""),
-		)
-		.unwrap_err()
-		.downcast::<DeleteError>()
-		.unwrap(),
+		*delete(&tmp_folder_path, &ItemPath::from("A/AA/AAA"), &[],)
+			.unwrap_err()
+			.downcast::<DeleteError>()
+			.unwrap(),
 		DeleteError::GetError(GetError::NotFound {
 			item_path: ItemPath::from("A/")
 		})
@@ -211,12 +207,7 @@ fn simple_delete_on_existing() {
 		build_test_db();
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
-	let old_AAA_etag = delete(
-		&tmp_folder_path,
-		&ItemPath::from("A/AA/AAA"),
-		&Etag::from(""),
-	)
-	.unwrap();
+	let old_AAA_etag = delete(&tmp_folder_path, &ItemPath::from("A/AA/AAA"), &[]).unwrap();
 
 	assert_eq!(AAA_etag, old_AAA_etag);
 
@@ -315,7 +306,7 @@ fn does_not_works_for_folders() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*delete(&tmp_folder_path, &ItemPath::from("A/AA/"), &Etag::from(""),)
+		*delete(&tmp_folder_path, &ItemPath::from("A/AA/"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -442,7 +433,7 @@ fn delete_with_if_match_not_found() {
 		*delete(
 			&tmp_folder_path,
 			&ItemPath::from("A/AA/AAA"),
-			&Etag::from("OTHER_ETAG"),
+			&[&Etag::from("OTHER_ETAG")],
 		)
 		.unwrap_err()
 		.downcast::<DeleteError>()
@@ -570,7 +561,7 @@ fn delete_with_if_match_found() {
 		build_test_db();
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
-	let old_AAA_etag = delete(&tmp_folder_path, &ItemPath::from("A/AA/AAA"), &AAA_etag).unwrap();
+	let old_AAA_etag = delete(&tmp_folder_path, &ItemPath::from("A/AA/AAA"), &[&AAA_etag]).unwrap();
 
 	assert_eq!(old_AAA_etag, AAA_etag);
 
@@ -671,7 +662,7 @@ fn delete_with_if_match_all() {
 	let old_AAA_etag = delete(
 		&tmp_folder_path,
 		&ItemPath::from("A/AA/AAA"),
-		&Etag::from("*"),
+		&[&Etag::from("*")],
 	)
 	.unwrap();
 
@@ -772,7 +763,7 @@ fn delete_with_existing_folder_conflict() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*delete(&tmp_folder_path, &ItemPath::from("A/AA"), &Etag::from(""),)
+		*delete(&tmp_folder_path, &ItemPath::from("A/AA"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -897,12 +888,7 @@ fn delete_in_public() {
 		build_test_db();
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
-	let old_BA_etag = delete(
-		&tmp_folder_path,
-		&ItemPath::from("public/B/BA"),
-		&Etag::from(""),
-	)
-	.unwrap();
+	let old_BA_etag = delete(&tmp_folder_path, &ItemPath::from("public/B/BA"), &[]).unwrap();
 
 	assert_eq!(old_BA_etag, BA_etag);
 
@@ -995,7 +981,7 @@ fn delete_in_incorrect_path() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*delete(&tmp_folder_path, &ItemPath::from("A/A\0A"), &Etag::from(""),)
+		*delete(&tmp_folder_path, &ItemPath::from("A/A\0A"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
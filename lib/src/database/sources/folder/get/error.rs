@@ -34,6 +34,11 @@ pub enum GetError {
 	IOError {
 		error: String,
 	},
+	/// The content of the document does not match the checksum of its metadata.
+	CorruptedContent {
+		item_path: crate::item::ItemPath,
+		os_path: std::path::PathBuf,
+	},
 	IsSystemFile,
 }
 impl std::fmt::Display for GetError {
@@ -48,136 +53,67 @@ impl std::fmt::Display for GetError {
 			Self::CanNotReadFile { os_path, error } => f.write_fmt(format_args!("can not read file `{:?}`, because {}", os_path, error)),
 			Self::CanNotDeserializeFile { os_path, error } => f.write_fmt(format_args!("can not deserialize file `{:?}`, because {}", os_path, error)),
 			Self::IOError { error } => f.write_fmt(format_args!("file system error : {}", error)),
+			Self::CorruptedContent { item_path, os_path } => f.write_fmt(format_args!("the content of `{}` in file `{:?}` is corrupted, because it does not match its checksum", item_path, os_path)),
 			Self::IsSystemFile => f.write_str("this is a system file, that should not be server"),
 		}
 	}
 }
 impl std::error::Error for GetError {}
-#[cfg(feature = "server")]
-impl crate::database::Error for GetError {
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse {
-		match self {
-			Self::Conflict { item_path } => {
-				if item_path.starts_with("public/") {
-					crate::database::build_http_json_response(
-						origin,
-						&actix_web::http::Method::GET,
-						actix_web::http::StatusCode::NOT_FOUND,
-						None,
-						None,
-						Some(format!("path not found : `{}`", item_path)),
-						should_have_body,
-					)
-				} else {
-					crate::database::build_http_json_response(
-						origin,
-						&actix_web::http::Method::GET,
-						actix_web::http::StatusCode::CONFLICT,
-						None,
-						None,
-						Some(format!("{}", self)),
-						should_have_body,
-					)
+impl GetError {
+	pub fn from_precondition(
+		item_path: crate::item::ItemPath,
+		failure: crate::database::PreconditionFailure,
+	) -> Self {
+		match failure {
+			crate::database::PreconditionFailure::NoIfMatch { search, found } => Self::NoIfMatch {
+				item_path,
+				search,
+				found,
+			},
+			crate::database::PreconditionFailure::IfNoneMatch { search, found } => {
+				Self::IfNoneMatch {
+					item_path,
+					search,
+					found,
 				}
 			}
-			Self::NotFound { item_path: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::NOT_FOUND,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::IncorrectItemName {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::CanNotBeListed { item_path } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::NOT_FOUND,
-				None,
-				None,
-				Some(format!("path not found : `{}`", item_path)),
-				should_have_body,
-			),
-			Self::NoIfMatch {
-				item_path: _,
-				search: _,
-				found: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::PRECONDITION_FAILED,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::IfNoneMatch {
-				item_path: _,
-				search: _,
-				found: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::PRECONDITION_FAILED,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::CanNotReadFile {
-				os_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::CanNotDeserializeFile {
-				os_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::IOError { error: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::IsSystemFile => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
+		}
+	}
+}
+impl From<GetError> for crate::database::DataSourceError {
+	fn from(error: GetError) -> Self {
+		match error {
+			GetError::Conflict { item_path } => Self::Conflict { item_path },
+			GetError::NotFound { item_path } => Self::NotFound { item_path },
+			GetError::IncorrectItemName { item_path, error } => {
+				Self::IncorrectItemName { item_path, error }
+			}
+			GetError::CanNotBeListed { item_path } => Self::CanNotBeListed { item_path },
+			GetError::NoIfMatch {
+				item_path,
+				search,
+				found,
+			} => Self::NoIfMatch {
+				item_path,
+				search,
+				found,
+			},
+			GetError::IfNoneMatch {
+				item_path,
+				search,
+				found,
+			} => Self::IfNoneMatch {
+				item_path,
+				search,
+				found,
+			},
+			GetError::IsSystemFile => Self::IsSystemFile,
+			GetError::CanNotReadFile { .. }
+			| GetError::CanNotDeserializeFile { .. }
+			| GetError::IOError { .. }
+			| GetError::CorruptedContent { .. } => Self::InternalError {
+				error: format!("{}", error),
+			},
 		}
 	}
 }
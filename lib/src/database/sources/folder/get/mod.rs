@@ -7,7 +7,7 @@ pub mod tests;
 pub fn get(
 	root_folder_path: &std::path::Path,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 	get_content: bool,
 ) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
@@ -49,31 +49,29 @@ pub fn get(
 				Ok(itemdata_file_content) => {
 					match toml::from_slice::<crate::item::DataDocument>(&itemdata_file_content) {
 						Ok(itemdata) => {
-							if !if_match.is_empty() && &itemdata.etag != if_match && if_match != "*"
-							{
-								return Err(Box::new(GetError::NoIfMatch {
-									item_path: path.clone(),
-									search: if_match.clone(),
-									found: itemdata.etag,
-								}));
-							}
-
-							if !if_none_match.is_empty() {
-								for none_match in if_none_match {
-									if &&itemdata.etag == none_match || *none_match == "*" {
-										return Err(Box::new(GetError::IfNoneMatch {
-											item_path: path.clone(),
-											search: (*none_match).clone(),
-											found: itemdata.etag,
-										}));
-									}
-								}
+							if let Err(failure) = crate::database::check_preconditions(
+								Some(&itemdata.etag),
+								if_match,
+								if_none_match,
+							) {
+								return Err(Box::new(GetError::from_precondition(
+									path.clone(),
+									failure,
+								)));
 							}
 
 							if get_content {
 								match std::fs::read(&target) {
 									Ok(file_content) => {
+										if !itemdata.is_intact(&file_content) {
+											return Err(Box::new(GetError::CorruptedContent {
+												item_path: path.clone(),
+												os_path: target,
+											}));
+										}
+
 										return Ok(crate::item::Item::Document {
+											content_length: file_content.len() as u64,
 											content: Some(file_content),
 											content_type: itemdata.content_type,
 											etag: itemdata.etag,
@@ -88,9 +86,24 @@ pub fn get(
 									}
 								}
 							} else {
+								// metadata written by older versions does not have it
+								let content_length = match itemdata.content_length {
+									Some(content_length) => content_length,
+									None => match std::fs::metadata(&target) {
+										Ok(metadata) => metadata.len(),
+										Err(error) => {
+											return Err(Box::new(GetError::CanNotReadFile {
+												os_path: target,
+												error: format!("{}", error),
+											}));
+										}
+									},
+								};
+
 								return Ok(crate::item::Item::Document {
 									content: None,
 									content_type: itemdata.content_type,
+									content_length,
 									etag: itemdata.etag,
 									last_modified: itemdata.last_modified,
 								});
@@ -129,25 +142,15 @@ pub fn get(
 				Ok(itemdata_file_content) => {
 					match toml::from_slice::<crate::item::DataFolder>(&itemdata_file_content) {
 						Ok(itemdata) => {
-							if !if_match.is_empty() && &itemdata.etag != if_match && if_match != "*"
-							{
-								return Err(Box::new(GetError::NoIfMatch {
-									item_path: path.clone(),
-									search: if_match.clone(),
-									found: itemdata.etag,
-								}));
-							}
-
-							if !if_none_match.is_empty() {
-								for none_match in if_none_match {
-									if &&itemdata.etag == none_match || *none_match == "*" {
-										return Err(Box::new(GetError::IfNoneMatch {
-											item_path: path.clone(),
-											search: (*none_match).clone(),
-											found: itemdata.etag,
-										}));
-									}
-								}
+							if let Err(failure) = crate::database::check_preconditions(
+								Some(&itemdata.etag),
+								if_match,
+								if_none_match,
+							) {
+								return Err(Box::new(GetError::from_precondition(
+									path.clone(),
+									failure,
+								)));
 							}
 
 							if get_content {
@@ -175,9 +178,13 @@ pub fn get(
 																		crate::item::ItemPathPart::Document(entry_name.clone())
 																	})
 																	.unwrap(),
-																&crate::item::Etag::from(""),
 																&[],
-																get_content,
+																&[],
+																// listings only need metadata of documents
+																dir_entry
+																	.file_type()
+																	.unwrap()
+																	.is_dir(),
 															);
 
 															match entry_item {
@@ -270,13 +277,7 @@ pub fn get(
 			item_path: path.folder_clone(),
 		}));
 	} else if let Some(parent) = path.parent() {
-		let get_parent = get(
-			root_folder_path,
-			&parent,
-			&crate::item::Etag::from(""),
-			&[],
-			false,
-		);
+		let get_parent = get(root_folder_path, &parent, &[], &[], false);
 		if let Err(get_parent) = get_parent {
 			let get_parent: GetError = *get_parent.downcast().unwrap();
 			if let GetError::Conflict { item_path: _ } = &get_parent {
@@ -4,6 +4,33 @@ use super::{get, GetError};
 use crate::item::{DataDocument, DataFolder, Etag, Item, ItemPath};
 use std::convert::TryFrom;
 
+/// `item`, as listed by [`get`] : documents inside folders only have their metadata.
+fn listing(item: &Item) -> Item {
+	return match item {
+		Item::Folder {
+			etag,
+			content: Some(content),
+		} => Item::Folder {
+			etag: etag.clone(),
+			content: Some(
+				content
+					.iter()
+					.map(|(name, child)| {
+						(
+							name.clone(),
+							Box::new(match **child {
+								Item::Folder { .. } => listing(child),
+								Item::Document { .. } => child.empty_clone(),
+							}),
+						)
+					})
+					.collect(),
+			),
+		},
+		_ => item.clone(),
+	};
+}
+
 #[test]
 fn all_tests_bulk() {
 	let AA = Item::new_doc(b"AA", "text/plain");
@@ -218,116 +245,79 @@ fn all_tests_bulk() {
 		panic!()
 	}
 
+	// folders are listed without the content of their documents
+	let root_without_public = listing(&root_without_public);
+	let A = listing(&A);
+	let B = listing(&B);
+
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		get(&root_path, &ItemPath::from(""), &Etag::from(""), &[], true).unwrap(),
+		get(&root_path, &ItemPath::from(""), &[], &[], true).unwrap(),
 		root_without_public.clone()
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/"), &[], &[], true).unwrap(),
 		A.clone()
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/AA"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/AA"), &[], &[], true).unwrap(),
 		AA.clone()
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/AB"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/AB"), &[], &[], true).unwrap(),
 		AB
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/AC"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/AC"), &[], &[], true).unwrap(),
 		AC
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("B/"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("B/"), &[], &[], true).unwrap(),
 		B
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("B/BA"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("B/BA"), &[], &[], true).unwrap(),
 		BA
 	);
+	assert_eq!(
+		get(&root_path, &ItemPath::from("B/BB"), &[], &[], true).unwrap(),
+		BB
+	);
+	assert_eq!(
+		get(&root_path, &ItemPath::from("public/C/CA"), &[], &[], true).unwrap(),
+		CA
+	);
+
+	////////////////////////////////////////////////////////////////////////////////////////////////
+
 	assert_eq!(
 		get(
 			&root_path,
-			&ItemPath::from("B/BB"),
-			&Etag::from(""),
+			&ItemPath::from(""),
+			&[root.get_etag()],
 			&[],
 			true
 		)
 		.unwrap(),
-		BB
+		root_without_public.clone()
 	);
 	assert_eq!(
 		get(
 			&root_path,
-			&ItemPath::from("public/C/CA"),
-			&Etag::from(""),
+			&ItemPath::from("A/"),
+			&[A.get_etag()],
 			&[],
 			true
 		)
 		.unwrap(),
-		CA
-	);
-
-	////////////////////////////////////////////////////////////////////////////////////////////////
-
-	assert_eq!(
-		get(&root_path, &ItemPath::from(""), root.get_etag(), &[], true).unwrap(),
-		root_without_public.clone()
-	);
-	assert_eq!(
-		get(&root_path, &ItemPath::from("A/"), A.get_etag(), &[], true).unwrap(),
 		A.clone()
 	);
 	assert_eq!(
 		get(
 			&root_path,
 			&ItemPath::from("A/AA"),
-			AA.get_etag(),
+			&[AA.get_etag()],
 			&[],
 			true
 		)
@@ -341,7 +331,7 @@ fn all_tests_bulk() {
 		get(
 			&root_path,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")],
 			true
 		)
@@ -352,7 +342,7 @@ fn all_tests_bulk() {
 		get(
 			&root_path,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")],
 			true
 		)
@@ -363,7 +353,7 @@ fn all_tests_bulk() {
 		get(
 			&root_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")],
 			true
 		)
@@ -374,7 +364,7 @@ fn all_tests_bulk() {
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		*get(&root_path, &ItemPath::from("A"), &Etag::from(""), &[], true)
+		*get(&root_path, &ItemPath::from("A"), &[], &[], true)
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -383,16 +373,10 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("A/AA/"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("A/AA/"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::Conflict {
 			item_path: ItemPath::from("A/AA")
 		}
@@ -401,7 +385,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/AC/not_exists"),
-			&Etag::from(""),
+			&[],
 			&[],
 			true
 		)
@@ -413,16 +397,10 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("A/not_exists"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("A/not_exists"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("A/not_exists")
 		}
@@ -431,7 +409,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/not_exists/nested"),
-			&Etag::from(""),
+			&[],
 			&[],
 			true
 		)
@@ -443,46 +421,28 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("B/not_exists"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("B/not_exists"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("B/not_exists")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("not_exists/"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("not_exists/"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("not_exists/")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("not_exists"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("not_exists"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("not_exists")
 		}
@@ -490,7 +450,7 @@ fn all_tests_bulk() {
 	/*
 	// useless with `ItemPath`
 	assert_eq!(
-		*get(&root_path, &ItemPath::from("."), &Etag::from(""), &[], true)
+		*get(&root_path, &ItemPath::from("."), &[], &[], true)
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -501,7 +461,7 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		get(&root_path, &ItemPath::from("."), &Etag::from(""), &[], true).unwrap(),
+		get(&root_path, &ItemPath::from("."), &[], &[], true).unwrap(),
 		root_without_public.clone()
 	);
 	/*
@@ -510,7 +470,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/.."),
-			&Etag::from(""),
+			&[],
 			&[],
 			true
 		)
@@ -524,14 +484,7 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/.."),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/.."), &[], &[], true).unwrap(),
 		root_without_public.clone()
 	);
 	/*
@@ -540,7 +493,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/../"),
-			&Etag::from(""),
+			&[],
 			&[],
 			true
 		)
@@ -554,14 +507,7 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/../"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/../"), &[], &[], true).unwrap(),
 		root_without_public.clone()
 	);
 	/*
@@ -570,7 +516,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/../AA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			true
 		)
@@ -584,47 +530,29 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("A/A\0A"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("A/A\0A"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IncorrectItemName {
 			item_path: ItemPath::from("A/A\0A"),
 			error: format!("`{}` should not contains `\\0` character", "A\0A")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("public/"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("public/"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::CanNotBeListed {
 			item_path: ItemPath::from("public/")
 		},
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("public/C/"),
-			&Etag::from(""),
-			&[],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("public/C/"), &[], &[], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::CanNotBeListed {
 			item_path: ItemPath::from("public/C/")
 		}
@@ -636,7 +564,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from(""),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&[],
 			true
 		)
@@ -653,7 +581,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&[],
 			true
 		)
@@ -670,7 +598,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&[],
 			true
 		)
@@ -690,7 +618,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -707,7 +635,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -724,7 +652,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -744,7 +672,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[root.get_etag()],
 			true
 		)
@@ -761,7 +689,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[A.get_etag()],
 			true
 		)
@@ -778,7 +706,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[AA.get_etag()],
 			true
 		)
@@ -795,77 +723,44 @@ fn all_tests_bulk() {
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		get(&root_path, &ItemPath::from(""), &Etag::from(""), &[], false).unwrap(),
+		get(&root_path, &ItemPath::from(""), &[], &[], false).unwrap(),
 		root_without_public.empty_clone()
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/"), &[], &[], false).unwrap(),
 		A.empty_clone()
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("A/AA"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("A/AA"), &[], &[], false).unwrap(),
 		AA.empty_clone()
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("public/"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("public/"), &[], &[], false)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::CanNotBeListed {
 			item_path: ItemPath::from("public/")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root_path,
-			&ItemPath::from("public/C/"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root_path, &ItemPath::from("public/C/"), &[], &[], false)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::CanNotBeListed {
 			item_path: ItemPath::from("public/C/")
 		}
 	);
 	assert_eq!(
-		get(
-			&root_path,
-			&ItemPath::from("public/C/CA"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap(),
+		get(&root_path, &ItemPath::from("public/C/CA"), &[], &[], false).unwrap(),
 		CA.empty_clone()
 	);
 	assert_eq!(
 		*get(
 			&root_path,
 			&ItemPath::from("public/not_exists"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -880,7 +775,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("public/not_exists/"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -898,7 +793,7 @@ fn all_tests_bulk() {
 		*get(
 			&root_path,
 			&ItemPath::from("A/.AA.itemdata.toml"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
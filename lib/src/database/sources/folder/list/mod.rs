@@ -0,0 +1,73 @@
+use super::GetError;
+
+#[cfg(test)]
+pub mod tests;
+
+pub fn list(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+	options: &crate::database::ListingOptions,
+) -> Result<crate::database::Listing, Box<dyn std::error::Error>> {
+	// also checks the path and the preconditions
+	let etag = match super::get::get(root_folder_path, path, if_match, if_none_match, false)? {
+		crate::item::Item::Folder { etag, .. } => etag,
+		crate::item::Item::Document { .. } => {
+			return Err(Box::new(GetError::Conflict {
+				item_path: path.document_clone(),
+			}));
+		}
+	};
+
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	let mut children = vec![];
+	match std::fs::read_dir(&target) {
+		Ok(dir_contents) => {
+			for dir_content in dir_contents {
+				match dir_content {
+					Ok(dir_entry) => {
+						let entry_name = String::from(dir_entry.file_name().to_str().unwrap());
+						if !entry_name.ends_with(".itemdata.toml") {
+							children.push((entry_name, dir_entry.file_type().unwrap().is_dir()));
+						}
+					}
+					Err(error) => {
+						return Err(Box::new(GetError::IOError {
+							error: format!("{}", error),
+						}));
+					}
+				}
+			}
+		}
+		Err(error) => {
+			return Err(Box::new(GetError::CanNotReadFile {
+				os_path: target,
+				error: format!("{}", error),
+			}));
+		}
+	}
+
+	// only the metadata of the listed children is read
+	return crate::database::listing::build_listing(etag, children, options, |name, is_folder| {
+		let child_path = path
+			.joined(&if is_folder {
+				crate::item::ItemPathPart::Folder(String::from(name))
+			} else {
+				crate::item::ItemPathPart::Document(String::from(name))
+			})
+			.unwrap();
+
+		match super::get::get(root_folder_path, &child_path, &[], &[], false) {
+			Ok(item) => Ok(Some(item)),
+			Err(error) => {
+				if let Some(GetError::CanNotBeListed { .. }) = error.downcast_ref::<GetError>() {
+					Ok(None)
+				} else {
+					Err(error)
+				}
+			}
+		}
+	});
+}
@@ -0,0 +1,139 @@
+use super::list;
+use crate::database::ListingOptions;
+use crate::item::{Item, ItemPath};
+
+#[test]
+fn t6hz1qw8mv3kxc5rnb() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	println!(
+		"folder dedicated to this test : {}",
+		tmp_folder.path().to_string_lossy()
+	);
+
+	for (path, content_type) in [
+		("photos/a.jpg", "image/jpeg"),
+		("photos/b.png", "image/png"),
+		("photos/c.jpg", "image/jpeg"),
+		("photos/d/da.jpg", "image/jpeg"),
+		("public/photos/e.jpg", "image/jpeg"),
+	] {
+		super::super::put::put(
+			tmp_folder.path(),
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(b"PHOTO", content_type),
+		)
+		.unwrap();
+	}
+
+	let keys = |listing: &crate::database::Listing| -> Vec<String> {
+		listing
+			.items
+			.iter()
+			.map(|(name, item)| crate::database::listing_key(name, item.is_folder()))
+			.collect()
+	};
+
+	let path = ItemPath::from("photos/");
+
+	let listing = list(
+		tmp_folder.path(),
+		&path,
+		&[],
+		&[],
+		&ListingOptions::default(),
+	)
+	.unwrap();
+	assert_eq!(
+		listing.etag,
+		super::super::get::get(tmp_folder.path(), &path, &[], &[], false)
+			.unwrap()
+			.get_etag()
+			.clone()
+	);
+	assert_eq!(keys(&listing), vec!["a.jpg", "b.png", "c.jpg", "d/"]);
+	assert_eq!(listing.next, None);
+	match &listing.items[0].1 {
+		Item::Document {
+			content,
+			content_length,
+			content_type,
+			..
+		} => {
+			assert_eq!(content, &None);
+			assert_eq!(*content_length, 5);
+			assert_eq!(content_type, "image/jpeg");
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	let options = ListingOptions {
+		limit: Some(3),
+		..Default::default()
+	};
+	let listing = list(tmp_folder.path(), &path, &[], &[], &options).unwrap();
+	assert_eq!(keys(&listing), vec!["a.jpg", "b.png", "c.jpg"]);
+	assert_eq!(listing.next, Some(String::from("c.jpg")));
+
+	let listing = list(
+		tmp_folder.path(),
+		&path,
+		&[],
+		&[],
+		&ListingOptions {
+			after: listing.next,
+			..options
+		},
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["d/"]);
+	assert_eq!(listing.next, None);
+
+	let listing = list(
+		tmp_folder.path(),
+		&path,
+		&[],
+		&[],
+		&ListingOptions {
+			content_type: Some(String::from("image/jpeg")),
+			..Default::default()
+		},
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["a.jpg", "c.jpg"]);
+
+	// `public/` can not be listed
+	let listing = list(
+		tmp_folder.path(),
+		&ItemPath::from(""),
+		&[],
+		&[],
+		&ListingOptions::default(),
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["photos/"]);
+	assert!(list(
+		tmp_folder.path(),
+		&ItemPath::from("public/photos/"),
+		&[],
+		&[],
+		&ListingOptions::default()
+	)
+	.is_err());
+	assert_eq!(
+		*list(
+			tmp_folder.path(),
+			&ItemPath::from("videos/"),
+			&[],
+			&[],
+			&ListingOptions::default()
+		)
+		.unwrap_err()
+		.downcast::<super::GetError>()
+		.unwrap(),
+		super::GetError::NotFound {
+			item_path: ItemPath::from("videos/")
+		}
+	);
+}
@@ -1,7 +1,12 @@
+mod batch;
+mod check;
 mod delete;
 mod get;
+mod list;
 mod put;
+mod rehash;
 
+pub use check::Inconsistency;
 pub use delete::DeleteError;
 pub use get::GetError;
 pub use put::PutError;
@@ -17,14 +22,37 @@ pub struct FolderStorage {
 	/// The path of the folder inside the file system where to store data.
 	pub root_folder_path: std::path::PathBuf,
 }
+impl FolderStorage {
+	/// Scans the whole data folder, and returns all the inconsistencies inside it,
+	/// like metadata files without document or documents without metadata file.
+	pub fn check(&self) -> Result<Vec<Inconsistency>, crate::database::DataSourceError> {
+		return check::check(&self.root_folder_path);
+	}
+
+	/// Reads the content of all documents, and returns the ones which do not match their checksum,
+	/// as [`Inconsistency::CorruptedContent`].
+	pub fn scrub(&self) -> Result<Vec<Inconsistency>, crate::database::DataSourceError> {
+		return check::scrub(&self.root_folder_path);
+	}
+
+	/// Fixes `inconsistencies` found by [`check`][`FolderStorage::check`], and returns the ones which can not be fixed.
+	///
+	/// Missing or broken metadata is written again, with a new ETag and the default content type for documents.
+	pub fn repair(
+		&mut self,
+		inconsistencies: &[Inconsistency],
+	) -> Result<Vec<Inconsistency>, crate::database::DataSourceError> {
+		return check::repair(&self.root_folder_path, inconsistencies);
+	}
+}
 impl crate::database::DataSource for FolderStorage {
 	fn get(
 		&self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		get_content: bool,
-	) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
+	) -> Result<crate::item::Item, crate::database::DataSourceError> {
 		get::get(
 			&self.root_folder_path,
 			path,
@@ -32,12 +60,13 @@ impl crate::database::DataSource for FolderStorage {
 			if_none_match,
 			get_content,
 		)
+		.map_err(crate::database::DataSourceError::from_boxed::<GetError>)
 	}
 
 	fn put(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		new_item: crate::item::Item,
 	) -> crate::database::PutResult {
@@ -53,8 +82,64 @@ impl crate::database::DataSource for FolderStorage {
 	fn delete(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
-	) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
 		delete::delete(&self.root_folder_path, path, if_match)
+			.map_err(crate::database::DataSourceError::from_boxed::<DeleteError>)
+	}
+
+	fn list(
+		&self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+		options: &crate::database::ListingOptions,
+	) -> Result<crate::database::Listing, crate::database::DataSourceError> {
+		list::list(
+			&self.root_folder_path,
+			path,
+			if_match,
+			if_none_match,
+			options,
+		)
+		.map_err(crate::database::DataSourceError::from_boxed::<GetError>)
+	}
+
+	fn delete_folder(
+		&mut self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+		batch::delete_folder(&self.root_folder_path, path, if_match, None)
+	}
+
+	fn batch(
+		&mut self,
+		operations: &[crate::database::BatchOperation],
+	) -> Vec<crate::database::BatchResult> {
+		batch::batch(&self.root_folder_path, operations)
+	}
+
+	fn documents_in(
+		&self,
+		path: &crate::item::ItemPath,
+	) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+		batch::documents_in(&self.root_folder_path, path)
+	}
+
+	fn set_metadata(
+		&mut self,
+		path: &crate::item::ItemPath,
+		etag: &crate::item::Etag,
+		last_modified: Option<time::OffsetDateTime>,
+	) -> Result<(), crate::database::DataSourceError> {
+		rehash::set_metadata(&self.root_folder_path, path, etag, last_modified)
+	}
+
+	fn rehash_etags(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError> {
+		rehash::rehash_etags(&self.root_folder_path, path)
 	}
 }
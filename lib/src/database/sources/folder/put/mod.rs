@@ -7,16 +7,55 @@ pub mod tests;
 pub fn put(
 	root_folder_path: &std::path::Path,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 	new_item: crate::item::Item,
+) -> crate::database::PutResult {
+	return put_internal(
+		root_folder_path,
+		path,
+		if_match,
+		if_none_match,
+		new_item,
+		None,
+	);
+}
+
+/// Same as [`put`], but if `touched_folders` is given, ETags of existing ancestors are not renewed :
+/// their paths are added to it instead, see [`renew_folder_etags`][`super::batch::renew_folder_etags`].
+pub fn put_internal(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+	new_item: crate::item::Item,
+	mut touched_folders: Option<&mut Vec<crate::item::ItemPath>>,
 ) -> crate::database::PutResult {
 	// TODO : test if path is document and new_item is folder (and vice-versa) ?
 	if path.is_folder() {
-		return crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders));
+		return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+			PutError::DoesNotWorksForFolders,
+		));
 	}
 
-	let item_fetch = super::get::get(root_folder_path, path, if_match, if_none_match, true);
+	let item_fetch = match super::get::get(root_folder_path, path, if_match, if_none_match, true) {
+		// a corrupted document can still be replaced by a new content
+		Err(error)
+			if matches!(
+				error.downcast_ref::<super::GetError>(),
+				Some(super::GetError::CorruptedContent { .. })
+			) && matches!(
+				new_item,
+				crate::item::Item::Document {
+					content: Some(_),
+					..
+				}
+			) =>
+		{
+			super::get::get(root_folder_path, path, if_match, if_none_match, false)
+		}
+		item_fetch => item_fetch,
+	};
 
 	let target_content_path = root_folder_path.join(std::path::PathBuf::from(path));
 	let target_data_path = root_folder_path
@@ -36,18 +75,32 @@ pub fn put(
 			if let crate::item::Item::Document {
 				content: new_content,
 				content_type: new_content_type,
+				content_length: new_content_length,
 				last_modified: new_last_modified,
 				..
 			} = new_item
 			{
+				let new_content_length = new_content
+					.as_ref()
+					.map_or(new_content_length, |content| content.len() as u64);
+
 				if new_content != old_content || new_content_type != old_content_type {
 					let new_etag = crate::item::Etag::new();
+					let new_checksum = new_content
+						.as_ref()
+						.or(old_content.as_ref())
+						.map(|content| crate::item::DataDocument::checksum_of(content));
 
 					for parent_path in path
 						.ancestors()
 						.into_iter()
 						.take(path.ancestors().len().saturating_sub(1))
 					{
+						if let Some(touched_folders) = touched_folders.as_mut() {
+							super::batch::touch(touched_folders, parent_path);
+							continue;
+						}
+
 						let target_parent_path =
 							root_folder_path.join(std::path::PathBuf::from(&parent_path));
 						let parent_datafile_path = target_parent_path.join(".folder.itemdata.toml");
@@ -58,21 +111,25 @@ pub fn put(
 								Ok(file_content) => match toml::from_slice(&file_content) {
 									Ok(file_content) => file_content,
 									Err(error) => {
-										return crate::database::PutResult::Err(Box::new(
-											PutError::CanNotDeserializeFile {
-												os_path: parent_datafile_path,
-												error: format!("{}", error),
-											},
-										));
+										return crate::database::PutResult::Err(
+											crate::database::DataSourceError::from(
+												PutError::CanNotDeserializeFile {
+													os_path: parent_datafile_path,
+													error: format!("{}", error),
+												},
+											),
+										);
 									}
 								},
 								Err(error) => {
-									return crate::database::PutResult::Err(Box::new(
-										PutError::CanNotReadFile {
-											os_path: parent_datafile_path,
-											error: format!("{}", error),
-										},
-									));
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(
+											PutError::CanNotReadFile {
+												os_path: parent_datafile_path,
+												error: format!("{}", error),
+											},
+										),
+									);
 								}
 							}
 						};
@@ -86,33 +143,37 @@ pub fn put(
 								if let Err(error) =
 									std::fs::write(&parent_datafile_path, &parent_datafile)
 								{
-									return crate::database::PutResult::Err(Box::new(
-										PutError::CanNotWriteFile {
-											os_path: parent_datafile_path,
-											error: format!("{}", error),
-										},
-									));
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(
+											PutError::CanNotWriteFile {
+												os_path: parent_datafile_path,
+												error: format!("{}", error),
+											},
+										),
+									);
 								}
 							}
 							Err(error) => {
-								return crate::database::PutResult::Err(Box::new(
-									PutError::CanNotSerializeFile {
-										os_path: parent_datafile_path,
-										error: format!("{}", error),
-									},
-								));
+								return crate::database::PutResult::Err(
+									crate::database::DataSourceError::from(
+										PutError::CanNotSerializeFile {
+											os_path: parent_datafile_path,
+											error: format!("{}", error),
+										},
+									),
+								);
 							}
 						}
 					}
 
 					if let Some(new_content) = new_content {
 						if let Err(error) = std::fs::write(&target_content_path, &new_content) {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::CanNotWriteFile {
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::CanNotWriteFile {
 									os_path: target_content_path,
 									error: format!("{}", error),
-								},
-							));
+								}),
+							);
 						}
 					}
 
@@ -120,25 +181,31 @@ pub fn put(
 						datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 						etag: new_etag.clone(),
 						content_type: new_content_type,
+						content_length: Some(new_content_length),
+						checksum: new_checksum,
 						last_modified: Some(time::OffsetDateTime::now_utc()),
 					}) {
 						Ok(datadoc) => {
 							if let Err(error) = std::fs::write(&target_data_path, &datadoc) {
-								return crate::database::PutResult::Err(Box::new(
-									PutError::CanNotWriteFile {
-										os_path: target_data_path,
-										error: format!("{}", error),
-									},
-								));
+								return crate::database::PutResult::Err(
+									crate::database::DataSourceError::from(
+										PutError::CanNotWriteFile {
+											os_path: target_data_path,
+											error: format!("{}", error),
+										},
+									),
+								);
 							}
 						}
 						Err(error) => {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::CanNotSerializeFile {
-									os_path: target_data_path,
-									error: format!("{}", error),
-								},
-							));
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(
+									PutError::CanNotSerializeFile {
+										os_path: target_data_path,
+										error: format!("{}", error),
+									},
+								),
+							);
 						}
 					}
 
@@ -147,28 +214,52 @@ pub fn put(
 						new_last_modified.unwrap_or_else(time::OffsetDateTime::now_utc),
 					);
 				} else {
-					return crate::database::PutResult::Err(Box::new(PutError::ContentNotChanged));
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::ContentNotChanged),
+					);
 				}
 			} else {
-				return crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders));
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::DoesNotWorksForFolders,
+				));
 			}
 		}
 		Ok(crate::item::Item::Folder { .. }) => {
-			return crate::database::PutResult::Err(Box::new(super::GetError::Conflict {
-				item_path: path.clone(),
-			}));
+			return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+				super::GetError::Conflict {
+					item_path: path.clone(),
+				},
+			));
 		}
 		Err(boxed_error) => {
 			let get_error = *boxed_error.downcast::<super::GetError>().unwrap();
 
 			if let super::GetError::NotFound { item_path: _ } = get_error {
+				if let Err(failure) =
+					crate::database::check_preconditions(None, if_match, if_none_match)
+				{
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::GetError(
+							super::GetError::from_precondition(path.clone(), failure),
+						)),
+					);
+				}
+
 				if let crate::item::Item::Document {
 					content: new_content,
 					content_type: new_content_type,
+					content_length: new_content_length,
 					last_modified: new_last_modified,
 					..
 				} = new_item
 				{
+					let new_content_length = new_content
+						.as_ref()
+						.map_or(new_content_length, |content| content.len() as u64);
+					let new_checksum = new_content
+						.as_ref()
+						.map(|content| crate::item::DataDocument::checksum_of(content));
+
 					let new_etag = crate::item::Etag::new();
 
 					for parent_path in path
@@ -181,12 +272,19 @@ pub fn put(
 						let parent_datafile_path = target_parent_path.join(".folder.itemdata.toml");
 
 						if let Err(error) = std::fs::create_dir_all(&target_parent_path) {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::CanNotWriteFile {
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::CanNotWriteFile {
 									os_path: target_parent_path,
 									error: format!("{}", error),
-								},
-							));
+								}),
+							);
+						}
+
+						if let Some(touched_folders) = touched_folders.as_mut() {
+							if parent_datafile_path.exists() {
+								super::batch::touch(touched_folders, parent_path);
+								continue;
+							}
 						}
 
 						let parent_datafile = crate::item::DataFolder {
@@ -198,33 +296,37 @@ pub fn put(
 							Ok(datafile) => {
 								if let Err(error) = std::fs::write(&parent_datafile_path, &datafile)
 								{
-									return crate::database::PutResult::Err(Box::new(
-										PutError::CanNotWriteFile {
-											os_path: parent_datafile_path,
-											error: format!("{}", error),
-										},
-									));
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(
+											PutError::CanNotWriteFile {
+												os_path: parent_datafile_path,
+												error: format!("{}", error),
+											},
+										),
+									);
 								}
 							}
 							Err(error) => {
-								return crate::database::PutResult::Err(Box::new(
-									PutError::CanNotSerializeFile {
-										os_path: parent_datafile_path,
-										error: format!("{}", error),
-									},
-								));
+								return crate::database::PutResult::Err(
+									crate::database::DataSourceError::from(
+										PutError::CanNotSerializeFile {
+											os_path: parent_datafile_path,
+											error: format!("{}", error),
+										},
+									),
+								);
 							}
 						}
 					}
 
 					if let Some(new_content) = new_content {
 						if let Err(error) = std::fs::write(&target_content_path, &new_content) {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::CanNotWriteFile {
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::CanNotWriteFile {
 									os_path: target_content_path,
 									error: format!("{}", error),
-								},
-							));
+								}),
+							);
 						}
 					}
 
@@ -232,25 +334,31 @@ pub fn put(
 						datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 						etag: new_etag.clone(),
 						content_type: new_content_type,
+						content_length: Some(new_content_length),
+						checksum: new_checksum,
 						last_modified: Some(time::OffsetDateTime::now_utc()),
 					}) {
 						Ok(datafile) => {
 							if let Err(error) = std::fs::write(&target_data_path, &datafile) {
-								return crate::database::PutResult::Err(Box::new(
-									PutError::CanNotWriteFile {
-										os_path: target_data_path,
-										error: format!("{}", error),
-									},
-								));
+								return crate::database::PutResult::Err(
+									crate::database::DataSourceError::from(
+										PutError::CanNotWriteFile {
+											os_path: target_data_path,
+											error: format!("{}", error),
+										},
+									),
+								);
 							}
 						}
 						Err(error) => {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::CanNotSerializeFile {
-									os_path: target_data_path,
-									error: format!("{}", error),
-								},
-							));
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(
+									PutError::CanNotSerializeFile {
+										os_path: target_data_path,
+										error: format!("{}", error),
+									},
+								),
+							);
 						}
 					}
 
@@ -259,12 +367,14 @@ pub fn put(
 						new_last_modified.unwrap_or_else(time::OffsetDateTime::now_utc),
 					);
 				} else {
-					return crate::database::PutResult::Err(Box::new(
-						PutError::DoesNotWorksForFolders,
-					));
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::DoesNotWorksForFolders),
+					);
 				}
 			} else {
-				return crate::database::PutResult::Err(Box::new(PutError::GetError(get_error)));
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::GetError(get_error),
+				));
 			}
 		}
 	}
@@ -96,7 +96,7 @@ fn simple_put_on_not_existing() {
 	let (AA_etag, _) = put(
 		&tmp_folder_path,
 		&ItemPath::from("AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -126,7 +126,7 @@ fn simple_put_on_existing() {
 	let (AA_etag, _) = put(
 		&tmp_folder_path,
 		&ItemPath::from("A/AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -170,17 +170,15 @@ fn content_not_changed() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AA", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::ContentNotChanged
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::ContentNotChanged)
 	);
 
 	let root_datafile: DataFolder =
@@ -218,17 +216,15 @@ fn does_not_works_for_folders() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_folder(vec![])
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::DoesNotWorksForFolders
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::DoesNotWorksForFolders)
 	);
 
 	assert_eq!(std::fs::read_dir(&tmp_folder_path).unwrap().count(), 0);
@@ -244,7 +240,7 @@ fn put_with_if_none_match_all_on_not_existing() {
 	let (AA_etag, _) = put(
 		&tmp_folder_path,
 		&ItemPath::from("A/AA"),
-		&Etag::from(""),
+		&[],
 		&[&Etag::from("*")],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -273,21 +269,19 @@ fn put_with_if_none_match_all_on_existing() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(GetError::IfNoneMatch {
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(GetError::IfNoneMatch {
 			item_path: ItemPath::from("A/AA"),
 			found: AA_etag.clone(),
 			search: Etag::from("*")
-		})
+		}))
 	);
 
 	let root_datafile: DataFolder =
@@ -326,21 +320,19 @@ fn put_with_if_match_not_found() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from("A/AA"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&[],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(GetError::NoIfMatch {
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(GetError::NoIfMatch {
 			item_path: ItemPath::from("A/AA"),
 			found: AA_etag.clone(),
 			search: Etag::from("ANOTHER_ETAG")
-		})
+		}))
 	);
 
 	let root_datafile: DataFolder =
@@ -381,7 +373,7 @@ fn put_with_if_match_found() {
 	(AA_etag, _) = put(
 		&tmp_folder_path,
 		&ItemPath::from("A/AA"),
-		&AA_etag,
+		&[&AA_etag],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -425,7 +417,7 @@ fn put_with_if_match_all() {
 	let (AA_etag, _) = put(
 		&tmp_folder_path,
 		&ItemPath::from("A/AA"),
-		&Etag::from("*"),
+		&[&Etag::from("*")],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -469,19 +461,17 @@ fn put_with_existing_document_conflict() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from("A/AA/AAA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AAA", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(GetError::Conflict {
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(GetError::Conflict {
 			item_path: ItemPath::from("A/AA")
-		})
+		}))
 	);
 
 	let root_datafile: DataFolder =
@@ -526,19 +516,17 @@ fn put_with_existing_folder_conflict() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from("A"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"A", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(GetError::Conflict {
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(GetError::Conflict {
 			item_path: ItemPath::from("A/")
-		})
+		}))
 	);
 
 	let root_datafile: DataFolder =
@@ -578,7 +566,7 @@ fn put_in_public() {
 	let (AA_etag, _) = put(
 		&tmp_folder_path,
 		&ItemPath::from("public/A/AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -629,20 +617,18 @@ fn put_in_incorrect_path() {
 	let tmp_folder_path = tmp_folder.path().to_path_buf();
 
 	assert_eq!(
-		*put(
+		put(
 			&tmp_folder_path,
 			&ItemPath::from("A/A\0A"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(GetError::IncorrectItemName {
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(GetError::IncorrectItemName {
 			item_path: ItemPath::from("A/A\0A"),
 			error: String::from("`A\0A` should not contains `\\0` character")
-		})
+		}))
 	);
 
 	assert_eq!(std::fs::read_dir(&tmp_folder_path).unwrap().count(), 0);
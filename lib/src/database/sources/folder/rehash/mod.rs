@@ -0,0 +1,197 @@
+#[cfg(test)]
+pub mod tests;
+
+pub fn rehash_etags(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	let result =
+		if (path.is_document() && target.is_file()) || (!path.is_document() && target.is_dir()) {
+			Some(rehash_item(root_folder_path, path, true)?)
+		} else {
+			None
+		};
+
+	for ancestor in path
+		.ancestors()
+		.into_iter()
+		.rev()
+		.filter(|ancestor| ancestor != path)
+	{
+		if root_folder_path
+			.join(std::path::PathBuf::from(&ancestor))
+			.is_dir()
+		{
+			rehash_item(root_folder_path, &ancestor, false)?;
+		}
+	}
+
+	return Ok(result);
+}
+
+/// Replaces the ETag and the last modification date of the document at `path`, in its metadata.
+pub fn set_metadata(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	etag: &crate::item::Etag,
+	last_modified: Option<time::OffsetDateTime>,
+) -> Result<(), crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	if !path.is_document() {
+		return Err(crate::database::DataSourceError::DoesNotWorksForFolders);
+	}
+	if !target.is_file() {
+		return Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		});
+	}
+
+	let itemdata_path = target.parent().unwrap().join(format!(
+		".{}.itemdata.toml",
+		target.file_name().unwrap().to_string_lossy()
+	));
+	let mut itemdata: crate::item::DataDocument = read_itemdata(&itemdata_path)?;
+	itemdata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+	itemdata.etag = etag.clone();
+	itemdata.last_modified = last_modified;
+
+	return write_itemdata(&itemdata_path, &itemdata);
+}
+
+/// Computes and saves the new ETag of the item at `path`.
+///
+/// If `recursive`, ETags of children of a folder are also computed,
+/// otherwise their current ETags are used.
+fn rehash_item(
+	root_folder_path: &std::path::Path,
+	path: &crate::item::ItemPath,
+	recursive: bool,
+) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+	let target = root_folder_path.join(std::path::PathBuf::from(path));
+
+	if path.is_document() {
+		let content = std::fs::read(&target).map_err(|error| io_error(&target, error))?;
+
+		let itemdata_path = target.parent().unwrap().join(format!(
+			".{}.itemdata.toml",
+			target.file_name().unwrap().to_string_lossy()
+		));
+		let mut itemdata: crate::item::DataDocument = read_itemdata(&itemdata_path)?;
+		let etag = crate::item::Etag::from_content(&content, &itemdata.content_type);
+		itemdata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+		itemdata.etag = etag.clone();
+		write_itemdata(&itemdata_path, &itemdata)?;
+
+		return Ok(etag);
+	} else {
+		let mut children = vec![];
+		for entry in std::fs::read_dir(&target).map_err(|error| io_error(&target, error))? {
+			let entry = entry.map_err(|error| io_error(&target, error))?;
+			let name = entry.file_name().to_string_lossy().to_string();
+			if name.ends_with(".itemdata.toml") {
+				continue;
+			}
+
+			let child_path = if entry.path().is_dir() {
+				path.joined_folder(&name)
+			} else {
+				path.joined_doc(&name)
+			}
+			.map_err(
+				|error| crate::database::DataSourceError::IncorrectItemName {
+					item_path: path.clone(),
+					error,
+				},
+			)?;
+
+			let child_etag = if recursive {
+				rehash_item(root_folder_path, &child_path, true)?
+			} else if child_path.is_document() {
+				read_itemdata::<crate::item::DataDocument>(
+					&entry
+						.path()
+						.parent()
+						.unwrap()
+						.join(format!(".{}.itemdata.toml", name)),
+				)?
+				.etag
+			} else {
+				read_itemdata::<crate::item::DataFolder>(
+					&entry.path().join(".folder.itemdata.toml"),
+				)?
+				.etag
+			};
+
+			if child_path.is_document() {
+				children.push((name, child_etag));
+			} else {
+				children.push((format!("{}/", name), child_etag));
+			}
+		}
+
+		let etag = crate::item::Etag::from_children(
+			children.iter().map(|(name, etag)| (name.as_str(), etag)),
+		);
+
+		let itemdata_path = target.join(".folder.itemdata.toml");
+		let mut itemdata = if itemdata_path.exists() {
+			read_itemdata(&itemdata_path)?
+		} else {
+			crate::item::DataFolder::default()
+		};
+		itemdata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+		itemdata.etag = etag.clone();
+		write_itemdata(&itemdata_path, &itemdata)?;
+
+		return Ok(etag);
+	}
+}
+
+pub(super) fn read_itemdata<T: serde::de::DeserializeOwned>(
+	itemdata_path: &std::path::Path,
+) -> Result<T, crate::database::DataSourceError> {
+	let content = std::fs::read(itemdata_path).map_err(|error| io_error(itemdata_path, error))?;
+
+	return toml::from_slice(&content).map_err(|error| {
+		crate::database::DataSourceError::InternalError {
+			error: format!(
+				"can not deserialize `{}` : {}",
+				itemdata_path.to_string_lossy(),
+				error
+			),
+		}
+	});
+}
+
+pub(super) fn write_itemdata<T: serde::Serialize>(
+	itemdata_path: &std::path::Path,
+	itemdata: &T,
+) -> Result<(), crate::database::DataSourceError> {
+	let content = toml::to_vec(itemdata).map_err(|error| {
+		crate::database::DataSourceError::InternalError {
+			error: format!(
+				"can not serialize `{}` : {}",
+				itemdata_path.to_string_lossy(),
+				error
+			),
+		}
+	})?;
+
+	return std::fs::write(itemdata_path, content).map_err(|error| io_error(itemdata_path, error));
+}
+
+pub(super) fn io_error(
+	os_path: &std::path::Path,
+	error: std::io::Error,
+) -> crate::database::DataSourceError {
+	return crate::database::DataSourceError::InternalError {
+		error: format!(
+			"can not access to `{}` : {}",
+			os_path.to_string_lossy(),
+			error
+		),
+	};
+}
@@ -0,0 +1,73 @@
+use super::rehash_etags;
+use crate::item::{DataDocument, Etag, Item, ItemPath};
+
+#[test]
+fn c7vn2kq9xj4rmp0wzd() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	println!(
+		"folder dedicated to this test : {}",
+		tmp_folder.path().to_string_lossy()
+	);
+
+	for (path, content) in [("A/AA", b"AA"), ("A/AB", b"AB"), ("B", b"BB")] {
+		super::super::put::put(
+			tmp_folder.path(),
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(content, "text/plain"),
+		)
+		.unwrap();
+	}
+
+	// same data, as in `MemoryStorage`
+	let mut expected = Item::new_folder(vec![
+		(
+			"A",
+			Item::new_folder(vec![
+				("AA", Item::new_doc(b"AA", "text/plain")),
+				("AB", Item::new_doc(b"AB", "text/plain")),
+			]),
+		),
+		("B", Item::new_doc(b"BB", "text/plain")),
+	]);
+	expected.rehash_etags();
+
+	assert_eq!(
+		rehash_etags(tmp_folder.path(), &ItemPath::from("")).unwrap(),
+		Some(expected.get_etag().clone())
+	);
+
+	let AA_data: DataDocument = toml::from_slice(
+		&std::fs::read(tmp_folder.path().join("A").join(".AA.itemdata.toml")).unwrap(),
+	)
+	.unwrap();
+	assert_eq!(
+		AA_data.etag,
+		Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+
+	std::fs::write(tmp_folder.path().join("A").join("AA"), b"AC").unwrap();
+	if let Some(Item::Document { content, .. }) = expected.get_child_mut(&ItemPath::from("A/AA")) {
+		*content = Some(b"AC".to_vec());
+	}
+	expected.rehash_etags();
+
+	assert_eq!(
+		rehash_etags(tmp_folder.path(), &ItemPath::from("A/AA")).unwrap(),
+		Some(Etag::from_content(
+			b"AC",
+			&crate::item::ContentType::from("text/plain")
+		))
+	);
+	assert_eq!(
+		rehash_etags(tmp_folder.path(), &ItemPath::from("A/AD")).unwrap(),
+		None
+	);
+	assert_eq!(
+		super::super::get::get(tmp_folder.path(), &ItemPath::from(""), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		expected.get_etag()
+	);
+}
@@ -0,0 +1,410 @@
+use wasm_bindgen::JsCast;
+
+const VERSION: u32 = 1;
+const DOCUMENTS: &str = "documents";
+const FOLDERS: &str = "folders";
+
+thread_local! {
+	/// Opened databases, by name.
+	///
+	/// `web_sys` types can not be shared between threads, but the browser only has one.
+	static DATABASES: std::cell::RefCell<std::collections::HashMap<String, web_sys::IdbDatabase>> =
+		std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+impl super::IndexedDbStorage {
+	/// Opens (or creates) the IndexedDB database `name`, and loads the metadata of all its records.
+	pub async fn open(name: &str) -> Result<Self, crate::database::DataSourceError> {
+		let factory = match web_sys::window() {
+			Some(window) => match window.indexed_db() {
+				Ok(Some(factory)) => factory,
+				Ok(None) => return Err(IndexedDbError::ThereIsNoIndexedDb.into()),
+				Err(_) => return Err(IndexedDbError::CanNotGetIndexedDb.into()),
+			},
+			None => return Err(IndexedDbError::CanNotGetWindow.into()),
+		};
+
+		let request = factory
+			.open_with_u32(name, VERSION)
+			.map_err(IndexedDbError::from)?;
+
+		let request_for_upgrade = request.clone();
+		let onupgradeneeded = wasm_bindgen::closure::Closure::once_into_js(move || {
+			if let Ok(database) = request_for_upgrade
+				.result()
+				.and_then(|result| result.dyn_into::<web_sys::IdbDatabase>())
+			{
+				let names = database.object_store_names();
+				for store_name in [DOCUMENTS, FOLDERS] {
+					if !names.contains(store_name) {
+						database.create_object_store(store_name).ok();
+					}
+				}
+			}
+		});
+		request.set_onupgradeneeded(Some(onupgradeneeded.unchecked_ref()));
+
+		let database: web_sys::IdbDatabase = completion(&request)
+			.await?
+			.dyn_into()
+			.map_err(IndexedDbError::from)?;
+
+		let documents = read_documents_data(&database).await?;
+
+		let mut folders = std::collections::BTreeMap::new();
+		let (keys, values) = read_all(&database, FOLDERS).await?;
+		for (key, value) in keys.iter().zip(values.iter()) {
+			if let (Some(key), Some(record)) = (
+				key.as_string(),
+				value
+					.as_string()
+					.and_then(|value| serde_json::from_str(&value).ok()),
+			) {
+				folders.insert(key, record);
+			}
+		}
+
+		DATABASES.with(|databases| {
+			databases.borrow_mut().insert(String::from(name), database);
+		});
+
+		return Ok(Self::from_records(
+			Box::new(BrowserIndexedDb {
+				name: String::from(name),
+				failure: std::sync::Arc::new(std::sync::Mutex::new(None)),
+			}),
+			documents,
+			folders,
+		));
+	}
+}
+
+/// Writes in an IndexedDB database of the web browser, opened by [`IndexedDbStorage::open`][`super::IndexedDbStorage::open`].
+///
+/// Transactions are not awaited : the next reads are served by the memory of [`IndexedDbStorage`][`super::IndexedDbStorage`],
+/// and the first error or abort of a transaction is kept for [`failure`][`super::IndexedDbStore::failure`].
+#[derive(Debug)]
+pub struct BrowserIndexedDb {
+	name: String,
+	failure: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+}
+impl BrowserIndexedDb {
+	fn transaction(&self) -> Result<web_sys::IdbTransaction, wasm_bindgen::JsValue> {
+		return DATABASES.with(|databases| match databases.borrow().get(&self.name) {
+			Some(database) => database.transaction_with_str_sequence_and_mode(
+				&js_sys::Array::of2(
+					&wasm_bindgen::JsValue::from_str(DOCUMENTS),
+					&wasm_bindgen::JsValue::from_str(FOLDERS),
+				),
+				web_sys::IdbTransactionMode::Readwrite,
+			),
+			None => Err(wasm_bindgen::JsValue::from_str(&format!(
+				"IndexedDB database `{}` is not opened",
+				self.name
+			))),
+		});
+	}
+
+	fn apply(
+		transaction: &web_sys::IdbTransaction,
+		change: super::Change,
+	) -> Result<(), wasm_bindgen::JsValue> {
+		match change {
+			super::Change::PutDocument(path, record) => {
+				let value = js_sys::Object::new();
+				js_sys::Reflect::set(
+					&value,
+					&wasm_bindgen::JsValue::from_str("data"),
+					&wasm_bindgen::JsValue::from_str(
+						&serde_json::to_string(&record.data).map_err(|error| {
+							wasm_bindgen::JsValue::from_str(&format!("{}", error))
+						})?,
+					),
+				)?;
+				js_sys::Reflect::set(
+					&value,
+					&wasm_bindgen::JsValue::from_str("content"),
+					&js_sys::Uint8Array::from(record.content.as_slice()),
+				)?;
+
+				transaction
+					.object_store(DOCUMENTS)?
+					.put_with_key(&value, &wasm_bindgen::JsValue::from_str(&path))?;
+			}
+			super::Change::PutDocumentData(path, data) => {
+				let data = serde_json::to_string(&data)
+					.map_err(|error| wasm_bindgen::JsValue::from_str(&format!("{}", error)))?;
+				let store = transaction.object_store(DOCUMENTS)?;
+				let request = store.get(&wasm_bindgen::JsValue::from_str(&path))?;
+
+				// the content is kept, so the record is read first, in the same transaction
+				let request_for_success = request.clone();
+				let transaction = transaction.clone();
+				let onsuccess = wasm_bindgen::closure::Closure::once_into_js(move || {
+					let written = request_for_success.result().and_then(|value| {
+						if value.is_object() {
+							js_sys::Reflect::set(
+								&value,
+								&wasm_bindgen::JsValue::from_str("data"),
+								&wasm_bindgen::JsValue::from_str(&data),
+							)?;
+							store.put_with_key(&value, &wasm_bindgen::JsValue::from_str(&path))?;
+						}
+
+						return Ok(());
+					});
+
+					if written.is_err() {
+						transaction.abort().ok();
+					}
+				});
+				request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+			}
+			super::Change::DeleteDocument(path) => {
+				transaction
+					.object_store(DOCUMENTS)?
+					.delete(&wasm_bindgen::JsValue::from_str(&path))?;
+			}
+			super::Change::PutFolder(path, record) => {
+				let value = serde_json::to_string(&record)
+					.map_err(|error| wasm_bindgen::JsValue::from_str(&format!("{}", error)))?;
+
+				transaction.object_store(FOLDERS)?.put_with_key(
+					&wasm_bindgen::JsValue::from_str(&value),
+					&wasm_bindgen::JsValue::from_str(&path),
+				)?;
+			}
+			super::Change::DeleteFolder(path) => {
+				transaction
+					.object_store(FOLDERS)?
+					.delete(&wasm_bindgen::JsValue::from_str(&path))?;
+			}
+		}
+
+		return Ok(());
+	}
+}
+impl super::IndexedDbStore for BrowserIndexedDb {
+	fn write(&self, changes: Vec<super::Change>) -> Result<(), wasm_bindgen::JsValue> {
+		let transaction = self.transaction()?;
+
+		let failure = self.failure.clone();
+		let transaction_for_failure = transaction.clone();
+		let onfailure = wasm_bindgen::closure::Closure::wrap(Box::new(move || {
+			let mut failure = failure.lock().unwrap();
+			if failure.is_none() {
+				*failure = Some(match transaction_for_failure.error() {
+					Some(error) => format!("{} : {}", error.name(), error.message()),
+					None => String::from("transaction aborted"),
+				});
+			}
+		}) as Box<dyn FnMut()>)
+		.into_js_value();
+		transaction.set_onerror(Some(onfailure.unchecked_ref()));
+		transaction.set_onabort(Some(onfailure.unchecked_ref()));
+
+		for change in changes {
+			if let Err(error) = Self::apply(&transaction, change) {
+				// none of the previous changes of this transaction are written
+				transaction.abort().ok();
+				return Err(error);
+			}
+		}
+
+		return Ok(());
+	}
+
+	fn failure(&self) -> Option<String> {
+		return self.failure.lock().unwrap().clone();
+	}
+
+	fn read_contents(&self, paths: Vec<String>) -> super::ContentsFuture {
+		// requests are all sent before the first await, so the transaction is still active
+		let requests = DATABASES.with(|databases| match databases.borrow().get(&self.name) {
+			Some(database) => {
+				let store = database
+					.transaction_with_str(DOCUMENTS)
+					.and_then(|transaction| transaction.object_store(DOCUMENTS))
+					.map_err(IndexedDbError::from)?;
+
+				let mut requests = vec![];
+				for path in paths {
+					let request = store
+						.get(&wasm_bindgen::JsValue::from_str(&path))
+						.map_err(IndexedDbError::from)?;
+					let promise = promise_of(&request);
+
+					requests.push((path, promise));
+				}
+
+				Ok(requests)
+			}
+			None => Err(IndexedDbError::RequestFailed(format!(
+				"IndexedDB database `{}` is not opened",
+				self.name
+			))),
+		});
+
+		return Box::pin(async move {
+			let mut contents = std::collections::BTreeMap::new();
+
+			for (path, promise) in requests? {
+				let value = wasm_bindgen_futures::JsFuture::from(promise)
+					.await
+					.map_err(IndexedDbError::from)?;
+
+				if let Some(content) = content_from_js(&value) {
+					contents.insert(path, content);
+				}
+			}
+
+			return Ok(contents);
+		});
+	}
+}
+
+fn data_from_js(value: &wasm_bindgen::JsValue) -> Option<crate::item::DataDocument> {
+	let data = js_sys::Reflect::get(value, &wasm_bindgen::JsValue::from_str("data"))
+		.ok()?
+		.as_string()?;
+
+	return serde_json::from_str(&data).ok();
+}
+
+fn content_from_js(value: &wasm_bindgen::JsValue) -> Option<Vec<u8>> {
+	let content = js_sys::Reflect::get(value, &wasm_bindgen::JsValue::from_str("content"))
+		.ok()?
+		.dyn_into::<js_sys::Uint8Array>()
+		.ok()?;
+
+	return Some(content.to_vec());
+}
+
+/// Returns the metadata of all records of the `documents` object store, by key.
+///
+/// Records are read one by one, so their contents are not all in memory at the same time.
+async fn read_documents_data(
+	database: &web_sys::IdbDatabase,
+) -> Result<
+	std::collections::BTreeMap<String, crate::item::DataDocument>,
+	crate::database::DataSourceError,
+> {
+	let request = database
+		.transaction_with_str(DOCUMENTS)
+		.and_then(|transaction| transaction.object_store(DOCUMENTS))
+		.and_then(|store| store.open_cursor())
+		.map_err(IndexedDbError::from)?;
+
+	let mut documents = std::collections::BTreeMap::new();
+	loop {
+		let cursor = completion(&request).await?;
+		if cursor.is_null() || cursor.is_undefined() {
+			break;
+		}
+		let cursor: web_sys::IdbCursorWithValue =
+			cursor.dyn_into().map_err(IndexedDbError::from)?;
+
+		if let (Some(key), Some(data)) = (
+			cursor.key().ok().and_then(|key| key.as_string()),
+			cursor.value().ok().as_ref().and_then(data_from_js),
+		) {
+			documents.insert(key, data);
+		}
+
+		cursor.continue_().map_err(IndexedDbError::from)?;
+	}
+
+	return Ok(documents);
+}
+
+/// Returns keys and values of all records of `store_name`, in the same order.
+async fn read_all(
+	database: &web_sys::IdbDatabase,
+	store_name: &str,
+) -> Result<(js_sys::Array, js_sys::Array), crate::database::DataSourceError> {
+	let store = database
+		.transaction_with_str(store_name)
+		.and_then(|transaction| transaction.object_store(store_name))
+		.map_err(IndexedDbError::from)?;
+
+	let keys = completion(&store.get_all_keys().map_err(IndexedDbError::from)?).await?;
+	let values = completion(&store.get_all().map_err(IndexedDbError::from)?).await?;
+
+	return Ok((keys.into(), values.into()));
+}
+
+/// Waits for the result of `request`.
+async fn completion(
+	request: &web_sys::IdbRequest,
+) -> Result<wasm_bindgen::JsValue, crate::database::DataSourceError> {
+	return wasm_bindgen_futures::JsFuture::from(promise_of(request))
+		.await
+		.map_err(|error| IndexedDbError::from(error).into());
+}
+
+/// Returns a promise of the next result of `request`.
+fn promise_of(request: &web_sys::IdbRequest) -> js_sys::Promise {
+	return js_sys::Promise::new(&mut |resolve, reject| {
+		let request_for_success = request.clone();
+		let onsuccess = wasm_bindgen::closure::Closure::once_into_js(move || {
+			resolve
+				.call1(
+					&wasm_bindgen::JsValue::NULL,
+					&request_for_success
+						.result()
+						.unwrap_or(wasm_bindgen::JsValue::UNDEFINED),
+				)
+				.ok();
+		});
+		request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+
+		let request_for_error = request.clone();
+		let onerror = wasm_bindgen::closure::Closure::once_into_js(move || {
+			reject
+				.call1(
+					&wasm_bindgen::JsValue::NULL,
+					&request_for_error
+						.error()
+						.ok()
+						.flatten()
+						.map(wasm_bindgen::JsValue::from)
+						.unwrap_or(wasm_bindgen::JsValue::UNDEFINED),
+				)
+				.ok();
+		});
+		request.set_onerror(Some(onerror.unchecked_ref()));
+	});
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IndexedDbError {
+	CanNotGetWindow,
+	CanNotGetIndexedDb,
+	ThereIsNoIndexedDb,
+	RequestFailed(String),
+}
+impl std::fmt::Display for IndexedDbError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+		match self {
+			Self::CanNotGetWindow => f.write_str("can not get window API"),
+			Self::CanNotGetIndexedDb => f.write_str("can not get IndexedDB API"),
+			Self::ThereIsNoIndexedDb => f.write_str("there is no IndexedDB available"),
+			Self::RequestFailed(error) => {
+				f.write_fmt(format_args!("IndexedDB request failed : {}", error))
+			}
+		}
+	}
+}
+impl std::error::Error for IndexedDbError {}
+impl From<wasm_bindgen::JsValue> for IndexedDbError {
+	fn from(error: wasm_bindgen::JsValue) -> Self {
+		Self::RequestFailed(format!("{:?}", error))
+	}
+}
+impl From<IndexedDbError> for crate::database::DataSourceError {
+	fn from(error: IndexedDbError) -> Self {
+		Self::InternalError {
+			error: format!("{}", error),
+		}
+	}
+}
@@ -0,0 +1,559 @@
+mod browser;
+
+#[cfg(test)]
+mod tests;
+
+pub use browser::{BrowserIndexedDb, IndexedDbError};
+
+/// Store data in web browser's IndexedDB.
+///
+/// Unlike [`LocalStorage`][`crate::database::LocalStorage`], it is not limited to a few megabytes,
+/// and the content of documents is stored as binary, without encoding.
+///
+/// IndexedDB can only be used asynchronously, but [`DataSource`][`crate::database::DataSource`] is not,
+/// so the metadata of all records is loaded when the storage is opened (see [`open`][`IndexedDbStorage::open`]) :
+/// listings of folders and metadata of documents only use memory, and changes are also written to the
+/// IndexedDB database. The content of documents is not kept in memory : it should be loaded with
+/// [`load_contents`][`IndexedDbStorage::load_contents`] before the operations which read it, like
+/// [`get`][`crate::database::DataSource::get`] with `get_content`, copies or moves, which fail otherwise.
+///
+/// Each [`put`][`crate::database::DataSource::put`] or [`delete`][`crate::database::DataSource::delete`]
+/// is written in one transaction, which is not awaited. When a write fails (like when the quota of the
+/// web browser is exceeded), the memory does not match the IndexedDB database anymore, so the next
+/// operations return an [`InternalError`][`crate::database::DataSourceError::InternalError`], until
+/// the storage is opened again.
+///
+/// [More on MDN](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API)
+#[derive(Debug)]
+pub struct IndexedDbStorage {
+	/// Items without the content of their documents.
+	mirror: crate::database::MemoryStorage,
+	/// Checksums of the content of documents, by path, which are not in [`mirror`][`IndexedDbStorage::mirror`].
+	checksums: std::collections::HashMap<String, String>,
+	/// Contents of documents loaded by [`load_contents`][`IndexedDbStorage::load_contents`], by path.
+	contents: std::collections::HashMap<String, Vec<u8>>,
+	store: Box<dyn IndexedDbStore>,
+	failed_write: Option<String>,
+}
+impl IndexedDbStorage {
+	/// Rebuilds the items from the metadata of the records of the `documents` and `folders` object stores,
+	/// which keys are paths of items, like `A/AA` and `A/` (the root folder is ``).
+	///
+	/// Missing records of folders are created, with a new [`Etag`][`crate::item::Etag`].
+	pub fn from_records(
+		store: Box<dyn IndexedDbStore>,
+		documents: std::collections::BTreeMap<String, crate::item::DataDocument>,
+		folders: std::collections::BTreeMap<String, FolderRecord>,
+	) -> Self {
+		return Self {
+			mirror: crate::database::MemoryStorage {
+				root_item: build_folder("", &documents, &folders),
+			},
+			checksums: documents
+				.iter()
+				.filter_map(|(path, data)| {
+					data.checksum
+						.clone()
+						.map(|checksum| (path.clone(), checksum))
+				})
+				.collect(),
+			contents: std::collections::HashMap::new(),
+			store,
+			failed_write: None,
+		};
+	}
+
+	/// Reads the content of the document at `path` (or of all documents inside the folder at `path`)
+	/// from the IndexedDB database, so the next operations can use it.
+	///
+	/// Contents are kept in memory until [`unload_contents`][`IndexedDbStorage::unload_contents`]
+	/// is called, or until their document is changed.
+	pub async fn load_contents(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<(), crate::database::DataSourceError> {
+		self.check_writes()?;
+
+		let paths = if path.is_folder() {
+			crate::database::DataSource::documents_in(&self.mirror, path)?
+		} else {
+			crate::database::DataSource::get(&self.mirror, path, &[], &[], false)?;
+
+			vec![path.clone()]
+		};
+		let keys: Vec<String> = paths
+			.iter()
+			.map(|path| path.to_string())
+			.filter(|key| !self.contents.contains_key(key))
+			.collect();
+
+		let contents = self.store.read_contents(keys).await?;
+		self.contents.extend(contents);
+
+		return Ok(());
+	}
+
+	/// Forgets the contents loaded by [`load_contents`][`IndexedDbStorage::load_contents`]
+	/// of the document at `path`, or of all documents inside the folder at `path`.
+	pub fn unload_contents(&mut self, path: &crate::item::ItemPath) {
+		let key = path.to_string();
+
+		if path.is_folder() {
+			self.contents.retain(|loaded, _| !loaded.starts_with(&key));
+		} else {
+			self.contents.remove(&key);
+		}
+	}
+
+	/// Fails if a previous write in the IndexedDB database failed.
+	fn check_writes(&self) -> Result<(), crate::database::DataSourceError> {
+		return match self.failed_write.clone().or_else(|| self.store.failure()) {
+			Some(error) => Err(crate::database::DataSourceError::InternalError {
+				error: format!(
+					"a previous write in IndexedDB failed, open the storage again to reload its records : {}",
+					error
+				),
+			}),
+			None => Ok(()),
+		};
+	}
+
+	/// Writes the records of the item at `path` (and of its children, if `recursive`) and of its ancestors,
+	/// or deletes them if they do not exist anymore, in one transaction.
+	fn persist(
+		&mut self,
+		path: &crate::item::ItemPath,
+		with_ancestors: bool,
+		recursive: bool,
+	) -> Result<(), crate::database::DataSourceError> {
+		let mut changes = vec![];
+
+		if with_ancestors {
+			let mut ancestors = path.ancestors();
+			ancestors.pop();
+
+			for ancestor in ancestors {
+				self.changes_of(&ancestor, false, &mut changes)?;
+			}
+		}
+		self.changes_of(path, recursive, &mut changes)?;
+
+		for change in &changes {
+			match change {
+				Change::PutDocument(key, record) => {
+					if let Some(checksum) = &record.data.checksum {
+						self.checksums.insert(key.clone(), checksum.clone());
+					}
+					self.contents.remove(key);
+				}
+				Change::DeleteDocument(key) => {
+					self.checksums.remove(key);
+					self.contents.remove(key);
+				}
+				_ => {}
+			}
+		}
+
+		if let Err(error) = self.store.write(changes) {
+			self.failed_write = Some(format!("{:?}", error));
+		}
+
+		return self.check_writes();
+	}
+
+	fn changes_of(
+		&self,
+		path: &crate::item::ItemPath,
+		recursive: bool,
+		changes: &mut Vec<Change>,
+	) -> Result<(), crate::database::DataSourceError> {
+		let key = path.to_string();
+
+		let item = self
+			.mirror
+			.root_item
+			.get_child(path)
+			.filter(|item| item.is_folder() == path.is_folder());
+
+		match item {
+			Some(crate::item::Item::Folder {
+				etag,
+				content: Some(content),
+			}) => {
+				let mut children: Vec<String> = content
+					.iter()
+					.map(|(name, child)| match **child {
+						crate::item::Item::Folder { .. } => format!("{name}/"),
+						crate::item::Item::Document { .. } => name.clone(),
+					})
+					.collect();
+				children.sort();
+
+				changes.push(Change::PutFolder(
+					key,
+					FolderRecord {
+						data: crate::item::DataFolder {
+							etag: etag.clone(),
+							..Default::default()
+						},
+						children: children.clone(),
+					},
+				));
+
+				if recursive {
+					for child in children {
+						let child = if let Some(name) = child.strip_suffix('/') {
+							path.joined_folder(name)
+						} else {
+							path.joined_doc(&child)
+						}
+						.map_err(|error| {
+							crate::database::DataSourceError::InternalError { error }
+						})?;
+
+						self.changes_of(&child, true, changes)?;
+					}
+				}
+			}
+			Some(crate::item::Item::Folder { content: None, .. }) => {}
+			Some(crate::item::Item::Document {
+				etag,
+				content,
+				content_type,
+				content_length,
+				last_modified,
+			}) => {
+				let data = crate::item::DataDocument {
+					etag: etag.clone(),
+					content_type: content_type.clone(),
+					content_length: Some(*content_length),
+					checksum: match content {
+						Some(content) => Some(crate::item::DataDocument::checksum_of(content)),
+						None => self.checksums.get(&key).cloned(),
+					},
+					last_modified: *last_modified,
+					..Default::default()
+				};
+
+				changes.push(match content {
+					Some(content) => Change::PutDocument(
+						key,
+						DocumentRecord {
+							data,
+							content: content.clone(),
+						},
+					),
+					// only put documents have their content in memory
+					None => Change::PutDocumentData(key, data),
+				});
+			}
+			None => {
+				if path.is_folder() {
+					changes.push(Change::DeleteFolder(key));
+				} else {
+					changes.push(Change::DeleteDocument(key));
+				}
+			}
+		}
+
+		return Ok(());
+	}
+}
+impl crate::database::DataSource for IndexedDbStorage {
+	fn get(
+		&self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+		get_content: bool,
+	) -> Result<crate::item::Item, crate::database::DataSourceError> {
+		self.check_writes()?;
+
+		let item = crate::database::DataSource::get(
+			&self.mirror,
+			path,
+			if_match,
+			if_none_match,
+			get_content,
+		)?;
+
+		return match item {
+			crate::item::Item::Document {
+				etag,
+				content_type,
+				content_length,
+				last_modified,
+				..
+			} if get_content => match self.contents.get(&path.to_string()) {
+				Some(content) => Ok(crate::item::Item::Document {
+					etag,
+					content: Some(content.clone()),
+					content_type,
+					content_length,
+					last_modified,
+				}),
+				None => Err(crate::database::DataSourceError::InternalError {
+					error: format!(
+						"the content of `{}` is not loaded, see `IndexedDbStorage::load_contents`",
+						path
+					),
+				}),
+			},
+			item => Ok(item),
+		};
+	}
+
+	fn put(
+		&mut self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+		new_item: crate::item::Item,
+	) -> crate::database::PutResult {
+		if let Err(error) = self.check_writes() {
+			return crate::database::PutResult::Err(error);
+		}
+
+		// the mirror compares contents to tell if they changed, but it only has checksums of the current ones
+		if let crate::item::Item::Document {
+			content: Some(new_content),
+			content_type: new_content_type,
+			..
+		} = &new_item
+		{
+			let current_checksum = self.checksums.get(&path.to_string());
+			if let Some(crate::item::Item::Document {
+				content,
+				content_type,
+				..
+			}) = self.mirror.root_item.get_child_mut(path)
+			{
+				if content_type == new_content_type
+					&& current_checksum
+						== Some(&crate::item::DataDocument::checksum_of(new_content))
+				{
+					*content = Some(new_content.clone());
+				}
+			}
+		}
+
+		let result = crate::database::DataSource::put(
+			&mut self.mirror,
+			path,
+			if_match,
+			if_none_match,
+			new_item,
+		);
+
+		let persisted = match result {
+			crate::database::PutResult::Err(_) => Ok(()),
+			_ => self.persist(path, true, false),
+		};
+
+		// the content is now in the IndexedDB database
+		if let Some(item) = self.mirror.root_item.get_child_mut(path) {
+			forget_contents(item);
+		}
+
+		return match persisted {
+			Ok(()) => result,
+			Err(error) => crate::database::PutResult::Err(error),
+		};
+	}
+
+	fn delete(
+		&mut self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+		self.check_writes()?;
+
+		let etag = crate::database::DataSource::delete(&mut self.mirror, path, if_match)?;
+
+		self.persist(path, true, false)?;
+
+		return Ok(etag);
+	}
+
+	fn documents_in(
+		&self,
+		path: &crate::item::ItemPath,
+	) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+		self.check_writes()?;
+
+		return crate::database::DataSource::documents_in(&self.mirror, path);
+	}
+
+	fn set_metadata(
+		&mut self,
+		path: &crate::item::ItemPath,
+		etag: &crate::item::Etag,
+		last_modified: Option<time::OffsetDateTime>,
+	) -> Result<(), crate::database::DataSourceError> {
+		self.check_writes()?;
+
+		crate::database::DataSource::set_metadata(&mut self.mirror, path, etag, last_modified)?;
+
+		return self.persist(path, false, false);
+	}
+
+	fn rehash_etags(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError> {
+		self.check_writes()?;
+
+		// documents which content is not loaded keep their ETag
+		if let Some(item) = self.mirror.root_item.get_child_mut(path) {
+			restore_contents(item, &path.to_string(), &self.contents);
+		}
+		let etag = crate::database::DataSource::rehash_etags(&mut self.mirror, path);
+		if let Some(item) = self.mirror.root_item.get_child_mut(path) {
+			forget_contents(item);
+		}
+		let etag = etag?;
+
+		self.persist(path, true, true)?;
+
+		return Ok(etag);
+	}
+}
+
+/// What is stored in the `documents` object store.
+#[derive(Clone)]
+pub struct DocumentRecord {
+	pub data: crate::item::DataDocument,
+	pub content: Vec<u8>,
+}
+
+/// What is stored in the `folders` object store.
+///
+/// It lists the names of its children (ending with `/` for folders), so listing a folder does not
+/// need to scan all the keys.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct FolderRecord {
+	pub data: crate::item::DataFolder,
+	pub children: Vec<String>,
+}
+
+/// A change of a record, in the `documents` or `folders` object store.
+#[derive(Clone)]
+pub enum Change {
+	PutDocument(String, DocumentRecord),
+	/// Replaces the metadata of an existing record of the `documents` object store, but not its content.
+	PutDocumentData(String, crate::item::DataDocument),
+	DeleteDocument(String),
+	PutFolder(String, FolderRecord),
+	DeleteFolder(String),
+}
+
+/// Writes in the object stores of an IndexedDB database.
+///
+/// It is implemented by [`BrowserIndexedDb`], and by an in-memory stand-in in tests.
+pub trait IndexedDbStore: std::fmt::Debug + Send + Sync {
+	/// Applies all `changes` in one transaction : all of them are written, or none.
+	///
+	/// It fails if the transaction can not be started, otherwise the writing can end later,
+	/// and its failure is reported by [`failure`][`IndexedDbStore::failure`].
+	fn write(&self, changes: Vec<Change>) -> Result<(), wasm_bindgen::JsValue>;
+	/// The error of the first transaction which failed (like when it was aborted), if any.
+	fn failure(&self) -> Option<String>;
+	/// Reads the content of the records at `paths` in the `documents` object store.
+	///
+	/// Paths without record are not in the result.
+	fn read_contents(&self, paths: Vec<String>) -> ContentsFuture;
+}
+
+/// The contents of documents by path, as returned by [`IndexedDbStore::read_contents`].
+///
+/// IndexedDB requests can only be awaited in the thread of the web browser, so it is not [`Send`].
+pub type ContentsFuture = std::pin::Pin<
+	Box<
+		dyn std::future::Future<
+			Output = Result<
+				std::collections::BTreeMap<String, Vec<u8>>,
+				crate::database::DataSourceError,
+			>,
+		>,
+	>,
+>;
+
+/// Removes the content of the document `item`, or of all documents inside the folder `item`.
+fn forget_contents(item: &mut crate::item::Item) {
+	match item {
+		crate::item::Item::Folder {
+			content: Some(children),
+			..
+		} => {
+			for child in children.values_mut() {
+				forget_contents(child);
+			}
+		}
+		crate::item::Item::Folder { content: None, .. } => {}
+		crate::item::Item::Document { content, .. } => *content = None,
+	}
+}
+
+/// Gives their loaded `contents` to the document `item` at `key`, or to the documents inside the folder `item` at `key`.
+fn restore_contents(
+	item: &mut crate::item::Item,
+	key: &str,
+	contents: &std::collections::HashMap<String, Vec<u8>>,
+) {
+	match item {
+		crate::item::Item::Folder {
+			content: Some(children),
+			..
+		} => {
+			for (name, child) in children.iter_mut() {
+				let child_key = if child.is_folder() {
+					format!("{key}{name}/")
+				} else {
+					format!("{key}{name}")
+				};
+
+				restore_contents(child, &child_key, contents);
+			}
+		}
+		crate::item::Item::Folder { content: None, .. } => {}
+		crate::item::Item::Document { content, .. } => *content = contents.get(key).cloned(),
+	}
+}
+
+fn build_folder(
+	path: &str,
+	documents: &std::collections::BTreeMap<String, crate::item::DataDocument>,
+	folders: &std::collections::BTreeMap<String, FolderRecord>,
+) -> crate::item::Item {
+	let mut content = std::collections::HashMap::new();
+
+	let (etag, children) = match folders.get(path) {
+		Some(record) => (record.data.etag.clone(), record.children.clone()),
+		None => (crate::item::Etag::new(), vec![]),
+	};
+
+	for child in children {
+		if let Some(name) = child.strip_suffix('/') {
+			content.insert(
+				String::from(name),
+				Box::new(build_folder(&format!("{path}{child}"), documents, folders)),
+			);
+		} else if let Some(data) = documents.get(&format!("{path}{child}")) {
+			content.insert(
+				child,
+				Box::new(crate::item::Item::Document {
+					etag: data.etag.clone(),
+					content: None,
+					content_type: data.content_type.clone(),
+					content_length: data.content_length.unwrap_or_default(),
+					last_modified: data.last_modified,
+				}),
+			);
+		}
+	}
+
+	return crate::item::Item::Folder {
+		etag,
+		content: Some(content),
+	};
+}
@@ -0,0 +1,881 @@
+#![allow(non_snake_case)]
+
+use super::{Change, DocumentRecord, FolderRecord, IndexedDbStorage, IndexedDbStore};
+use crate::database::{DataSource, DataSourceError, PutResult};
+use crate::item::{Etag, Item, ItemPath};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Records by path, like in an object store.
+type Records<T> = Arc<Mutex<BTreeMap<String, T>>>;
+
+/// In-memory stand-in of the object stores of an IndexedDB database.
+#[derive(Debug, Clone, Default)]
+struct IndexedDbMock {
+	documents: Records<(String, Vec<u8>)>,
+	folders: Records<String>,
+	/// When set, transactions are aborted, like when the quota of the web browser is exceeded.
+	full: Arc<Mutex<bool>>,
+	failure: Arc<Mutex<Option<String>>>,
+}
+impl IndexedDbMock {
+	fn document(&self, path: &str) -> Option<DocumentRecord> {
+		return self
+			.documents
+			.lock()
+			.unwrap()
+			.get(path)
+			.map(|(data, content)| DocumentRecord {
+				data: serde_json::from_str(data).unwrap(),
+				content: content.clone(),
+			});
+	}
+
+	fn folder(&self, path: &str) -> Option<FolderRecord> {
+		return self
+			.folders
+			.lock()
+			.unwrap()
+			.get(path)
+			.map(|record| serde_json::from_str(record).unwrap());
+	}
+
+	/// Like [`IndexedDbStorage::open`], loads the metadata of all the records in a new storage.
+	fn reopen(&self) -> IndexedDbStorage {
+		let documents_paths: Vec<String> = self.documents.lock().unwrap().keys().cloned().collect();
+		let documents = documents_paths
+			.into_iter()
+			.map(|path| {
+				let record = self.document(&path).unwrap();
+				(path, record.data)
+			})
+			.collect();
+
+		let folders_paths: Vec<String> = self.folders.lock().unwrap().keys().cloned().collect();
+		let folders = folders_paths
+			.into_iter()
+			.map(|path| {
+				let record = self.folder(&path).unwrap();
+				(path, record)
+			})
+			.collect();
+
+		return IndexedDbStorage::from_records(Box::new(self.clone()), documents, folders);
+	}
+}
+impl IndexedDbStore for IndexedDbMock {
+	fn write(&self, changes: Vec<Change>) -> Result<(), wasm_bindgen::JsValue> {
+		if *self.full.lock().unwrap() {
+			self.failure
+				.lock()
+				.unwrap()
+				.get_or_insert(String::from("QuotaExceededError"));
+			return Ok(());
+		}
+
+		let mut documents = self.documents.lock().unwrap();
+		let mut folders = self.folders.lock().unwrap();
+		for change in changes {
+			match change {
+				Change::PutDocument(path, record) => {
+					documents.insert(
+						path,
+						(serde_json::to_string(&record.data).unwrap(), record.content),
+					);
+				}
+				Change::PutDocumentData(path, data) => {
+					if let Some((record_data, _)) = documents.get_mut(&path) {
+						*record_data = serde_json::to_string(&data).unwrap();
+					}
+				}
+				Change::DeleteDocument(path) => {
+					documents.remove(&path);
+				}
+				Change::PutFolder(path, record) => {
+					folders.insert(path, serde_json::to_string(&record).unwrap());
+				}
+				Change::DeleteFolder(path) => {
+					folders.remove(&path);
+				}
+			}
+		}
+
+		return Ok(());
+	}
+
+	fn failure(&self) -> Option<String> {
+		return self.failure.lock().unwrap().clone();
+	}
+
+	fn read_contents(&self, paths: Vec<String>) -> super::ContentsFuture {
+		let documents = self.documents.lock().unwrap();
+		let contents = paths
+			.into_iter()
+			.filter_map(|path| {
+				let content = documents.get(&path)?.1.clone();
+				Some((path, content))
+			})
+			.collect();
+
+		return Box::pin(std::future::ready(Ok(contents)));
+	}
+}
+
+/// Runs `future`, which does not wait for anything, like the futures of [`IndexedDbMock`].
+fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+	let mut future = std::pin::pin!(future);
+	let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+
+	loop {
+		if let std::task::Poll::Ready(result) = future.as_mut().poll(&mut context) {
+			return result;
+		}
+	}
+}
+
+fn build_test_db() -> (IndexedDbMock, IndexedDbStorage, Etag, Etag, Etag) {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("A/AA"),
+		&[],
+		&[],
+		Item::new_doc(b"AA", "text/plain"),
+	) {
+		PutResult::Created(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+	storage
+		.put(
+			&ItemPath::from("public/C/CA"),
+			&[],
+			&[],
+			Item::new_doc(b"CA", "text/plain"),
+		)
+		.unwrap();
+
+	let root_etag = mock.folder("").unwrap().data.etag;
+	let A_etag = mock.folder("A/").unwrap().data.etag;
+
+	return (mock, storage, root_etag, A_etag, AA_etag);
+}
+
+/// Checks that the records of [`build_test_db`] are not changed.
+fn assert_unchanged(mock: &IndexedDbMock, root_etag: &Etag, A_etag: &Etag, AA_etag: &Etag) {
+	assert_eq!(&mock.folder("").unwrap().data.etag, root_etag);
+	assert_eq!(&mock.folder("A/").unwrap().data.etag, A_etag);
+	assert_eq!(&mock.document("A/AA").unwrap().data.etag, AA_etag);
+	assert_eq!(mock.document("A/AA").unwrap().content, b"AA");
+}
+
+#[test]
+fn simple_put_on_not_existing() {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("AA"),
+		&[],
+		&[],
+		Item::new_doc(b"AA", "text/plain"),
+	) {
+		PutResult::Created(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+
+	assert_eq!(mock.folder("").unwrap().children, vec![String::from("AA")]);
+	let AA = mock.document("AA").unwrap();
+	assert_eq!(AA.data.etag, AA_etag);
+	assert_eq!(AA.data.content_type, "text/plain");
+	assert_eq!(AA.content, b"AA");
+}
+
+#[test]
+fn simple_put_on_existing() {
+	let (mock, mut storage, root_etag, A_etag, old_AA_etag) = build_test_db();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("A/AA"),
+		&[],
+		&[],
+		Item::new_doc(b"AA2", "text/plain2"),
+	) {
+		PutResult::Updated(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+
+	assert_ne!(old_AA_etag, AA_etag);
+	assert_ne!(mock.folder("").unwrap().data.etag, root_etag);
+	assert_ne!(mock.folder("A/").unwrap().data.etag, A_etag);
+	assert!(mock.folder("A/AA/").is_none());
+
+	let AA = mock.document("A/AA").unwrap();
+	assert_eq!(AA.data.etag, AA_etag);
+	assert_eq!(AA.data.content_type, "text/plain2");
+	assert_eq!(AA.content, b"AA2");
+}
+
+#[test]
+fn put_binary_content() {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	let content: Vec<u8> = (0..=255).collect();
+	storage
+		.put(
+			&ItemPath::from("A/picture.png"),
+			&[],
+			&[],
+			Item::new_doc(&content, "image/png"),
+		)
+		.unwrap();
+
+	assert_eq!(mock.document("A/picture.png").unwrap().content, content);
+}
+
+#[test]
+fn put_with_if_match_not_found() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert!(matches!(
+		storage.put(
+			&ItemPath::from("A/AA"),
+			&[&Etag::from("ANOTHER_ETAG")],
+			&[],
+			Item::new_doc(b"AA2", "text/plain"),
+		),
+		PutResult::Err(DataSourceError::NoIfMatch { .. })
+	));
+
+	assert_eq!(mock.folder("").unwrap().data.etag, root_etag);
+	assert_eq!(mock.folder("A/").unwrap().data.etag, A_etag);
+	assert_eq!(mock.document("A/AA").unwrap().data.etag, AA_etag);
+	assert_eq!(mock.document("A/AA").unwrap().content, b"AA");
+}
+
+#[test]
+fn content_not_changed() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.put(
+				&ItemPath::from("A/AA"),
+				&[],
+				&[],
+				Item::new_doc(b"AA", "text/plain"),
+			)
+			.unwrap_err(),
+		DataSourceError::ContentNotChanged
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+}
+
+#[test]
+fn does_not_works_for_folders() {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	assert_eq!(
+		storage
+			.put(&ItemPath::from(""), &[], &[], Item::new_folder(vec![]))
+			.unwrap_err(),
+		DataSourceError::DoesNotWorksForFolders
+	);
+
+	assert!(mock.documents.lock().unwrap().is_empty());
+	assert!(mock.folders.lock().unwrap().is_empty());
+}
+
+#[test]
+fn put_with_if_none_match_all_on_not_existing() {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("A/AA"),
+		&[],
+		&[&Etag::from("*")],
+		Item::new_doc(b"AA", "text/plain"),
+	) {
+		PutResult::Created(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+
+	assert_eq!(mock.folder("").unwrap().children, vec![String::from("A/")]);
+	assert_eq!(
+		mock.folder("A/").unwrap().children,
+		vec![String::from("AA")]
+	);
+	assert_eq!(mock.document("A/AA").unwrap().data.etag, AA_etag);
+	assert_eq!(mock.document("A/AA").unwrap().content, b"AA");
+}
+
+#[test]
+fn put_with_if_none_match_all_on_existing() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.put(
+				&ItemPath::from("A/AA"),
+				&[],
+				&[&Etag::from("*")],
+				Item::new_doc(b"AA2", "text/plain2"),
+			)
+			.unwrap_err(),
+		DataSourceError::IfNoneMatch {
+			item_path: ItemPath::from("A/AA"),
+			search: Etag::from("*"),
+			found: AA_etag.clone(),
+		}
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+}
+
+#[test]
+fn put_with_if_match_found() {
+	let (mock, mut storage, root_etag, A_etag, old_AA_etag) = build_test_db();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("A/AA"),
+		&[&old_AA_etag],
+		&[],
+		Item::new_doc(b"AA2", "text/plain2"),
+	) {
+		PutResult::Updated(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+
+	assert_ne!(AA_etag, old_AA_etag);
+	assert_ne!(mock.folder("").unwrap().data.etag, root_etag);
+	assert_ne!(mock.folder("A/").unwrap().data.etag, A_etag);
+	assert_eq!(mock.document("A/AA").unwrap().data.etag, AA_etag);
+	assert_eq!(mock.document("A/AA").unwrap().content, b"AA2");
+}
+
+#[test]
+fn put_with_if_match_all() {
+	let (mock, mut storage, root_etag, A_etag, old_AA_etag) = build_test_db();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("A/AA"),
+		&[&Etag::from("*")],
+		&[],
+		Item::new_doc(b"AA2", "text/plain2"),
+	) {
+		PutResult::Updated(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+
+	assert_ne!(AA_etag, old_AA_etag);
+	assert_ne!(mock.folder("").unwrap().data.etag, root_etag);
+	assert_ne!(mock.folder("A/").unwrap().data.etag, A_etag);
+	assert_eq!(mock.document("A/AA").unwrap().data.etag, AA_etag);
+	assert_eq!(mock.document("A/AA").unwrap().content, b"AA2");
+}
+
+#[test]
+fn put_with_existing_document_conflict() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.put(
+				&ItemPath::from("A/AA/AAA"),
+				&[],
+				&[],
+				Item::new_doc(b"AAA", "text/plain"),
+			)
+			.unwrap_err(),
+		DataSourceError::Conflict {
+			item_path: ItemPath::from("A/AA")
+		}
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+	assert!(mock.folder("A/AA/").is_none());
+	assert!(mock.document("A/AA/AAA").is_none());
+}
+
+#[test]
+fn put_with_existing_folder_conflict() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.put(
+				&ItemPath::from("A"),
+				&[],
+				&[],
+				Item::new_doc(b"A", "text/plain"),
+			)
+			.unwrap_err(),
+		DataSourceError::Conflict {
+			item_path: ItemPath::from("A/")
+		}
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+	assert!(mock.document("A").is_none());
+}
+
+#[test]
+fn put_in_public() {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	let AA_etag = match storage.put(
+		&ItemPath::from("public/A/AA"),
+		&[],
+		&[],
+		Item::new_doc(b"AA", "text/plain"),
+	) {
+		PutResult::Created(etag, _) => etag,
+		result => panic!("unexpected result : {:?}", result),
+	};
+
+	assert_eq!(
+		mock.folder("").unwrap().children,
+		vec![String::from("public/")]
+	);
+	assert_eq!(
+		mock.folder("public/").unwrap().children,
+		vec![String::from("A/")]
+	);
+	assert_eq!(
+		mock.folder("public/A/").unwrap().children,
+		vec![String::from("AA")]
+	);
+	assert_eq!(mock.document("public/A/AA").unwrap().data.etag, AA_etag);
+	assert_eq!(mock.document("public/A/AA").unwrap().content, b"AA");
+}
+
+#[test]
+fn put_in_incorrect_path() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert!(matches!(
+		storage.put(
+			&ItemPath::from("A/A\0A"),
+			&[],
+			&[],
+			Item::new_doc(b"AA", "text/plain"),
+		),
+		PutResult::Err(DataSourceError::IncorrectItemName { .. })
+	));
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+	assert_eq!(
+		mock.folder("A/").unwrap().children,
+		vec![String::from("AA")]
+	);
+}
+
+#[test]
+fn simple_delete_on_not_existing() {
+	let mock = IndexedDbMock::default();
+	let mut storage = mock.reopen();
+
+	assert!(matches!(
+		storage.delete(&ItemPath::from("A/AA"), &[]),
+		Err(DataSourceError::NotFound { .. })
+	));
+
+	assert!(mock.documents.lock().unwrap().is_empty());
+	assert!(mock.folders.lock().unwrap().is_empty());
+}
+
+#[test]
+fn simple_delete_on_existing() {
+	let (mock, mut storage, root_etag, _, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage.delete(&ItemPath::from("A/AA"), &[]).unwrap(),
+		AA_etag
+	);
+
+	assert!(mock.document("A/AA").is_none());
+	assert!(mock.folder("A/").is_none());
+
+	let root = mock.folder("").unwrap();
+	assert_ne!(root.data.etag, root_etag);
+	assert_eq!(root.children, vec![String::from("public/")]);
+}
+
+#[test]
+fn delete_in_public() {
+	let (mock, mut storage, _, _, _) = build_test_db();
+
+	storage.delete(&ItemPath::from("public/C/CA"), &[]).unwrap();
+
+	assert!(mock.document("public/C/CA").is_none());
+	assert!(mock.folder("public/C/").is_none());
+	assert!(mock.folder("public/").is_none());
+	assert_eq!(mock.folder("").unwrap().children, vec![String::from("A/")]);
+}
+
+#[test]
+fn delete_does_not_works_for_folders() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage.delete(&ItemPath::from("A/"), &[]).unwrap_err(),
+		DataSourceError::DoesNotWorksForFolders
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+}
+
+#[test]
+fn delete_with_if_match_not_found() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.delete(&ItemPath::from("A/AA"), &[&Etag::from("ANOTHER_ETAG")])
+			.unwrap_err(),
+		DataSourceError::NoIfMatch {
+			item_path: ItemPath::from("A/AA"),
+			search: Etag::from("ANOTHER_ETAG"),
+			found: AA_etag.clone(),
+		}
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+}
+
+#[test]
+fn delete_with_if_match_found() {
+	let (mock, mut storage, _, _, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.delete(&ItemPath::from("A/AA"), &[&AA_etag])
+			.unwrap(),
+		AA_etag
+	);
+
+	assert!(mock.document("A/AA").is_none());
+	assert!(mock.folder("A/").is_none());
+}
+
+#[test]
+fn delete_with_if_match_all() {
+	let (mock, mut storage, _, _, AA_etag) = build_test_db();
+
+	assert_eq!(
+		storage
+			.delete(&ItemPath::from("A/AA"), &[&Etag::from("*")])
+			.unwrap(),
+		AA_etag
+	);
+
+	assert!(mock.document("A/AA").is_none());
+	assert!(mock.folder("A/").is_none());
+}
+
+#[test]
+fn delete_with_existing_folder_conflict() {
+	let (mock, mut storage, _, _, _) = build_test_db();
+	storage
+		.put(
+			&ItemPath::from("A/AB/ABA"),
+			&[],
+			&[],
+			Item::new_doc(b"ABA", "text/plain"),
+		)
+		.unwrap();
+	let root_etag = mock.folder("").unwrap().data.etag;
+	let A_etag = mock.folder("A/").unwrap().data.etag;
+	let AA_etag = mock.document("A/AA").unwrap().data.etag;
+
+	assert_eq!(
+		storage.delete(&ItemPath::from("A/AB"), &[]).unwrap_err(),
+		DataSourceError::Conflict {
+			item_path: ItemPath::from("A/AB/")
+		}
+	);
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+	assert!(mock.document("A/AB/ABA").is_some());
+}
+
+#[test]
+fn delete_in_incorrect_path() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	assert!(matches!(
+		storage.delete(&ItemPath::from("A/A\0A"), &[]),
+		Err(DataSourceError::IncorrectItemName { .. })
+	));
+
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+}
+
+#[test]
+fn all_tests_bulk() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+	storage
+		.put(
+			&ItemPath::from("A/AB"),
+			&[],
+			&[],
+			Item::new_doc(b"AB", "text/plain"),
+		)
+		.unwrap();
+
+	assert_eq!(
+		mock.folder("A/").unwrap().children,
+		vec![String::from("AA"), String::from("AB")]
+	);
+	assert_eq!(
+		mock.folder("").unwrap().children,
+		vec![String::from("A/"), String::from("public/")]
+	);
+
+	// the same items are loaded from the records
+	let mut reopened = mock.reopen();
+	block_on(reopened.load_contents(&ItemPath::from(""))).unwrap();
+	block_on(storage.load_contents(&ItemPath::from(""))).unwrap();
+	for path in ["", "A/", "A/AA", "A/AB", "public/C/CA"] {
+		assert_eq!(
+			reopened.get(&ItemPath::from(path), &[], &[], true).unwrap(),
+			storage.get(&ItemPath::from(path), &[], &[], true).unwrap(),
+		);
+	}
+	assert_ne!(
+		reopened
+			.get(&ItemPath::from(""), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		&root_etag
+	);
+	assert_ne!(
+		reopened
+			.get(&ItemPath::from("A/"), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		&A_etag
+	);
+	assert_eq!(
+		reopened
+			.get(&ItemPath::from("A/AA"), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		&AA_etag
+	);
+
+	assert!(matches!(
+		reopened.get(&ItemPath::from("A/AC"), &[], &[], true),
+		Err(DataSourceError::NotFound { .. })
+	));
+	assert!(matches!(
+		reopened.get(&ItemPath::from("public/C/"), &[], &[], true),
+		Err(DataSourceError::CanNotBeListed { .. })
+	));
+	assert!(matches!(
+		reopened.get(
+			&ItemPath::from("A/AA"),
+			&[&Etag::from("ANOTHER_ETAG")],
+			&[],
+			true
+		),
+		Err(DataSourceError::NoIfMatch { .. })
+	));
+}
+
+#[test]
+fn rehash_writes_all_records() {
+	let (mock, mut storage, _, _, _) = build_test_db();
+
+	block_on(storage.load_contents(&ItemPath::from(""))).unwrap();
+	let root_etag = storage.rehash_etags(&ItemPath::from("")).unwrap().unwrap();
+
+	assert_eq!(mock.folder("").unwrap().data.etag, root_etag);
+	assert_eq!(
+		mock.document("A/AA").unwrap().data.etag,
+		Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+	assert_eq!(
+		mock.document("public/C/CA").unwrap().data.etag,
+		Etag::from_content(b"CA", &crate::item::ContentType::from("text/plain"))
+	);
+	assert_eq!(
+		mock.reopen()
+			.get(&ItemPath::from("A/"), &[], &[], false)
+			.unwrap(),
+		storage.get(&ItemPath::from("A/"), &[], &[], false).unwrap()
+	);
+}
+
+#[test]
+fn folder_listings() {
+	let (mock, _, _, _, _) = build_test_db();
+	let storage = mock.reopen();
+
+	let root = storage.get(&ItemPath::from(""), &[], &[], true).unwrap();
+	match root {
+		Item::Folder {
+			content: Some(content),
+			..
+		} => {
+			let mut names: Vec<&String> = content.keys().collect();
+			names.sort();
+			assert_eq!(names, vec!["A", "public"]);
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	let A = storage.get(&ItemPath::from("A/"), &[], &[], true).unwrap();
+	match A {
+		Item::Folder {
+			content: Some(content),
+			..
+		} => assert_eq!(content.keys().collect::<Vec<&String>>(), vec!["AA"]),
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	assert_eq!(
+		storage
+			.get(&ItemPath::from("A"), &[], &[], true)
+			.unwrap_err(),
+		DataSourceError::Conflict {
+			item_path: ItemPath::from("A/")
+		}
+	);
+	assert_eq!(
+		storage
+			.get(&ItemPath::from("A/AA/"), &[], &[], true)
+			.unwrap_err(),
+		DataSourceError::Conflict {
+			item_path: ItemPath::from("A/AA")
+		}
+	);
+	assert_eq!(
+		storage
+			.get(&ItemPath::from("not_exists/"), &[], &[], true)
+			.unwrap_err(),
+		DataSourceError::NotFound {
+			item_path: ItemPath::from("not_exists/")
+		}
+	);
+	assert_eq!(
+		storage
+			.get(&ItemPath::from("A/AA"), &[], &[&Etag::from("*")], true)
+			.unwrap_err(),
+		DataSourceError::IfNoneMatch {
+			item_path: ItemPath::from("A/AA"),
+			search: Etag::from("*"),
+			found: mock.document("A/AA").unwrap().data.etag,
+		}
+	);
+}
+
+#[test]
+fn failed_write_is_reported() {
+	let (mock, mut storage, root_etag, A_etag, AA_etag) = build_test_db();
+
+	*mock.full.lock().unwrap() = true;
+	assert!(matches!(
+		storage.put(
+			&ItemPath::from("A/AB"),
+			&[],
+			&[],
+			Item::new_doc(b"AB", "text/plain"),
+		),
+		PutResult::Err(DataSourceError::InternalError { .. })
+	));
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+	assert!(mock.document("A/AB").is_none());
+
+	// the memory does not match the records anymore
+	*mock.full.lock().unwrap() = false;
+	assert!(matches!(
+		storage.get(&ItemPath::from("A/AA"), &[], &[], true),
+		Err(DataSourceError::InternalError { .. })
+	));
+	assert!(matches!(
+		storage.delete(&ItemPath::from("A/AA"), &[]),
+		Err(DataSourceError::InternalError { .. })
+	));
+	assert_unchanged(&mock, &root_etag, &A_etag, &AA_etag);
+
+	let mut reopened = IndexedDbMock {
+		failure: Arc::new(Mutex::new(None)),
+		..mock.clone()
+	}
+	.reopen();
+	block_on(reopened.load_contents(&ItemPath::from("A/"))).unwrap();
+	assert!(matches!(
+		reopened.get(&ItemPath::from("A/AB"), &[], &[], true),
+		Err(DataSourceError::NotFound { .. })
+	));
+	assert_eq!(
+		reopened
+			.get(&ItemPath::from("A/AA"), &[], &[], true)
+			.unwrap()
+			.get_etag(),
+		&AA_etag
+	);
+}
+
+#[test]
+fn q7vx2mk9tz4wnc8rhd() {
+	let (mock, mut storage, _, _, AA_etag) = build_test_db();
+
+	// contents are not kept in memory after writes
+	assert!(matches!(
+		storage.get(&ItemPath::from("A/AA"), &[], &[], true),
+		Err(DataSourceError::InternalError { .. })
+	));
+	assert_eq!(
+		storage
+			.get(&ItemPath::from("A/AA"), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		&AA_etag
+	);
+
+	block_on(storage.load_contents(&ItemPath::from("A/AA"))).unwrap();
+	match storage
+		.get(&ItemPath::from("A/AA"), &[], &[], true)
+		.unwrap()
+	{
+		Item::Document {
+			content,
+			content_length,
+			..
+		} => {
+			assert_eq!(content, Some(b"AA".to_vec()));
+			assert_eq!(content_length, 2);
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	storage.unload_contents(&ItemPath::from("A/"));
+	assert!(matches!(
+		storage.get(&ItemPath::from("A/AA"), &[], &[], true),
+		Err(DataSourceError::InternalError { .. })
+	));
+	assert!(matches!(
+		block_on(storage.load_contents(&ItemPath::from("A/AB"))),
+		Err(DataSourceError::NotFound { .. })
+	));
+
+	// metadata are written without losing the content of the record
+	let new_etag = Etag::from("NEW_ETAG");
+	storage
+		.set_metadata(&ItemPath::from("A/AA"), &new_etag, None)
+		.unwrap();
+	let AA = mock.document("A/AA").unwrap();
+	assert_eq!(AA.data.etag, new_etag);
+	assert_eq!(AA.content, b"AA");
+	assert_eq!(
+		AA.data.checksum,
+		Some(crate::item::DataDocument::checksum_of(b"AA"))
+	);
+}
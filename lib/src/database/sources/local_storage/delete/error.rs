@@ -52,83 +52,18 @@ impl std::fmt::Display for DeleteError {
 	}
 }
 impl std::error::Error for DeleteError {}
-#[cfg(feature = "server")]
-impl crate::database::Error for DeleteError {
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse {
-		match self {
-			// TODO : we have to find a way to change method
-			Self::GetError(get_error) => {
-				crate::database::Error::to_response(get_error, origin, should_have_body)
-			}
-			Self::DoesNotWorksForFolders => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::CanNotDelete {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::CanNotReadFile {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::CanNotWriteFile {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::CanNotSerializeFile {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
-			Self::CanNotDeserializeFile {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				None,
-				should_have_body,
-			),
+impl From<DeleteError> for crate::database::DataSourceError {
+	fn from(error: DeleteError) -> Self {
+		match error {
+			DeleteError::GetError(get_error) => Self::from(get_error),
+			DeleteError::DoesNotWorksForFolders => Self::DoesNotWorksForFolders,
+			DeleteError::CanNotDelete { .. }
+			| DeleteError::CanNotReadFile { .. }
+			| DeleteError::CanNotWriteFile { .. }
+			| DeleteError::CanNotSerializeFile { .. }
+			| DeleteError::CanNotDeserializeFile { .. } => Self::InternalError {
+				error: format!("{}", error),
+			},
 		}
 	}
 }
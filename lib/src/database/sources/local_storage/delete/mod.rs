@@ -8,7 +8,7 @@ pub fn delete(
 	storage: &dyn super::Storage,
 	prefix: &str,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 ) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
 	if path.is_folder() {
 		return Err(Box::new(DeleteError::DoesNotWorksForFolders));
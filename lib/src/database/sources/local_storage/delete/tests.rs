@@ -76,6 +76,7 @@ fn build_test_db() -> (
 		etag: AAA_etag,
 		content_type: AAA_content_type,
 		last_modified: AAA_last_modified,
+		..
 	} = AAA.clone()
 	{
 		storage
@@ -85,6 +86,8 @@ fn build_test_db() -> (
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: AAA_etag,
 					content_type: AAA_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: AAA_last_modified,
 				})
 				.unwrap(),
@@ -103,6 +106,7 @@ fn build_test_db() -> (
 		etag: AB_etag,
 		content_type: AB_content_type,
 		last_modified: AB_last_modified,
+		..
 	} = AB.clone()
 	{
 		storage
@@ -112,6 +116,8 @@ fn build_test_db() -> (
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: AB_etag,
 					content_type: AB_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: AB_last_modified,
 				})
 				.unwrap(),
@@ -162,6 +168,7 @@ fn build_test_db() -> (
 		etag: BA_etag,
 		content_type: BA_content_type,
 		last_modified: BA_last_modified,
+		..
 	} = BA.clone()
 	{
 		storage
@@ -171,6 +178,8 @@ fn build_test_db() -> (
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: BA_etag,
 					content_type: BA_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: BA_last_modified,
 				})
 				.unwrap(),
@@ -221,15 +230,10 @@ fn simple_delete_on_not_existing() {
 	}
 
 	assert_eq!(
-		*delete(
-			&storage,
-			&prefix,
-			&ItemPath::from("A/AA/AAA"),
-			&Etag::from(""),
-		)
-		.unwrap_err()
-		.downcast::<DeleteError>()
-		.unwrap(),
+		*delete(&storage, &prefix, &ItemPath::from("A/AA/AAA"), &[],)
+			.unwrap_err()
+			.downcast::<DeleteError>()
+			.unwrap(),
 		DeleteError::GetError(GetError::NotFound {
 			item_path: ItemPath::from("A/")
 		})
@@ -246,13 +250,7 @@ fn simple_delete_on_not_existing() {
 fn simple_delete_on_existing() {
 	let (storage, prefix, root_etag, A_etag, _, _, AAA_etag, _, _, _) = build_test_db();
 
-	let old_AAA_etag = delete(
-		&storage,
-		&prefix,
-		&ItemPath::from("A/AA/AAA"),
-		&Etag::from(""),
-	)
-	.unwrap();
+	let old_AAA_etag = delete(&storage, &prefix, &ItemPath::from("A/AA/AAA"), &[]).unwrap();
 
 	assert_eq!(AAA_etag, old_AAA_etag);
 
@@ -325,7 +323,7 @@ fn does_not_works_for_folders() {
 	let (storage, prefix, root_etag, A_etag, AA_etag, _, AAA_etag, _, _, _) = build_test_db();
 
 	assert_eq!(
-		*delete(&storage, &prefix, &ItemPath::from("A/AA/"), &Etag::from(""),)
+		*delete(&storage, &prefix, &ItemPath::from("A/AA/"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -425,7 +423,7 @@ fn delete_with_if_match_not_found() {
 			&storage,
 			&prefix,
 			&ItemPath::from("A/AA/AAA"),
-			&Etag::from("OTHER_ETAG"),
+			&[&Etag::from("OTHER_ETAG")],
 		)
 		.unwrap_err()
 		.downcast::<DeleteError>()
@@ -525,7 +523,8 @@ fn delete_with_if_match_not_found() {
 fn delete_with_if_match_found() {
 	let (storage, prefix, root_etag, A_etag, _, _, AAA_etag, _, _, _) = build_test_db();
 
-	let old_AAA_etag = delete(&storage, &prefix, &ItemPath::from("A/AA/AAA"), &AAA_etag).unwrap();
+	let old_AAA_etag =
+		delete(&storage, &prefix, &ItemPath::from("A/AA/AAA"), &[&AAA_etag]).unwrap();
 
 	assert_eq!(old_AAA_etag, AAA_etag);
 
@@ -601,7 +600,7 @@ fn delete_with_if_match_all() {
 		&storage,
 		&prefix,
 		&ItemPath::from("A/AA/AAA"),
-		&Etag::from("*"),
+		&[&Etag::from("*")],
 	)
 	.unwrap();
 
@@ -676,7 +675,7 @@ fn delete_with_existing_folder_conflict() {
 	let (storage, prefix, root_etag, A_etag, AA_etag, _, AAA_etag, _, _, _) = build_test_db();
 
 	assert_eq!(
-		*delete(&storage, &prefix, &ItemPath::from("A/AA"), &Etag::from(""),)
+		*delete(&storage, &prefix, &ItemPath::from("A/AA"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -773,13 +772,7 @@ fn delete_with_existing_folder_conflict() {
 fn delete_in_public() {
 	let (storage, prefix, root_etag, _, _, _, _, _, _, BA_etag) = build_test_db();
 
-	let old_BA_etag = delete(
-		&storage,
-		&prefix,
-		&ItemPath::from("public/B/BA"),
-		&Etag::from(""),
-	)
-	.unwrap();
+	let old_BA_etag = delete(&storage, &prefix, &ItemPath::from("public/B/BA"), &[]).unwrap();
 
 	assert_eq!(old_BA_etag, BA_etag);
 
@@ -839,15 +832,10 @@ fn delete_in_incorrect_path() {
 	}
 
 	assert_eq!(
-		*delete(
-			&storage,
-			&prefix,
-			&ItemPath::from("A/A\0A"),
-			&Etag::from(""),
-		)
-		.unwrap_err()
-		.downcast::<DeleteError>()
-		.unwrap(),
+		*delete(&storage, &prefix, &ItemPath::from("A/A\0A"), &[],)
+			.unwrap_err()
+			.downcast::<DeleteError>()
+			.unwrap(),
 		DeleteError::GetError(GetError::IncorrectItemName {
 			item_path: ItemPath::from("A/A\0A"),
 			error: String::from("`A\0A` should not contains `\\0` character")
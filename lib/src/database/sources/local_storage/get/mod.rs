@@ -8,7 +8,7 @@ pub fn get(
 	storage: &dyn super::Storage,
 	prefix: &str,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 	get_content: bool,
 ) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
@@ -66,9 +66,10 @@ pub fn get(
 											&crate::item::ItemPath::from(
 												key.strip_prefix(&format!("{}/", prefix)).unwrap(),
 											),
-											&crate::item::Etag::from(""),
 											&[],
-											true,
+											&[],
+											// listings only need metadata of documents
+											false,
 										)
 										.unwrap(),
 									),
@@ -83,7 +84,7 @@ pub fn get(
 												storage,
 												prefix,
 												&path.joined_folder(name).unwrap(),
-												&crate::item::Etag::from(""),
+												&[],
 												&[],
 												true,
 											);
@@ -118,32 +119,12 @@ pub fn get(
 
 			match serde_json::from_str::<crate::item::DataFolder>(&folderdata_content) {
 				Ok(folderdata) => {
-					if !if_match.is_empty() {
-						let upper_if_match = if_match.trim().to_uppercase();
-						if folderdata.etag.trim().to_uppercase() != upper_if_match
-							&& upper_if_match != "*"
-						{
-							return Err(Box::new(GetError::NoIfMatch {
-								item_path: path.clone(),
-								search: if_match.clone(),
-								found: folderdata.etag,
-							}));
-						}
-					}
-
-					if !if_none_match.is_empty() {
-						for search_etag in if_none_match {
-							if folderdata.etag.trim().to_uppercase()
-								== search_etag.trim().to_uppercase()
-								|| search_etag.trim() == "*"
-							{
-								return Err(Box::new(GetError::IfNoneMatch {
-									item_path: path.clone(),
-									search: (*search_etag).clone(),
-									found: folderdata.etag,
-								}));
-							}
-						}
+					if let Err(failure) = crate::database::check_preconditions(
+						Some(&folderdata.etag),
+						if_match,
+						if_none_match,
+					) {
+						return Err(Box::new(GetError::from_precondition(path.clone(), failure)));
 					}
 
 					return Ok(crate::item::Item::Folder {
@@ -195,37 +176,42 @@ pub fn get(
 								None
 							};
 
-							if !if_match.is_empty() {
-								let upper_if_match = if_match.trim().to_uppercase();
-								if filedata.etag.trim().to_uppercase() != upper_if_match
-									&& upper_if_match != "*"
-								{
-									return Err(Box::new(GetError::NoIfMatch {
+							if let Err(failure) = crate::database::check_preconditions(
+								Some(&filedata.etag),
+								if_match,
+								if_none_match,
+							) {
+								return Err(Box::new(GetError::from_precondition(
+									path.clone(),
+									failure,
+								)));
+							}
+
+							if let Some(content) = &content {
+								if !filedata.is_intact(content) {
+									return Err(Box::new(GetError::CorruptedContent {
 										item_path: path.clone(),
-										search: if_match.clone(),
-										found: filedata.etag,
 									}));
 								}
 							}
 
-							if !if_none_match.is_empty() {
-								for search_etag in if_none_match {
-									if filedata.etag.trim().to_uppercase()
-										== search_etag.trim().to_uppercase() || search_etag.trim()
-										== "*"
-									{
-										return Err(Box::new(GetError::IfNoneMatch {
-											item_path: path.clone(),
-											search: (*search_etag).clone(),
-											found: filedata.etag,
-										}));
+							let content_length = match (&content, filedata.content_length) {
+								(Some(content), _) => content.len() as u64,
+								(None, Some(content_length)) => content_length,
+								// metadata written by older versions does not have it
+								(None, None) => {
+									match storage.get_item(&format!("{}/{}", prefix, path)) {
+										Ok(Some(content)) => base64::decode(content)
+											.map_or(0, |content| content.len() as u64),
+										_ => 0,
 									}
 								}
-							}
+							};
 
 							return Ok(crate::item::Item::Document {
 								etag: filedata.etag,
 								content_type: filedata.content_type,
+								content_length,
 								last_modified: filedata.last_modified,
 								content,
 							});
@@ -252,14 +238,7 @@ pub fn get(
 							if path != &crate::item::ItemPath::from("") {
 								let parent = path.parent().unwrap();
 
-								let parent_get = get(
-									storage,
-									prefix,
-									&parent,
-									&crate::item::Etag::from(""),
-									&[],
-									false,
-								);
+								let parent_get = get(storage, prefix, &parent, &[], &[], false);
 
 								if let Ok(crate::item::Item::Document { .. }) = parent_get {
 									return Err(Box::new(GetError::Conflict {
@@ -3,6 +3,33 @@
 use super::{super::LocalStorageMock, super::Storage, get, GetError};
 use crate::item::{DataDocument, DataFolder, Etag, Item, ItemPath};
 
+/// `item`, as listed by [`get`] : documents inside folders only have their metadata.
+fn listing(item: &Item) -> Item {
+	return match item {
+		Item::Folder {
+			etag,
+			content: Some(content),
+		} => Item::Folder {
+			etag: etag.clone(),
+			content: Some(
+				content
+					.iter()
+					.map(|(name, child)| {
+						(
+							name.clone(),
+							Box::new(match **child {
+								Item::Folder { .. } => listing(child),
+								Item::Document { .. } => child.empty_clone(),
+							}),
+						)
+					})
+					.collect(),
+			),
+		},
+		_ => item.clone(),
+	};
+}
+
 // TODO : test if folderdata found but content is file
 // TODO : test if filedata found but content is folder
 
@@ -74,6 +101,7 @@ fn all_tests_bulk() {
 		etag: AA_etag,
 		content_type: AA_content_type,
 		last_modified: AA_last_modified,
+		..
 	} = AA.clone()
 	{
 		storage
@@ -83,6 +111,8 @@ fn all_tests_bulk() {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: AA_etag,
 					content_type: AA_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: AA_last_modified,
 				})
 				.unwrap(),
@@ -99,6 +129,7 @@ fn all_tests_bulk() {
 		etag: AB_etag,
 		content_type: AB_content_type,
 		last_modified: AB_last_modified,
+		..
 	} = AB.clone()
 	{
 		storage
@@ -108,6 +139,8 @@ fn all_tests_bulk() {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: AB_etag,
 					content_type: AB_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: AB_last_modified,
 				})
 				.unwrap(),
@@ -124,6 +157,7 @@ fn all_tests_bulk() {
 		etag: AC_etag,
 		content_type: AC_content_type,
 		last_modified: AC_last_modified,
+		..
 	} = AC.clone()
 	{
 		storage
@@ -133,6 +167,8 @@ fn all_tests_bulk() {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: AC_etag,
 					content_type: AC_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: AC_last_modified,
 				})
 				.unwrap(),
@@ -160,6 +196,7 @@ fn all_tests_bulk() {
 		etag: BA_etag,
 		content_type: BA_content_type,
 		last_modified: BA_last_modified,
+		..
 	} = BA.clone()
 	{
 		storage
@@ -169,6 +206,8 @@ fn all_tests_bulk() {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: BA_etag,
 					content_type: BA_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: BA_last_modified,
 				})
 				.unwrap(),
@@ -185,6 +224,7 @@ fn all_tests_bulk() {
 		etag: BB_etag,
 		content_type: BB_content_type,
 		last_modified: BB_last_modified,
+		..
 	} = BB.clone()
 	{
 		storage
@@ -194,6 +234,8 @@ fn all_tests_bulk() {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: BB_etag,
 					content_type: BB_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: BB_last_modified,
 				})
 				.unwrap(),
@@ -232,6 +274,7 @@ fn all_tests_bulk() {
 		etag: CA_etag,
 		content_type: CA_content_type,
 		last_modified: CA_last_modified,
+		..
 	} = CA.clone()
 	{
 		storage
@@ -241,6 +284,8 @@ fn all_tests_bulk() {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: CA_etag,
 					content_type: CA_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: CA_last_modified,
 				})
 				.unwrap(),
@@ -263,32 +308,21 @@ fn all_tests_bulk() {
 	keys.sort();
 	dbg!(keys);
 
+	// folders are listed without the content of their documents
+	let root_without_public = listing(&root_without_public);
+	let A = listing(&A);
+	let B = listing(&B);
+
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	println!("//////// 010 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from(""),
-			&Etag::from(""),
-			&vec![],
-			true
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from(""), &[], &vec![], true).unwrap(),
 		root_without_public.clone()
 	);
 	println!("//////// 020 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from("A/"),
-			&Etag::from(""),
-			&vec![],
-			true
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from("A/"), &[], &vec![], true).unwrap(),
 		A.clone()
 	);
 	println!("//////// 030 ////////");
@@ -297,7 +331,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -310,7 +344,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AB"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -323,7 +357,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AC"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -332,15 +366,7 @@ fn all_tests_bulk() {
 	);
 	println!("//////// 060 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from("B/"),
-			&Etag::from(""),
-			&vec![],
-			true
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from("B/"), &[], &vec![], true).unwrap(),
 		B
 	);
 	println!("//////// 070 ////////");
@@ -349,7 +375,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("B/BA"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -362,7 +388,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("B/BB"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -375,7 +401,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/C/CA"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -391,7 +417,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from(""),
-			root.get_etag(),
+			&[root.get_etag()],
 			&vec![],
 			true
 		)
@@ -404,7 +430,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/"),
-			A.get_etag(),
+			&[A.get_etag()],
 			&vec![],
 			true
 		)
@@ -417,7 +443,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA"),
-			AA.get_etag(),
+			&[AA.get_etag()],
 			&vec![],
 			true
 		)
@@ -433,7 +459,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")],
 			true
 		)
@@ -446,7 +472,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")],
 			true
 		)
@@ -459,7 +485,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")],
 			true
 		)
@@ -471,17 +497,10 @@ fn all_tests_bulk() {
 
 	println!("//////// 160 ////////");
 	assert_eq!(
-		*get(
-			&storage,
-			prefix,
-			&ItemPath::from("A"),
-			&Etag::from(""),
-			&vec![],
-			true
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&storage, prefix, &ItemPath::from("A"), &[], &vec![], true)
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::Conflict {
 			item_path: ItemPath::from("A/")
 		}
@@ -492,7 +511,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA/"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -509,7 +528,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AC/not_exists"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -526,7 +545,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/not_exists"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -543,7 +562,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/not_exists/nested"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -560,7 +579,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("B/not_exists"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -577,7 +596,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("not_exists/"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -594,7 +613,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("not_exists"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -613,7 +632,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("."),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -628,15 +647,7 @@ fn all_tests_bulk() {
 	*/
 	println!("//////// 245 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from("."),
-			&Etag::from(""),
-			&vec![],
-			true
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from("."), &[], &vec![], true).unwrap(),
 		root_without_public,
 	);
 	/*
@@ -647,7 +658,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/.."),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -666,7 +677,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/.."),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -681,7 +692,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/../"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -700,7 +711,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/../"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -715,7 +726,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/../AA"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -734,7 +745,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/A\0A"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -752,7 +763,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -769,7 +780,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/C/"),
-			&Etag::from(""),
+			&[],
 			&vec![],
 			true
 		)
@@ -789,7 +800,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from(""),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&vec![],
 			true
 		)
@@ -808,7 +819,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&vec![],
 			true
 		)
@@ -827,7 +838,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&vec![],
 			true
 		)
@@ -849,7 +860,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -868,7 +879,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -887,7 +898,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -909,7 +920,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[root.get_etag()],
 			true
 		)
@@ -928,7 +939,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[A.get_etag()],
 			true
 		)
@@ -947,7 +958,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[AA.get_etag()],
 			true
 		)
@@ -965,41 +976,17 @@ fn all_tests_bulk() {
 
 	println!("//////// 400 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from(""),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from(""), &[], &[], false).unwrap(),
 		root_without_public.empty_clone()
 	);
 	println!("//////// 410 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from("A/"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from("A/"), &[], &[], false).unwrap(),
 		A.empty_clone()
 	);
 	println!("//////// 420 ////////");
 	assert_eq!(
-		get(
-			&storage,
-			prefix,
-			&ItemPath::from("A/AA"),
-			&Etag::from(""),
-			&[],
-			false
-		)
-		.unwrap(),
+		get(&storage, prefix, &ItemPath::from("A/AA"), &[], &[], false).unwrap(),
 		AA.empty_clone()
 	);
 	println!("//////// 430 ////////");
@@ -1008,7 +995,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -1025,7 +1012,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/C/"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -1042,7 +1029,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/C/CA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -1055,7 +1042,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/not_exists"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -1072,7 +1059,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("public/not_exists/"),
-			&Etag::from(""),
+			&[],
 			&[],
 			false
 		)
@@ -1092,7 +1079,7 @@ fn all_tests_bulk() {
 			&storage,
 			prefix,
 			&ItemPath::from("A/.AA.itemdata.json"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			true
 		)
@@ -1,6 +1,7 @@
 mod delete;
 mod get;
 mod put;
+mod rehash;
 
 pub use delete::*;
 pub use get::{get, GetError};
@@ -20,10 +21,10 @@ impl crate::database::DataSource for LocalStorage {
 	fn get(
 		&self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		get_content: bool,
-	) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
+	) -> Result<crate::item::Item, crate::database::DataSourceError> {
 		match web_sys::window() {
 			Some(window) => match window.local_storage() {
 				Ok(Some(local_storage)) => get(
@@ -33,18 +34,25 @@ impl crate::database::DataSource for LocalStorage {
 					if_match,
 					if_none_match,
 					get_content,
-				),
-				Ok(None) => Err(Box::new(LocalStorageError::ThereIsNoLocalStorage)),
-				Err(_) => Err(Box::new(LocalStorageError::CanNotGetLocalStorage)),
+				)
+				.map_err(crate::database::DataSourceError::from_boxed::<GetError>),
+				Ok(None) => Err(crate::database::DataSourceError::from(
+					LocalStorageError::ThereIsNoLocalStorage,
+				)),
+				Err(_) => Err(crate::database::DataSourceError::from(
+					LocalStorageError::CanNotGetLocalStorage,
+				)),
 			},
-			None => Err(Box::new(LocalStorageError::CanNotGetWindow)),
+			None => Err(crate::database::DataSourceError::from(
+				LocalStorageError::CanNotGetWindow,
+			)),
 		}
 	}
 
 	fn put(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		new_item: crate::item::Item,
 	) -> crate::database::PutResult {
@@ -58,35 +66,102 @@ impl crate::database::DataSource for LocalStorage {
 					if_none_match,
 					new_item,
 				),
-				Ok(None) => crate::database::PutResult::Err(Box::new(
+				Ok(None) => {
+					crate::database::PutResult::Err(crate::database::DataSourceError::from(
+						super::local_storage::LocalStorageError::ThereIsNoLocalStorage,
+					))
+				}
+				Err(_) => crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					super::local_storage::LocalStorageError::CanNotGetLocalStorage,
+				)),
+			},
+			None => crate::database::PutResult::Err(crate::database::DataSourceError::from(
+				super::local_storage::LocalStorageError::CanNotGetWindow,
+			)),
+		}
+	}
+
+	fn delete(
+		&mut self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+		match web_sys::window() {
+			Some(window) => match window.local_storage() {
+				Ok(Some(local_storage)) => delete(&local_storage, &self.prefix, path, if_match)
+					.map_err(crate::database::DataSourceError::from_boxed::<DeleteError>),
+				Ok(None) => Err(crate::database::DataSourceError::from(
 					super::local_storage::LocalStorageError::ThereIsNoLocalStorage,
 				)),
-				Err(_) => crate::database::PutResult::Err(Box::new(
+				Err(_) => Err(crate::database::DataSourceError::from(
 					super::local_storage::LocalStorageError::CanNotGetLocalStorage,
 				)),
 			},
-			None => crate::database::PutResult::Err(Box::new(
+			None => Err(crate::database::DataSourceError::from(
 				super::local_storage::LocalStorageError::CanNotGetWindow,
 			)),
 		}
 	}
 
-	fn delete(
+	fn documents_in(
+		&self,
+		path: &crate::item::ItemPath,
+	) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+		match web_sys::window() {
+			Some(window) => match window.local_storage() {
+				Ok(Some(local_storage)) => rehash::documents_in(&local_storage, &self.prefix, path),
+				Ok(None) => Err(crate::database::DataSourceError::from(
+					super::local_storage::LocalStorageError::ThereIsNoLocalStorage,
+				)),
+				Err(_) => Err(crate::database::DataSourceError::from(
+					super::local_storage::LocalStorageError::CanNotGetLocalStorage,
+				)),
+			},
+			None => Err(crate::database::DataSourceError::from(
+				super::local_storage::LocalStorageError::CanNotGetWindow,
+			)),
+		}
+	}
+
+	fn set_metadata(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
-	) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
+		etag: &crate::item::Etag,
+		last_modified: Option<time::OffsetDateTime>,
+	) -> Result<(), crate::database::DataSourceError> {
 		match web_sys::window() {
 			Some(window) => match window.local_storage() {
-				Ok(Some(local_storage)) => delete(&local_storage, &self.prefix, path, if_match),
-				Ok(None) => Err(Box::new(
+				Ok(Some(local_storage)) => {
+					rehash::set_metadata(&local_storage, &self.prefix, path, etag, last_modified)
+				}
+				Ok(None) => Err(crate::database::DataSourceError::from(
 					super::local_storage::LocalStorageError::ThereIsNoLocalStorage,
 				)),
-				Err(_) => Err(Box::new(
+				Err(_) => Err(crate::database::DataSourceError::from(
 					super::local_storage::LocalStorageError::CanNotGetLocalStorage,
 				)),
 			},
-			None => Err(Box::new(
+			None => Err(crate::database::DataSourceError::from(
+				super::local_storage::LocalStorageError::CanNotGetWindow,
+			)),
+		}
+	}
+
+	fn rehash_etags(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError> {
+		match web_sys::window() {
+			Some(window) => match window.local_storage() {
+				Ok(Some(local_storage)) => rehash::rehash_etags(&local_storage, &self.prefix, path),
+				Ok(None) => Err(crate::database::DataSourceError::from(
+					super::local_storage::LocalStorageError::ThereIsNoLocalStorage,
+				)),
+				Err(_) => Err(crate::database::DataSourceError::from(
+					super::local_storage::LocalStorageError::CanNotGetLocalStorage,
+				)),
+			},
+			None => Err(crate::database::DataSourceError::from(
 				super::local_storage::LocalStorageError::CanNotGetWindow,
 			)),
 		}
@@ -141,38 +216,10 @@ impl std::fmt::Display for LocalStorageError {
 	}
 }
 impl std::error::Error for LocalStorageError {}
-#[cfg(feature = "server")]
-impl crate::database::Error for LocalStorageError {
-	// TODO : we have to find a way to change method
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse {
-		match self {
-			Self::CanNotGetWindow => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::CanNotGetLocalStorage => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::ThereIsNoLocalStorage => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
+impl From<LocalStorageError> for crate::database::DataSourceError {
+	fn from(error: LocalStorageError) -> Self {
+		Self::InternalError {
+			error: format!("{}", error),
 		}
 	}
 }
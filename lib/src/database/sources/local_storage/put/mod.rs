@@ -8,11 +8,30 @@ pub fn put(
 	storage: &dyn super::Storage,
 	prefix: &str,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 	item: crate::item::Item,
 ) -> crate::database::PutResult {
-	match super::get(storage, prefix, path, if_match, if_none_match, true) {
+	let item_fetch = match super::get(storage, prefix, path, if_match, if_none_match, true) {
+		// a corrupted document can still be replaced by a new content
+		Err(error)
+			if matches!(
+				error.downcast_ref::<super::GetError>(),
+				Some(super::GetError::CorruptedContent { .. })
+			) && matches!(
+				item,
+				crate::item::Item::Document {
+					content: Some(_),
+					..
+				}
+			) =>
+		{
+			super::get(storage, prefix, path, if_match, if_none_match, false)
+		}
+		item_fetch => item_fetch,
+	};
+
+	match item_fetch {
 		Ok(crate::item::Item::Document {
 			content_type: old_content_type,
 			content: old_content,
@@ -36,6 +55,10 @@ pub fn put(
 									etag: new_etag.clone(),
 									last_modified: Some(time::OffsetDateTime::now_utc()),
 									content_type: new_content_type,
+									content_length: Some(new_content.len() as u64),
+									checksum: Some(crate::item::DataDocument::checksum_of(
+										&new_content,
+									)),
 								});
 
 							let filedata_path = crate::item::ItemPath::from(
@@ -53,9 +76,13 @@ pub fn put(
 										.set_item(&format!("{}", filedata_path), &serialized_data)
 										.is_err()
 									{
-										return crate::database::PutResult::Err(Box::new(
-											PutError::GetError(super::GetError::CanNotGetStorage),
-										));
+										return crate::database::PutResult::Err(
+											crate::database::DataSourceError::from(
+												PutError::GetError(
+													super::GetError::CanNotGetStorage,
+												),
+											),
+										);
 									}
 									if storage
 										.set_item(
@@ -64,9 +91,13 @@ pub fn put(
 										)
 										.is_err()
 									{
-										return crate::database::PutResult::Err(Box::new(
-											PutError::GetError(super::GetError::CanNotGetStorage),
-										));
+										return crate::database::PutResult::Err(
+											crate::database::DataSourceError::from(
+												PutError::GetError(
+													super::GetError::CanNotGetStorage,
+												),
+											),
+										);
 									}
 
 									for ancestor in path
@@ -96,15 +127,15 @@ pub fn put(
 														match serde_json::to_string(&new_folderdata) {
 														Ok(new_folderdata_content) => {
 															if storage.set_item(&format!("{}", folderdata_path), &new_folderdata_content).is_err() {
-																return crate::database::PutResult::Err(Box::new(PutError::GetError(super::GetError::CanNotGetStorage)));
+																return crate::database::PutResult::Err(crate::database::DataSourceError::from(PutError::GetError(super::GetError::CanNotGetStorage)));
 															}
 														}
-														Err(error) => return crate::database::PutResult::Err(Box::new(PutError::CanNotSerializeFile { item_path: folderdata_path, error: format!("{}", error) })),
+														Err(error) => return crate::database::PutResult::Err(crate::database::DataSourceError::from(PutError::CanNotSerializeFile { item_path: folderdata_path, error: format!("{}", error) })),
 													}
 													}
 													Err(error) => {
 														return crate::database::PutResult::Err(
-															Box::new(
+															crate::database::DataSourceError::from(
 																PutError::CanNotDeserializeFile {
 																	item_path: folderdata_path,
 																	error: format!("{}", error),
@@ -117,16 +148,20 @@ pub fn put(
 											Ok(None) => {
 												// it should not happen
 												// we can not fix it easily here because risks or conflict
-												return crate::database::PutResult::Err(Box::new(
-													PutError::InternalError,
-												));
+												return crate::database::PutResult::Err(
+													crate::database::DataSourceError::from(
+														PutError::InternalError,
+													),
+												);
 											}
 											Err(_) => {
-												return crate::database::PutResult::Err(Box::new(
-													PutError::GetError(
-														super::GetError::CanNotGetStorage,
+												return crate::database::PutResult::Err(
+													crate::database::DataSourceError::from(
+														PutError::GetError(
+															super::GetError::CanNotGetStorage,
+														),
 													),
-												));
+												);
 											}
 										}
 									}
@@ -138,48 +173,66 @@ pub fn put(
 									);
 								}
 								Err(error) => {
-									return crate::database::PutResult::Err(Box::new(
-										PutError::CanNotDeserializeFile {
-											item_path: filedata_path,
-											error: format!("{}", error),
-										},
-									));
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(
+											PutError::CanNotDeserializeFile {
+												item_path: filedata_path,
+												error: format!("{}", error),
+											},
+										),
+									);
 								}
 							}
 						}
 						None => {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::NoContentInside {
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::NoContentInside {
 									item_path: path.clone(),
-								},
-							));
+								}),
+							);
 						}
 					}
 				} else {
-					return crate::database::PutResult::Err(Box::new(PutError::ContentNotChanged));
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::ContentNotChanged),
+					);
 				}
 			} else {
-				return crate::database::PutResult::Err(Box::new(PutError::GetError(
-					super::GetError::Conflict {
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::GetError(super::GetError::Conflict {
 						item_path: path.clone(),
-					},
-				)));
+					}),
+				));
 			}
 		}
 		Ok(crate::item::Item::Folder { .. }) => {
 			if let crate::item::Item::Folder { .. } = item {
-				return crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders));
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::DoesNotWorksForFolders,
+				));
 			} else {
-				return crate::database::PutResult::Err(Box::new(PutError::GetError(
-					super::GetError::Conflict {
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::GetError(super::GetError::Conflict {
 						item_path: path.clone(),
-					},
-				)));
+					}),
+				));
 			}
 		}
 		Err(error) => {
 			let error = *error.downcast::<super::GetError>().unwrap();
 
+			if let super::GetError::NotFound { .. } = error {
+				if let Err(failure) =
+					crate::database::check_preconditions(None, if_match, if_none_match)
+				{
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::GetError(
+							super::GetError::from_precondition(path.clone(), failure),
+						)),
+					);
+				}
+			}
+
 			return match error {
 				super::GetError::NotFound { .. } => match item {
 					crate::item::Item::Document {
@@ -189,6 +242,8 @@ pub fn put(
 					} => {
 						let datadocument = crate::item::DataDocument {
 							content_type: new_content_type,
+							content_length: Some(new_content.len() as u64),
+							checksum: Some(crate::item::DataDocument::checksum_of(&new_content)),
 							..Default::default()
 						};
 
@@ -204,9 +259,11 @@ pub fn put(
 							)
 							.is_err()
 						{
-							return crate::database::PutResult::Err(Box::new(PutError::GetError(
-								super::GetError::CanNotGetStorage,
-							)));
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::GetError(
+									super::GetError::CanNotGetStorage,
+								)),
+							);
 						}
 
 						if storage
@@ -216,9 +273,11 @@ pub fn put(
 							)
 							.is_err()
 						{
-							return crate::database::PutResult::Err(Box::new(PutError::GetError(
-								super::GetError::CanNotGetStorage,
-							)));
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::GetError(
+									super::GetError::CanNotGetStorage,
+								)),
+							);
 						}
 
 						for ancestor in path
@@ -235,11 +294,13 @@ pub fn put(
 								ancestor.file_name()
 							)) {
 								Ok(Some(_)) => {
-									return crate::database::PutResult::Err(Box::new(
-										PutError::GetError(super::GetError::Conflict {
-											item_path: ancestor,
-										}),
-									))
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(PutError::GetError(
+											super::GetError::Conflict {
+												item_path: ancestor,
+											},
+										)),
+									)
 								}
 								Ok(None) => {
 									let datafolder = crate::item::DataFolder::default();
@@ -254,18 +315,24 @@ pub fn put(
 										)
 										.is_err()
 									{
-										return crate::database::PutResult::Err(Box::new(
-											PutError::GetError(super::GetError::CanNotGetStorage),
-										));
+										return crate::database::PutResult::Err(
+											crate::database::DataSourceError::from(
+												PutError::GetError(
+													super::GetError::CanNotGetStorage,
+												),
+											),
+										);
 									}
 								}
 								Err(_) => {
-									return crate::database::PutResult::Err(Box::new(
-										PutError::CanNotDeserializeFile {
-											item_path: ancestor,
-											error: String::new(),
-										},
-									));
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(
+											PutError::CanNotDeserializeFile {
+												item_path: ancestor,
+												error: String::new(),
+											},
+										),
+									);
 								}
 							}
 						}
@@ -278,15 +345,19 @@ pub fn put(
 						)
 					}
 					crate::item::Item::Document { content: None, .. } => {
-						crate::database::PutResult::Err(Box::new(PutError::NoContentInside {
-							item_path: path.clone(), // TODO : not really path, but the `item` parameter of this method
-						}))
-					}
-					crate::item::Item::Folder { .. } => {
-						crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders))
+						crate::database::PutResult::Err(crate::database::DataSourceError::from(
+							PutError::NoContentInside {
+								item_path: path.clone(), // TODO : not really path, but the `item` parameter of this method
+							},
+						))
 					}
+					crate::item::Item::Folder { .. } => crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::DoesNotWorksForFolders),
+					),
 				},
-				_ => crate::database::PutResult::Err(Box::new(PutError::GetError(error))),
+				_ => crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::GetError(error),
+				)),
 			};
 		}
 	}
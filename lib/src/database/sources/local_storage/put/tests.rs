@@ -45,6 +45,7 @@ fn build_test_db() -> (LocalStorageMock, String, Etag, Etag, Etag) {
 		etag: AA_etag,
 		content_type: AA_content_type,
 		last_modified: AA_last_modified,
+		..
 	} = AA.clone()
 	{
 		storage
@@ -54,6 +55,8 @@ fn build_test_db() -> (LocalStorageMock, String, Etag, Etag, Etag) {
 					datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 					etag: AA_etag,
 					content_type: AA_content_type,
+					content_length: Some(content.len() as u64),
+					checksum: Some(DataDocument::checksum_of(&content)),
 					last_modified: AA_last_modified,
 				})
 				.unwrap(),
@@ -85,7 +88,7 @@ fn simple_put_on_not_existing() {
 		&storage,
 		&prefix,
 		&ItemPath::from("AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -124,7 +127,7 @@ fn simple_put_on_existing() {
 		&storage,
 		&prefix,
 		&ItemPath::from("A/AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -190,18 +193,16 @@ fn content_not_changed() {
 	let (storage, prefix, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AA", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::ContentNotChanged
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::ContentNotChanged)
 	);
 
 	assert_eq!(
@@ -263,18 +264,16 @@ fn does_not_works_for_folders() {
 	let storage = LocalStorageMock::new();
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_folder(vec![])
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::DoesNotWorksForFolders
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::DoesNotWorksForFolders)
 	);
 
 	assert_eq!(storage.length().unwrap(), 0);
@@ -289,7 +288,7 @@ fn put_with_if_none_match_all_on_not_existing() {
 		&storage,
 		&prefix,
 		&ItemPath::from("A/AA"),
-		&Etag::from(""),
+		&[],
 		&[&Etag::from("*")],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -345,22 +344,22 @@ fn put_with_if_none_match_all_on_existing() {
 	let (storage, prefix, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::IfNoneMatch {
-			item_path: ItemPath::from("A/AA"),
-			found: AA_etag.clone(),
-			search: Etag::from("*")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::IfNoneMatch {
+				item_path: ItemPath::from("A/AA"),
+				found: AA_etag.clone(),
+				search: Etag::from("*")
+			}
+		))
 	);
 
 	assert_eq!(
@@ -421,22 +420,22 @@ fn put_with_if_match_not_found() {
 	let (storage, prefix, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from("A/AA"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&[],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::NoIfMatch {
-			item_path: ItemPath::from("A/AA"),
-			found: AA_etag.clone(),
-			search: Etag::from("ANOTHER_ETAG")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::NoIfMatch {
+				item_path: ItemPath::from("A/AA"),
+				found: AA_etag.clone(),
+				search: Etag::from("ANOTHER_ETAG")
+			}
+		))
 	);
 
 	assert_eq!(
@@ -500,7 +499,7 @@ fn put_with_if_match_found() {
 		&storage,
 		&prefix,
 		&ItemPath::from("A/AA"),
-		&AA_etag,
+		&[&AA_etag],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -567,7 +566,7 @@ fn put_with_if_match_all() {
 		&storage,
 		&prefix,
 		&ItemPath::from("A/AA"),
-		&Etag::from("*"),
+		&[&Etag::from("*")],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -633,20 +632,20 @@ fn put_with_existing_document_conflict() {
 	let (storage, prefix, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from("A/AA/AAA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AAA", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::Conflict {
-			item_path: ItemPath::from("A/AA")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::Conflict {
+				item_path: ItemPath::from("A/AA")
+			}
+		))
 	);
 
 	assert_eq!(
@@ -707,20 +706,20 @@ fn put_with_existing_folder_conflict() {
 	let (storage, prefix, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from("A"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"A", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::Conflict {
-			item_path: ItemPath::from("A/")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::Conflict {
+				item_path: ItemPath::from("A/")
+			}
+		))
 	);
 
 	assert_eq!(
@@ -785,7 +784,7 @@ fn put_in_public() {
 		&storage,
 		&prefix,
 		&ItemPath::from("public/A/AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -854,21 +853,21 @@ fn put_in_incorrect_path() {
 	let prefix = String::from("pontus_onyx_put_test");
 
 	assert_eq!(
-		*put(
+		put(
 			&storage,
 			&prefix,
 			&ItemPath::from("A/A\0A"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::IncorrectItemName {
-			item_path: ItemPath::from("A/A\0A"),
-			error: String::from("`A\0A` should not contains `\\0` character")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::IncorrectItemName {
+				item_path: ItemPath::from("A/A\0A"),
+				error: String::from("`A\0A` should not contains `\\0` character")
+			}
+		))
 	);
 
 	assert_eq!(storage.length().unwrap(), 0);
@@ -0,0 +1,263 @@
+#[cfg(test)]
+pub mod tests;
+
+pub fn rehash_etags(
+	storage: &dyn super::Storage,
+	prefix: &str,
+	path: &crate::item::ItemPath,
+) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError> {
+	let mut keys = vec![];
+	for i in 0..storage.length().map_err(storage_error)? {
+		if let Some(key) = storage.key(i).map_err(storage_error)? {
+			keys.push(key);
+		}
+	}
+
+	let exists = if path.is_document() {
+		storage
+			.get_item(&format!("{}/{}", prefix, path))
+			.map_err(storage_error)?
+			.is_some()
+	} else {
+		keys.contains(&folderdata_key(prefix, path))
+	};
+
+	let result = if exists {
+		Some(rehash_item(storage, prefix, &keys, path, true)?)
+	} else {
+		None
+	};
+
+	for ancestor in path
+		.ancestors()
+		.into_iter()
+		.rev()
+		.filter(|ancestor| ancestor != path)
+	{
+		if keys.contains(&folderdata_key(prefix, &ancestor)) {
+			rehash_item(storage, prefix, &keys, &ancestor, false)?;
+		}
+	}
+
+	return Ok(result);
+}
+
+/// Lists the paths of all documents inside the folder at `path`, even in `public/`.
+pub fn documents_in(
+	storage: &dyn super::Storage,
+	prefix: &str,
+	path: &crate::item::ItemPath,
+) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+	if !path.is_folder() {
+		return Err(crate::database::DataSourceError::Conflict {
+			item_path: path.clone(),
+		});
+	}
+
+	let mut keys = vec![];
+	for i in 0..storage.length().map_err(storage_error)? {
+		if let Some(key) = storage.key(i).map_err(storage_error)? {
+			keys.push(key);
+		}
+	}
+
+	if !keys.contains(&folderdata_key(prefix, path)) {
+		return Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		});
+	}
+
+	let mut documents: Vec<crate::item::ItemPath> = keys
+		.iter()
+		.filter(|key| !key.ends_with(".itemdata.json"))
+		.filter_map(|key| key.strip_prefix(&format!("{}/", prefix)))
+		.filter(|remain| remain.starts_with(&format!("{}", path)))
+		.map(crate::item::ItemPath::from)
+		.collect();
+	documents.sort_by_key(|document| format!("{}", document));
+
+	return Ok(documents);
+}
+
+/// Replaces the ETag and the last modification date of the document at `path`, in its metadata.
+pub fn set_metadata(
+	storage: &dyn super::Storage,
+	prefix: &str,
+	path: &crate::item::ItemPath,
+	etag: &crate::item::Etag,
+	last_modified: Option<time::OffsetDateTime>,
+) -> Result<(), crate::database::DataSourceError> {
+	if !path.is_document() {
+		return Err(crate::database::DataSourceError::DoesNotWorksForFolders);
+	}
+	if storage
+		.get_item(&format!("{}/{}", prefix, path))
+		.map_err(storage_error)?
+		.is_none()
+	{
+		return Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		});
+	}
+
+	let filedata_key = filedata_key(prefix, path);
+	let mut filedata: crate::item::DataDocument = read_itemdata(storage, &filedata_key)?;
+	filedata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+	filedata.etag = etag.clone();
+	filedata.last_modified = last_modified;
+
+	return write_itemdata(storage, &filedata_key, &filedata);
+}
+
+/// Computes and saves the new ETag of the item at `path`.
+///
+/// If `recursive`, ETags of children of a folder are also computed,
+/// otherwise their current ETags are used.
+fn rehash_item(
+	storage: &dyn super::Storage,
+	prefix: &str,
+	keys: &[String],
+	path: &crate::item::ItemPath,
+	recursive: bool,
+) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+	if path.is_document() {
+		let content = storage
+			.get_item(&format!("{}/{}", prefix, path))
+			.map_err(storage_error)?
+			.unwrap_or_default();
+		let content = base64::decode(content).map_err(|error| {
+			crate::database::DataSourceError::InternalError {
+				error: format!("can not decode content of `{}` : {}", path, error),
+			}
+		})?;
+
+		let filedata_key = filedata_key(prefix, path);
+		let mut filedata: crate::item::DataDocument = read_itemdata(storage, &filedata_key)?;
+		let etag = crate::item::Etag::from_content(&content, &filedata.content_type);
+		filedata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+		filedata.etag = etag.clone();
+		write_itemdata(storage, &filedata_key, &filedata)?;
+
+		return Ok(etag);
+	} else {
+		let mut children = std::collections::BTreeSet::new();
+		for key in keys {
+			if let Some(remain) = key.strip_prefix(&format!("{}/{}", prefix, path)) {
+				if remain.is_empty() || remain.ends_with(".itemdata.json") {
+					continue;
+				}
+
+				match remain.split_once('/') {
+					Some((name, _)) => children.insert((String::from(name), true)),
+					None => children.insert((String::from(remain), false)),
+				};
+			}
+		}
+
+		let mut children_etags = vec![];
+		for (name, is_folder) in children {
+			let child = if is_folder {
+				crate::item::ItemPathPart::Folder(name)
+			} else {
+				crate::item::ItemPathPart::Document(name)
+			};
+			let child_path = path.joined(&child).map_err(|error| {
+				crate::database::DataSourceError::IncorrectItemName {
+					item_path: path.clone(),
+					error,
+				}
+			})?;
+
+			let child_etag = if recursive {
+				rehash_item(storage, prefix, keys, &child_path, true)?
+			} else if child_path.is_document() {
+				read_itemdata::<crate::item::DataDocument>(
+					storage,
+					&filedata_key(prefix, &child_path),
+				)?
+				.etag
+			} else {
+				read_itemdata::<crate::item::DataFolder>(
+					storage,
+					&folderdata_key(prefix, &child_path),
+				)?
+				.etag
+			};
+
+			children_etags.push((format!("{}", child), child_etag));
+		}
+
+		let etag = crate::item::Etag::from_children(
+			children_etags
+				.iter()
+				.map(|(name, etag)| (name.as_str(), etag)),
+		);
+
+		let folderdata_key = folderdata_key(prefix, path);
+		let mut folderdata = if keys.contains(&folderdata_key) {
+			read_itemdata(storage, &folderdata_key)?
+		} else {
+			crate::item::DataFolder::default()
+		};
+		folderdata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+		folderdata.etag = etag.clone();
+		write_itemdata(storage, &folderdata_key, &folderdata)?;
+
+		return Ok(etag);
+	}
+}
+
+fn filedata_key(prefix: &str, path: &crate::item::ItemPath) -> String {
+	let parent = path
+		.parent()
+		.unwrap_or_else(|| crate::item::ItemPath::from(""));
+
+	return format!(
+		"{}/{}",
+		prefix,
+		parent
+			.joined_doc(&format!(".{}.itemdata.json", path.file_name()))
+			.unwrap()
+	);
+}
+
+fn folderdata_key(prefix: &str, path: &crate::item::ItemPath) -> String {
+	return format!(
+		"{}",
+		crate::item::ItemPath::from(format!("{}/{}.folder.itemdata.json", prefix, path).as_str())
+	);
+}
+
+fn read_itemdata<T: serde::de::DeserializeOwned>(
+	storage: &dyn super::Storage,
+	key: &str,
+) -> Result<T, crate::database::DataSourceError> {
+	let content = storage
+		.get_item(key)
+		.map_err(storage_error)?
+		.unwrap_or_default();
+
+	return serde_json::from_str(&content).map_err(|error| {
+		crate::database::DataSourceError::InternalError {
+			error: format!("can not deserialize `{}` : {}", key, error),
+		}
+	});
+}
+
+fn write_itemdata<T: serde::Serialize>(
+	storage: &dyn super::Storage,
+	key: &str,
+	itemdata: &T,
+) -> Result<(), crate::database::DataSourceError> {
+	let content = serde_json::to_string(itemdata).map_err(|error| {
+		crate::database::DataSourceError::InternalError {
+			error: format!("can not serialize `{}` : {}", key, error),
+		}
+	})?;
+
+	return storage.set_item(key, &content).map_err(storage_error);
+}
+
+fn storage_error(_: wasm_bindgen::JsValue) -> crate::database::DataSourceError {
+	return crate::database::DataSourceError::from(super::GetError::CanNotGetStorage);
+}
@@ -0,0 +1,125 @@
+use super::{super::LocalStorageMock, super::Storage, rehash_etags};
+use crate::item::{DataDocument, Etag, Item, ItemPath};
+
+#[test]
+fn w2hj6dx0qb8ltf5ynk() {
+	let prefix = "pontus_onyx_rehash_test";
+	let storage = LocalStorageMock::new();
+
+	for (path, content) in [("A/AA", b"AA"), ("A/AB", b"AB"), ("B", b"BB")] {
+		super::super::put(
+			&storage,
+			prefix,
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(content, "text/plain"),
+		)
+		.unwrap();
+	}
+
+	// same data, as in `MemoryStorage`
+	let mut expected = Item::new_folder(vec![
+		(
+			"A",
+			Item::new_folder(vec![
+				("AA", Item::new_doc(b"AA", "text/plain")),
+				("AB", Item::new_doc(b"AB", "text/plain")),
+			]),
+		),
+		("B", Item::new_doc(b"BB", "text/plain")),
+	]);
+	expected.rehash_etags();
+
+	assert_eq!(
+		rehash_etags(&storage, prefix, &ItemPath::from("")).unwrap(),
+		Some(expected.get_etag().clone())
+	);
+
+	let AA_data: DataDocument = serde_json::from_str(
+		&storage
+			.get_item(&format!("{}/A/.AA.itemdata.json", prefix))
+			.unwrap()
+			.unwrap(),
+	)
+	.unwrap();
+	assert_eq!(
+		AA_data.etag,
+		Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+
+	assert_eq!(
+		rehash_etags(&storage, prefix, &ItemPath::from("A/AD")).unwrap(),
+		None
+	);
+	assert_eq!(
+		super::super::get(&storage, prefix, &ItemPath::from("A/"), &[], &[], false)
+			.unwrap()
+			.get_etag(),
+		expected
+			.get_child(&ItemPath::from("A/"))
+			.unwrap()
+			.get_etag()
+	);
+}
+
+#[test]
+fn c5vq1nz8kd3mrx7hpt() {
+	let prefix = "pontus_onyx_metadata_test";
+	let storage = LocalStorageMock::new();
+
+	for path in ["A/AA", "public/B/BA", "C"] {
+		super::super::put(
+			&storage,
+			prefix,
+			&ItemPath::from(path),
+			&[],
+			&[],
+			Item::new_doc(b"content", "text/plain"),
+		)
+		.unwrap();
+	}
+
+	assert_eq!(
+		super::documents_in(&storage, prefix, &ItemPath::from("")).unwrap(),
+		vec![
+			ItemPath::from("A/AA"),
+			ItemPath::from("C"),
+			ItemPath::from("public/B/BA"),
+		]
+	);
+	assert_eq!(
+		super::documents_in(&storage, prefix, &ItemPath::from("public/")).unwrap(),
+		vec![ItemPath::from("public/B/BA")]
+	);
+	assert!(super::documents_in(&storage, prefix, &ItemPath::from("D/")).is_err());
+
+	let last_modified = time::OffsetDateTime::UNIX_EPOCH;
+	super::set_metadata(
+		&storage,
+		prefix,
+		&ItemPath::from("A/AA"),
+		&Etag::from("restored"),
+		Some(last_modified),
+	)
+	.unwrap();
+
+	let AA_data: DataDocument = serde_json::from_str(
+		&storage
+			.get_item(&format!("{}/A/.AA.itemdata.json", prefix))
+			.unwrap()
+			.unwrap(),
+	)
+	.unwrap();
+	assert_eq!(AA_data.etag, Etag::from("restored"));
+	assert_eq!(AA_data.last_modified, Some(last_modified));
+
+	assert!(super::set_metadata(
+		&storage,
+		prefix,
+		&ItemPath::from("A/AB"),
+		&Etag::from("restored"),
+		None,
+	)
+	.is_err());
+}
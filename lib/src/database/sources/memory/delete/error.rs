@@ -33,71 +33,25 @@ impl std::fmt::Display for DeleteError {
 	}
 }
 impl std::error::Error for DeleteError {}
-#[cfg(feature = "server")]
-impl crate::database::Error for DeleteError {
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse {
-		match self {
-			Self::Conflict { item_path: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::DELETE,
-				actix_web::http::StatusCode::CONFLICT,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::DoesNotWorksForFolders => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::DELETE,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::NotFound { item_path: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::DELETE,
-				actix_web::http::StatusCode::NOT_FOUND,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::NoContentInside { item_path: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::DELETE,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::IncorrectItemName {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::DELETE,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::NoIfMatch {
-				item_path: _,
-				search: _,
-				found: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::DELETE,
-				actix_web::http::StatusCode::PRECONDITION_FAILED,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
+impl From<DeleteError> for crate::database::DataSourceError {
+	fn from(error: DeleteError) -> Self {
+		match error {
+			DeleteError::Conflict { item_path } => Self::Conflict { item_path },
+			DeleteError::DoesNotWorksForFolders => Self::DoesNotWorksForFolders,
+			DeleteError::NotFound { item_path } => Self::NotFound { item_path },
+			DeleteError::NoContentInside { item_path } => Self::NoContentInside { item_path },
+			DeleteError::IncorrectItemName { item_path, error } => {
+				Self::IncorrectItemName { item_path, error }
+			}
+			DeleteError::NoIfMatch {
+				item_path,
+				search,
+				found,
+			} => Self::NoIfMatch {
+				item_path,
+				search,
+				found,
+			},
 		}
 	}
 }
@@ -7,7 +7,7 @@ pub mod tests;
 pub fn delete(
 	root_item: &mut crate::item::Item,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 ) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
 	if path.is_folder() {
 		return Err(Box::new(DeleteError::DoesNotWorksForFolders));
@@ -44,50 +44,20 @@ pub fn delete(
 				crate::item::Item::Document {
 					etag: found_etag, ..
 				} => {
-					if !if_match.is_empty() && if_match.trim() != "*" && if_match != found_etag {
+					if let Err(crate::database::PreconditionFailure::NoIfMatch { search, found }) =
+						crate::database::check_preconditions(Some(found_etag), if_match, &[])
+					{
 						return Err(Box::new(DeleteError::NoIfMatch {
 							item_path: path.clone(),
-							search: if_match.clone(),
-							found: found_etag.clone(),
+							search,
+							found,
 						}));
 					}
 					let old_etag = found_etag.clone();
 
 					parent_content.remove(path.file_name());
 
-					{
-						for path_part in path
-							.ancestors()
-							.into_iter()
-							.take(path.ancestors().len().saturating_sub(1))
-							.rev()
-						{
-							if let Some(crate::item::Item::Folder {
-								content: Some(parent_content),
-								etag,
-							}) = root_item.get_child_mut(&path_part)
-							{
-								let mut to_delete = vec![];
-								for (child_name, child_item) in &*parent_content {
-									if let crate::item::Item::Folder {
-										content: Some(child_content),
-										..
-									} = &**child_item
-									{
-										if child_content.is_empty() {
-											to_delete.push(child_name.clone());
-										}
-									}
-								}
-
-								for child_name in to_delete {
-									parent_content.remove(&child_name);
-								}
-
-								*etag = crate::item::Etag::new();
-							}
-						}
-					}
+					renew_ancestors(root_item, path);
 
 					return Ok(old_etag);
 				}
@@ -124,3 +94,80 @@ pub fn delete(
 		}
 	}
 }
+
+/// Deletes the folder at `path` with all its content, even in `public/`.
+pub fn delete_folder(
+	root_item: &mut crate::item::Item,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
+	if !path.is_folder() {
+		return Err(Box::new(super::GetError::Conflict {
+			item_path: path.clone(),
+		}));
+	}
+
+	let folder = super::get::get_internal_mut(root_item, path, if_match, &[])?;
+	let old_etag = folder.get_etag().clone();
+
+	match path.parent() {
+		Some(parent_path) => {
+			if let Some(crate::item::Item::Folder {
+				content: Some(parent_content),
+				..
+			}) = root_item.get_child_mut(&parent_path)
+			{
+				parent_content.remove(path.file_name());
+			}
+
+			renew_ancestors(root_item, path);
+		}
+		None => {
+			if let crate::item::Item::Folder {
+				content: Some(content),
+				etag,
+			} = folder
+			{
+				content.clear();
+				*etag = crate::item::Etag::new();
+			}
+		}
+	}
+
+	return Ok(old_etag);
+}
+
+/// Removes empty folders from the ancestors of `path`, and gives a new ETag to the remaining ones.
+fn renew_ancestors(root_item: &mut crate::item::Item, path: &crate::item::ItemPath) {
+	for path_part in path
+		.ancestors()
+		.into_iter()
+		.take(path.ancestors().len().saturating_sub(1))
+		.rev()
+	{
+		if let Some(crate::item::Item::Folder {
+			content: Some(parent_content),
+			etag,
+		}) = root_item.get_child_mut(&path_part)
+		{
+			let mut to_delete = vec![];
+			for (child_name, child_item) in &*parent_content {
+				if let crate::item::Item::Folder {
+					content: Some(child_content),
+					..
+				} = &**child_item
+				{
+					if child_content.is_empty() {
+						to_delete.push(child_name.clone());
+					}
+				}
+			}
+
+			for child_name in to_delete {
+				parent_content.remove(&child_name);
+			}
+
+			*etag = crate::item::Etag::new();
+		}
+	}
+}
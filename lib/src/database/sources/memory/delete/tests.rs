@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
 
-use super::{delete, DeleteError};
+use super::{delete, delete_folder, DeleteError};
 use crate::item::{Etag, Item, ItemPath};
 
 fn build_test_db() -> (Item, Etag, Etag, Etag, Etag, Etag, Etag) {
@@ -92,7 +92,7 @@ fn simple_delete_on_not_existing() {
 	let root_etag = root.get_etag().clone();
 
 	assert_eq!(
-		*delete(&mut root, &ItemPath::from("A/AA/AAA/AAAA"), &Etag::from(""),)
+		*delete(&mut root, &ItemPath::from("A/AA/AAA/AAAA"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -117,8 +117,7 @@ fn simple_delete_on_not_existing() {
 fn simple_delete_on_existing() {
 	let (mut root, root_etag, A_etag, _, _, AAAA_etag, AB_etag) = build_test_db();
 
-	let old_AAAA_etag =
-		delete(&mut root, &ItemPath::from("A/AA/AAA/AAAA"), &Etag::from("")).unwrap();
+	let old_AAAA_etag = delete(&mut root, &ItemPath::from("A/AA/AAA/AAAA"), &[]).unwrap();
 
 	assert_eq!(AAAA_etag, old_AAAA_etag);
 
@@ -158,7 +157,7 @@ fn does_not_works_for_folders() {
 	let (mut root, root_etag, A_etag, AA_etag, AAA_etag, AAAA_etag, _) = build_test_db();
 
 	assert_eq!(
-		*delete(&mut root, &ItemPath::from("A/AA/"), &Etag::from(""),)
+		*delete(&mut root, &ItemPath::from("A/AA/"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -216,7 +215,7 @@ fn delete_with_if_match_not_found() {
 		*delete(
 			&mut root,
 			&ItemPath::from("A/AA/AAA/AAAA"),
-			&Etag::from("OTHER_ETAG"),
+			&[&Etag::from("OTHER_ETAG")],
 		)
 		.unwrap_err()
 		.downcast::<DeleteError>()
@@ -275,7 +274,7 @@ fn delete_with_if_match_not_found() {
 fn delete_with_if_match_found() {
 	let (mut root, root_etag, A_etag, _, _, AAAA_etag, _) = build_test_db();
 
-	let old_AAAA_etag = delete(&mut root, &ItemPath::from("A/AA/AAA/AAAA"), &AAAA_etag).unwrap();
+	let old_AAAA_etag = delete(&mut root, &ItemPath::from("A/AA/AAA/AAAA"), &[&AAAA_etag]).unwrap();
 
 	assert_eq!(old_AAAA_etag, AAAA_etag);
 
@@ -312,7 +311,7 @@ fn delete_with_if_match_all() {
 	let old_AAAA_etag = delete(
 		&mut root,
 		&ItemPath::from("A/AA/AAA/AAAA"),
-		&Etag::from("*"),
+		&[&Etag::from("*")],
 	)
 	.unwrap();
 
@@ -349,7 +348,7 @@ fn delete_with_existing_folder_conflict() {
 	let (mut root, root_etag, A_etag, AA_etag, AAA_etag, _, _) = build_test_db();
 
 	assert_eq!(
-		*delete(&mut root, &ItemPath::from("A/AA"), &Etag::from(""),)
+		*delete(&mut root, &ItemPath::from("A/AA"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -405,12 +404,7 @@ fn delete_with_existing_folder_conflict() {
 fn delete_in_public() {
 	let (mut root, root_etag, _, _, _, _, _) = build_test_db();
 
-	delete(
-		&mut root,
-		&ItemPath::from("public/C/CC/CCC"),
-		&Etag::from(""),
-	)
-	.unwrap();
+	delete(&mut root, &ItemPath::from("public/C/CC/CCC"), &[]).unwrap();
 
 	if let Item::Folder {
 		etag,
@@ -433,7 +427,7 @@ fn delete_in_incorrect_path() {
 	let root_etag = root.get_etag().clone();
 
 	assert_eq!(
-		*delete(&mut root, &ItemPath::from("A/A\0A"), &Etag::from(""),)
+		*delete(&mut root, &ItemPath::from("A/A\0A"), &[],)
 			.unwrap_err()
 			.downcast::<DeleteError>()
 			.unwrap(),
@@ -454,3 +448,36 @@ fn delete_in_incorrect_path() {
 		panic!();
 	}
 }
+
+#[test]
+fn delete_folder_with_content() {
+	let (mut root, root_etag, A_etag, AA_etag, _, _, _) = build_test_db();
+
+	assert_eq!(
+		*delete_folder(&mut root, &ItemPath::from("A/AA/"), &[&Etag::from("wrong")])
+			.unwrap_err()
+			.downcast::<super::super::GetError>()
+			.unwrap(),
+		super::super::GetError::NoIfMatch {
+			item_path: ItemPath::from("A/AA/"),
+			search: Etag::from("wrong"),
+			found: AA_etag.clone(),
+		}
+	);
+
+	assert_eq!(
+		delete_folder(&mut root, &ItemPath::from("A/AA/"), &[&AA_etag]).unwrap(),
+		AA_etag
+	);
+	assert!(root.get_child(&ItemPath::from("A/AA/")).is_none());
+	assert!(root.get_child(&ItemPath::from("A/AB")).is_some());
+	assert_ne!(
+		root.get_child(&ItemPath::from("A/")).unwrap().get_etag(),
+		&A_etag
+	);
+	assert_ne!(root.get_etag(), &root_etag);
+
+	delete_folder(&mut root, &ItemPath::from("public/C/"), &[]).unwrap();
+	assert!(root.get_child(&ItemPath::from("public/")).is_none());
+	assert!(root.get_child(&ItemPath::from("A/AB")).is_some());
+}
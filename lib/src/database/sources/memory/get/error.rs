@@ -41,112 +41,55 @@ impl std::fmt::Display for GetError {
 	}
 }
 impl std::error::Error for GetError {}
-#[cfg(feature = "server")]
-impl crate::database::Error for GetError {
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse {
-		match self {
-			Self::Conflict { item_path } => {
-				if item_path.starts_with("public/") {
-					crate::database::build_http_json_response(
-						origin,
-						&actix_web::http::Method::GET,
-						actix_web::http::StatusCode::NOT_FOUND,
-						None,
-						None,
-						Some(format!("path not found : `{}`", item_path)),
-						should_have_body,
-					)
-				} else {
-					crate::database::build_http_json_response(
-						origin,
-						&actix_web::http::Method::GET,
-						actix_web::http::StatusCode::CONFLICT,
-						None,
-						None,
-						Some(format!("{}", self)),
-						should_have_body,
-					)
+impl GetError {
+	pub fn from_precondition(
+		item_path: crate::item::ItemPath,
+		failure: crate::database::PreconditionFailure,
+	) -> Self {
+		match failure {
+			crate::database::PreconditionFailure::NoIfMatch { search, found } => Self::NoIfMatch {
+				item_path,
+				search,
+				found,
+			},
+			crate::database::PreconditionFailure::IfNoneMatch { search, found } => {
+				Self::IfNoneMatch {
+					item_path,
+					search,
+					found,
 				}
 			}
-			Self::NotFound { item_path: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::NOT_FOUND,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::NoContentInside { item_path } => {
-				if item_path.starts_with("public/") {
-					crate::database::build_http_json_response(
-						origin,
-						&actix_web::http::Method::GET,
-						actix_web::http::StatusCode::NOT_FOUND,
-						None,
-						None,
-						Some(format!("path not found : `{}`", item_path)),
-						should_have_body,
-					)
-				} else {
-					crate::database::build_http_json_response(
-						origin,
-						&actix_web::http::Method::GET,
-						actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-						None,
-						None,
-						Some(format!("{}", self)),
-						should_have_body,
-					)
-				}
+		}
+	}
+}
+impl From<GetError> for crate::database::DataSourceError {
+	fn from(error: GetError) -> Self {
+		match error {
+			GetError::Conflict { item_path } => Self::Conflict { item_path },
+			GetError::NotFound { item_path } => Self::NotFound { item_path },
+			GetError::NoContentInside { item_path } => Self::NoContentInside { item_path },
+			GetError::IncorrectItemName { item_path, error } => {
+				Self::IncorrectItemName { item_path, error }
 			}
-			Self::IncorrectItemName {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::CanNotBeListed { item_path } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::NOT_FOUND,
-				None,
-				None,
-				Some(format!("path not found : `{}`", item_path)),
-				should_have_body,
-			),
-			Self::NoIfMatch {
-				item_path: _,
-				search: _,
-				found: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::PRECONDITION_FAILED,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::IfNoneMatch {
-				item_path: _,
-				search: _,
-				found: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::GET,
-				actix_web::http::StatusCode::PRECONDITION_FAILED,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
+			GetError::CanNotBeListed { item_path } => Self::CanNotBeListed { item_path },
+			GetError::NoIfMatch {
+				item_path,
+				search,
+				found,
+			} => Self::NoIfMatch {
+				item_path,
+				search,
+				found,
+			},
+			GetError::IfNoneMatch {
+				item_path,
+				search,
+				found,
+			} => Self::IfNoneMatch {
+				item_path,
+				search,
+				found,
+			},
 		}
 	}
 }
@@ -7,9 +7,19 @@ pub mod tests;
 pub fn get(
 	root_item: &crate::item::Item,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 ) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
+	// TODO : expensive clone here
+	return get_internal(root_item, path, if_match, if_none_match).cloned();
+}
+
+pub fn get_internal<'a>(
+	root_item: &'a crate::item::Item,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+) -> Result<&'a crate::item::Item, Box<dyn std::error::Error>> {
 	let paths = path.parts_iter();
 
 	let mut pending = Some(root_item);
@@ -57,29 +67,13 @@ pub fn get(
 			crate::item::Item::Folder {
 				etag: found_etag, ..
 			} => {
-				if !if_match.is_empty() {
-					let upper_if_match = if_match.trim().to_uppercase();
-					if found_etag.trim().to_uppercase() != upper_if_match && upper_if_match != "*" {
-						return Err(Box::new(GetError::NoIfMatch {
-							item_path: cumulated_path,
-							search: if_match.clone(),
-							found: found_etag.clone(),
-						}));
-					}
-				}
-
-				if !if_none_match.is_empty() {
-					for search_etag in if_none_match {
-						if found_etag.trim().to_uppercase() == search_etag.trim().to_uppercase()
-							|| search_etag.trim() == "*"
-						{
-							return Err(Box::new(GetError::IfNoneMatch {
-								item_path: cumulated_path,
-								search: (*search_etag).clone(),
-								found: found_etag.clone(),
-							}));
-						}
-					}
+				if let Err(failure) =
+					crate::database::check_preconditions(Some(found_etag), if_match, if_none_match)
+				{
+					return Err(Box::new(GetError::from_precondition(
+						cumulated_path,
+						failure,
+					)));
 				}
 
 				if path.is_folder() {
@@ -88,7 +82,7 @@ pub fn get(
 							item_path: path.clone(),
 						}));
 					} else {
-						Ok(item.clone()) // TODO : expensive clone here
+						Ok(item)
 					}
 				} else {
 					Err(Box::new(GetError::Conflict {
@@ -99,33 +93,17 @@ pub fn get(
 			crate::item::Item::Document {
 				etag: found_etag, ..
 			} => {
-				if !if_match.is_empty() {
-					let upper_if_match = if_match.trim().to_uppercase();
-					if found_etag.trim().to_uppercase() != upper_if_match && upper_if_match != "*" {
-						return Err(Box::new(GetError::NoIfMatch {
-							item_path: cumulated_path,
-							search: if_match.clone(),
-							found: found_etag.clone(),
-						}));
-					}
-				}
-
-				if !if_none_match.is_empty() {
-					for search_etag in if_none_match {
-						if found_etag.trim().to_uppercase() == search_etag.trim().to_uppercase()
-							|| search_etag.trim() == "*"
-						{
-							return Err(Box::new(GetError::IfNoneMatch {
-								item_path: cumulated_path,
-								search: (*search_etag).clone(),
-								found: found_etag.clone(),
-							}));
-						}
-					}
+				if let Err(failure) =
+					crate::database::check_preconditions(Some(found_etag), if_match, if_none_match)
+				{
+					return Err(Box::new(GetError::from_precondition(
+						cumulated_path,
+						failure,
+					)));
 				}
 
 				if !path.is_folder() {
-					Ok(item.clone()) // TODO : expensive clone here
+					Ok(item)
 				} else {
 					Err(Box::new(GetError::Conflict {
 						item_path: cumulated_path.document_clone(),
@@ -142,7 +120,7 @@ pub fn get(
 pub fn get_internal_mut<'a>(
 	root_item: &'a mut crate::item::Item,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 ) -> Result<&'a mut crate::item::Item, Box<dyn std::error::Error>> {
 	let paths = path.parts_iter();
@@ -194,29 +172,13 @@ pub fn get_internal_mut<'a>(
 			crate::item::Item::Folder {
 				etag: found_etag, ..
 			} => {
-				if !if_match.is_empty() {
-					let upper_if_match = if_match.trim().to_uppercase();
-					if found_etag.trim().to_uppercase() != upper_if_match && upper_if_match != "*" {
-						return Err(Box::new(GetError::NoIfMatch {
-							item_path: cumulated_path,
-							search: if_match.clone(),
-							found: found_etag.clone(),
-						}));
-					}
-				}
-
-				if !if_none_match.is_empty() {
-					for search_etag in if_none_match {
-						if found_etag.trim().to_uppercase() == search_etag.trim().to_uppercase()
-							|| search_etag.trim() == "*"
-						{
-							return Err(Box::new(GetError::IfNoneMatch {
-								item_path: cumulated_path,
-								search: (*search_etag).clone(),
-								found: found_etag.clone(),
-							}));
-						}
-					}
+				if let Err(failure) =
+					crate::database::check_preconditions(Some(found_etag), if_match, if_none_match)
+				{
+					return Err(Box::new(GetError::from_precondition(
+						cumulated_path,
+						failure,
+					)));
 				}
 
 				if path.is_folder() {
@@ -230,29 +192,13 @@ pub fn get_internal_mut<'a>(
 			crate::item::Item::Document {
 				etag: found_etag, ..
 			} => {
-				if !if_match.is_empty() {
-					let upper_if_match = if_match.trim().to_uppercase();
-					if found_etag.trim().to_uppercase() != upper_if_match && upper_if_match != "*" {
-						return Err(Box::new(GetError::NoIfMatch {
-							item_path: cumulated_path,
-							search: if_match.clone(),
-							found: found_etag.clone(),
-						}));
-					}
-				}
-
-				if !if_none_match.is_empty() {
-					for search_etag in if_none_match {
-						if found_etag.trim().to_uppercase() == search_etag.trim().to_uppercase()
-							|| search_etag.trim() == "*"
-						{
-							return Err(Box::new(GetError::IfNoneMatch {
-								item_path: cumulated_path,
-								search: (*search_etag).clone(),
-								found: found_etag.clone(),
-							}));
-						}
-					}
+				if let Err(failure) =
+					crate::database::check_preconditions(Some(found_etag), if_match, if_none_match)
+				{
+					return Err(Box::new(GetError::from_precondition(
+						cumulated_path,
+						failure,
+					)));
 				}
 
 				if !path.is_folder() {
@@ -41,60 +41,51 @@ fn all_tests_bulk() {
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		get(&root, &ItemPath::from(""), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from(""), &[], &vec![]).unwrap(),
 		root.clone() // TODO : should return root_without_public, but recursion in get make it buggy.
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("A/"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/"), &[], &vec![]).unwrap(),
 		A.clone()
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("A/AA"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/AA"), &[], &vec![]).unwrap(),
 		AA.clone()
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("A/AB"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/AB"), &[], &vec![]).unwrap(),
 		AB
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("A/AC"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/AC"), &[], &vec![]).unwrap(),
 		AC
 	);
+	assert_eq!(get(&root, &ItemPath::from("B/"), &[], &vec![]).unwrap(), B);
 	assert_eq!(
-		get(&root, &ItemPath::from("B/"), &Etag::from(""), &vec![]).unwrap(),
-		B
-	);
-	assert_eq!(
-		get(&root, &ItemPath::from("B/BA"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("B/BA"), &[], &vec![]).unwrap(),
 		BA
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("B/BB"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("B/BB"), &[], &vec![]).unwrap(),
 		BB
 	);
 	assert_eq!(
-		get(
-			&root,
-			&ItemPath::from("public/C/CA"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap(),
+		get(&root, &ItemPath::from("public/C/CA"), &[], &vec![]).unwrap(),
 		CA
 	);
 
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		get(&root, &ItemPath::from(""), root.get_etag(), &vec![]).unwrap(),
+		get(&root, &ItemPath::from(""), &[root.get_etag()], &vec![]).unwrap(),
 		root.clone() // TODO : should return root_without_public, but recursion in get make it buggy.
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("A/"), A.get_etag(), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/"), &[A.get_etag()], &vec![]).unwrap(),
 		A.clone()
 	);
 	assert_eq!(
-		get(&root, &ItemPath::from("A/AA"), AA.get_etag(), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/AA"), &[AA.get_etag()], &vec![]).unwrap(),
 		AA.clone()
 	);
 
@@ -104,7 +95,7 @@ fn all_tests_bulk() {
 		get(
 			&root,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")]
 		)
 		.unwrap(),
@@ -114,7 +105,7 @@ fn all_tests_bulk() {
 		get(
 			&root,
 			&ItemPath::from("A/"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")]
 		)
 		.unwrap(),
@@ -124,7 +115,7 @@ fn all_tests_bulk() {
 		get(
 			&root,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("ANOTHER_ETAG")]
 		)
 		.unwrap(),
@@ -134,7 +125,7 @@ fn all_tests_bulk() {
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		*get(&root, &ItemPath::from("A"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -143,7 +134,7 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(&root, &ItemPath::from("A/AA/"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A/AA/"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -152,85 +143,55 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/AC/not_exists"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/AC/not_exists"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::Conflict {
 			item_path: ItemPath::from("A/AC")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/not_exists"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/not_exists"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("A/not_exists")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/not_exists/nested"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/not_exists/nested"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("A/not_exists/")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("B/not_exists"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("B/not_exists"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("B/not_exists")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("not_exists/"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("not_exists/"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("not_exists/")
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("not_exists"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("not_exists"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::NotFound {
 			item_path: ItemPath::from("not_exists")
 		}
@@ -238,7 +199,7 @@ fn all_tests_bulk() {
 	/*
 	useless with `ItemPath`
 	assert_eq!(
-		*get(&root, &ItemPath::from("."), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("."), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -249,13 +210,13 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		get(&root, &ItemPath::from("."), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("."), &[], &vec![]).unwrap(),
 		root.clone()
 	);
 	/*
 	useless with `ItemPath`
 	assert_eq!(
-		*get(&root, &ItemPath::from("A/.."), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A/.."), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -266,13 +227,13 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		get(&root, &ItemPath::from("A/.."), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/.."), &[], &vec![]).unwrap(),
 		root.clone(),
 	);
 	/*
 	useless with `ItemPath`
 	assert_eq!(
-		*get(&root, &ItemPath::from("A/../"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A/../"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -283,13 +244,13 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		get(&root, &ItemPath::from("A/../"), &Etag::from(""), &vec![]).unwrap(),
+		get(&root, &ItemPath::from("A/../"), &[], &vec![]).unwrap(),
 		root.clone(),
 	);
 	/*
 	useless with `ItemPath`
 	assert_eq!(
-		*get(&root, &ItemPath::from("A/../AA"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A/../AA"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -302,7 +263,7 @@ fn all_tests_bulk() {
 	/*
 	// useless with `ItemPath` :
 	assert_eq!(
-		*get(&root, &ItemPath::from("A/../AA"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A/../AA"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -313,7 +274,7 @@ fn all_tests_bulk() {
 	);
 	*/
 	assert_eq!(
-		*get(&root, &ItemPath::from("A/A\0A"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("A/A\0A"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -323,7 +284,7 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(&root, &ItemPath::from("public/"), &Etag::from(""), &vec![])
+		*get(&root, &ItemPath::from("public/"), &[], &vec![])
 			.unwrap_err()
 			.downcast::<GetError>()
 			.unwrap(),
@@ -332,15 +293,10 @@ fn all_tests_bulk() {
 		},
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("public/C/"),
-			&Etag::from(""),
-			&vec![]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("public/C/"), &[], &vec![])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::CanNotBeListed {
 			item_path: ItemPath::from("public/C/")
 		}
@@ -352,7 +308,7 @@ fn all_tests_bulk() {
 		*get(
 			&root,
 			&ItemPath::from(""),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&vec![]
 		)
 		.unwrap_err()
@@ -368,7 +324,7 @@ fn all_tests_bulk() {
 		*get(
 			&root,
 			&ItemPath::from("A/"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&vec![]
 		)
 		.unwrap_err()
@@ -384,7 +340,7 @@ fn all_tests_bulk() {
 		*get(
 			&root,
 			&ItemPath::from("A/AA"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&vec![]
 		)
 		.unwrap_err()
@@ -400,15 +356,10 @@ fn all_tests_bulk() {
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from(""),
-			&Etag::from(""),
-			&[&Etag::from("*")]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from(""), &[], &[&Etag::from("*")])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IfNoneMatch {
 			item_path: ItemPath::from(""),
 			search: Etag::from("*"),
@@ -416,15 +367,10 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/"),
-			&Etag::from(""),
-			&[&Etag::from("*")]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/"), &[], &[&Etag::from("*")])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IfNoneMatch {
 			item_path: ItemPath::from("A/"),
 			search: Etag::from("*"),
@@ -432,15 +378,10 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/AA"),
-			&Etag::from(""),
-			&[&Etag::from("*")]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/AA"), &[], &[&Etag::from("*")])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IfNoneMatch {
 			item_path: ItemPath::from("A/AA"),
 			search: Etag::from("*"),
@@ -451,15 +392,10 @@ fn all_tests_bulk() {
 	////////////////////////////////////////////////////////////////////////////////////////////////
 
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from(""),
-			&Etag::from(""),
-			&[root.get_etag()]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from(""), &[], &[root.get_etag()])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IfNoneMatch {
 			item_path: ItemPath::from(""),
 			search: root.get_etag().clone(),
@@ -467,15 +403,10 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/"),
-			&Etag::from(""),
-			&[A.get_etag()]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/"), &[], &[A.get_etag()])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IfNoneMatch {
 			item_path: ItemPath::from("A/"),
 			search: A.get_etag().clone(),
@@ -483,15 +414,10 @@ fn all_tests_bulk() {
 		}
 	);
 	assert_eq!(
-		*get(
-			&root,
-			&ItemPath::from("A/AA"),
-			&Etag::from(""),
-			&[AA.get_etag()]
-		)
-		.unwrap_err()
-		.downcast::<GetError>()
-		.unwrap(),
+		*get(&root, &ItemPath::from("A/AA"), &[], &[AA.get_etag()])
+			.unwrap_err()
+			.downcast::<GetError>()
+			.unwrap(),
 		GetError::IfNoneMatch {
 			item_path: ItemPath::from("A/AA"),
 			search: AA.get_etag().clone(),
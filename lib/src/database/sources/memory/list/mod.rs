@@ -0,0 +1,50 @@
+#[cfg(test)]
+pub mod tests;
+
+pub fn list(
+	root_item: &crate::item::Item,
+	path: &crate::item::ItemPath,
+	if_match: &[&crate::item::Etag],
+	if_none_match: &[&crate::item::Etag],
+	options: &crate::database::ListingOptions,
+) -> Result<crate::database::Listing, Box<dyn std::error::Error>> {
+	match super::get::get_internal(root_item, path, if_match, if_none_match)? {
+		crate::item::Item::Folder {
+			etag,
+			content: Some(content),
+		} => Ok(crate::database::listing::list_children(
+			etag.clone(),
+			content,
+			options,
+		)),
+		crate::item::Item::Folder { content: None, .. } => {
+			Err(Box::new(super::GetError::NoContentInside {
+				item_path: path.clone(),
+			}))
+		}
+		crate::item::Item::Document { .. } => Err(Box::new(super::GetError::Conflict {
+			item_path: path.document_clone(),
+		})),
+	}
+}
+
+/// Lists the paths of all documents inside the folder at `path`, even in `public/`.
+pub fn documents_in(
+	storage: &super::MemoryStorage,
+	path: &crate::item::ItemPath,
+) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+	match storage.root_item.get_child(path) {
+		Some(folder @ crate::item::Item::Folder { .. }) if path.is_folder() => {
+			let mut documents = vec![];
+			crate::database::batch::collect_documents(storage, path, folder, &mut documents)?;
+
+			Ok(documents)
+		}
+		Some(_) => Err(crate::database::DataSourceError::Conflict {
+			item_path: path.clone(),
+		}),
+		None => Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		}),
+	}
+}
@@ -0,0 +1,152 @@
+use super::list;
+use crate::database::ListingOptions;
+use crate::item::{Item, ItemPath};
+
+fn photo(content_type: &str, days_ago: i64) -> Item {
+	let mut result = Item::new_doc(b"PHOTO", content_type);
+	if let Item::Document { last_modified, .. } = &mut result {
+		*last_modified = Some(time::OffsetDateTime::now_utc() - time::Duration::days(days_ago));
+	}
+
+	return result;
+}
+
+fn keys(listing: &crate::database::Listing) -> Vec<String> {
+	return listing
+		.items
+		.iter()
+		.map(|(name, item)| crate::database::listing_key(name, item.is_folder()))
+		.collect();
+}
+
+#[test]
+fn v8kq3mz5tx1hwn7cdr() {
+	let root = Item::new_folder(vec![(
+		"photos",
+		Item::new_folder(vec![
+			("a.jpg", photo("image/jpeg", 10)),
+			("b.png", photo("image/png", 5)),
+			("c.jpg", photo("image/jpeg", 1)),
+			(
+				"d",
+				Item::new_folder(vec![("da.jpg", photo("image/jpeg", 1))]),
+			),
+			("e", Item::new_folder(vec![])),
+		]),
+	)]);
+	let path = ItemPath::from("photos/");
+
+	let listing = list(&root, &path, &[], &[], &ListingOptions::default()).unwrap();
+	assert_eq!(&listing.etag, root.get_child(&path).unwrap().get_etag());
+	assert_eq!(keys(&listing), vec!["a.jpg", "b.png", "c.jpg", "d/"]);
+	assert_eq!(listing.next, None);
+	assert!(listing.items.iter().all(|(_, item)| match item {
+		Item::Document { content, .. } => content.is_none(),
+		Item::Folder { content, .. } => content.is_none(),
+	}));
+
+	let options = ListingOptions {
+		limit: Some(2),
+		..Default::default()
+	};
+	let listing = list(&root, &path, &[], &[], &options).unwrap();
+	assert_eq!(keys(&listing), vec!["a.jpg", "b.png"]);
+	assert_eq!(listing.next, Some(String::from("b.png")));
+
+	let listing = list(
+		&root,
+		&path,
+		&[],
+		&[],
+		&ListingOptions {
+			after: listing.next,
+			..options.clone()
+		},
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["c.jpg", "d/"]);
+	assert_eq!(listing.next, None);
+
+	let listing = list(
+		&root,
+		&path,
+		&[],
+		&[],
+		&ListingOptions {
+			prefix: Some(String::from("c")),
+			..Default::default()
+		},
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["c.jpg"]);
+
+	let listing = list(
+		&root,
+		&path,
+		&[],
+		&[],
+		&ListingOptions {
+			content_type: Some(String::from("Image/JPEG; charset=binary")),
+			limit: Some(1),
+			..Default::default()
+		},
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["a.jpg"]);
+	assert_eq!(listing.next, Some(String::from("a.jpg")));
+
+	let listing = list(
+		&root,
+		&path,
+		&[],
+		&[],
+		&ListingOptions {
+			modified_since: Some(time::OffsetDateTime::now_utc() - time::Duration::days(7)),
+			..Default::default()
+		},
+	)
+	.unwrap();
+	assert_eq!(keys(&listing), vec!["b.png", "c.jpg"]);
+}
+
+#[test]
+fn g2xw9nc4qk6tzm1vbh() {
+	let root = Item::new_folder(vec![(
+		"A",
+		Item::new_folder(vec![("AA", Item::new_doc(b"AA", "text/plain"))]),
+	)]);
+	let etag = root
+		.get_child(&ItemPath::from("A/"))
+		.unwrap()
+		.get_etag()
+		.clone();
+
+	assert_eq!(
+		*list(
+			&root,
+			&ItemPath::from("B/"),
+			&[],
+			&[],
+			&ListingOptions::default()
+		)
+		.unwrap_err()
+		.downcast::<super::super::GetError>()
+		.unwrap(),
+		super::super::GetError::NotFound {
+			item_path: ItemPath::from("B/")
+		}
+	);
+	assert!(matches!(
+		*list(
+			&root,
+			&ItemPath::from("A/"),
+			&[],
+			&[&etag],
+			&ListingOptions::default()
+		)
+		.unwrap_err()
+		.downcast::<super::super::GetError>()
+		.unwrap(),
+		super::super::GetError::IfNoneMatch { .. }
+	));
+}
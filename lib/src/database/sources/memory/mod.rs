@@ -1,6 +1,8 @@
 mod delete;
 mod get;
+mod list;
 mod put;
+mod rehash;
 
 pub use delete::DeleteError;
 pub use get::GetError;
@@ -21,17 +23,18 @@ impl crate::database::DataSource for MemoryStorage {
 	fn get(
 		&self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		_get_content: bool,
-	) -> Result<crate::item::Item, Box<dyn std::error::Error>> {
+	) -> Result<crate::item::Item, crate::database::DataSourceError> {
 		get::get(&self.root_item, path, if_match, if_none_match)
+			.map_err(crate::database::DataSourceError::from_boxed::<GetError>)
 	}
 
 	fn put(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		new_item: crate::item::Item,
 	) -> crate::database::PutResult {
@@ -41,8 +44,52 @@ impl crate::database::DataSource for MemoryStorage {
 	fn delete(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
-	) -> Result<crate::item::Etag, Box<dyn std::error::Error>> {
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
 		delete::delete(&mut self.root_item, path, if_match)
+			.map_err(crate::database::DataSourceError::from_boxed::<DeleteError>)
+	}
+
+	fn delete_folder(
+		&mut self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+		delete::delete_folder(&mut self.root_item, path, if_match)
+			.map_err(crate::database::DataSourceError::from_boxed::<GetError>)
+	}
+
+	fn list(
+		&self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+		options: &crate::database::ListingOptions,
+	) -> Result<crate::database::Listing, crate::database::DataSourceError> {
+		list::list(&self.root_item, path, if_match, if_none_match, options)
+			.map_err(crate::database::DataSourceError::from_boxed::<GetError>)
+	}
+
+	fn documents_in(
+		&self,
+		path: &crate::item::ItemPath,
+	) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+		list::documents_in(self, path)
+	}
+
+	fn set_metadata(
+		&mut self,
+		path: &crate::item::ItemPath,
+		etag: &crate::item::Etag,
+		last_modified: Option<time::OffsetDateTime>,
+	) -> Result<(), crate::database::DataSourceError> {
+		rehash::set_metadata(&mut self.root_item, path, etag, last_modified)
+	}
+
+	fn rehash_etags(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError> {
+		Ok(rehash::rehash_etags(&mut self.root_item, path))
 	}
 }
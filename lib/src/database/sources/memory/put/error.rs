@@ -30,59 +30,16 @@ impl std::fmt::Display for PutError {
 	}
 }
 impl std::error::Error for PutError {}
-#[cfg(feature = "server")]
-impl crate::database::Error for PutError {
-	fn to_response(&self, origin: &str, should_have_body: bool) -> actix_web::HttpResponse {
-		match self {
-			Self::GetError(error) => error.to_response(origin, should_have_body),
-			Self::NoContentInside { item_path: _ } => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::DoesNotWorksForFolders => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::BAD_REQUEST,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::InternalError => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::ContentNotChanged => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::NOT_MODIFIED,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
-			Self::CanNotFetchParent {
-				item_path: _,
-				error: _,
-			} => crate::database::build_http_json_response(
-				origin,
-				&actix_web::http::Method::PUT,
-				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-				None,
-				None,
-				Some(format!("{}", self)),
-				should_have_body,
-			),
+impl From<PutError> for crate::database::DataSourceError {
+	fn from(error: PutError) -> Self {
+		match error {
+			PutError::GetError(get_error) => Self::from(get_error),
+			PutError::NoContentInside { item_path } => Self::NoContentInside { item_path },
+			PutError::DoesNotWorksForFolders => Self::DoesNotWorksForFolders,
+			PutError::ContentNotChanged => Self::ContentNotChanged,
+			PutError::InternalError | PutError::CanNotFetchParent { .. } => Self::InternalError {
+				error: format!("{}", error),
+			},
 		}
 	}
 }
@@ -7,24 +7,26 @@ pub mod tests;
 pub fn put(
 	root_item: &mut crate::item::Item,
 	path: &crate::item::ItemPath,
-	if_match: &crate::item::Etag,
+	if_match: &[&crate::item::Etag],
 	if_none_match: &[&crate::item::Etag],
 	item: crate::item::Item,
 ) -> crate::database::PutResult {
 	if path.is_folder() {
-		return crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders));
+		return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+			PutError::DoesNotWorksForFolders,
+		));
 	}
 
 	let mut cumultated_path = crate::item::ItemPath::from("");
 	for path_part in path.parts_iter() {
 		cumultated_path = cumultated_path.joined(path_part).unwrap();
 		if let Err(error) = path_part.check_validity(false) {
-			return crate::database::PutResult::Err(Box::new(PutError::GetError(
-				super::GetError::IncorrectItemName {
+			return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+				PutError::GetError(super::GetError::IncorrectItemName {
 					item_path: cumultated_path,
 					error,
-				},
-			)));
+				}),
+			));
 		}
 	}
 
@@ -55,37 +57,32 @@ pub fn put(
 				etag,
 				content,
 				content_type,
+				content_length,
 				last_modified,
 			} = found
 			{
 				if let crate::item::Item::Document {
 					content: new_content,
 					content_type: new_content_type,
+					content_length: new_content_length,
 					..
 				} = item
 				{
 					let new_etag = crate::item::Etag::new();
 
-					if if_match.trim() != "" && (etag != if_match && if_match != "*") {
-						return crate::database::PutResult::Err(Box::new(PutError::GetError(
-							super::GetError::NoIfMatch {
-								item_path: path.clone(),
-								found: etag.clone(),
-								search: if_match.clone(),
-							},
-						)));
-					}
-
 					if content_type == &new_content_type && content == &new_content {
-						return crate::database::PutResult::Err(Box::new(
-							PutError::ContentNotChanged,
-						));
+						return crate::database::PutResult::Err(
+							crate::database::DataSourceError::from(PutError::ContentNotChanged),
+						);
 					}
 
 					*etag = new_etag.clone();
 					let now = time::OffsetDateTime::now_utc();
 					*last_modified = Some(now);
 					*content_type = new_content_type;
+					*content_length = new_content
+						.as_ref()
+						.map_or(new_content_length, |content| content.len() as u64);
 					*content = new_content;
 
 					{
@@ -104,9 +101,11 @@ pub fn put(
 									*etag = crate::item::Etag::new();
 								}
 								None => {
-									return crate::database::PutResult::Err(Box::new(
-										PutError::InternalError,
-									));
+									return crate::database::PutResult::Err(
+										crate::database::DataSourceError::from(
+											PutError::InternalError,
+										),
+									);
 								}
 							}
 						}
@@ -114,22 +113,29 @@ pub fn put(
 
 					return crate::database::PutResult::Updated(new_etag, now);
 				} else {
-					return crate::database::PutResult::Err(Box::new(
-						PutError::DoesNotWorksForFolders,
-					));
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::DoesNotWorksForFolders),
+					);
 				}
 			} else {
-				return crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders));
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::DoesNotWorksForFolders,
+				));
 			}
 		}
 		Err(error) => match *error.downcast::<super::GetError>().unwrap() {
 			super::GetError::NotFound { .. } => {
-				match super::get::get_internal_mut(
-					root_item,
-					&path.parent().unwrap(),
-					&crate::item::Etag::from(""),
-					&[],
-				) {
+				if let Err(failure) =
+					crate::database::check_preconditions(None, if_match, if_none_match)
+				{
+					return crate::database::PutResult::Err(
+						crate::database::DataSourceError::from(PutError::GetError(
+							super::GetError::from_precondition(path.clone(), failure),
+						)),
+					);
+				}
+
+				match super::get::get_internal_mut(root_item, &path.parent().unwrap(), &[], &[]) {
 					Ok(parent_folder) => match parent_folder {
 						crate::item::Item::Folder {
 							content: Some(content),
@@ -138,6 +144,7 @@ pub fn put(
 							if let crate::item::Item::Document {
 								content: new_content,
 								content_type: new_content_type,
+								content_length: new_content_length,
 								last_modified: new_last_modified,
 								..
 							} = item
@@ -145,6 +152,9 @@ pub fn put(
 								let new_etag = crate::item::Etag::new();
 								let new_item = crate::item::Item::Document {
 									etag: new_etag.clone(),
+									content_length: new_content
+										.as_ref()
+										.map_or(new_content_length, |content| content.len() as u64),
 									content: new_content,
 									content_type: new_content_type,
 									last_modified: Some(time::OffsetDateTime::now_utc()),
@@ -167,9 +177,11 @@ pub fn put(
 												*etag = crate::item::Etag::new();
 											}
 											None => {
-												return crate::database::PutResult::Err(Box::new(
-													PutError::InternalError,
-												));
+												return crate::database::PutResult::Err(
+													crate::database::DataSourceError::from(
+														PutError::InternalError,
+													),
+												);
 											}
 										}
 									}
@@ -180,37 +192,43 @@ pub fn put(
 									new_last_modified.unwrap_or_else(time::OffsetDateTime::now_utc),
 								);
 							} else {
-								return crate::database::PutResult::Err(Box::new(
-									PutError::DoesNotWorksForFolders,
-								));
+								return crate::database::PutResult::Err(
+									crate::database::DataSourceError::from(
+										PutError::DoesNotWorksForFolders,
+									),
+								);
 							}
 						}
 						crate::item::Item::Folder { content: None, .. } => {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::NoContentInside {
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::NoContentInside {
 									item_path: path.clone(),
-								},
-							));
+								}),
+							);
 						}
 						_ => {
-							return crate::database::PutResult::Err(Box::new(
-								PutError::InternalError,
-							));
+							return crate::database::PutResult::Err(
+								crate::database::DataSourceError::from(PutError::InternalError),
+							);
 						}
 					},
 					Err(error) => {
 						let error = *error.downcast::<super::GetError>().unwrap();
-						return crate::database::PutResult::Err(Box::new(PutError::GetError(
-							error,
-						)));
+						return crate::database::PutResult::Err(
+							crate::database::DataSourceError::from(PutError::GetError(error)),
+						);
 					}
 				}
 			}
 			super::GetError::CanNotBeListed { .. } => {
-				return crate::database::PutResult::Err(Box::new(PutError::DoesNotWorksForFolders));
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::DoesNotWorksForFolders,
+				));
 			}
 			error => {
-				return crate::database::PutResult::Err(Box::new(PutError::GetError(error)));
+				return crate::database::PutResult::Err(crate::database::DataSourceError::from(
+					PutError::GetError(error),
+				));
 			}
 		},
 	}
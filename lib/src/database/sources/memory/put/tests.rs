@@ -50,7 +50,7 @@ fn simple_put_on_not_existing() {
 	let (AA_etag, _) = put(
 		&mut root,
 		&ItemPath::from("AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -87,7 +87,7 @@ fn simple_put_on_existing() {
 	let (AA_etag, _) = put(
 		&mut root,
 		&ItemPath::from("A/AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -137,17 +137,15 @@ fn content_not_changed() {
 	let (mut root, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AA", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::ContentNotChanged
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::ContentNotChanged)
 	);
 
 	if let Item::Folder {
@@ -193,17 +191,15 @@ fn does_not_works_for_folders() {
 	let root_etag = root.get_etag().clone();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from(""),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_folder(vec![])
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::DoesNotWorksForFolders
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::DoesNotWorksForFolders)
 	);
 	if let Item::Folder {
 		etag,
@@ -225,7 +221,7 @@ fn put_with_if_none_match_all_on_not_existing() {
 	let (AA_etag, _) = put(
 		&mut root,
 		&ItemPath::from("A/AA"),
-		&Etag::from(""),
+		&[],
 		&[&Etag::from("*")],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -272,21 +268,21 @@ fn put_with_if_none_match_all_on_existing() {
 	let (mut root, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from("A/AA"),
-			&Etag::from(""),
+			&[],
 			&[&Etag::from("*")],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::IfNoneMatch {
-			item_path: ItemPath::from("A/AA"),
-			found: AA_etag.clone(),
-			search: Etag::from("*")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::IfNoneMatch {
+				item_path: ItemPath::from("A/AA"),
+				found: AA_etag.clone(),
+				search: Etag::from("*")
+			}
+		))
 	);
 
 	if let Item::Folder {
@@ -331,21 +327,21 @@ fn put_with_if_match_not_found() {
 	let (mut root, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from("A/AA"),
-			&Etag::from("ANOTHER_ETAG"),
+			&[&Etag::from("ANOTHER_ETAG")],
 			&[],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::NoIfMatch {
-			item_path: ItemPath::from("A/AA"),
-			found: AA_etag.clone(),
-			search: Etag::from("ANOTHER_ETAG")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::NoIfMatch {
+				item_path: ItemPath::from("A/AA"),
+				found: AA_etag.clone(),
+				search: Etag::from("ANOTHER_ETAG")
+			}
+		))
 	);
 
 	if let Item::Folder {
@@ -392,7 +388,7 @@ fn put_with_if_match_found() {
 	(AA_etag, _) = put(
 		&mut root,
 		&ItemPath::from("A/AA"),
-		&AA_etag,
+		&[&AA_etag],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -442,7 +438,7 @@ fn put_with_if_match_all() {
 	let (AA_etag, _) = put(
 		&mut root,
 		&ItemPath::from("A/AA"),
-		&Etag::from("*"),
+		&[&Etag::from("*")],
 		&[],
 		Item::new_doc(b"AA2", "text/plain2"),
 	)
@@ -492,19 +488,19 @@ fn put_with_existing_document_conflict() {
 	let (mut root, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from("A/AA/AAA"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AAA", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::Conflict {
-			item_path: ItemPath::from("A/AA")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::Conflict {
+				item_path: ItemPath::from("A/AA")
+			}
+		))
 	);
 
 	if let Item::Folder {
@@ -549,19 +545,19 @@ fn put_with_existing_folder_conflict() {
 	let (mut root, root_etag, A_etag, AA_etag) = build_test_db();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from("A"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"A", "text/plain")
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::Conflict {
-			item_path: ItemPath::from("A/")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::Conflict {
+				item_path: ItemPath::from("A/")
+			}
+		))
 	);
 
 	if let Item::Folder {
@@ -609,7 +605,7 @@ fn put_in_public() {
 	let (AA_etag, _) = put(
 		&mut root,
 		&ItemPath::from("public/A/AA"),
-		&Etag::from(""),
+		&[],
 		&[],
 		Item::new_doc(b"AA", "text/plain"),
 	)
@@ -664,20 +660,20 @@ fn put_in_incorrect_path() {
 	let root_etag = root.get_etag().clone();
 
 	assert_eq!(
-		*put(
+		put(
 			&mut root,
 			&ItemPath::from("A/A\0A"),
-			&Etag::from(""),
+			&[],
 			&[],
 			Item::new_doc(b"AA2", "text/plain2"),
 		)
-		.unwrap_err()
-		.downcast::<PutError>()
-		.unwrap(),
-		PutError::GetError(super::super::GetError::IncorrectItemName {
-			item_path: ItemPath::from("A/A\0A"),
-			error: String::from("`A\0A` should not contains `\\0` character")
-		})
+		.unwrap_err(),
+		crate::database::DataSourceError::from(PutError::GetError(
+			super::super::GetError::IncorrectItemName {
+				item_path: ItemPath::from("A/A\0A"),
+				error: String::from("`A\0A` should not contains `\\0` character")
+			}
+		))
 	);
 
 	if let Item::Folder {
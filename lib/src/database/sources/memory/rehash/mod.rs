@@ -0,0 +1,54 @@
+#[cfg(test)]
+pub mod tests;
+
+pub fn rehash_etags(
+	root_item: &mut crate::item::Item,
+	path: &crate::item::ItemPath,
+) -> Option<crate::item::Etag> {
+	let result = root_item.get_child_mut(path).map(|item| {
+		item.rehash_etags();
+		item.get_etag().clone()
+	});
+
+	let ancestors = path.ancestors();
+	for ancestor in ancestors
+		.into_iter()
+		.rev()
+		.filter(|ancestor| ancestor != path)
+	{
+		if let Some(crate::item::Item::Folder {
+			etag,
+			content: Some(content),
+		}) = root_item.get_child_mut(&ancestor)
+		{
+			*etag = crate::item::Item::children_etag(content);
+		}
+	}
+
+	return result;
+}
+
+/// Replaces the ETag and the last modification date of the document at `path`.
+pub fn set_metadata(
+	root_item: &mut crate::item::Item,
+	path: &crate::item::ItemPath,
+	new_etag: &crate::item::Etag,
+	new_last_modified: Option<time::OffsetDateTime>,
+) -> Result<(), crate::database::DataSourceError> {
+	match root_item.get_child_mut(path) {
+		Some(crate::item::Item::Document {
+			etag,
+			last_modified,
+			..
+		}) if path.is_document() => {
+			*etag = new_etag.clone();
+			*last_modified = new_last_modified;
+
+			Ok(())
+		}
+		Some(_) => Err(crate::database::DataSourceError::DoesNotWorksForFolders),
+		None => Err(crate::database::DataSourceError::NotFound {
+			item_path: path.clone(),
+		}),
+	}
+}
@@ -0,0 +1,48 @@
+use super::rehash_etags;
+use crate::item::{Etag, Item, ItemPath};
+
+#[test]
+fn kq4m8zt1wv6xcy9hbn() {
+	let mut root = Item::new_folder(vec![(
+		"A",
+		Item::new_folder(vec![
+			("AA", Item::new_doc(b"AA", "text/plain")),
+			("AB", Item::new_doc(b"AB", "text/plain")),
+		]),
+	)]);
+
+	let etag = rehash_etags(&mut root, &ItemPath::from("A/AA")).unwrap();
+	assert_eq!(
+		etag,
+		Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+	assert_eq!(
+		root.get_child(&ItemPath::from("A/AA")).unwrap().get_etag(),
+		&etag
+	);
+
+	// siblings are not modified
+	assert_ne!(
+		root.get_child(&ItemPath::from("A/AB")).unwrap().get_etag(),
+		&Etag::from_content(b"AB", &crate::item::ContentType::from("text/plain"))
+	);
+
+	let AA_etag = Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"));
+	let AB_etag = root
+		.get_child(&ItemPath::from("A/AB"))
+		.unwrap()
+		.get_etag()
+		.clone();
+	let A_etag = Etag::from_children(vec![("AA", &AA_etag), ("AB", &AB_etag)]);
+	assert_eq!(
+		root.get_child(&ItemPath::from("A/")).unwrap().get_etag(),
+		&A_etag
+	);
+	assert_eq!(root.get_etag(), &Etag::from_children(vec![("A/", &A_etag)]));
+
+	assert_eq!(rehash_etags(&mut root, &ItemPath::from("A/AC")), None);
+	assert_eq!(
+		root.get_child(&ItemPath::from("A/")).unwrap().get_etag(),
+		&A_etag
+	);
+}
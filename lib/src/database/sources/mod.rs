@@ -1,31 +1,38 @@
 #[cfg(feature = "server_file_storage")]
 pub mod folder;
+#[cfg(feature = "server_indexed_db")]
+pub mod indexed_db;
 #[cfg(feature = "server_local_storage")]
 pub mod local_storage;
 pub mod memory;
 
 #[cfg(feature = "server_file_storage")]
 pub use folder::FolderStorage;
+#[cfg(feature = "server_indexed_db")]
+pub use indexed_db::IndexedDbStorage;
 #[cfg(feature = "server_local_storage")]
 pub use local_storage::LocalStorage;
 pub use memory::MemoryStorage;
 
+#[cfg(test)]
+mod tests;
+
 // TODO : File{file_path: std::path::PathBuf},
 
 /// Specify how the database should interact with its data.
-pub trait DataSource: std::fmt::Debug + Send {
+pub trait DataSource: std::fmt::Debug + Send + Sync {
 	fn get(
 		&self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		get_content: bool,
-	) -> Result<crate::item::Item, Box<dyn std::error::Error>>;
+	) -> Result<crate::item::Item, crate::database::DataSourceError>;
 
 	fn put(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
+		if_match: &[&crate::item::Etag],
 		if_none_match: &[&crate::item::Etag],
 		new_item: crate::item::Item,
 	) -> crate::database::PutResult;
@@ -33,6 +40,273 @@ pub trait DataSource: std::fmt::Debug + Send {
 	fn delete(
 		&mut self,
 		path: &crate::item::ItemPath,
-		if_match: &crate::item::Etag,
-	) -> Result<crate::item::Etag, Box<dyn std::error::Error>>;
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError>;
+
+	/// Lists the children of the folder at `path`, ordered and filtered by `options`.
+	///
+	/// The default implementation gets the whole folder, backends should list only what is needed.
+	fn list(
+		&self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+		options: &crate::database::ListingOptions,
+	) -> Result<crate::database::Listing, crate::database::DataSourceError> {
+		match self.get(path, if_match, if_none_match, true)? {
+			crate::item::Item::Folder {
+				etag,
+				content: Some(content),
+			} => Ok(crate::database::listing::list_children(
+				etag, &content, options,
+			)),
+			crate::item::Item::Folder { content: None, .. } => {
+				Err(crate::database::DataSourceError::NoContentInside {
+					item_path: path.clone(),
+				})
+			}
+			crate::item::Item::Document { .. } => Err(crate::database::DataSourceError::Conflict {
+				item_path: path.clone(),
+			}),
+		}
+	}
+
+	/// Copies the document at `from` to `to`.
+	///
+	/// `if_match` applies to `from`, and `if_none_match` to `to`.
+	/// The default implementation gets the document, then puts it at `to`.
+	fn copy_item(
+		&mut self,
+		from: &crate::item::ItemPath,
+		to: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+	) -> crate::database::PutResult {
+		match self.get(from, if_match, &[], true) {
+			Ok(item @ crate::item::Item::Document { .. }) => {
+				return self.put(to, &[], if_none_match, item);
+			}
+			Ok(crate::item::Item::Folder { .. }) => {
+				return crate::database::PutResult::Err(
+					crate::database::DataSourceError::DoesNotWorksForFolders,
+				);
+			}
+			Err(error) => {
+				return crate::database::PutResult::Err(error);
+			}
+		}
+	}
+
+	/// Moves the document at `from` to `to`.
+	///
+	/// `if_match` applies to `from`, and `if_none_match` to `to`.
+	/// The default implementation copies the document, then deletes it from `from` :
+	/// if this delete fails, `to` is restored like it was before the move.
+	fn move_item(
+		&mut self,
+		from: &crate::item::ItemPath,
+		to: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+		if_none_match: &[&crate::item::Etag],
+	) -> crate::database::PutResult {
+		if from == to {
+			return crate::database::PutResult::Err(crate::database::DataSourceError::Conflict {
+				item_path: to.clone(),
+			});
+		}
+
+		let previous = match self.get(to, &[], &[], true) {
+			Ok(crate::item::Item::Document {
+				etag,
+				content,
+				content_type,
+				content_length,
+				last_modified,
+			}) => Some((
+				crate::item::Item::Document {
+					etag: etag.clone(),
+					content,
+					content_type,
+					content_length,
+					last_modified,
+				},
+				etag,
+				last_modified,
+			)),
+			_ => None,
+		};
+
+		let result = self.copy_item(from, to, if_match, if_none_match);
+
+		if let crate::database::PutResult::Created(_, _)
+		| crate::database::PutResult::Updated(_, _) = result
+		{
+			if let Err(error) = self.delete(from, &[]) {
+				match previous {
+					Some((item, previous_etag, previous_last_modified)) => {
+						if !matches!(
+							self.put(to, &[], &[], item),
+							crate::database::PutResult::Err(_)
+						) {
+							self.set_metadata(to, &previous_etag, previous_last_modified)
+								.ok();
+						}
+					}
+					// do not leave a copy behind, if there was nothing at `to` before
+					None => {
+						self.delete(to, &[]).ok();
+					}
+				}
+
+				return crate::database::PutResult::Err(error);
+			}
+		}
+
+		return result;
+	}
+
+	/// Deletes the folder at `path` with all the documents inside it.
+	///
+	/// `if_match` applies to the folder, and its ETag is returned.
+	/// The default implementation gets the folder, then deletes its documents one by one,
+	/// so it does not work in `public/`, where folders can not be listed.
+	fn delete_folder(
+		&mut self,
+		path: &crate::item::ItemPath,
+		if_match: &[&crate::item::Etag],
+	) -> Result<crate::item::Etag, crate::database::DataSourceError> {
+		if !path.is_folder() {
+			return Err(crate::database::DataSourceError::Conflict {
+				item_path: path.clone(),
+			});
+		}
+
+		let folder = self.get(path, if_match, &[], false)?;
+
+		let mut documents = vec![];
+		crate::database::batch::collect_documents(self, path, &folder, &mut documents)?;
+
+		for document in documents {
+			self.delete(&document, &[])?;
+		}
+
+		return Ok(folder.get_etag().clone());
+	}
+
+	/// Lists the paths of all documents inside the folder at `path`, and inside its children folders.
+	///
+	/// The default implementation gets the folder, so it does not work in `public/`,
+	/// where folders can not be listed.
+	fn documents_in(
+		&self,
+		path: &crate::item::ItemPath,
+	) -> Result<Vec<crate::item::ItemPath>, crate::database::DataSourceError> {
+		let folder = self.get(path, &[], &[], false)?;
+
+		let mut documents = vec![];
+		crate::database::batch::collect_documents(self, path, &folder, &mut documents)?;
+
+		return Ok(documents);
+	}
+
+	/// Replaces the ETag and the last modification date of the document at `path`,
+	/// without changing its content or its ancestors.
+	///
+	/// It is used to import documents with the metadata they had before.
+	/// The default implementation does not support it.
+	fn set_metadata(
+		&mut self,
+		path: &crate::item::ItemPath,
+		_etag: &crate::item::Etag,
+		_last_modified: Option<time::OffsetDateTime>,
+	) -> Result<(), crate::database::DataSourceError> {
+		return Err(crate::database::DataSourceError::InternalError {
+			error: format!("this data source can not set the metadata of `{}`", path),
+		});
+	}
+
+	/// Applies `operations` in their order, and returns their results in the same order.
+	///
+	/// A failed operation does not prevent the next ones to be applied.
+	/// The default implementation applies them one by one, backends should renew
+	/// the ETag of each ancestor only once for the whole batch.
+	fn batch(
+		&mut self,
+		operations: &[crate::database::BatchOperation],
+	) -> Vec<crate::database::BatchResult> {
+		return operations
+			.iter()
+			.map(|operation| crate::database::batch::apply(self, operation))
+			.collect();
+	}
+
+	/// Replaces the ETags of the item at `path` (and of all its children) and of its ancestors
+	/// by ones derived from their content, see [`EtagStrategy::ContentHash`][`crate::database::EtagStrategy::ContentHash`].
+	///
+	/// Returns the new ETag of the item at `path`, or `None` if it does not exist (anymore).
+	fn rehash_etags(
+		&mut self,
+		path: &crate::item::ItemPath,
+	) -> Result<Option<crate::item::Etag>, crate::database::DataSourceError>;
+}
+
+/// A boxed future, as returned by [`AsyncDataSource`].
+pub type DataSourceFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send>>;
+
+/// Asynchronous counterpart of [`DataSource`].
+///
+/// It takes its arguments by value, so the returned future does not borrow
+/// anything and can be awaited without blocking the executor.
+pub trait AsyncDataSource: Send + Sync {
+	fn get(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		get_content: bool,
+	) -> DataSourceFuture<Result<crate::item::Item, crate::database::DataSourceError>>;
+
+	fn put(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		new_item: crate::item::Item,
+	) -> DataSourceFuture<crate::database::PutResult>;
+
+	fn delete(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+	) -> DataSourceFuture<Result<crate::item::Etag, crate::database::DataSourceError>>;
+
+	fn list(
+		&self,
+		path: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+		options: crate::database::ListingOptions,
+	) -> DataSourceFuture<Result<crate::database::Listing, crate::database::DataSourceError>>;
+
+	fn copy_item(
+		&self,
+		from: crate::item::ItemPath,
+		to: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+	) -> DataSourceFuture<crate::database::PutResult>;
+
+	fn move_item(
+		&self,
+		from: crate::item::ItemPath,
+		to: crate::item::ItemPath,
+		if_match: Vec<crate::item::Etag>,
+		if_none_match: Vec<crate::item::Etag>,
+	) -> DataSourceFuture<crate::database::PutResult>;
+
+	/// Fails only if the batch could not be run at all, results of operations are given one by one.
+	fn batch(
+		&self,
+		operations: Vec<crate::database::BatchOperation>,
+	) -> DataSourceFuture<Result<Vec<crate::database::BatchResult>, crate::database::DataSourceError>>;
 }
@@ -0,0 +1,97 @@
+use super::{DataSource, MemoryStorage};
+use crate::database::{DataSourceError, PutResult};
+use crate::item::{Etag, Item, ItemPath};
+
+/// Only uses the default implementations of [`DataSource`], and can not delete `locked`.
+#[derive(Debug)]
+struct LockedStorage {
+	memory: MemoryStorage,
+	locked: ItemPath,
+}
+impl DataSource for LockedStorage {
+	fn get(
+		&self,
+		path: &ItemPath,
+		if_match: &[&Etag],
+		if_none_match: &[&Etag],
+		get_content: bool,
+	) -> Result<Item, DataSourceError> {
+		return self.memory.get(path, if_match, if_none_match, get_content);
+	}
+
+	fn put(
+		&mut self,
+		path: &ItemPath,
+		if_match: &[&Etag],
+		if_none_match: &[&Etag],
+		new_item: Item,
+	) -> PutResult {
+		return self.memory.put(path, if_match, if_none_match, new_item);
+	}
+
+	fn delete(&mut self, path: &ItemPath, if_match: &[&Etag]) -> Result<Etag, DataSourceError> {
+		if path == &self.locked {
+			return Err(DataSourceError::InternalError {
+				error: format!("`{}` is locked", path),
+			});
+		}
+
+		return self.memory.delete(path, if_match);
+	}
+
+	fn set_metadata(
+		&mut self,
+		path: &ItemPath,
+		etag: &Etag,
+		last_modified: Option<time::OffsetDateTime>,
+	) -> Result<(), DataSourceError> {
+		return self.memory.set_metadata(path, etag, last_modified);
+	}
+
+	fn rehash_etags(&mut self, path: &ItemPath) -> Result<Option<Etag>, DataSourceError> {
+		return self.memory.rehash_etags(path);
+	}
+}
+
+#[test]
+fn t4nw8kx2vq6mzr1hcd() {
+	let mut storage = LockedStorage {
+		memory: MemoryStorage {
+			root_item: Item::new_folder(vec![(
+				"A",
+				Item::new_folder(vec![
+					("AA", Item::new_doc(b"AA", "text/plain")),
+					("AB", Item::new_doc(b"AB", "text/html")),
+				]),
+			)]),
+		},
+		locked: ItemPath::from("A/AA"),
+	};
+	let before = storage
+		.get(&ItemPath::from("A/AB"), &[], &[], true)
+		.unwrap();
+
+	// the previous document is restored
+	assert!(matches!(
+		storage.move_item(&ItemPath::from("A/AA"), &ItemPath::from("A/AB"), &[], &[]),
+		PutResult::Err(DataSourceError::InternalError { .. })
+	));
+	assert_eq!(
+		storage
+			.get(&ItemPath::from("A/AB"), &[], &[], true)
+			.unwrap(),
+		before
+	);
+
+	// the copy is removed
+	assert!(matches!(
+		storage.move_item(&ItemPath::from("A/AA"), &ItemPath::from("A/AC"), &[], &[]),
+		PutResult::Err(DataSourceError::InternalError { .. })
+	));
+	assert!(matches!(
+		storage.get(&ItemPath::from("A/AC"), &[], &[], true),
+		Err(DataSourceError::NotFound { .. })
+	));
+
+	assert!(storage.get(&ItemPath::from("A/AA"), &[], &[], true).is_ok());
+}
@@ -0,0 +1,651 @@
+use super::{
+	AsyncDataSource, BatchOperation, BatchResult, DataSourceError, Database, EtagStrategy,
+	ImportEtags, PutResult, Trash,
+};
+use crate::item::{Etag, Item, ItemPath};
+
+fn build_test_db() -> Database {
+	return Database::new(Box::new(super::MemoryStorage {
+		root_item: Item::new_folder(vec![(
+			"A",
+			Item::new_folder(vec![("AA", Item::new_doc(b"AA", "text/plain"))]),
+		)]),
+	}));
+}
+
+#[actix_rt::test]
+async fn f3rl6b2jvgyk0a6w5u() {
+	let database = build_test_db();
+
+	let (etag, _) = AsyncDataSource::put(
+		&database,
+		ItemPath::from("A/AB"),
+		vec![],
+		vec![],
+		Item::new_doc(b"AB", "text/plain"),
+	)
+	.await
+	.unwrap();
+
+	match AsyncDataSource::get(&database, ItemPath::from("A/AB"), vec![], vec![], true)
+		.await
+		.unwrap()
+	{
+		Item::Document {
+			etag: found_etag,
+			content: Some(content),
+			..
+		} => {
+			assert_eq!(found_etag, etag);
+			assert_eq!(content, b"AB".to_vec());
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	assert_eq!(
+		AsyncDataSource::delete(&database, ItemPath::from("A/AB"), vec![])
+			.await
+			.unwrap(),
+		etag
+	);
+
+	assert_eq!(
+		AsyncDataSource::get(&database, ItemPath::from("A/AB"), vec![], vec![], true)
+			.await
+			.unwrap_err(),
+		DataSourceError::NotFound {
+			item_path: ItemPath::from("A/AB")
+		}
+	);
+}
+
+#[actix_rt::test]
+async fn tx5frqzl3zv3a4x3os() {
+	let database = build_test_db();
+
+	let reads = (0..8)
+		.map(|_| AsyncDataSource::get(&database, ItemPath::from("A/AA"), vec![], vec![], true));
+
+	for result in futures::future::join_all(reads).await {
+		assert!(result.is_ok());
+	}
+
+	// the synchronous API and the asynchronous one share the same source
+	assert!(database.get(&ItemPath::from("A/AA"), &[], &[]).is_ok());
+}
+
+#[test]
+fn m5c0rbk2xq9wjh7vfe() {
+	let first = build_test_db().with_etag_strategy(EtagStrategy::ContentHash);
+	let second = build_test_db().with_etag_strategy(EtagStrategy::ContentHash);
+
+	for database in [&first, &second] {
+		assert_eq!(
+			database
+				.put(
+					&ItemPath::from("A/AB"),
+					Item::new_doc(b"AB", "text/plain"),
+					&[],
+					&[]
+				)
+				.unwrap()
+				.0,
+			Etag::from_content(b"AB", &crate::item::ContentType::from("text/plain"))
+		);
+	}
+
+	// `A/AA` still has a random ETag
+	assert_ne!(
+		first.get(&ItemPath::from(""), &[], &[]).unwrap().get_etag(),
+		second
+			.get(&ItemPath::from(""), &[], &[])
+			.unwrap()
+			.get_etag()
+	);
+
+	first.rehash_etags(&ItemPath::from("")).unwrap();
+	second.rehash_etags(&ItemPath::from("")).unwrap();
+	assert_eq!(
+		first.get(&ItemPath::from(""), &[], &[]).unwrap().get_etag(),
+		second
+			.get(&ItemPath::from(""), &[], &[])
+			.unwrap()
+			.get_etag()
+	);
+
+	let before = first
+		.get(&ItemPath::from(""), &[], &[])
+		.unwrap()
+		.get_etag()
+		.clone();
+	first.delete(&ItemPath::from("A/AB"), &[]).unwrap();
+	assert_ne!(
+		first.get(&ItemPath::from(""), &[], &[]).unwrap().get_etag(),
+		&before
+	);
+	assert_eq!(
+		first
+			.get(&ItemPath::from("A/"), &[], &[])
+			.unwrap()
+			.get_etag(),
+		&Etag::from_children(vec![(
+			"AA",
+			&Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+		)])
+	);
+}
+
+#[test]
+fn p9vd4xn2hw7kqz1rtc() {
+	let database = build_test_db().with_etag_strategy(EtagStrategy::ContentHash);
+	database.rehash_etags(&ItemPath::from("")).unwrap();
+
+	let (etag, _) = database
+		.move_item(&ItemPath::from("A/AA"), &ItemPath::from("B/BA"), &[], &[])
+		.unwrap();
+	assert_eq!(
+		etag,
+		Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+	assert!(matches!(
+		database.get(&ItemPath::from("A/AA"), &[], &[]).unwrap_err(),
+		DataSourceError::NotFound { .. }
+	));
+
+	// ancestors of both paths are updated
+	let expected = Etag::from_children(vec![(
+		"B/",
+		&Etag::from_children(vec![(
+			"BA",
+			&Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain")),
+		)]),
+	)]);
+	assert_eq!(
+		database
+			.get(&ItemPath::from(""), &[], &[])
+			.unwrap()
+			.get_etag(),
+		&expected
+	);
+
+	let (etag, _) = database
+		.copy_item(&ItemPath::from("B/BA"), &ItemPath::from("B/BB"), &[], &[])
+		.unwrap();
+	assert_eq!(
+		etag,
+		Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+	assert!(database.get(&ItemPath::from("B/BA"), &[], &[]).is_ok());
+
+	assert!(matches!(
+		database
+			.copy_item(
+				&ItemPath::from("B/BA"),
+				&ItemPath::from("B/BB"),
+				&[],
+				&[&Etag::from("*")]
+			)
+			.unwrap_err(),
+		DataSourceError::IfNoneMatch { .. }
+	));
+	assert!(matches!(
+		database
+			.move_item(
+				&ItemPath::from("B/BA"),
+				&ItemPath::from("B/BC"),
+				&[&Etag::from("OTHER")],
+				&[]
+			)
+			.unwrap_err(),
+		DataSourceError::NoIfMatch { .. }
+	));
+	assert_eq!(
+		database
+			.copy_item(&ItemPath::from("B/"), &ItemPath::from("C/"), &[], &[])
+			.unwrap_err(),
+		DataSourceError::DoesNotWorksForFolders
+	);
+}
+
+#[test]
+fn h6xc2nq9tw4kmz7vrb() {
+	let database = build_test_db().with_etag_strategy(EtagStrategy::ContentHash);
+	database.rehash_etags(&ItemPath::from("")).unwrap();
+
+	let results = database.batch(&[
+		BatchOperation::Put {
+			path: ItemPath::from("B/BA"),
+			if_match: vec![],
+			if_none_match: vec![],
+			item: Item::new_doc(b"BA", "text/plain"),
+		},
+		BatchOperation::Delete {
+			path: ItemPath::from("A/"),
+			if_match: vec![Etag::from("OTHER")],
+		},
+		BatchOperation::Delete {
+			path: ItemPath::from("A/"),
+			if_match: vec![],
+		},
+	]);
+
+	match &results[..] {
+		[BatchResult::Put(PutResult::Created(etag, _)), BatchResult::Delete(Err(DataSourceError::NoIfMatch { .. })), BatchResult::Delete(Ok(_))] =>
+		{
+			assert_eq!(
+				etag,
+				&Etag::from_content(b"BA", &crate::item::ContentType::from("text/plain"))
+			);
+		}
+		results => panic!("unexpected results : {:?}", results),
+	}
+
+	assert!(matches!(
+		database.get(&ItemPath::from("A/AA"), &[], &[]).unwrap_err(),
+		DataSourceError::NotFound { .. }
+	));
+
+	let expected = Etag::from_children(vec![(
+		"B/",
+		&Etag::from_children(vec![(
+			"BA",
+			&Etag::from_content(b"BA", &crate::item::ContentType::from("text/plain")),
+		)]),
+	)]);
+	assert_eq!(
+		database
+			.get(&ItemPath::from(""), &[], &[])
+			.unwrap()
+			.get_etag(),
+		&expected
+	);
+}
+
+#[test]
+fn w2kc8rn5qz7tmh3xvd() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	let trash_path = tmp_folder.path().join("trash");
+
+	let database = Database::new(Box::new(super::MemoryStorage {
+		root_item: Item::new_folder(vec![
+			(
+				"user",
+				Item::new_folder(vec![(
+					"notes",
+					Item::new_folder(vec![
+						("a.txt", Item::new_doc(b"A", "text/plain")),
+						(
+							"old",
+							Item::new_folder(vec![("b.txt", Item::new_doc(b"B", "text/plain"))]),
+						),
+					]),
+				)]),
+			),
+			(
+				"public",
+				Item::new_folder(vec![(
+					"user",
+					Item::new_folder(vec![(
+						"shared",
+						Item::new_folder(vec![("c.txt", Item::new_doc(b"C", "text/plain"))]),
+					)]),
+				)]),
+			),
+		]),
+	}))
+	.with_trash(Trash::in_folder(&trash_path, time::Duration::days(30)).unwrap());
+
+	let a_etag = database
+		.get(&ItemPath::from("user/notes/a.txt"), &[], &[])
+		.unwrap()
+		.get_etag()
+		.clone();
+
+	// a failed deletion keeps nothing
+	assert!(database
+		.delete(&ItemPath::from("user/notes/a.txt"), &[&Etag::from("wrong")])
+		.is_err());
+	assert!(database.trashed_items("user").unwrap().is_empty());
+
+	database
+		.delete(&ItemPath::from("user/notes/a.txt"), &[])
+		.unwrap();
+
+	let results = database.batch(&[
+		BatchOperation::Delete {
+			path: ItemPath::from("user/notes/old/"),
+			if_match: vec![],
+		},
+		BatchOperation::Delete {
+			path: ItemPath::from("public/user/shared/"),
+			if_match: vec![],
+		},
+	]);
+	assert!(results[0].is_ok());
+	assert!(!results[1].is_ok());
+	assert!(database
+		.get(&ItemPath::from("public/user/shared/c.txt"), &[], &[])
+		.is_ok());
+
+	let trashed = database.trashed_items("user").unwrap();
+	assert_eq!(
+		trashed
+			.iter()
+			.map(|trashed| trashed.path.as_str())
+			.collect::<Vec<&str>>(),
+		vec!["user/notes/old/b.txt", "user/notes/a.txt"]
+	);
+	assert_eq!(trashed[1].etag, a_etag);
+	assert!(database.trashed_items("other").unwrap().is_empty());
+
+	// saved trashed items are loaded again
+	let reloaded = Trash::in_folder(&trash_path, time::Duration::days(30)).unwrap();
+	assert_eq!(reloaded.list("user"), trashed);
+
+	// only their metadata is kept in memory, their content is read from their file
+	match &reloaded.get(&trashed[0].id).unwrap().item {
+		Item::Document { content, .. } => assert_eq!(content, &None),
+		item => panic!("unexpected item : {:?}", item),
+	}
+	match reloaded.load(&trashed[0].id).unwrap().unwrap().item {
+		Item::Document { content, .. } => assert_eq!(content, Some(b"B".to_vec())),
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	// something was put at its path again
+	database
+		.put(
+			&ItemPath::from("user/notes/a.txt"),
+			Item::new_doc(b"A2", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap();
+	assert!(matches!(
+		database.restore_trashed(&trashed[1].id),
+		PutResult::Err(DataSourceError::IfNoneMatch { .. })
+	));
+
+	database.restore_trashed(&trashed[0].id).unwrap();
+	match database
+		.get(&ItemPath::from("user/notes/old/b.txt"), &[], &[])
+		.unwrap()
+	{
+		Item::Document { content, .. } => assert_eq!(content, Some(b"B".to_vec())),
+		item => panic!("unexpected item : {:?}", item),
+	}
+	assert_eq!(database.trashed_items("user").unwrap().len(), 1);
+
+	let mut kept = Trash::in_folder(&trash_path, time::Duration::MAX).unwrap();
+	assert_eq!(
+		kept.purge(time::OffsetDateTime::now_utc() + time::Duration::days(31))
+			.unwrap(),
+		0
+	);
+	assert_eq!(database.trashed_items("user").unwrap().len(), 1);
+
+	let mut expired = Trash::in_folder(&trash_path, time::Duration::days(30)).unwrap();
+	assert_eq!(
+		expired
+			.purge(time::OffsetDateTime::now_utc() + time::Duration::days(31))
+			.unwrap(),
+		1
+	);
+	assert_eq!(std::fs::read_dir(&trash_path).unwrap().count(), 0);
+}
+
+#[cfg(feature = "server_file_storage")]
+#[test]
+fn k7tb3xw9nq2hvm5czr() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+
+	let database = Database::new(Box::new(super::FolderStorage {
+		root_folder_path: tmp_folder.path().to_path_buf(),
+	}));
+	for (path, content) in [
+		("alice/notes/a.txt", "A"),
+		("alice/notes/old/b.txt", "B"),
+		("public/alice/shared/c.txt", "C"),
+		("bob/notes/d.txt", "D"),
+	] {
+		database
+			.put(
+				&ItemPath::from(path),
+				Item::new_doc(content.as_bytes(), "text/plain"),
+				&[],
+				&[],
+			)
+			.unwrap();
+	}
+
+	let mut archive = vec![];
+	assert_eq!(database.export_user("alice", &mut archive), Ok(3));
+	assert!(database.export_user("../bob", &mut vec![]).is_err());
+
+	let exported = |path: &str| {
+		database
+			.get(&ItemPath::from(path), &[], &[])
+			.unwrap()
+			.empty_clone()
+	};
+
+	let preserved = Database::new(Box::new(super::MemoryStorage {
+		root_item: Item::new_folder(vec![]),
+	}));
+	assert_eq!(
+		preserved.import_user("carol", &archive[..], ImportEtags::Preserve),
+		Ok(3)
+	);
+	for (path, imported_path) in [
+		("alice/notes/a.txt", "carol/notes/a.txt"),
+		("alice/notes/old/b.txt", "carol/notes/old/b.txt"),
+		("public/alice/shared/c.txt", "public/carol/shared/c.txt"),
+	] {
+		match (
+			exported(path),
+			preserved
+				.get(&ItemPath::from(imported_path), &[], &[])
+				.unwrap(),
+		) {
+			(
+				Item::Document {
+					etag,
+					content_type,
+					last_modified,
+					..
+				},
+				Item::Document {
+					etag: imported_etag,
+					content_type: imported_content_type,
+					last_modified: imported_last_modified,
+					..
+				},
+			) => {
+				assert_eq!(etag, imported_etag);
+				assert_eq!(content_type, imported_content_type);
+				assert_eq!(last_modified, imported_last_modified);
+			}
+			items => panic!("unexpected items : {:?}", items),
+		}
+	}
+	match preserved
+		.get(&ItemPath::from("carol/notes/a.txt"), &[], &[])
+		.unwrap()
+	{
+		Item::Document { content, .. } => assert_eq!(content, Some(b"A".to_vec())),
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	let regenerated = Database::new(Box::new(super::MemoryStorage {
+		root_item: Item::new_folder(vec![]),
+	}));
+	assert_eq!(
+		regenerated.import_user("alice", &archive[..], ImportEtags::Regenerate),
+		Ok(3)
+	);
+	assert_ne!(
+		regenerated
+			.get(&ItemPath::from("alice/notes/a.txt"), &[], &[])
+			.unwrap()
+			.get_etag(),
+		exported("alice/notes/a.txt").get_etag()
+	);
+}
+
+#[test]
+fn w8kd3qz6mx1tvn4hrc() {
+	let build_archive = |entries: &[(&str, &[u8])]| {
+		let mut builder = tar::Builder::new(vec![]);
+		for (path, data) in entries {
+			let mut header = tar::Header::new_gnu();
+			header.set_size(data.len() as u64);
+			header.set_mode(0o644);
+			builder.append_data(&mut header, path, *data).unwrap();
+		}
+
+		builder.into_inner().unwrap()
+	};
+	let itemdata = toml::to_vec(&crate::item::DataDocument {
+		checksum: Some(crate::item::DataDocument::checksum_of(b"B")),
+		..Default::default()
+	})
+	.unwrap();
+
+	let database = Database::new(Box::new(super::MemoryStorage {
+		root_item: Item::new_folder(vec![]),
+	}));
+
+	for entries in [
+		// the content of the last document does not match its checksum
+		vec![
+			("storage/notes/a.txt", &b"A"[..]),
+			("storage/notes/.b.txt.itemdata.toml", &itemdata[..]),
+			("storage/notes/b.txt", &b"X"[..]),
+		],
+		// metadata without document
+		vec![
+			("storage/notes/a.txt", &b"A"[..]),
+			("storage/notes/.b.txt.itemdata.toml", &itemdata[..]),
+		],
+		// incorrect path
+		vec![
+			("storage/notes/a.txt", &b"A"[..]),
+			("storage/folder/b.txt", &b"B"[..]),
+		],
+		// out of the user folders
+		vec![
+			("storage/notes/a.txt", &b"A"[..]),
+			("other/b.txt", &b"B"[..]),
+		],
+	] {
+		let archive = build_archive(&entries);
+
+		assert!(database
+			.import_user("dave", &archive[..], ImportEtags::Preserve)
+			.is_err());
+		assert!(database
+			.get(&ItemPath::from("dave/notes/a.txt"), &[], &[])
+			.is_err());
+	}
+
+	let archive = build_archive(&[
+		("storage/notes/a.txt", &b"A"[..]),
+		("storage/notes/b.txt", &b"B"[..]),
+		("storage/notes/.b.txt.itemdata.toml", &itemdata[..]),
+	]);
+	assert_eq!(
+		database.import_user("dave", &archive[..], ImportEtags::Preserve),
+		Ok(2)
+	);
+}
+
+#[test]
+#[cfg(feature = "server_file_storage")]
+fn n6wd2rk9tq4xbz8mfh() {
+	let database = Database::new(Box::new(super::MemoryStorage {
+		root_item: Item::new_folder(vec![]),
+	}));
+	for (path, content) in [
+		("alice/notes/a.txt", "A"),
+		("alice/notes/old/b.txt", "B"),
+		("public/alice/shared/c.txt", "C"),
+	] {
+		database
+			.put(
+				&ItemPath::from(path),
+				Item::new_doc(content.as_bytes(), "text/plain"),
+				&[],
+				&[],
+			)
+			.unwrap();
+	}
+
+	let tmp_folder = tempfile::tempdir().unwrap();
+	let data_path = tmp_folder.path().join("data");
+	let destination = || super::FolderStorage {
+		root_folder_path: data_path.clone(),
+	};
+
+	assert_eq!(
+		database.migrate_to(&mut destination(), super::MigrationDestination::Empty),
+		Ok(super::Migration {
+			copied: 3,
+			unchanged: 0,
+			removed: 0,
+		})
+	);
+
+	let migrated = Database::new(Box::new(destination()));
+	for path in [
+		"alice/notes/a.txt",
+		"alice/notes/old/b.txt",
+		"public/alice/shared/c.txt",
+	] {
+		assert_eq!(
+			migrated.get(&ItemPath::from(path), &[], &[]),
+			database.get(&ItemPath::from(path), &[], &[])
+		);
+	}
+
+	// changes made during the first migration
+	database
+		.put(
+			&ItemPath::from("alice/notes/a.txt"),
+			Item::new_doc(b"AA", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap();
+	database
+		.delete(&ItemPath::from("alice/notes/old/b.txt"), &[])
+		.unwrap();
+
+	// documents of the destination are not lost by mistake
+	assert!(matches!(
+		database.migrate_to(&mut destination(), super::MigrationDestination::Empty),
+		Err(DataSourceError::DestinationNotEmpty { .. })
+	));
+	assert!(migrated
+		.get(&ItemPath::from("alice/notes/old/b.txt"), &[], &[])
+		.is_ok());
+	assert_ne!(
+		migrated.get(&ItemPath::from("alice/notes/a.txt"), &[], &[]),
+		database.get(&ItemPath::from("alice/notes/a.txt"), &[], &[])
+	);
+
+	assert_eq!(
+		database.migrate_to(&mut destination(), super::MigrationDestination::DeleteExtra),
+		Ok(super::Migration {
+			copied: 1,
+			unchanged: 1,
+			removed: 1,
+		})
+	);
+	assert_eq!(
+		migrated.get(&ItemPath::from("alice/notes/a.txt"), &[], &[]),
+		database.get(&ItemPath::from("alice/notes/a.txt"), &[], &[])
+	);
+	assert!(migrated
+		.get(&ItemPath::from("alice/notes/old/b.txt"), &[], &[])
+		.is_err());
+}
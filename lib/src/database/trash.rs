@@ -0,0 +1,251 @@
+/// A document deleted while [`Database`][`crate::database::Database`] had a [`Trash`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TrashedItem {
+	/// Identifies it in the [`Trash`], to restore it.
+	pub id: String,
+	/// Where it was before its deletion, like `user/notes/a.txt`.
+	pub path: String,
+	/// Its ETag when it was deleted.
+	pub etag: crate::item::Etag,
+	pub deleted_at: time::OffsetDateTime,
+	/// The deleted document.
+	///
+	/// When the [`Trash`] saves it in a file, its content is only there, see [`Trash::load`].
+	pub item: crate::item::Item,
+}
+impl TrashedItem {
+	/// The user which owned it, which is the first folder of its path (or the second, in `public/`).
+	pub fn owner(&self) -> Option<&str> {
+		let parts: Vec<&str> = self.path.split('/').collect();
+
+		// the last part is the name of the document
+		return match &parts[..parts.len() - 1] {
+			["public", username, ..] => Some(*username),
+			[username, ..] if *username != "public" => Some(*username),
+			_ => None,
+		}
+		.filter(|username| !username.is_empty());
+	}
+}
+
+/// Keeps documents deleted through [`Database`][`crate::database::Database`], so their owners can restore them.
+///
+/// They are purged once they are older than its retention.
+///
+/// When it saves them in a folder, only their metadata is kept in memory, and their content is read
+/// from their file when they are restored.
+#[derive(Debug)]
+pub struct Trash {
+	retention: time::Duration,
+	folder_path: Option<std::path::PathBuf>,
+	items: Vec<TrashedItem>,
+}
+impl Trash {
+	/// Creates a trash which only keeps deleted documents in memory.
+	pub fn new(retention: time::Duration) -> Self {
+		return Self {
+			retention,
+			folder_path: None,
+			items: vec![],
+		};
+	}
+
+	/// Creates a trash which saves each deleted document in a file of `folder_path`,
+	/// and loads the metadata of the ones which are already there.
+	pub fn in_folder(
+		folder_path: &std::path::Path,
+		retention: time::Duration,
+	) -> Result<Self, String> {
+		std::fs::create_dir_all(folder_path).map_err(|error| {
+			format!(
+				"can not create `{}` : {}",
+				folder_path.to_string_lossy(),
+				error
+			)
+		})?;
+
+		let entries = std::fs::read_dir(folder_path).map_err(|error| {
+			format!(
+				"can not read `{}` : {}",
+				folder_path.to_string_lossy(),
+				error
+			)
+		})?;
+
+		let mut items = vec![];
+		for entry in entries.flatten() {
+			if entry.path().extension() == Some(std::ffi::OsStr::new("bin")) {
+				let bytes = std::fs::read(entry.path()).map_err(|error| {
+					format!(
+						"can not read `{}` : {}",
+						entry.path().to_string_lossy(),
+						error
+					)
+				})?;
+
+				let trashed = bincode::deserialize::<TrashedItem>(&bytes).map_err(|error| {
+					format!(
+						"can not parse `{}` : {}",
+						entry.path().to_string_lossy(),
+						error
+					)
+				})?;
+
+				items.push(TrashedItem {
+					item: trashed.item.empty_clone(),
+					..trashed
+				});
+			}
+		}
+		items.sort_by_key(|item| item.deleted_at);
+
+		return Ok(Self {
+			retention,
+			folder_path: Some(folder_path.to_path_buf()),
+			items,
+		});
+	}
+
+	pub fn retention(&self) -> time::Duration {
+		return self.retention;
+	}
+
+	/// The items owned by `username`, without their content, the most recently deleted first.
+	pub fn list(&self, username: &str) -> Vec<TrashedItem> {
+		return self
+			.items
+			.iter()
+			.rev()
+			.filter(|trashed| trashed.owner() == Some(username))
+			.map(|trashed| TrashedItem {
+				item: trashed.item.empty_clone(),
+				..trashed.clone()
+			})
+			.collect();
+	}
+
+	/// The item with `id`, without its content if it is saved in a file.
+	pub fn get(&self, id: &str) -> Option<&TrashedItem> {
+		return self.items.iter().find(|trashed| trashed.id == id);
+	}
+
+	/// The item with `id`, with its content, which is read from its file if it is saved in one.
+	pub fn load(&self, id: &str) -> Result<Option<TrashedItem>, String> {
+		let trashed = match self.get(id) {
+			Some(trashed) => trashed,
+			None => return Ok(None),
+		};
+
+		return match self.file_path(id) {
+			Some(file_path) => {
+				let bytes = std::fs::read(&file_path).map_err(|error| {
+					format!("can not read `{}` : {}", file_path.to_string_lossy(), error)
+				})?;
+
+				bincode::deserialize::<TrashedItem>(&bytes)
+					.map(Some)
+					.map_err(|error| {
+						format!(
+							"can not parse `{}` : {}",
+							file_path.to_string_lossy(),
+							error
+						)
+					})
+			}
+			None => Ok(Some(trashed.clone())),
+		};
+	}
+
+	/// Adds `item`, which was at `path`, and gives its id.
+	pub fn push(
+		&mut self,
+		path: &crate::item::ItemPath,
+		item: crate::item::Item,
+	) -> Result<String, String> {
+		self.purge(time::OffsetDateTime::now_utc())?;
+
+		let trashed = TrashedItem {
+			id: ulid::Ulid::new().to_string(),
+			path: format!("{}", path),
+			etag: item.get_etag().clone(),
+			deleted_at: time::OffsetDateTime::now_utc(),
+			item,
+		};
+
+		if let Some(file_path) = self.file_path(&trashed.id) {
+			let bytes = bincode::serialize(&trashed).map_err(|error| format!("{}", error))?;
+			std::fs::write(&file_path, bytes).map_err(|error| {
+				format!(
+					"can not write `{}` : {}",
+					file_path.to_string_lossy(),
+					error
+				)
+			})?;
+		}
+
+		let id = trashed.id.clone();
+		if self.folder_path.is_some() {
+			// its content is in its file
+			self.items.push(TrashedItem {
+				item: trashed.item.empty_clone(),
+				..trashed
+			});
+		} else {
+			self.items.push(trashed);
+		}
+
+		return Ok(id);
+	}
+
+	/// Removes the item with `id` and gives it (without its content if it was saved in a file), if it exists.
+	pub fn remove(&mut self, id: &str) -> Result<Option<TrashedItem>, String> {
+		let position = match self.items.iter().position(|trashed| trashed.id == id) {
+			Some(position) => position,
+			None => return Ok(None),
+		};
+
+		if let Some(file_path) = self.file_path(id) {
+			if file_path.exists() {
+				std::fs::remove_file(&file_path).map_err(|error| {
+					format!(
+						"can not delete `{}` : {}",
+						file_path.to_string_lossy(),
+						error
+					)
+				})?;
+			}
+		}
+
+		return Ok(Some(self.items.remove(position)));
+	}
+
+	/// Removes the items which were deleted more than its retention before `now`, and gives how many they were.
+	///
+	/// Items which would expire after the last representable date are never purged.
+	pub fn purge(&mut self, now: time::OffsetDateTime) -> Result<usize, String> {
+		let expired: Vec<String> = self
+			.items
+			.iter()
+			.filter(|trashed| {
+				trashed
+					.deleted_at
+					.checked_add(self.retention)
+					.is_some_and(|expiration| expiration < now)
+			})
+			.map(|trashed| trashed.id.clone())
+			.collect();
+
+		for id in &expired {
+			self.remove(id)?;
+		}
+
+		return Ok(expired.len());
+	}
+
+	fn file_path(&self, id: &str) -> Option<std::path::PathBuf> {
+		return self
+			.folder_path
+			.as_ref()
+			.map(|folder_path| folder_path.join(format!("{}.bin", id)));
+	}
+}
@@ -0,0 +1,234 @@
+use std::sync::{Arc, Mutex};
+
+#[derive(serde::Deserialize)]
+pub struct ArchiveQuery {
+	/// `preserve` (by default) or `regenerate`, see [`ImportEtags`][`crate::database::ImportEtags`].
+	etags: Option<String>,
+}
+
+/// Exports all documents of `username` as a tar archive, see
+/// [`Database::export_user`][`crate::database::Database::export_user`].
+///
+/// The token should be able to read all modules of `username`.
+#[actix_web::get("/archive/{username}")]
+pub async fn get_archive(
+	username: actix_web::web::Path<String>,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	settings: actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>,
+	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	let username = username.into_inner();
+
+	if let Err(response) = check_token(
+		&request,
+		&username,
+		&actix_web::http::Method::GET,
+		&settings,
+		&access_tokens,
+	) {
+		return *response;
+	}
+
+	let exported = {
+		let database = database.get_ref().clone();
+		let username = username.clone();
+
+		actix_web::web::block(move || {
+			let mut archive = vec![];
+			database
+				.export_user(&username, &mut archive)
+				.map(|_| archive)
+		})
+		.await
+		.unwrap_or_else(|error| {
+			Err(crate::database::DataSourceError::InternalError {
+				error: format!("{}", error),
+			})
+		})
+	};
+
+	match exported {
+		Ok(archive) => {
+			return actix_web::HttpResponse::Ok()
+				.content_type("application/x-tar")
+				.insert_header((
+					actix_web::http::header::CONTENT_DISPOSITION,
+					format!("attachment; filename=\"{}.tar\"", username),
+				))
+				.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"))
+				.body(archive);
+		}
+		Err(error) => return error_response(&request, error, &logger),
+	}
+}
+
+/// Imports an archive made by [`get_archive`] in the storage of `username`, in place of existing documents.
+///
+/// The token should be able to write in all modules of `username`.
+#[actix_web::put("/archive/{username}")]
+// actix extracts each argument of the handler, like in the other ones
+#[allow(clippy::too_many_arguments)]
+pub async fn put_archive(
+	mut request_payload: actix_web::web::Payload,
+	username: actix_web::web::Path<String>,
+	query: actix_web::web::Query<ArchiveQuery>,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	settings: actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>,
+	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	let username = username.into_inner();
+
+	if let Err(response) = check_token(
+		&request,
+		&username,
+		&actix_web::http::Method::PUT,
+		&settings,
+		&access_tokens,
+	) {
+		return *response;
+	}
+
+	let etags = match query.etags.as_deref() {
+		None | Some("preserve") => crate::database::ImportEtags::Preserve,
+		Some("regenerate") => crate::database::ImportEtags::Regenerate,
+		Some(_) => {
+			return crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::BAD_REQUEST,
+				None,
+				None,
+				Some(String::from("etags should be `preserve` or `regenerate`")),
+				true,
+			);
+		}
+	};
+
+	let max_bytes = settings
+		.lock()
+		.unwrap()
+		.archive_max_bytes
+		.unwrap_or_else(|| {
+			crate::http_server::Settings::new(std::path::PathBuf::from("."))
+				.archive_max_bytes
+				.unwrap()
+		});
+	let too_large = || {
+		crate::database::build_http_json_response(
+			request.method(),
+			actix_web::http::StatusCode::PAYLOAD_TOO_LARGE,
+			None,
+			None,
+			Some(format!(
+				"archives should not be bigger than {} bytes",
+				max_bytes
+			)),
+			true,
+		)
+	};
+
+	// refused before reading it, when its size is announced
+	if request
+		.headers()
+		.get(actix_web::http::header::CONTENT_LENGTH)
+		.and_then(|length| length.to_str().ok())
+		.and_then(|length| length.parse::<u64>().ok())
+		.is_some_and(|length| length > max_bytes)
+	{
+		return too_large();
+	}
+
+	let mut content = actix_web::web::BytesMut::new();
+	while let Some(request_body) = futures::StreamExt::next(&mut request_payload).await {
+		let request_body = request_body.unwrap();
+		if (content.len() + request_body.len()) as u64 > max_bytes {
+			return too_large();
+		}
+		content.extend_from_slice(&request_body);
+	}
+
+	let imported = {
+		let database = database.get_ref().clone();
+
+		actix_web::web::block(move || database.import_user(&username, &content[..], etags))
+			.await
+			.unwrap_or_else(|error| {
+				Err(crate::database::DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+	};
+
+	match imported {
+		Ok(count) => {
+			return actix_web::HttpResponse::Ok()
+				.content_type("application/json")
+				.body(serde_json::json!({ "imported": count }).to_string());
+		}
+		Err(error) => return error_response(&request, error, &logger),
+	}
+}
+
+/// Checks that the token of `request` belongs to `username` and allows `method` on all its modules.
+fn check_token(
+	request: &actix_web::HttpRequest,
+	username: &str,
+	method: &actix_web::http::Method,
+	settings: &Mutex<crate::http_server::Settings>,
+	access_tokens: &Mutex<Vec<crate::http_server::AccessBearer>>,
+) -> Result<(), Box<actix_web::HttpResponse>> {
+	let max_token_lifetime_seconds = super::max_token_lifetime_seconds(&settings.lock().unwrap());
+
+	let token = super::token_of_user(request, username, access_tokens, max_token_lifetime_seconds)?;
+
+	// only the `*` module allows the root folder of the user
+	if token
+		.is_allowed(
+			max_token_lifetime_seconds,
+			method,
+			format!("/storage/{}/", username),
+		)
+		.unwrap_or(false)
+	{
+		return Ok(());
+	} else {
+		return Err(Box::new(crate::database::build_http_json_response(
+			request.method(),
+			actix_web::http::StatusCode::FORBIDDEN,
+			None,
+			None,
+			None,
+			true,
+		)));
+	}
+}
+
+fn error_response(
+	request: &actix_web::HttpRequest,
+	error: crate::database::DataSourceError,
+	logger: &Mutex<charlie_buffalo::Logger>,
+) -> actix_web::HttpResponse {
+	if let crate::database::DataSourceError::InternalError { .. } = error {
+		logger.lock().unwrap().push(
+			vec![
+				(String::from("level"), String::from("ERROR")),
+				(String::from("module"), String::from("https?")),
+				(String::from("method"), request.method().to_string()),
+				(String::from("path"), String::from(request.path())),
+			],
+			Some(&format!("error from database : {error}")),
+		);
+	}
+
+	return error.to_response(request.method(), true);
+}
+
+#[cfg(test)]
+mod tests;
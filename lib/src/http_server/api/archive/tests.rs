@@ -0,0 +1,185 @@
+#[actix_rt::test]
+async fn z3hq8vm2xr6ktn9wcb() {
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+			root_item: crate::item::Item::new_folder(vec![
+				(
+					"user",
+					crate::item::Item::new_folder(vec![(
+						"notes",
+						crate::item::Item::new_folder(vec![(
+							"a.txt",
+							crate::item::Item::new_doc(b"A", "text/plain"),
+						)]),
+					)]),
+				),
+				(
+					"public",
+					crate::item::Item::new_folder(vec![(
+						"user",
+						crate::item::Item::new_folder(vec![(
+							"shared",
+							crate::item::Item::new_folder(vec![(
+								"b.png",
+								crate::item::Item::new_doc(b"B", "image/png"),
+							)]),
+						)]),
+					)]),
+				),
+			]),
+		}));
+
+	let logger = charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(logger));
+
+	let token = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("*"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		}],
+		"test",
+		"user",
+	);
+	let module_token = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("notes"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		}],
+		"test",
+		"user",
+	);
+	let access_tokens = std::sync::Arc::new(std::sync::Mutex::new(vec![
+		token.clone(),
+		module_token.clone(),
+	]));
+
+	let mut settings = crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+	settings.query_access_token = Some(true);
+	let settings = std::sync::Arc::new(std::sync::Mutex::new(settings));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(database.clone()))
+			.app_data(actix_web::web::Data::new(settings.clone()))
+			.app_data(actix_web::web::Data::new(logger))
+			.app_data(actix_web::web::Data::new(access_tokens))
+			.service(super::get_archive)
+			.service(super::put_archive),
+	)
+	.await;
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/archive/user")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", module_token.get_name()),
+		))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/archive/user")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", token.get_name()),
+		))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+	assert_eq!(
+		response
+			.headers()
+			.get(actix_web::http::header::CONTENT_TYPE)
+			.unwrap(),
+		"application/x-tar"
+	);
+	let archive = actix_web::test::read_body(response).await;
+
+	// the token can also be given in the query, like the `Auth` middleware allows
+	let request = actix_web::test::TestRequest::get()
+		.uri(&format!(
+			"/archive/user?access_token={}",
+			pct_str::PctString::encode(token.get_name().chars(), pct_str::URIReserved)
+		))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+	assert_eq!(actix_web::test::read_body(response).await, archive);
+
+	database
+		.delete(&crate::item::ItemPath::from("user/notes/a.txt"), &[])
+		.unwrap();
+	database
+		.delete(
+			&crate::item::ItemPath::from("public/user/shared/b.png"),
+			&[],
+		)
+		.unwrap();
+
+	let request = actix_web::test::TestRequest::put()
+		.uri("/archive/user?etags=wrong")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", token.get_name()),
+		))
+		.set_payload(archive.clone())
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+	settings.lock().unwrap().archive_max_bytes = Some(archive.len() as u64 - 1);
+	let request = actix_web::test::TestRequest::put()
+		.uri("/archive/user")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", token.get_name()),
+		))
+		.set_payload(archive.clone())
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(
+		response.status(),
+		actix_web::http::StatusCode::PAYLOAD_TOO_LARGE
+	);
+	settings.lock().unwrap().archive_max_bytes = None;
+
+	let request = actix_web::test::TestRequest::put()
+		.uri("/archive/user?etags=regenerate")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", token.get_name()),
+		))
+		.set_payload(archive)
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+
+	let response: serde_json::Value =
+		serde_json::from_slice(&actix_web::test::read_body(response).await).unwrap();
+	assert_eq!(response["imported"], 2);
+
+	match database
+		.get(
+			&crate::item::ItemPath::from("public/user/shared/b.png"),
+			&[],
+			&[],
+		)
+		.unwrap()
+	{
+		crate::item::Item::Document {
+			content,
+			content_type,
+			..
+		} => {
+			assert_eq!(content, Some(b"B".to_vec()));
+			assert_eq!(content_type, "image/png");
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+}
@@ -0,0 +1,333 @@
+use std::sync::{Arc, Mutex};
+
+#[derive(serde::Deserialize)]
+pub struct BatchRequest {
+	operations: Vec<BatchRequestOperation>,
+}
+
+/// One operation of a [`BatchRequest`], which looks like its own HTTP request.
+#[derive(serde::Deserialize)]
+pub struct BatchRequestOperation {
+	/// `PUT` or `DELETE`.
+	method: String,
+	/// Like `/storage/user/folder/document`, or `/storage/user/folder/` to delete a folder with all its content.
+	path: String,
+	content_type: Option<String>,
+	/// The content of the document as text, for `PUT`.
+	content: Option<String>,
+	/// The content of the document encoded in base64, for `PUT` of binary documents.
+	content_base64: Option<String>,
+	/// Like the `If-Match` HTTP header.
+	if_match: Option<String>,
+	/// Like the `If-None-Match` HTTP header.
+	if_none_match: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct BatchResponseResult {
+	path: String,
+	http_code: u16,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	http_description: Option<&'static str>,
+	#[serde(rename = "ETag", skip_serializing_if = "Option::is_none")]
+	etag: Option<crate::item::Etag>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	hint: Option<String>,
+}
+
+/// Applies many `PUT` and `DELETE` operations in one request, in their order.
+///
+/// Each operation has its own conditions and is checked against the scopes of the token,
+/// and a failed one does not prevent the next ones. ETags of the folders touched by the
+/// operations are updated only once, see [`Database::batch`][`crate::database::Database::batch`].
+///
+/// The response gives the result of each operation, in the same order.
+#[actix_web::post("/batch")]
+pub async fn post_batch(
+	mut request_payload: actix_web::web::Payload,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	settings: actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>,
+	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
+	dbevent_sender: actix_web::web::Data<std::sync::mpsc::Sender<crate::http_server::DbEvent>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	let mut content = actix_web::web::BytesMut::new();
+	while let Some(request_body) = futures::StreamExt::next(&mut request_payload).await {
+		let request_body = request_body.unwrap();
+		content.extend_from_slice(&request_body);
+	}
+
+	let batch: BatchRequest = match serde_json::from_slice(&content) {
+		Ok(batch) => batch,
+		Err(error) => {
+			return crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::BAD_REQUEST,
+				None,
+				None,
+				Some(format!("the body is not a correct batch : {}", error)),
+				true,
+			);
+		}
+	};
+
+	let token = super::token_of(&request, &access_tokens);
+	let user = match &token {
+		Some(bearer) => String::from(bearer.get_username()),
+		None => String::from("Unknown"),
+	};
+	let max_token_lifetime_seconds = super::max_token_lifetime_seconds(&settings.lock().unwrap());
+
+	let mut results: Vec<Option<BatchResponseResult>> = vec![];
+	let mut operations = vec![];
+	for requested in &batch.operations {
+		match operation_of(requested) {
+			Ok((method, operation)) => {
+				let is_allowed = match &token {
+					Some(token) => token
+						.is_allowed(max_token_lifetime_seconds, &method, &requested.path)
+						.unwrap_or(false),
+					None => false,
+				};
+
+				if is_allowed {
+					operations.push(operation);
+					results.push(None);
+				} else {
+					results.push(Some(result_of(
+						requested,
+						actix_web::http::StatusCode::FORBIDDEN,
+						None,
+						None,
+					)));
+				}
+			}
+			Err(hint) => {
+				results.push(Some(result_of(
+					requested,
+					actix_web::http::StatusCode::BAD_REQUEST,
+					None,
+					Some(hint),
+				)));
+			}
+		}
+	}
+
+	let paths: Vec<crate::item::ItemPath> = operations
+		.iter()
+		.map(|operation| operation.path().clone())
+		.collect();
+
+	let applied =
+		match crate::database::AsyncDataSource::batch(database.get_ref(), operations).await {
+			Ok(applied) => applied,
+			Err(error) => {
+				return error.to_response(request.method(), true);
+			}
+		};
+
+	let mut applied = paths.into_iter().zip(applied);
+	for (requested, result) in batch.operations.iter().zip(results.iter_mut()) {
+		if result.is_none() {
+			if let Some((path, applied)) = applied.next() {
+				*result = Some(applied_result(
+					requested,
+					&path,
+					applied,
+					&logger,
+					&dbevent_sender,
+					&user,
+				));
+			}
+		}
+	}
+
+	return actix_web::HttpResponse::Ok()
+		.content_type("application/json")
+		.body(
+			serde_json::json!({
+				"results": results.into_iter().flatten().collect::<Vec<BatchResponseResult>>(),
+			})
+			.to_string(),
+		);
+}
+
+/// Checks `requested`, and returns the method needed by this operation with it.
+fn operation_of(
+	requested: &BatchRequestOperation,
+) -> Result<(actix_web::http::Method, crate::database::BatchOperation), String> {
+	let path = match requested.path.strip_prefix("/storage/") {
+		Some(path)
+			if !path.is_empty()
+				&& !path
+					.split('/')
+					.any(|segment| segment == "." || segment == "..") =>
+		{
+			crate::item::ItemPath::from(path)
+		}
+		_ => {
+			return Err(String::from(
+				"the path should be an item in `/storage/`, like `/storage/user/folder/document`",
+			));
+		}
+	};
+
+	let if_match = etags_of(&requested.if_match);
+
+	match requested.method.to_uppercase().as_str() {
+		"PUT" => {
+			if path.is_folder() {
+				return Err(String::from("only documents can be put"));
+			}
+
+			let content_type = match &requested.content_type {
+				Some(content_type) => crate::item::ContentType::from(content_type.as_str()),
+				None => {
+					return Err(String::from("missing content_type"));
+				}
+			};
+
+			let content = match (&requested.content, &requested.content_base64) {
+				(Some(content), None) => content.as_bytes().to_vec(),
+				(None, Some(content)) => match base64::decode(content) {
+					Ok(content) => content,
+					Err(error) => {
+						return Err(format!("content_base64 is not correct base64 : {}", error));
+					}
+				},
+				_ => {
+					return Err(String::from(
+						"exactly one of content and content_base64 is needed",
+					));
+				}
+			};
+
+			return Ok((
+				actix_web::http::Method::PUT,
+				crate::database::BatchOperation::Put {
+					path,
+					if_match,
+					if_none_match: etags_of(&requested.if_none_match),
+					item: crate::item::Item::Document {
+						etag: crate::item::Etag::from(""),
+						content_length: content.len() as u64,
+						content: Some(content),
+						content_type,
+						last_modified: Some(time::OffsetDateTime::now_utc()),
+					},
+				},
+			));
+		}
+		"DELETE" => {
+			return Ok((
+				actix_web::http::Method::DELETE,
+				crate::database::BatchOperation::Delete { path, if_match },
+			));
+		}
+		_ => {
+			return Err(String::from("the method should be PUT or DELETE"));
+		}
+	}
+}
+
+/// The ETags of a value like the ones of the `If-Match` and `If-None-Match` HTTP headers.
+fn etags_of(value: &Option<String>) -> Vec<crate::item::Etag> {
+	return value
+		.as_deref()
+		.unwrap_or_default()
+		.split(',')
+		.map(|etag| etag.trim())
+		.filter(|etag| !etag.is_empty())
+		.map(|etag| match etag.strip_prefix("W/") {
+			Some(weak) => crate::item::Etag::from(format!("W/{}", weak.trim_matches('"'))),
+			None => crate::item::Etag::from(etag.trim_matches('"')),
+		})
+		.collect();
+}
+
+fn result_of(
+	requested: &BatchRequestOperation,
+	code: actix_web::http::StatusCode,
+	etag: Option<crate::item::Etag>,
+	hint: Option<String>,
+) -> BatchResponseResult {
+	return BatchResponseResult {
+		path: requested.path.clone(),
+		http_code: code.as_u16(),
+		http_description: code.canonical_reason(),
+		etag,
+		hint,
+	};
+}
+
+fn applied_result(
+	requested: &BatchRequestOperation,
+	path: &crate::item::ItemPath,
+	applied: crate::database::BatchResult,
+	logger: &Arc<Mutex<charlie_buffalo::Logger>>,
+	dbevent_sender: &std::sync::mpsc::Sender<crate::http_server::DbEvent>,
+	user: &str,
+) -> BatchResponseResult {
+	let (method, date, etag, code) = match applied {
+		crate::database::BatchResult::Put(crate::database::PutResult::Created(
+			etag,
+			last_modified,
+		)) => (
+			crate::http_server::DbEventMethod::Create,
+			last_modified,
+			etag,
+			actix_web::http::StatusCode::CREATED,
+		),
+		crate::database::BatchResult::Put(crate::database::PutResult::Updated(
+			etag,
+			last_modified,
+		)) => (
+			crate::http_server::DbEventMethod::Update,
+			last_modified,
+			etag,
+			actix_web::http::StatusCode::OK,
+		),
+		crate::database::BatchResult::Delete(Ok(etag)) => (
+			crate::http_server::DbEventMethod::Delete,
+			time::OffsetDateTime::now_utc(),
+			etag,
+			actix_web::http::StatusCode::OK,
+		),
+		crate::database::BatchResult::Put(crate::database::PutResult::Err(error))
+		| crate::database::BatchResult::Delete(Err(error)) => {
+			if let crate::database::DataSourceError::InternalError { .. } = error {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("level"), String::from("ERROR")),
+						(String::from("module"), String::from("https?")),
+						(String::from("method"), requested.method.to_uppercase()),
+						(String::from("path"), path.to_string()),
+					],
+					Some(&format!("error from database : {error}")),
+				);
+			}
+
+			return result_of(requested, error.status_code(), None, error.hint());
+		}
+	};
+
+	dbevent_sender
+		.send(crate::http_server::DbEvent {
+			id: ulid::Ulid::new().to_string(),
+			method,
+			date,
+			path: String::from("/storage/") + &path.to_string(),
+			etag: etag.clone(),
+			user: String::from(user),
+			dbversion: String::from(env!("CARGO_PKG_VERSION")),
+		})
+		.ok();
+
+	return result_of(requested, code, Some(etag), None);
+}
+
+#[cfg(test)]
+mod tests;
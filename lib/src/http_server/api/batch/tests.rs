@@ -0,0 +1,210 @@
+#[actix_rt::test]
+async fn v5tq8mz2kc7xhw4rnd() {
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+			root_item: crate::item::Item::new_folder(vec![(
+				"user",
+				crate::item::Item::new_folder(vec![
+					(
+						"notes",
+						crate::item::Item::new_folder(vec![
+							("a.txt", crate::item::Item::new_doc(b"A", "text/plain")),
+							(
+								"old",
+								crate::item::Item::new_folder(vec![
+									("b.txt", crate::item::Item::new_doc(b"B", "text/plain")),
+									(
+										"c",
+										crate::item::Item::new_folder(vec![(
+											"d.txt",
+											crate::item::Item::new_doc(b"D", "text/plain"),
+										)]),
+									),
+								]),
+							),
+						]),
+					),
+					(
+						"pictures",
+						crate::item::Item::new_folder(vec![(
+							"e.png",
+							crate::item::Item::new_doc(b"E", "image/png"),
+						)]),
+					),
+				]),
+			)]),
+		}));
+
+	let logger = charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(logger));
+
+	let token = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("notes"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		}],
+		"test",
+		"user",
+	);
+	let access_tokens = std::sync::Arc::new(std::sync::Mutex::new(vec![token.clone()]));
+
+	let settings = std::sync::Arc::new(std::sync::Mutex::new(crate::http_server::Settings::new(
+		tempfile::tempdir().unwrap().into_path(),
+	)));
+
+	let (events, events_receiver) = std::sync::mpsc::channel::<crate::http_server::DbEvent>();
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(database.clone()))
+			.app_data(actix_web::web::Data::new(settings))
+			.app_data(actix_web::web::Data::new(logger))
+			.app_data(actix_web::web::Data::new(access_tokens))
+			.app_data(actix_web::web::Data::new(events))
+			.service(super::post_batch),
+	)
+	.await;
+
+	let a_etag: String = database
+		.get(&crate::item::ItemPath::from("user/notes/a.txt"), &[], &[])
+		.unwrap()
+		.get_etag()
+		.clone()
+		.into();
+
+	let body = serde_json::json!({
+		"operations": [
+			{
+				"method": "PUT",
+				"path": "/storage/user/notes/new.txt",
+				"content_type": "text/plain",
+				"content": "NEW",
+			},
+			{
+				"method": "PUT",
+				"path": "/storage/user/notes/image.png",
+				"content_type": "image/png",
+				"content_base64": "iVBORw==",
+				"if_none_match": "*",
+			},
+			{
+				"method": "PUT",
+				"path": "/storage/user/notes/a.txt",
+				"content_type": "text/plain",
+				"content": "A2",
+				"if_match": "\"wrong\"",
+			},
+			{
+				"method": "PUT",
+				"path": "/storage/user/notes/a.txt",
+				"content_type": "text/plain",
+				"content": "A2",
+				"if_match": format!("\"{}\"", a_etag),
+			},
+			{
+				"method": "DELETE",
+				"path": "/storage/user/notes/old/",
+			},
+			{
+				"method": "DELETE",
+				"path": "/storage/user/pictures/",
+			},
+			{
+				"method": "DELETE",
+				"path": "/storage/user/notes/missing.txt",
+			},
+			{
+				"method": "GET",
+				"path": "/storage/user/notes/a.txt",
+			},
+			{
+				"method": "DELETE",
+				"path": "/storage/user/notes/../pictures/e.png",
+			},
+		],
+	});
+
+	let request = actix_web::test::TestRequest::post()
+		.uri("/batch")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", token.get_name()),
+		))
+		.set_payload(body.to_string())
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+
+	let response: serde_json::Value =
+		serde_json::from_slice(&actix_web::test::read_body(response).await).unwrap();
+	let results = response["results"].as_array().unwrap();
+
+	let codes: Vec<(&str, u64)> = results
+		.iter()
+		.map(|result| {
+			(
+				result["path"].as_str().unwrap(),
+				result["http_code"].as_u64().unwrap(),
+			)
+		})
+		.collect();
+	assert_eq!(
+		codes,
+		vec![
+			("/storage/user/notes/new.txt", 201),
+			("/storage/user/notes/image.png", 201),
+			("/storage/user/notes/a.txt", 412),
+			("/storage/user/notes/a.txt", 200),
+			("/storage/user/notes/old/", 200),
+			("/storage/user/pictures/", 403),
+			("/storage/user/notes/missing.txt", 404),
+			("/storage/user/notes/a.txt", 400),
+			("/storage/user/notes/../pictures/e.png", 400),
+		]
+	);
+	assert!(results[0]["ETag"].is_string());
+	assert!(results[2]["ETag"].is_null());
+
+	let get = |path: &str| database.get(&crate::item::ItemPath::from(path), &[], &[]);
+
+	match get("user/notes/image.png").unwrap() {
+		crate::item::Item::Document { content, .. } => {
+			assert_eq!(content, Some(b"\x89PNG".to_vec()))
+		}
+		item => panic!("unexpected item : {:?}", item),
+	}
+	match get("user/notes/a.txt").unwrap() {
+		crate::item::Item::Document { content, .. } => assert_eq!(content, Some(b"A2".to_vec())),
+		item => panic!("unexpected item : {:?}", item),
+	}
+	assert!(get("user/notes/old/").is_err());
+	assert!(get("user/notes/old/c/d.txt").is_err());
+	assert!(get("user/pictures/e.png").is_ok());
+
+	let events: Vec<String> = events_receiver.try_iter().map(|event| event.path).collect();
+	assert_eq!(
+		events,
+		vec![
+			String::from("/storage/user/notes/new.txt"),
+			String::from("/storage/user/notes/image.png"),
+			String::from("/storage/user/notes/a.txt"),
+			String::from("/storage/user/notes/old/"),
+		]
+	);
+
+	let request = actix_web::test::TestRequest::post()
+		.uri("/batch")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", token.get_name()),
+		))
+		.set_payload("not a batch")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,264 @@
+#[derive(serde::Deserialize)]
+pub struct ConnectQuery {
+	app: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ConnectForm {
+	app: String,
+	password: String,
+	token: String,
+}
+
+/// Storage-first bearer token issuance (section 11 of the draft) : the user starts from this server,
+/// and is redirected to the app with a `remotestorage` field in the URL fragment, like `#remotestorage=user@example.com`.
+///
+/// This page only asks for the address of the app (which can be prefilled by the `app` query parameter)
+/// and for the password of the user : the redirection is done by [`post_connect`], once the user confirmed.
+///
+/// The app then asks the user for confirmation, and connects as usual (via [`get_oauth`][`crate::http_server::get_oauth`]).
+#[actix_web::get("/connect/{username}")]
+pub async fn get_connect(
+	request: actix_web::HttpRequest,
+	path: actix_web::web::Path<String>,
+	query: actix_web::web::Query<ConnectQuery>,
+	users: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<crate::http_server::Users>>>,
+	settings: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<crate::http_server::Settings>>>,
+	form_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>>,
+	>,
+) -> impl actix_web::Responder {
+	let username = path.into_inner();
+
+	if !is_known_user(&request, &username, &users, &settings) {
+		return not_found();
+	}
+
+	return actix_web::HttpResponse::Ok()
+		.content_type("text/html")
+		.body(connect_page(
+			&username,
+			query.app.as_deref().unwrap_or_default(),
+			&new_form_token(&request, &form_tokens),
+			None,
+		));
+}
+
+/// Redirects to the app given in the form of [`get_connect`], if the password of the user is correct.
+#[actix_web::post("/connect/{username}")]
+pub async fn post_connect(
+	request: actix_web::HttpRequest,
+	path: actix_web::web::Path<String>,
+	form: actix_web::web::Form<ConnectForm>,
+	users: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<crate::http_server::Users>>>,
+	settings: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<crate::http_server::Settings>>>,
+	program_state: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<crate::http_server::ProgramState>>,
+	>,
+	form_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>>,
+	>,
+) -> impl actix_web::Responder {
+	let username = path.into_inner();
+	let request_host = String::from(request.connection_info().host());
+
+	if !is_known_user(&request, &username, &users, &settings) {
+		return not_found();
+	}
+
+	// each form can only be submitted once
+	let form_token = {
+		let mut form_tokens = form_tokens.lock().unwrap();
+		let position = form_tokens
+			.iter()
+			.position(|token| token.get_value() == form.token);
+
+		position.map(|position| form_tokens.remove(position))
+	};
+	if !matches!(form_token, Some(token) if !token.has_expirated()) {
+		return actix_web::HttpResponse::Forbidden()
+			.content_type("text/html")
+			.body(connect_page(
+				&username,
+				&form.app,
+				&new_form_token(&request, &form_tokens),
+				Some("This form has expired, please submit it again."),
+			));
+	}
+
+	let app = form.app.trim();
+	let app = app.split('#').next().unwrap_or(app);
+
+	if !(app.starts_with("https://") || app.starts_with("http://")) {
+		return actix_web::HttpResponse::BadRequest()
+			.content_type("text/html")
+			.body(connect_page(
+				&username,
+				&form.app,
+				&new_form_token(&request, &form_tokens),
+				Some("The address of the app should start with https:// or http://"),
+			));
+	}
+
+	// like the oauth form, slows down password guessing
+	std::thread::sleep(std::time::Duration::from_secs(
+		settings
+			.lock()
+			.unwrap()
+			.oauth_wait_seconds
+			.unwrap_or_else(|| {
+				crate::http_server::Settings::new(std::path::PathBuf::from("."))
+					.oauth_wait_seconds
+					.unwrap()
+			}),
+	));
+	if !users
+		.lock()
+		.unwrap()
+		.check(&username, &mut String::from(&form.password))
+	{
+		return actix_web::HttpResponse::Forbidden()
+			.content_type("text/html")
+			.body(connect_page(
+				&username,
+				&form.app,
+				&new_form_token(&request, &form_tokens),
+				Some("The password is not correct."),
+			));
+	}
+
+	let server_address = crate::http_server::build_server_address(
+		&settings.lock().unwrap().for_host(&request_host),
+		&program_state.lock().unwrap(),
+	);
+	let host = server_address
+		.split_once("://")
+		.map(|(_, rest)| rest)
+		.unwrap_or(&server_address)
+		.split('/')
+		.next()
+		.unwrap_or_default();
+
+	return actix_web::HttpResponse::Found()
+		.insert_header((
+			actix_web::http::header::LOCATION,
+			format!(
+				"{}#remotestorage={}",
+				app,
+				pct_str::PctString::encode(
+					format!("{}@{}", username, host).chars(),
+					pct_str::URIReserved
+				)
+			),
+		))
+		.finish();
+}
+
+fn is_known_user(
+	request: &actix_web::HttpRequest,
+	username: &str,
+	users: &std::sync::Mutex<crate::http_server::Users>,
+	settings: &std::sync::Mutex<crate::http_server::Settings>,
+) -> bool {
+	return users
+		.lock()
+		.unwrap()
+		.get_usernames()
+		.into_iter()
+		.any(|name| name == username)
+		&& settings
+			.lock()
+			.unwrap()
+			.has_user(request.connection_info().host(), username);
+}
+
+/// It does not repeat the requested username, which comes from anyone.
+fn not_found() -> actix_web::HttpResponse {
+	return actix_web::HttpResponse::NotFound()
+		.content_type("text/html")
+		.body(format!(
+			r#"<!DOCTYPE html>
+<html>
+	<head>
+		<meta charset="UTF-8">
+		<title>{} : connect an app</title>
+	</head>
+	<body>
+		<h1>Connect an app</h1>
+		<p class="error">This account does not exists.</p>
+	</body>
+</html>"#,
+			env!("CARGO_PKG_NAME"),
+		));
+}
+
+fn new_form_token(
+	request: &actix_web::HttpRequest,
+	form_tokens: &std::sync::Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>,
+) -> String {
+	let ip = request
+		.peer_addr()
+		.unwrap_or_else(|| std::net::SocketAddr::from(([0, 0, 0, 0], 0)));
+	let new_token = crate::http_server::middlewares::OauthFormToken::new(ip);
+
+	let mut form_tokens = form_tokens.lock().unwrap();
+	form_tokens.retain(|token| !token.should_be_cleaned(&ip));
+	form_tokens.push(new_token.clone());
+
+	return String::from(new_token.get_value());
+}
+
+fn connect_page(username: &str, app: &str, form_token: &str, error: Option<&str>) -> String {
+	return format!(
+		r#"<!DOCTYPE html>
+<html>
+	<head>
+		<meta charset="UTF-8">
+		<meta http-equiv="X-UA-Compatible" content="IE=edge">
+		<meta name="viewport" content="width=device-width, initial-scale=1.0">
+		<title>{} : connect an app</title>
+	</head>
+	<body>
+		<h1>Connect an app</h1>
+		<p>You are on your account management for this database.</p>{}
+		<form method="post" action="">
+			<input type="hidden" name="token" value="{}">
+
+			<p>Account : {}<br>
+				Address of the app : <input type="url" name="app" value="{}" placeholder="https://app.example.com/"><br>
+				Password : <input type="password" name="password" value="">
+			</p>
+
+			<p><i>You will be redirected on this app, which should ask you to connect to this account.</i></p>
+			<input type="submit">
+		</form>
+	</body>
+</html>"#,
+		env!("CARGO_PKG_NAME"),
+		match error {
+			Some(error) => format!("\n\t\t<p class=\"error\">{}</p>", error),
+			None => String::new(),
+		},
+		html_escape(form_token),
+		html_escape(username),
+		html_escape(app),
+	);
+}
+
+/// Escapes `text` to be written in HTML, as content or in quoted attributes.
+fn html_escape(text: &str) -> String {
+	return text
+		.chars()
+		.map(|character| match character {
+			'&' => String::from("&amp;"),
+			'<' => String::from("&lt;"),
+			'>' => String::from("&gt;"),
+			'"' => String::from("&quot;"),
+			'\'' => String::from("&#39;"),
+			character => String::from(character),
+		})
+		.collect();
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,219 @@
+use actix_web::http::StatusCode;
+
+fn form_token_of(body: &[u8]) -> String {
+	let body = String::from_utf8(body.to_vec()).unwrap();
+	let start = body.find(r#"name="token" value=""#).unwrap() + r#"name="token" value=""#.len();
+
+	// like a browser, reads the value without its HTML escaping
+	return body[start..start + body[start..].find('"').unwrap()]
+		.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&quot;", "\"")
+		.replace("&#39;", "'")
+		.replace("&amp;", "&");
+}
+
+#[actix_rt::test]
+async fn basics() {
+	let mut users = crate::http_server::Users::new();
+	users.insert("user", &mut String::from("password")).unwrap();
+	let users = std::sync::Arc::new(std::sync::Mutex::new(users));
+
+	let mut settings = crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+	settings.domain = Some(String::from("example.com"));
+	settings.port = 80;
+	settings.force_https = Some(false);
+	settings.oauth_wait_seconds = Some(0);
+	let settings = std::sync::Arc::new(std::sync::Mutex::new(settings));
+
+	let program_state =
+		std::sync::Arc::new(std::sync::Mutex::new(crate::http_server::ProgramState {
+			https_mode: false,
+			last_scrub: None,
+		}));
+
+	let form_tokens: std::sync::Arc<
+		std::sync::Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>,
+	> = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(users))
+			.app_data(actix_web::web::Data::new(settings))
+			.app_data(actix_web::web::Data::new(program_state))
+			.app_data(actix_web::web::Data::new(form_tokens))
+			.service(super::get_connect)
+			.service(super::post_connect),
+	)
+	.await;
+
+	let tests = vec![
+		("/connect/user", StatusCode::OK),
+		("/connect/not_existing", StatusCode::NOT_FOUND),
+		(
+			"/connect/not_existing?app=https%3A%2F%2Fapp.example.org%2F",
+			StatusCode::NOT_FOUND,
+		),
+		("/connect/user?app=javascript%3Aalert(1)", StatusCode::OK),
+		(
+			"/connect/user?app=https%3A%2F%2Fapp.example.org%2F",
+			StatusCode::OK,
+		),
+	];
+
+	for (uri, expected_status) in tests {
+		println!("GET {}", uri);
+
+		let request = actix_web::test::TestRequest::get()
+			.uri(uri)
+			.peer_addr(std::net::SocketAddr::from(([127, 0, 0, 1], 8080)))
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+
+		assert_eq!(response.status(), expected_status);
+		assert_eq!(
+			response.headers().get(actix_web::http::header::LOCATION),
+			None
+		);
+	}
+
+	let tests = vec![
+		(
+			"https://app.example.org/",
+			"password",
+			true,
+			StatusCode::FOUND,
+			Some("https://app.example.org/#remotestorage=user%40example.com"),
+		),
+		(
+			"https://app.example.org/page#access_token=abc",
+			"password",
+			true,
+			StatusCode::FOUND,
+			Some("https://app.example.org/page#remotestorage=user%40example.com"),
+		),
+		(
+			"https://app.example.org/",
+			"password",
+			false,
+			StatusCode::FORBIDDEN,
+			None,
+		),
+		(
+			"https://app.example.org/",
+			"wrong",
+			true,
+			StatusCode::FORBIDDEN,
+			None,
+		),
+		(
+			"javascript:alert(1)",
+			"password",
+			true,
+			StatusCode::BAD_REQUEST,
+			None,
+		),
+	];
+
+	for (app_address, password, valid_token, expected_status, expected_location) in tests {
+		println!(
+			"POST /connect/user {} {} {}",
+			app_address, password, valid_token
+		);
+
+		let request = actix_web::test::TestRequest::get()
+			.uri("/connect/user")
+			.peer_addr(std::net::SocketAddr::from(([127, 0, 0, 1], 8080)))
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+		let token = if valid_token {
+			form_token_of(&actix_web::test::read_body(response).await)
+		} else {
+			String::from("not_a_token")
+		};
+
+		let request = actix_web::test::TestRequest::post()
+			.uri("/connect/user")
+			.peer_addr(std::net::SocketAddr::from(([127, 0, 0, 1], 8080)))
+			.set_form([
+				("app", app_address),
+				("password", password),
+				("token", token.as_str()),
+			])
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+
+		assert_eq!(response.status(), expected_status);
+		assert_eq!(
+			response
+				.headers()
+				.get(actix_web::http::header::LOCATION)
+				.map(|value| value.to_str().unwrap()),
+			expected_location
+		);
+
+		if valid_token {
+			// the form can not be submitted again
+			let request = actix_web::test::TestRequest::post()
+				.uri("/connect/user")
+				.peer_addr(std::net::SocketAddr::from(([127, 0, 0, 1], 8080)))
+				.set_form([
+					("app", app_address),
+					("password", password),
+					("token", token.as_str()),
+				])
+				.to_request();
+			let response = actix_web::test::call_service(&app, request).await;
+
+			assert_eq!(response.status(), StatusCode::FORBIDDEN);
+		}
+	}
+}
+
+#[actix_rt::test]
+async fn m4tw8qzc1xkv6rn3pd() {
+	let mut users = crate::http_server::Users::new();
+	users
+		.insert("<b>user", &mut String::from("password"))
+		.unwrap();
+	let users = std::sync::Arc::new(std::sync::Mutex::new(users));
+
+	let mut settings = crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+	settings.domain = Some(String::from("example.com"));
+	let settings = std::sync::Arc::new(std::sync::Mutex::new(settings));
+
+	let form_tokens: std::sync::Arc<
+		std::sync::Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>,
+	> = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(users))
+			.app_data(actix_web::web::Data::new(settings))
+			.app_data(actix_web::web::Data::new(form_tokens))
+			.service(super::get_connect),
+	)
+	.await;
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/connect/%3Cb%3Euser?app=%22%3E%3Cscript%3E")
+		.peer_addr(std::net::SocketAddr::from(([127, 0, 0, 1], 8080)))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::OK);
+	let body = String::from_utf8(actix_web::test::read_body(response).await.to_vec()).unwrap();
+	assert!(body.contains("&lt;b&gt;user"));
+	assert!(body.contains("&quot;&gt;&lt;script&gt;"));
+	assert!(!body.contains("<b>"));
+	assert!(!body.contains("<script>"));
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/connect/%3Cscript%3Ealert(1)")
+		.peer_addr(std::net::SocketAddr::from(([127, 0, 0, 1], 8080)))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	let body = String::from_utf8(actix_web::test::read_body(response).await.to_vec()).unwrap();
+	assert!(!body.contains("script"));
+	assert!(!body.contains("alert"));
+}
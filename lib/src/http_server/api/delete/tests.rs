@@ -15,6 +15,7 @@ async fn basics() {
 							crate::item::Item::Document {
 								etag: crate::item::Etag::new(),
 								content: Some(b"HELLO".to_vec()),
+								content_length: b"HELLO".len() as u64,
 								content_type: crate::item::ContentType::from("text/plain"),
 								last_modified: Some(time::OffsetDateTime::now_utc()),
 							},
@@ -23,7 +24,6 @@ async fn basics() {
 				)]),
 			)]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
@@ -134,6 +134,7 @@ async fn if_match() {
 							crate::item::Item::Document {
 								etag: crate::item::Etag::from("A"),
 								content: Some(b"HELLO".to_vec()),
+								content_length: b"HELLO".len() as u64,
 								content_type: crate::item::ContentType::from("text/plain"),
 								last_modified: Some(time::OffsetDateTime::now_utc()),
 							},
@@ -142,7 +143,6 @@ async fn if_match() {
 				)]),
 			)]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
@@ -1,33 +1,66 @@
 use std::sync::{Arc, Mutex};
 
+/// Query parameters of paginated and filtered listings of folders, an extension advertised in webfinger.
+///
+/// Listings are ordered by the keys of their items, see [`ListingOptions`][`crate::database::ListingOptions`].
+#[derive(Debug, serde::Deserialize)]
+pub struct ListingQuery {
+	limit: Option<usize>,
+	/// The `next` field of the previous page.
+	after: Option<String>,
+	prefix: Option<String>,
+	/// In RFC 2822 format, like the `Last-Modified` fields of listings.
+	modified_since: Option<String>,
+	content_type: Option<String>,
+}
+impl ListingQuery {
+	fn is_paginated(&self) -> bool {
+		return self.limit.is_some()
+			|| self.after.is_some()
+			|| self.prefix.is_some()
+			|| self.modified_since.is_some()
+			|| self.content_type.is_some();
+	}
+}
+
 #[actix_web::get("/storage/{requested_item:.*}")]
 pub async fn get_item(
 	path: actix_web::web::Path<String>,
 	request: actix_web::HttpRequest,
-	database: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<crate::database::Database>>>,
+	database: actix_web::web::Data<crate::database::Database>,
 	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
 ) -> impl actix_web::Responder {
-	// TODO : check security issue about this ?
-	let all_origins = actix_web::http::header::HeaderValue::from_bytes(b"*").unwrap();
-	let origin = request
-		.headers()
-		.get(actix_web::http::header::ORIGIN)
-		.unwrap_or(&all_origins)
-		.to_str()
-		.unwrap();
-
 	let local_path = crate::item::ItemPath::from(path.into_inner().as_str());
 
-	// TODO : If-Match does not works with GET ?
-	match database.lock().unwrap().get(
-		&local_path,
-		super::convert_actix_if_match(&request)
-			.first()
-			.unwrap_or(&crate::item::Etag::from("")),
-		&super::convert_actix_if_none_match(&request)
-			.iter()
-			.collect::<Vec<&crate::item::Etag>>(),
-	) {
+	// only listings have options, documents ignore the query
+	if local_path.is_folder() {
+		match actix_web::web::Query::<ListingQuery>::from_query(request.query_string()) {
+			Ok(query) if query.is_paginated() => {
+				return get_listing(local_path, &request, &query, &database, &logger).await;
+			}
+			Ok(_) => {}
+			Err(error) => {
+				return crate::database::build_http_json_response(
+					request.method(),
+					actix_web::http::StatusCode::BAD_REQUEST,
+					None,
+					None,
+					Some(format!("{}", error)),
+					true,
+				);
+			}
+		}
+	}
+
+	match crate::database::AsyncDataSource::get(
+		database.get_ref(),
+		local_path.clone(),
+		super::convert_actix_if_match(&request),
+		super::convert_actix_if_none_match(&request),
+		true,
+	)
+	.await
+	{
 		Ok(crate::item::Item::Document {
 			etag,
 			content: Some(content),
@@ -49,16 +82,7 @@ pub async fn get_item(
 				));
 			}
 			response.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"));
-			response.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin));
-
-			if origin != "*" {
-				response.insert_header((actix_web::http::header::VARY, "Origin"));
-			}
 
-			response.insert_header((
-				actix_web::http::header::ACCESS_CONTROL_EXPOSE_HEADERS,
-				"Content-Length, Content-Type, Etag, Last-Modified",
-			));
 			response.content_type(content_type);
 
 			return response.body(content);
@@ -74,54 +98,11 @@ pub async fn get_item(
 					content: Some(content),
 					..
 				} => !content.is_empty(),
-				crate::item::Item::Folder { content: None, .. } => todo!(),
+				// emptiness is unknown without content
+				crate::item::Item::Folder { content: None, .. } => true,
 			}) {
-				match &**child {
-					crate::item::Item::Folder { etag, .. } => {
-						items_result[format!("{}/", child_name)] = serde_json::json!({
-							"ETag": etag,
-						});
-					}
-					crate::item::Item::Document {
-						etag,
-						content: Some(document_content),
-						content_type,
-						last_modified,
-					} => {
-						let child_name: String = child_name.into();
-						items_result[child_name] = serde_json::json!({
-							"ETag": etag,
-							"Content-Type": content_type,
-							"Content-Length": document_content.len(),
-							"Last-Modified": if let Some(last_modified) = last_modified {
-								serde_json::Value::from(last_modified.format(&time::format_description::well_known::Rfc2822).unwrap_or_default())
-							} else {
-								serde_json::Value::Null
-							},
-						});
-					}
-					crate::item::Item::Document { content: None, .. } => {
-						logger.lock().unwrap().push(
-							vec![
-								(String::from("level"), String::from("ERROR")),
-								(String::from("module"), String::from("https?")),
-								(String::from("method"), String::from("GET")),
-								(String::from("path"), local_path.to_string()),
-							],
-							Some("document has no content"),
-						);
-
-						return crate::database::build_http_json_response(
-							origin,
-							request.method(),
-							actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-							None,
-							None,
-							None,
-							true,
-						);
-					}
-				}
+				items_result[crate::database::listing_key(child_name, child.is_folder())] =
+					listing_item(child);
 			}
 
 			let folder_etag: String = folder_etag.into();
@@ -130,16 +111,6 @@ pub async fn get_item(
 			response.content_type("application/ld+json");
 			response.insert_header((actix_web::http::header::ETAG, folder_etag));
 			response.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"));
-			response.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin));
-
-			if origin != "*" {
-				response.insert_header((actix_web::http::header::VARY, "Origin"));
-			}
-
-			response.insert_header((
-				actix_web::http::header::ACCESS_CONTROL_EXPOSE_HEADERS,
-				"Content-Length, Content-Type, Etag, Last-Modified",
-			));
 
 			return response.body(
 				serde_json::json!({
@@ -161,7 +132,6 @@ pub async fn get_item(
 			);
 
 			return crate::database::build_http_json_response(
-				origin,
 				request.method(),
 				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
 				None,
@@ -182,7 +152,6 @@ pub async fn get_item(
 			);
 
 			return crate::database::build_http_json_response(
-				origin,
 				request.method(),
 				actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
 				None,
@@ -192,21 +161,7 @@ pub async fn get_item(
 			);
 		}
 		Err(e) => {
-			if e.is::<crate::database::sources::memory::GetError>() {
-				crate::database::Error::to_response(
-					&*e.downcast::<crate::database::sources::memory::GetError>()
-						.unwrap(),
-					origin,
-					true,
-				)
-			} else if e.is::<crate::database::sources::folder::GetError>() {
-				crate::database::Error::to_response(
-					&*e.downcast::<crate::database::sources::folder::GetError>()
-						.unwrap(),
-					origin,
-					true,
-				)
-			} else {
+			if let crate::database::DataSourceError::InternalError { .. } = e {
 				logger.lock().unwrap().push(
 					vec![
 						(String::from("level"), String::from("ERROR")),
@@ -216,17 +171,136 @@ pub async fn get_item(
 					],
 					Some(&format!("error from database : {e}")),
 				);
+			}
+
+			e.to_response(request.method(), true)
+		}
+	}
+}
 
-				crate::database::build_http_json_response(
-					origin,
+async fn get_listing(
+	local_path: crate::item::ItemPath,
+	request: &actix_web::HttpRequest,
+	query: &ListingQuery,
+	database: &crate::database::Database,
+	logger: &Arc<Mutex<charlie_buffalo::Logger>>,
+) -> actix_web::HttpResponse {
+	let modified_since = match &query.modified_since {
+		Some(modified_since) => match time::OffsetDateTime::parse(
+			modified_since.trim(),
+			&time::format_description::well_known::Rfc2822,
+		) {
+			Ok(modified_since) => Some(modified_since),
+			Err(_) => {
+				return crate::database::build_http_json_response(
 					request.method(),
-					actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-					None,
+					actix_web::http::StatusCode::BAD_REQUEST,
 					None,
 					None,
+					Some(String::from(
+						"`modified_since` should be a date in RFC 2822 format",
+					)),
 					true,
-				)
+				);
 			}
+		},
+		None => None,
+	};
+
+	if query.limit == Some(0) {
+		return crate::database::build_http_json_response(
+			request.method(),
+			actix_web::http::StatusCode::BAD_REQUEST,
+			None,
+			None,
+			Some(String::from("`limit` should be greater than 0")),
+			true,
+		);
+	}
+
+	let options = crate::database::ListingOptions {
+		prefix: query.prefix.clone(),
+		modified_since,
+		content_type: query.content_type.clone(),
+		after: query.after.clone(),
+		limit: query.limit,
+	};
+
+	match crate::database::AsyncDataSource::list(
+		database,
+		local_path.clone(),
+		super::convert_actix_if_match(request),
+		super::convert_actix_if_none_match(request),
+		options,
+	)
+	.await
+	{
+		Ok(listing) => {
+			let mut items_result = serde_json::json!({});
+			for (child_name, child) in &listing.items {
+				items_result[crate::database::listing_key(child_name, child.is_folder())] =
+					listing_item(child);
+			}
+
+			let folder_etag: String = listing.etag.into();
+
+			let mut response = actix_web::HttpResponse::Ok();
+			response.content_type("application/ld+json");
+			response.insert_header((actix_web::http::header::ETAG, folder_etag));
+			response.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"));
+
+			return response.body(
+				serde_json::json!({
+					"@context": "http://remotestorage.io/spec/folder-description",
+					"items": items_result,
+					"next": listing.next,
+				})
+				.to_string(),
+			);
+		}
+		Err(e) => {
+			if let crate::database::DataSourceError::InternalError { .. } = e {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("level"), String::from("ERROR")),
+						(String::from("module"), String::from("https?")),
+						(String::from("method"), String::from("GET")),
+						(String::from("path"), local_path.to_string()),
+					],
+					Some(&format!("error from database : {e}")),
+				);
+			}
+
+			return e.to_response(request.method(), true);
+		}
+	}
+}
+
+/// The description of `child` in the listing of its folder.
+fn listing_item(child: &crate::item::Item) -> serde_json::Value {
+	match child {
+		crate::item::Item::Folder { etag, .. } => {
+			return serde_json::json!({
+				"ETag": etag,
+			});
+		}
+		crate::item::Item::Document {
+			etag,
+			content_type,
+			content_length,
+			last_modified,
+			..
+		} => {
+			return serde_json::json!({
+				"ETag": etag,
+				"Content-Type": content_type,
+				"Content-Length": content_length,
+				"Last-Modified": if let Some(last_modified) = last_modified {
+					serde_json::Value::from(last_modified.format(&time::format_description::well_known::Rfc2822).unwrap_or_default())
+				} else {
+					serde_json::Value::Null
+				},
+			});
 		}
 	}
 }
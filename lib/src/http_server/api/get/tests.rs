@@ -16,6 +16,7 @@ async fn basics() {
 								crate::item::Item::Document {
 									etag: crate::item::Etag::new(),
 									content: Some(b"HELLO".to_vec()),
+									content_length: b"HELLO".len() as u64,
 									content_type: crate::item::ContentType::from("text/plain"),
 									last_modified: Some(time::OffsetDateTime::now_utc()),
 								},
@@ -36,6 +37,7 @@ async fn basics() {
 									crate::item::Item::Document {
 										etag: crate::item::Etag::new(),
 										content: Some(b"HELLO".to_vec()),
+										content_length: b"HELLO".len() as u64,
 										content_type: crate::item::ContentType::from("text/plain"),
 										last_modified: Some(time::OffsetDateTime::now_utc()),
 									},
@@ -46,7 +48,6 @@ async fn basics() {
 				),
 			]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
@@ -167,6 +168,7 @@ async fn if_none_match() {
 							crate::item::Item::Document {
 								etag: crate::item::Etag::from("A"),
 								content: Some(b"HELLO".to_vec()),
+								content_length: b"HELLO".len() as u64,
 								content_type: crate::item::ContentType::from("text/plain"),
 								last_modified: Some(time::OffsetDateTime::now_utc()),
 							},
@@ -175,7 +177,6 @@ async fn if_none_match() {
 				)]),
 			)]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
@@ -197,7 +198,7 @@ async fn if_none_match() {
 		(
 			010,
 			vec![EntityTag::new(false, "A".into())],
-			StatusCode::PRECONDITION_FAILED,
+			StatusCode::NOT_MODIFIED,
 		),
 		(
 			020,
@@ -205,12 +206,12 @@ async fn if_none_match() {
 				EntityTag::new(false, "A".into()),
 				EntityTag::new(false, "B".into()),
 			],
-			StatusCode::PRECONDITION_FAILED,
+			StatusCode::NOT_MODIFIED,
 		),
 		(
 			030,
 			vec![EntityTag::new(false, "*".into())],
-			StatusCode::PRECONDITION_FAILED,
+			StatusCode::NOT_MODIFIED,
 		),
 		(
 			040,
@@ -244,3 +245,118 @@ async fn if_none_match() {
 		println!("OK");
 	}
 }
+
+#[actix_rt::test]
+async fn r4nw7kx2cq9zhm5tvb() {
+	let photo = |content_type: &str, days_ago: i64| crate::item::Item::Document {
+		etag: crate::item::Etag::new(),
+		content: Some(b"PHOTO".to_vec()),
+		content_length: b"PHOTO".len() as u64,
+		content_type: crate::item::ContentType::from(content_type),
+		last_modified: Some(time::OffsetDateTime::now_utc() - time::Duration::days(days_ago)),
+	};
+
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+			root_item: crate::item::Item::new_folder(vec![(
+				"user",
+				crate::item::Item::new_folder(vec![(
+					"photos",
+					crate::item::Item::new_folder(vec![
+						("a.jpg", photo("image/jpeg", 10)),
+						("b.png", photo("image/png", 5)),
+						("c.jpg", photo("image/jpeg", 1)),
+						(
+							"d",
+							crate::item::Item::new_folder(vec![("da.jpg", photo("image/jpeg", 1))]),
+						),
+					]),
+				)]),
+			)]),
+		}));
+
+	let logger = charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(logger));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(database))
+			.app_data(actix_web::web::Data::new(logger))
+			.service(super::get_item),
+	)
+	.await;
+
+	let since = (time::OffsetDateTime::now_utc() - time::Duration::days(7))
+		.format(&time::format_description::well_known::Rfc2822)
+		.unwrap()
+		.replace(' ', "%20")
+		.replace('+', "%2B");
+	let modified_since = format!("modified_since={}", since);
+
+	let tests = vec![
+		("limit=2", vec!["a.jpg", "b.png"], Some("b.png")),
+		("limit=2&after=b.png", vec!["c.jpg", "d/"], None),
+		("prefix=c", vec!["c.jpg"], None),
+		(
+			"content_type=image%2Fjpeg&limit=1",
+			vec!["a.jpg"],
+			Some("a.jpg"),
+		),
+		(modified_since.as_str(), vec!["b.png", "c.jpg"], None),
+	];
+
+	for (query, expected_keys, expected_next) in tests {
+		println!("GET /storage/user/photos/?{}", query);
+
+		let request = actix_web::test::TestRequest::get()
+			.uri(&format!("/storage/user/photos/?{}", query))
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert!(response
+			.headers()
+			.get(actix_web::http::header::ETAG)
+			.is_some());
+
+		let body: serde_json::Value =
+			serde_json::from_slice(&actix_web::test::read_body(response).await).unwrap();
+		assert_eq!(
+			body["items"]
+				.as_object()
+				.unwrap()
+				.keys()
+				.collect::<Vec<&String>>(),
+			expected_keys
+		);
+		if let Some(item) = body["items"].get("c.jpg") {
+			assert_eq!(item["Content-Length"], 5);
+		}
+		assert_eq!(body["next"], serde_json::json!(expected_next));
+	}
+
+	for query in ["limit=0", "limit=abc", "modified_since=yesterday"] {
+		let request = actix_web::test::TestRequest::get()
+			.uri(&format!("/storage/user/photos/?{}", query))
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+	}
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/storage/user/missing/?limit=2")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+	// documents do not have listing options
+	let request = actix_web::test::TestRequest::get()
+		.uri("/storage/user/photos/a.jpg?limit=abc")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::OK);
+}
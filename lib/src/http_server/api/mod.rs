@@ -1,16 +1,37 @@
+mod archive;
+mod batch;
+mod connect;
 mod delete;
 mod get;
 mod head;
 mod oauth;
 mod options;
 mod put;
+mod scrub;
+mod transfer;
+mod trash;
 
+pub use archive::{get_archive, put_archive};
+pub use batch::post_batch;
+pub use connect::{get_connect, post_connect};
 pub use delete::delete_item;
 pub use get::get_item;
 pub use head::head_item;
 pub use oauth::*;
 pub use options::options_item;
 pub use put::put_item;
+pub use scrub::get_scrub;
+pub use transfer::{copy_item, move_item};
+pub use trash::{get_trash, restore_trashed};
+
+/// Keeps the weakness of the entity tag, with the `W/` prefix.
+fn convert_actix_entity_tag(etag: &actix_web::http::header::EntityTag) -> crate::item::Etag {
+	if etag.weak {
+		crate::item::Etag::from(format!("W/{}", etag.tag().trim()))
+	} else {
+		crate::item::Etag::from(etag.tag().trim())
+	}
+}
 
 fn convert_actix_if_match(request: &actix_web::HttpRequest) -> Vec<crate::item::Etag> {
 	let res: Result<actix_web::http::header::IfMatch, actix_web::error::ParseError> =
@@ -21,14 +42,16 @@ fn convert_actix_if_match(request: &actix_web::HttpRequest) -> Vec<crate::item::
 			actix_web::http::header::IfMatch::Any => vec![crate::item::Etag::from("*")],
 			actix_web::http::header::IfMatch::Items(items) => items
 				.into_iter()
-				.map(|etag| crate::item::Etag::from(etag.tag().trim()))
+				.map(|etag| convert_actix_entity_tag(&etag))
 				.collect(),
 		},
 		Err(_) => vec![],
 	}
 }
 
-fn convert_actix_if_none_match(request: &actix_web::HttpRequest) -> Vec<crate::item::Etag> {
+pub(super) fn convert_actix_if_none_match(
+	request: &actix_web::HttpRequest,
+) -> Vec<crate::item::Etag> {
 	let res: Result<actix_web::http::header::IfNoneMatch, actix_web::error::ParseError> =
 		actix_web::http::header::Header::parse(request);
 
@@ -39,9 +62,114 @@ fn convert_actix_if_none_match(request: &actix_web::HttpRequest) -> Vec<crate::i
 			}
 			actix_web::http::header::IfNoneMatch::Items(items) => items
 				.into_iter()
-				.map(|etag| crate::item::Etag::from(etag.tag().trim()))
+				.map(|etag| convert_actix_entity_tag(&etag))
 				.collect::<Vec<crate::item::Etag>>(),
 		},
 		Err(_) => vec![],
 	}
 }
+
+/// The decoded path of the `Destination` header of `MOVE` and `COPY` requests, like `/storage/user/folder/document`.
+///
+/// It can be an absolute URL (whose host is ignored) or an absolute path, but it should be in `/storage/`
+/// and without `.` or `..` segments.
+pub(super) fn destination_of(headers: &actix_web::http::header::HeaderMap) -> Option<String> {
+	let destination = headers.get("Destination")?.to_str().ok()?.trim();
+
+	let path = match destination.split_once("://") {
+		Some((_, rest)) => &rest[rest.find('/')?..],
+		None => destination,
+	};
+	let path = path.split(['?', '#']).next().unwrap_or_default();
+	let path = pct_str::PctString::new(path).ok()?.decode();
+
+	if path.len() > "/storage/".len()
+		&& path.starts_with("/storage/")
+		&& !path
+			.split('/')
+			.any(|segment| segment == "." || segment == "..")
+	{
+		return Some(path);
+	} else {
+		return None;
+	}
+}
+
+/// The known token given by `request`, resolved like the [`Auth`][`crate::http_server::middlewares::Auth`] middleware does.
+pub(super) fn token_of(
+	request: &actix_web::HttpRequest,
+	access_tokens: &std::sync::Mutex<Vec<crate::http_server::AccessBearer>>,
+) -> Option<crate::http_server::AccessBearer> {
+	let (token, _) = crate::http_server::middlewares::request_token(request)?;
+
+	return access_tokens
+		.lock()
+		.unwrap()
+		.iter()
+		.find(|bearer| bearer.get_name() == token)
+		.cloned();
+}
+
+/// The name of the user which owns the token given by `request`, or `Unknown`, for [`DbEvent`][`crate::http_server::DbEvent`]s.
+pub(super) fn user_of(
+	request: &actix_web::HttpRequest,
+	access_tokens: &std::sync::Mutex<Vec<crate::http_server::AccessBearer>>,
+) -> String {
+	return match token_of(request, access_tokens) {
+		Some(bearer) => String::from(bearer.get_username()),
+		None => String::from("Unknown"),
+	};
+}
+
+/// The `token_lifetime_seconds` of `settings`, or its default value.
+pub(super) fn max_token_lifetime_seconds(settings: &crate::http_server::Settings) -> u64 {
+	return settings.token_lifetime_seconds.unwrap_or_else(|| {
+		crate::http_server::Settings::new(std::path::PathBuf::from("."))
+			.token_lifetime_seconds
+			.unwrap()
+	});
+}
+
+/// The token of `request`, if it belongs to `username` and is not expired.
+pub(super) fn token_of_user(
+	request: &actix_web::HttpRequest,
+	username: &str,
+	access_tokens: &std::sync::Mutex<Vec<crate::http_server::AccessBearer>>,
+	max_token_lifetime_seconds: u64,
+) -> Result<crate::http_server::AccessBearer, Box<actix_web::HttpResponse>> {
+	let token = match token_of(request, access_tokens) {
+		Some(token) => token,
+		None => {
+			return Err(Box::new(crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::UNAUTHORIZED,
+				None,
+				None,
+				None,
+				true,
+			)));
+		}
+	};
+
+	// the path does not matter, it only fails when the token is expired
+	let is_alive = token
+		.is_allowed(
+			max_token_lifetime_seconds,
+			&actix_web::http::Method::GET,
+			"/storage/",
+		)
+		.is_ok();
+
+	if token.get_username() != username || !is_alive {
+		return Err(Box::new(crate::database::build_http_json_response(
+			request.method(),
+			actix_web::http::StatusCode::FORBIDDEN,
+			None,
+			None,
+			None,
+			true,
+		)));
+	}
+
+	return Ok(token);
+}
@@ -26,7 +26,7 @@ pub async fn post_oauth(
 	program_state: actix_web::web::Data<Arc<Mutex<crate::http_server::ProgramState>>>,
 	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
 ) -> actix_web::Result<actix_web::HttpResponse> {
-	let _host = request.headers().get("host");
+	let host = String::from(request.connection_info().host());
 	let origin = request.headers().get("origin");
 	let _referer = request.headers().get("referer");
 
@@ -34,27 +34,26 @@ pub async fn post_oauth(
 	let current_domain = settings
 		.lock()
 		.unwrap()
+		.for_host(&host)
 		.domain
-		.as_ref()
-		.unwrap_or_else(|| &localhost)
-		.clone();
+		.unwrap_or(localhost);
 
 	match origin {
 		Some(path) => {
-			let settings = settings.lock().unwrap().clone();
+			let settings = settings.lock().unwrap().for_host(&host);
 
 			let mut allowed_domains = vec![];
 			// TODO : probably a security issue :
 			allowed_domains.push(format!("http://{current_domain}:{}", settings.port));
 			// TODO : probably a security issue :
 			if settings.port == 80 {
-				allowed_domains.push(String::from("http://{current_domain}"));
+				allowed_domains.push(format!("http://{current_domain}"));
 			}
 			if program_state.lock().unwrap().https_mode {
 				if let Some(https) = settings.https.clone() {
 					allowed_domains.push(format!("https://{current_domain}:{}", https.port));
-					if https.port != 443 {
-						allowed_domains.push(String::from("https://{current_domain}"));
+					if https.port == 443 {
+						allowed_domains.push(format!("https://{current_domain}"));
 					}
 				}
 			}
@@ -68,7 +67,7 @@ pub async fn post_oauth(
 				if program_state.lock().unwrap().https_mode {
 					if let Some(https) = settings.https.clone() {
 						allowed_domains.push(format!("https://{}:{}", domain, https.port));
-						if https.port != 443 {
+						if https.port == 443 {
 							allowed_domains.push(format!("https://{}", domain));
 						}
 					}
@@ -231,6 +230,7 @@ pub async fn post_oauth(
 			.lock()
 			.unwrap()
 			.check(&form.username, &mut String::from(&form.password))
+			&& settings.lock().unwrap().has_user(&host, &form.username)
 		{
 			// TODO : what if form.redirect_uri already contains fragment `#something` ?
 
@@ -12,7 +12,7 @@ pub async fn put_item(
 	mut request_payload: actix_web::web::Payload,
 	request: actix_web::HttpRequest,
 	path: actix_web::web::Path<String>,
-	database: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<crate::database::Database>>>,
+	database: actix_web::web::Data<crate::database::Database>,
 	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
 	dbevent_sender: actix_web::web::Data<std::sync::mpsc::Sender<crate::http_server::DbEvent>>,
 	access_tokens: actix_web::web::Data<
@@ -28,18 +28,8 @@ pub async fn put_item(
 
 	let content_type = request.headers().get("content-type");
 
-	// TODO : check security issue about this ?
-	let all_origins = actix_web::http::header::HeaderValue::from_bytes(b"*").unwrap();
-	let origin = request
-		.headers()
-		.get(actix_web::http::header::ORIGIN)
-		.unwrap_or(&all_origins)
-		.to_str()
-		.unwrap();
-
 	if content_type.is_none() {
 		return crate::database::build_http_json_response(
-			origin,
 			request.method(),
 			actix_web::http::StatusCode::BAD_REQUEST,
 			None,
@@ -51,44 +41,23 @@ pub async fn put_item(
 
 	let local_path = crate::item::ItemPath::from(path.into_inner().as_str());
 
-	let user = match request
-		.headers()
-		.get(actix_web::http::header::AUTHORIZATION)
-	{
-		Some(token) => {
-			let token = match token.to_str().unwrap_or_default().strip_prefix("Bearer ") {
-				Some(token) => token,
-				None => token.to_str().unwrap_or_default(),
-			};
-
-			match access_tokens
-				.lock()
-				.unwrap()
-				.iter()
-				.find(|bearer| bearer.get_name() == token)
-			{
-				Some(bearer) => String::from(bearer.get_username()),
-				None => String::from("Unknown"),
-			}
-		}
-		None => String::from("Unknown"),
-	};
+	let user = super::user_of(&request, &access_tokens);
 
-	match database.lock().unwrap().put(
-		&local_path,
+	match crate::database::AsyncDataSource::put(
+		database.get_ref(),
+		local_path.clone(),
+		super::convert_actix_if_match(&request),
+		super::convert_actix_if_none_match(&request),
 		crate::item::Item::Document {
 			etag: crate::item::Etag::from(""),
 			content: Some(content.to_vec()),
 			content_type: crate::item::ContentType::from(content_type.unwrap().to_str().unwrap()),
+			content_length: content.len() as u64,
 			last_modified: Some(time::OffsetDateTime::now_utc()),
 		},
-		super::convert_actix_if_match(&request)
-			.first()
-			.unwrap_or(&crate::item::Etag::from("")),
-		&super::convert_actix_if_none_match(&request)
-			.iter()
-			.collect::<Vec<&crate::item::Etag>>(),
-	) {
+	)
+	.await
+	{
 		crate::database::PutResult::Created(new_etag, last_modified) => {
 			dbevent_sender
 				.send(crate::http_server::DbEvent {
@@ -103,7 +72,6 @@ pub async fn put_item(
 				.ok();
 
 			return crate::database::build_http_json_response(
-				origin,
 				request.method(),
 				actix_web::http::StatusCode::CREATED,
 				Some(new_etag),
@@ -126,7 +94,6 @@ pub async fn put_item(
 				.ok();
 
 			return crate::database::build_http_json_response(
-				origin,
 				request.method(),
 				actix_web::http::StatusCode::OK,
 				Some(new_etag),
@@ -136,21 +103,7 @@ pub async fn put_item(
 			);
 		}
 		crate::database::PutResult::Err(e) => {
-			if e.is::<crate::database::sources::memory::PutError>() {
-				crate::database::Error::to_response(
-					&*e.downcast::<crate::database::sources::memory::PutError>()
-						.unwrap(),
-					origin,
-					true,
-				)
-			} else if e.is::<crate::database::sources::folder::PutError>() {
-				crate::database::Error::to_response(
-					&*e.downcast::<crate::database::sources::folder::PutError>()
-						.unwrap(),
-					origin,
-					true,
-				)
-			} else {
+			if let crate::database::DataSourceError::InternalError { .. } = e {
 				logger.lock().unwrap().push(
 					vec![
 						(String::from("level"), String::from("ERROR")),
@@ -160,17 +113,9 @@ pub async fn put_item(
 					],
 					Some(&format!("error from database : {e}")),
 				);
-
-				crate::database::build_http_json_response(
-					origin,
-					request.method(),
-					actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-					None,
-					None,
-					None,
-					true,
-				)
 			}
+
+			e.to_response(request.method(), true)
 		}
 	}
 }
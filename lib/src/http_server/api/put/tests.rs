@@ -6,7 +6,6 @@ async fn basics() {
 		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
 			root_item: crate::item::Item::new_folder(vec![]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
@@ -109,6 +108,7 @@ async fn if_none_match() {
 								crate::item::Item::Document {
 									etag: crate::item::Etag::from("A"),
 									content: Some(b"HELLO".to_vec()),
+									content_length: b"HELLO".len() as u64,
 									content_type: crate::item::ContentType::from("text/plain"),
 									last_modified: Some(time::OffsetDateTime::now_utc()),
 								},
@@ -118,6 +118,7 @@ async fn if_none_match() {
 								crate::item::Item::Document {
 									etag: crate::item::Etag::from("A"),
 									content: Some(b"HELLO".to_vec()),
+									content_length: b"HELLO".len() as u64,
 									content_type: crate::item::ContentType::from("text/plain"),
 									last_modified: Some(time::OffsetDateTime::now_utc()),
 								},
@@ -127,7 +128,6 @@ async fn if_none_match() {
 				)]),
 			)]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
@@ -237,6 +237,7 @@ async fn if_match() {
 							crate::item::Item::Document {
 								etag: crate::item::Etag::from("A"),
 								content: Some(b"HELLO".to_vec()),
+								content_length: b"HELLO".len() as u64,
 								content_type: crate::item::ContentType::from("text/plain"),
 								last_modified: Some(time::OffsetDateTime::now_utc()),
 							},
@@ -245,7 +246,6 @@ async fn if_match() {
 				)]),
 			)]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let mut logger = charlie_buffalo::Logger::new(
 		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
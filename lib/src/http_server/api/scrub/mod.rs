@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests;
+
+use std::sync::{Arc, Mutex};
+
+/// The report of the last [`scrub_data`][`crate::http_server::scrub_data`], with the damaged documents.
+///
+/// The token should belong to a user which can manage the settings of the server.
+#[actix_web::get("/admin/scrub")]
+pub async fn get_scrub(
+	request: actix_web::HttpRequest,
+	settings: actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>,
+	users: actix_web::web::Data<Arc<Mutex<crate::http_server::Users>>>,
+	program_state: actix_web::web::Data<Arc<Mutex<crate::http_server::ProgramState>>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	let token = match super::token_of(&request, &access_tokens) {
+		Some(token) => token,
+		None => {
+			return crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::UNAUTHORIZED,
+				None,
+				None,
+				None,
+				true,
+			);
+		}
+	};
+
+	let max_token_lifetime_seconds = super::max_token_lifetime_seconds(&settings.lock().unwrap());
+	// the path does not matter, it only fails when the token is expired
+	let is_alive = token
+		.is_allowed(
+			max_token_lifetime_seconds,
+			&actix_web::http::Method::GET,
+			"/storage/",
+		)
+		.is_ok();
+
+	if !is_alive
+		|| !users.lock().unwrap().has_right(
+			token.get_username(),
+			&crate::http_server::UserRight::ManageServerSettings,
+		) {
+		return crate::database::build_http_json_response(
+			request.method(),
+			actix_web::http::StatusCode::FORBIDDEN,
+			None,
+			None,
+			None,
+			true,
+		);
+	}
+
+	let body = match &program_state.lock().unwrap().last_scrub {
+		Some(report) => serde_json::json!({
+			"Date": report
+				.date
+				.format(&time::format_description::well_known::Rfc2822)
+				.unwrap_or_default(),
+			"damaged": report
+				.damaged
+				.iter()
+				.map(|path| format!("/storage/{}", path))
+				.collect::<Vec<String>>(),
+		}),
+		// scrubbing is disabled, or not done yet
+		None => serde_json::json!({
+			"Date": null,
+			"damaged": [],
+		}),
+	};
+
+	return actix_web::HttpResponse::Ok()
+		.content_type("application/json")
+		.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"))
+		.body(body.to_string());
+}
@@ -0,0 +1,113 @@
+#[actix_rt::test]
+async fn w5kx9tq2mz7rbn4hcv() {
+	let settings = crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+	let data_path = std::path::PathBuf::from(&settings.data_path);
+
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::FolderStorage {
+			root_folder_path: data_path.clone(),
+		}));
+	for path in ["admin/notes/a.txt", "admin/notes/b.txt"] {
+		database
+			.put(
+				&crate::item::ItemPath::from(path),
+				crate::item::Item::new_doc(b"content", "text/plain"),
+				&[],
+				&[],
+			)
+			.unwrap();
+	}
+	std::fs::write(
+		data_path.join("admin").join("notes").join("b.txt"),
+		b"damaged",
+	)
+	.unwrap();
+
+	let logger = charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(logger));
+
+	let program_state = std::sync::Arc::new(std::sync::Mutex::new(
+		crate::http_server::ProgramState::default(),
+	));
+
+	let report =
+		crate::http_server::scrub_data(&settings, &database, &program_state, &logger).unwrap();
+	assert_eq!(
+		report.damaged,
+		vec![crate::item::ItemPath::from("admin/notes/b.txt")]
+	);
+	assert!(database
+		.get(&crate::item::ItemPath::from("admin/notes/b.txt"), &[], &[])
+		.is_err());
+
+	let mut users = crate::http_server::Users::new();
+	users
+		.insert("admin", &mut String::from("password"))
+		.unwrap();
+	users.insert("user", &mut String::from("password")).unwrap();
+	users
+		.add_right("admin", crate::http_server::UserRight::ManageServerSettings)
+		.unwrap();
+	let users = std::sync::Arc::new(std::sync::Mutex::new(users));
+
+	let scope = crate::scope::Scope {
+		module: String::from("*"),
+		right_type: crate::scope::ScopeRightType::ReadWrite,
+	};
+	let admin_token = crate::http_server::AccessBearer::new(vec![scope.clone()], "test", "admin");
+	let user_token = crate::http_server::AccessBearer::new(vec![scope], "test", "user");
+	let access_tokens = std::sync::Arc::new(std::sync::Mutex::new(vec![
+		admin_token.clone(),
+		user_token.clone(),
+	]));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+				std::sync::Mutex::new(settings),
+			)))
+			.app_data(actix_web::web::Data::new(users))
+			.app_data(actix_web::web::Data::new(program_state))
+			.app_data(actix_web::web::Data::new(access_tokens))
+			.service(super::get_scrub),
+	)
+	.await;
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/admin/scrub")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/admin/scrub")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", user_token.get_name()),
+		))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/admin/scrub")
+		.insert_header((
+			actix_web::http::header::AUTHORIZATION,
+			format!("Bearer {}", admin_token.get_name()),
+		))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+
+	let response: serde_json::Value =
+		serde_json::from_slice(&actix_web::test::read_body(response).await).unwrap();
+	assert_eq!(
+		response["damaged"],
+		serde_json::json!(["/storage/admin/notes/b.txt"])
+	);
+}
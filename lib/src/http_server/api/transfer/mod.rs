@@ -0,0 +1,206 @@
+use std::sync::{Arc, Mutex};
+
+/// Moves a document to the path given by the `Destination` header, like the `MOVE` method of WebDAV (RFC 4918).
+///
+/// It is done in one operation on the server, see [`Database::move_item`][`crate::database::Database::move_item`].
+/// `If-Match` applies to the moved document, and `Overwrite: F` forbids to replace an existing document at the destination.
+#[actix_web::route("/storage/{requested_item:.*}", method = "MOVE")]
+pub async fn move_item(
+	path: actix_web::web::Path<String>,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
+	dbevent_sender: actix_web::web::Data<std::sync::mpsc::Sender<crate::http_server::DbEvent>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	return transfer(
+		path.into_inner(),
+		&request,
+		&database,
+		&logger,
+		&dbevent_sender,
+		&access_tokens,
+		true,
+	)
+	.await;
+}
+
+/// Copies a document to the path given by the `Destination` header, like the `COPY` method of WebDAV (RFC 4918).
+///
+/// `If-Match` applies to the copied document, and `Overwrite: F` forbids to replace an existing document at the destination.
+#[actix_web::route("/storage/{requested_item:.*}", method = "COPY")]
+pub async fn copy_item(
+	path: actix_web::web::Path<String>,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
+	dbevent_sender: actix_web::web::Data<std::sync::mpsc::Sender<crate::http_server::DbEvent>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	return transfer(
+		path.into_inner(),
+		&request,
+		&database,
+		&logger,
+		&dbevent_sender,
+		&access_tokens,
+		false,
+	)
+	.await;
+}
+
+async fn transfer(
+	path: String,
+	request: &actix_web::HttpRequest,
+	database: &crate::database::Database,
+	logger: &Arc<Mutex<charlie_buffalo::Logger>>,
+	dbevent_sender: &std::sync::mpsc::Sender<crate::http_server::DbEvent>,
+	access_tokens: &Arc<Mutex<Vec<crate::http_server::AccessBearer>>>,
+	remove_source: bool,
+) -> actix_web::HttpResponse {
+	let from = crate::item::ItemPath::from(path.as_str());
+
+	let to = match super::destination_of(request.headers()) {
+		Some(destination) => crate::item::ItemPath::from(
+			destination
+				.strip_prefix("/storage/")
+				.unwrap_or(destination.as_str()),
+		),
+		None => {
+			return crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::BAD_REQUEST,
+				None,
+				None,
+				Some(String::from(
+					"the `Destination` HTTP header should be an address in `/storage/`",
+				)),
+				true,
+			);
+		}
+	};
+
+	let if_none_match = match request.headers().get("Overwrite") {
+		Some(overwrite) if overwrite.to_str().unwrap_or_default().trim() == "F" => {
+			vec![crate::item::Etag::from("*")]
+		}
+		_ => vec![],
+	};
+
+	// the ETag of the source is needed for its event, and then ensures that this version is the transferred one
+	let source_etag = match crate::database::AsyncDataSource::get(
+		database,
+		from.clone(),
+		super::convert_actix_if_match(request),
+		vec![],
+		false,
+	)
+	.await
+	{
+		Ok(crate::item::Item::Document { etag, .. }) => etag,
+		Ok(crate::item::Item::Folder { .. }) => {
+			return crate::database::DataSourceError::DoesNotWorksForFolders
+				.to_response(request.method(), true);
+		}
+		Err(e) => {
+			return e.to_response(request.method(), true);
+		}
+	};
+
+	let result = if remove_source {
+		crate::database::AsyncDataSource::move_item(
+			database,
+			from.clone(),
+			to.clone(),
+			vec![source_etag.clone()],
+			if_none_match,
+		)
+		.await
+	} else {
+		crate::database::AsyncDataSource::copy_item(
+			database,
+			from.clone(),
+			to.clone(),
+			vec![source_etag.clone()],
+			if_none_match,
+		)
+		.await
+	};
+
+	let user = super::user_of(request, access_tokens);
+
+	let (new_etag, last_modified, method, status) = match result {
+		crate::database::PutResult::Created(new_etag, last_modified) => (
+			new_etag,
+			last_modified,
+			crate::http_server::DbEventMethod::Create,
+			actix_web::http::StatusCode::CREATED,
+		),
+		crate::database::PutResult::Updated(new_etag, last_modified) => (
+			new_etag,
+			last_modified,
+			crate::http_server::DbEventMethod::Update,
+			actix_web::http::StatusCode::OK,
+		),
+		crate::database::PutResult::Err(e) => {
+			if let crate::database::DataSourceError::InternalError { .. } = e {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("level"), String::from("ERROR")),
+						(String::from("module"), String::from("https?")),
+						(
+							String::from("method"),
+							String::from(request.method().as_str()),
+						),
+						(String::from("path"), from.to_string()),
+					],
+					Some(&format!("error from database : {e}")),
+				);
+			}
+
+			return e.to_response(request.method(), true);
+		}
+	};
+
+	dbevent_sender
+		.send(crate::http_server::DbEvent {
+			id: ulid::Ulid::new().to_string(),
+			method,
+			date: last_modified,
+			path: String::from("/storage/") + &to.to_string(),
+			etag: new_etag.clone(),
+			user: user.clone(),
+			dbversion: String::from(env!("CARGO_PKG_VERSION")),
+		})
+		.ok();
+
+	if remove_source {
+		dbevent_sender
+			.send(crate::http_server::DbEvent {
+				id: ulid::Ulid::new().to_string(),
+				method: crate::http_server::DbEventMethod::Delete,
+				date: last_modified,
+				path: String::from("/storage/") + &from.to_string(),
+				etag: source_etag,
+				user,
+				dbversion: String::from(env!("CARGO_PKG_VERSION")),
+			})
+			.ok();
+	}
+
+	return crate::database::build_http_json_response(
+		request.method(),
+		status,
+		Some(new_etag),
+		Some(last_modified),
+		None,
+		true,
+	);
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,158 @@
+use actix_web::http::{Method, StatusCode};
+
+#[actix_rt::test]
+async fn j3wz8qm1xk5tcv9hnd() {
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+			root_item: crate::item::Item::new_folder(vec![(
+				"user",
+				crate::item::Item::new_folder(vec![(
+					"notes",
+					crate::item::Item::new_folder(vec![
+						("a.txt", crate::item::Item::new_doc(b"A", "text/plain")),
+						("b.txt", crate::item::Item::new_doc(b"B", "text/plain")),
+					]),
+				)]),
+			)]),
+		}));
+
+	let logger = charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(logger));
+
+	let access_tokens: std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>> =
+		std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+	let (events, events_receiver) = std::sync::mpsc::channel::<crate::http_server::DbEvent>();
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(database.clone()))
+			.app_data(actix_web::web::Data::new(logger))
+			.app_data(actix_web::web::Data::new(access_tokens))
+			.app_data(actix_web::web::Data::new(events))
+			.service(super::move_item)
+			.service(super::copy_item),
+	)
+	.await;
+
+	let tests = vec![
+		(
+			"MOVE",
+			"/storage/user/notes/a.txt",
+			Some("/storage/user/archives/a.txt"),
+			None,
+			StatusCode::CREATED,
+		),
+		(
+			"MOVE",
+			"/storage/user/notes/a.txt",
+			Some("/storage/user/archives/a.txt"),
+			None,
+			StatusCode::NOT_FOUND,
+		),
+		(
+			"COPY",
+			"/storage/user/notes/b.txt",
+			Some("http://localhost/storage/user/archives/a.txt"),
+			Some("F"),
+			StatusCode::PRECONDITION_FAILED,
+		),
+		(
+			"COPY",
+			"/storage/user/notes/b.txt",
+			Some("http://localhost/storage/user/archives/a.txt"),
+			None,
+			StatusCode::OK,
+		),
+		(
+			"COPY",
+			"/storage/user/notes/b.txt",
+			None,
+			None,
+			StatusCode::BAD_REQUEST,
+		),
+		(
+			"COPY",
+			"/storage/user/notes/b.txt",
+			Some("/other/b.txt"),
+			None,
+			StatusCode::BAD_REQUEST,
+		),
+		(
+			"MOVE",
+			"/storage/user/notes/",
+			Some("/storage/user/archives/notes/"),
+			None,
+			StatusCode::BAD_REQUEST,
+		),
+		(
+			"MOVE",
+			"/storage/user/notes/b.txt",
+			Some("/storage/user/notes/b.txt"),
+			None,
+			StatusCode::CONFLICT,
+		),
+	];
+
+	for (method, uri, destination, overwrite, expected_status) in tests {
+		println!("{} {} to {:?}", method, uri, destination);
+
+		let mut request = actix_web::test::TestRequest::default()
+			.method(Method::from_bytes(method.as_bytes()).unwrap())
+			.uri(uri);
+		if let Some(destination) = destination {
+			request = request.insert_header(("Destination", destination));
+		}
+		if let Some(overwrite) = overwrite {
+			request = request.insert_header(("Overwrite", overwrite));
+		}
+		let response = actix_web::test::call_service(&app, request.to_request()).await;
+
+		assert_eq!(response.status(), expected_status);
+	}
+
+	let get = |path: &str| database.get(&crate::item::ItemPath::from(path), &[], &[]);
+
+	assert!(get("user/notes/a.txt").is_err());
+	assert!(get("user/notes/b.txt").is_ok());
+	match get("user/archives/a.txt").unwrap() {
+		crate::item::Item::Document { content, .. } => assert_eq!(content, Some(b"B".to_vec())),
+		item => panic!("unexpected item : {:?}", item),
+	}
+
+	let events: Vec<(String, String)> = events_receiver
+		.try_iter()
+		.map(|event| {
+			(
+				String::from(match event.method {
+					crate::http_server::DbEventMethod::Create => "Create",
+					crate::http_server::DbEventMethod::Update => "Update",
+					crate::http_server::DbEventMethod::Delete => "Delete",
+				}),
+				event.path,
+			)
+		})
+		.collect();
+	assert_eq!(
+		events,
+		vec![
+			(
+				String::from("Create"),
+				String::from("/storage/user/archives/a.txt")
+			),
+			(
+				String::from("Delete"),
+				String::from("/storage/user/notes/a.txt")
+			),
+			(
+				String::from("Update"),
+				String::from("/storage/user/archives/a.txt")
+			),
+		]
+	);
+}
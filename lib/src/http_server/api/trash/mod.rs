@@ -0,0 +1,215 @@
+use std::sync::{Arc, Mutex};
+
+/// Lists the documents of `username` which are in the trash, the most recently deleted first,
+/// see [`Database::with_trash`][`crate::database::Database::with_trash`].
+///
+/// Only the documents which the token can read are listed.
+#[actix_web::get("/trash/{username}")]
+pub async fn get_trash(
+	username: actix_web::web::Path<String>,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	settings: actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	let username = username.into_inner();
+	let max_token_lifetime_seconds = super::max_token_lifetime_seconds(&settings.lock().unwrap());
+
+	let token = match super::token_of_user(
+		&request,
+		&username,
+		&access_tokens,
+		max_token_lifetime_seconds,
+	) {
+		Ok(token) => token,
+		Err(response) => return *response,
+	};
+
+	if !database.has_trash() {
+		return no_trash(&request);
+	}
+
+	let trashed_items = match database.trashed_items(&username) {
+		Ok(trashed_items) => trashed_items,
+		Err(error) => return error.to_response(request.method(), true),
+	};
+
+	let items: Vec<serde_json::Value> = trashed_items
+		.into_iter()
+		.filter(|trashed| {
+			token
+				.is_allowed(
+					max_token_lifetime_seconds,
+					&actix_web::http::Method::GET,
+					format!("/storage/{}", trashed.path),
+				)
+				.unwrap_or(false)
+		})
+		.map(|trashed| {
+			let mut item = serde_json::json!({
+				"id": trashed.id,
+				"path": format!("/storage/{}", trashed.path),
+				"ETag": trashed.etag,
+				"Deleted": trashed
+					.deleted_at
+					.format(&time::format_description::well_known::Rfc2822)
+					.unwrap_or_default(),
+			});
+
+			if let crate::item::Item::Document {
+				content_type,
+				content_length,
+				..
+			} = &trashed.item
+			{
+				item["Content-Type"] = serde_json::Value::from(String::from(content_type.clone()));
+				item["Content-Length"] = serde_json::Value::from(*content_length);
+			}
+
+			item
+		})
+		.collect();
+
+	return actix_web::HttpResponse::Ok()
+		.content_type("application/json")
+		.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"))
+		.body(serde_json::json!({ "items": items }).to_string());
+}
+
+/// Puts the document with `id` back at its former path, if the token can write there and
+/// if there is no item at this path again.
+#[actix_web::post("/trash/{username}/{id}")]
+pub async fn restore_trashed(
+	path: actix_web::web::Path<(String, String)>,
+	request: actix_web::HttpRequest,
+	database: actix_web::web::Data<crate::database::Database>,
+	settings: actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>,
+	logger: actix_web::web::Data<Arc<Mutex<charlie_buffalo::Logger>>>,
+	dbevent_sender: actix_web::web::Data<std::sync::mpsc::Sender<crate::http_server::DbEvent>>,
+	access_tokens: actix_web::web::Data<
+		std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
+	>,
+) -> impl actix_web::Responder {
+	let (username, id) = path.into_inner();
+	let max_token_lifetime_seconds = super::max_token_lifetime_seconds(&settings.lock().unwrap());
+
+	let token = match super::token_of_user(
+		&request,
+		&username,
+		&access_tokens,
+		max_token_lifetime_seconds,
+	) {
+		Ok(token) => token,
+		Err(response) => return *response,
+	};
+
+	if !database.has_trash() {
+		return no_trash(&request);
+	}
+
+	let trashed = match database
+		.trashed_item(&id)
+		.filter(|trashed| trashed.owner() == Some(username.as_str()))
+	{
+		Some(trashed) => trashed,
+		None => {
+			return crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::NOT_FOUND,
+				None,
+				None,
+				None,
+				true,
+			);
+		}
+	};
+
+	let storage_path = format!("/storage/{}", trashed.path);
+	if !token
+		.is_allowed(
+			max_token_lifetime_seconds,
+			&actix_web::http::Method::PUT,
+			storage_path.clone(),
+		)
+		.unwrap_or(false)
+	{
+		return crate::database::build_http_json_response(
+			request.method(),
+			actix_web::http::StatusCode::FORBIDDEN,
+			None,
+			None,
+			None,
+			true,
+		);
+	}
+
+	let restored = {
+		let database = database.get_ref().clone();
+		actix_web::web::block(move || database.restore_trashed(&id))
+			.await
+			.unwrap_or_else(|error| {
+				crate::database::PutResult::Err(crate::database::DataSourceError::InternalError {
+					error: format!("{}", error),
+				})
+			})
+	};
+
+	match restored {
+		crate::database::PutResult::Created(etag, last_modified)
+		| crate::database::PutResult::Updated(etag, last_modified) => {
+			dbevent_sender
+				.send(crate::http_server::DbEvent {
+					id: ulid::Ulid::new().to_string(),
+					method: crate::http_server::DbEventMethod::Create,
+					date: last_modified,
+					path: storage_path,
+					etag: etag.clone(),
+					user: username,
+					dbversion: String::from(env!("CARGO_PKG_VERSION")),
+				})
+				.ok();
+
+			return crate::database::build_http_json_response(
+				request.method(),
+				actix_web::http::StatusCode::CREATED,
+				Some(etag),
+				Some(last_modified),
+				None,
+				true,
+			);
+		}
+		crate::database::PutResult::Err(error) => {
+			if let crate::database::DataSourceError::InternalError { .. } = error {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("level"), String::from("ERROR")),
+						(String::from("module"), String::from("https?")),
+						(String::from("method"), String::from("POST")),
+						(String::from("path"), String::from(request.path())),
+					],
+					Some(&format!("error from database : {error}")),
+				);
+			}
+
+			return error.to_response(request.method(), true);
+		}
+	}
+}
+
+fn no_trash(request: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+	return crate::database::build_http_json_response(
+		request.method(),
+		actix_web::http::StatusCode::NOT_FOUND,
+		None,
+		None,
+		Some(String::from(
+			"deleted documents are not kept on this server",
+		)),
+		true,
+	);
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,164 @@
+#[actix_rt::test]
+async fn r4nx9kt2vm6qzc8hwd() {
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+			root_item: crate::item::Item::new_folder(vec![(
+				"user",
+				crate::item::Item::new_folder(vec![
+					(
+						"notes",
+						crate::item::Item::new_folder(vec![(
+							"a.txt",
+							crate::item::Item::new_doc(b"A", "text/plain"),
+						)]),
+					),
+					(
+						"pictures",
+						crate::item::Item::new_folder(vec![(
+							"b.png",
+							crate::item::Item::new_doc(b"B", "image/png"),
+						)]),
+					),
+				]),
+			)]),
+		}))
+		.with_trash(crate::database::Trash::new(time::Duration::days(30)));
+
+	database
+		.delete(&crate::item::ItemPath::from("user/notes/a.txt"), &[])
+		.unwrap();
+	database
+		.delete(&crate::item::ItemPath::from("user/pictures/b.png"), &[])
+		.unwrap();
+
+	let logger = charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(logger));
+
+	let token = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("notes"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		}],
+		"test",
+		"user",
+	);
+	let other_token = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("*"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		}],
+		"test",
+		"other",
+	);
+	let access_tokens = std::sync::Arc::new(std::sync::Mutex::new(vec![
+		token.clone(),
+		other_token.clone(),
+	]));
+
+	let settings = std::sync::Arc::new(std::sync::Mutex::new(crate::http_server::Settings::new(
+		tempfile::tempdir().unwrap().into_path(),
+	)));
+
+	let (events, events_receiver) = std::sync::mpsc::channel::<crate::http_server::DbEvent>();
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(database.clone()))
+			.app_data(actix_web::web::Data::new(settings))
+			.app_data(actix_web::web::Data::new(logger))
+			.app_data(actix_web::web::Data::new(access_tokens))
+			.app_data(actix_web::web::Data::new(events))
+			.service(super::get_trash)
+			.service(super::restore_trashed),
+	)
+	.await;
+
+	let request = |method: actix_web::http::Method, uri: &str, token: &str| {
+		actix_web::test::TestRequest::default()
+			.method(method)
+			.uri(uri)
+			.insert_header((
+				actix_web::http::header::AUTHORIZATION,
+				format!("Bearer {}", token),
+			))
+			.to_request()
+	};
+
+	let response = actix_web::test::call_service(
+		&app,
+		request(
+			actix_web::http::Method::GET,
+			"/trash/user",
+			other_token.get_name(),
+		),
+	)
+	.await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+	let response = actix_web::test::call_service(
+		&app,
+		request(
+			actix_web::http::Method::GET,
+			"/trash/user",
+			token.get_name(),
+		),
+	)
+	.await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+
+	let response: serde_json::Value =
+		serde_json::from_slice(&actix_web::test::read_body(response).await).unwrap();
+	let items = response["items"].as_array().unwrap();
+	assert_eq!(items.len(), 1);
+	assert_eq!(items[0]["path"], "/storage/user/notes/a.txt");
+	assert_eq!(items[0]["Content-Type"], "text/plain");
+	assert_eq!(items[0]["Content-Length"], 1);
+	assert!(items[0]["Deleted"].is_string());
+	let id = items[0]["id"].as_str().unwrap();
+
+	// documents out of the scopes of the token can not be restored
+	let pictures_id = database.trashed_items("user").unwrap()[0].id.clone();
+	let response = actix_web::test::call_service(
+		&app,
+		request(
+			actix_web::http::Method::POST,
+			&format!("/trash/user/{}", pictures_id),
+			token.get_name(),
+		),
+	)
+	.await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+	let response = actix_web::test::call_service(
+		&app,
+		request(
+			actix_web::http::Method::POST,
+			&format!("/trash/user/{}", id),
+			token.get_name(),
+		),
+	)
+	.await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::CREATED);
+	assert!(database
+		.get(&crate::item::ItemPath::from("user/notes/a.txt"), &[], &[])
+		.is_ok());
+
+	let response = actix_web::test::call_service(
+		&app,
+		request(
+			actix_web::http::Method::POST,
+			&format!("/trash/user/{}", id),
+			token.get_name(),
+		),
+	)
+	.await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+	let events: Vec<String> = events_receiver.try_iter().map(|event| event.path).collect();
+	assert_eq!(events, vec![String::from("/storage/user/notes/a.txt")]);
+}
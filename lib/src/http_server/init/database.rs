@@ -2,13 +2,122 @@ use std::sync::{Arc, Mutex};
 
 pub fn load_or_create_database(
 	settings: &super::Settings,
+	workspace_path: &std::path::Path,
 	logger: Arc<Mutex<charlie_buffalo::Logger>>,
-) -> Arc<Mutex<crate::database::Database>> {
-	let database = Arc::new(Mutex::new(crate::database::Database::new(Box::new(
-		crate::database::sources::FolderStorage {
+) -> crate::database::Database {
+	let mut database =
+		crate::database::Database::new(Box::new(crate::database::sources::FolderStorage {
 			root_folder_path: std::path::PathBuf::from(settings.data_path.clone()),
-		},
-	))));
+		}));
+
+	if settings.content_etags.unwrap_or_default() {
+		database = database.with_etag_strategy(crate::database::EtagStrategy::ContentHash);
+
+		// existing items could have been created with random ETags, so they are derived once,
+		// at the first startup after enabling this setting
+		if !super::has_content_etags(workspace_path) {
+			match database.rehash_etags(&crate::item::ItemPath::from("")) {
+				Ok(_) => {
+					logger.lock().unwrap().push(
+						vec![
+							(String::from("event"), String::from("setup")),
+							(String::from("module"), String::from("database")),
+							(String::from("level"), String::from("INFO")),
+						],
+						Some("ETags derived from content"),
+					);
+
+					if let Err(error) = super::set_content_etags(workspace_path, true) {
+						logger.lock().unwrap().push(
+							vec![
+								(String::from("event"), String::from("setup")),
+								(String::from("module"), String::from("database")),
+								(String::from("level"), String::from("WARNING")),
+							],
+							Some(&format!(
+								"can not record that ETags are derived from content : {}",
+								error
+							)),
+						);
+					}
+				}
+				Err(error) => {
+					logger.lock().unwrap().push(
+						vec![
+							(String::from("event"), String::from("setup")),
+							(String::from("module"), String::from("database")),
+							(String::from("level"), String::from("WARNING")),
+						],
+						Some(&format!("can not derive ETags from content : {}", error)),
+					);
+				}
+			}
+		}
+	} else if super::has_content_etags(workspace_path) {
+		// new items get random ETags from now, so they should be derived again if this setting is enabled again
+		if let Err(error) = super::set_content_etags(workspace_path, false) {
+			logger.lock().unwrap().push(
+				vec![
+					(String::from("event"), String::from("setup")),
+					(String::from("module"), String::from("database")),
+					(String::from("level"), String::from("WARNING")),
+				],
+				Some(&format!(
+					"can not record that ETags are not derived from content anymore : {}",
+					error
+				)),
+			);
+		}
+	}
+
+	if let Some(trash_settings) = &settings.trash {
+		let trash_path = match &trash_settings.path {
+			Some(path) => std::path::PathBuf::from(path),
+			None => std::path::Path::new(&settings.data_path)
+				.parent()
+				.unwrap_or_else(|| std::path::Path::new(&settings.data_path))
+				.join("trash"),
+		};
+
+		// a retention too long to be represented keeps deleted documents forever
+		let retention = i64::try_from(trash_settings.retention_days)
+			.ok()
+			.and_then(|days| days.checked_mul(24 * 60 * 60))
+			.map(time::Duration::seconds)
+			.unwrap_or(time::Duration::MAX);
+
+		match crate::database::Trash::in_folder(&trash_path, retention) {
+			Ok(trash) => {
+				database = database.with_trash(trash);
+
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("event"), String::from("setup")),
+						(String::from("module"), String::from("database")),
+						(String::from("level"), String::from("INFO")),
+					],
+					Some(&format!(
+						"deleted documents are kept {} days in `{}`",
+						trash_settings.retention_days,
+						trash_path.to_string_lossy()
+					)),
+				);
+			}
+			Err(error) => {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("event"), String::from("setup")),
+						(String::from("module"), String::from("database")),
+						(String::from("level"), String::from("WARNING")),
+					],
+					Some(&format!(
+						"can not load trash, deleted documents are lost : {}",
+						error
+					)),
+				);
+			}
+		}
+	}
 
 	logger.lock().unwrap().push(
 		vec![
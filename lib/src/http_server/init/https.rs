@@ -2,7 +2,7 @@ use std::sync::{Arc, Mutex};
 
 pub fn setup_and_run_https_server(
 	settings: Arc<Mutex<super::Settings>>,
-	database: Arc<Mutex<crate::database::Database>>,
+	database: crate::database::Database,
 	access_tokens: Arc<Mutex<Vec<crate::http_server::AccessBearer>>>,
 	oauth_form_tokens: Arc<Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>>,
 	users: Arc<Mutex<crate::http_server::Users>>,
@@ -62,6 +62,8 @@ pub fn setup_and_run_https_server(
 															.wrap(crate::http_server::middlewares::Auth {
 																logger: logger_for_server.clone(),
 															})
+															.wrap(crate::http_server::middlewares::WebAuthoring)
+															.wrap(crate::http_server::middlewares::Cors)
 															.wrap(crate::http_server::middlewares::Logger {
 																logger: logger_for_server.clone(),
 															})
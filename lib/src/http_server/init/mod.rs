@@ -2,10 +2,12 @@ mod database;
 mod https;
 mod logger;
 mod settings;
+mod upgrade;
 mod users;
 
 pub use database::*;
 pub use https::*;
 pub use logger::*;
 pub use settings::*;
+pub use upgrade::*;
 pub use users::*;
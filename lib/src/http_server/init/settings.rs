@@ -117,6 +117,16 @@ pub struct Settings {
 	pub force_https: Option<bool>,
 	pub domain: Option<String>,
 	pub domain_suffix: Option<String>,
+	/// Other domains of this server, like `www.example.com` for `example.com`.
+	///
+	/// Webfinger only answers for accounts on `domain` or on one of these aliases.
+	pub domain_aliases: Option<Vec<String>>,
+	/// Users which can not be found by webfinger, so apps can not discover their storage.
+	///
+	/// They can still connect to apps which already know their storage (see `/connect/<user>`).
+	pub webfinger_hidden_users: Option<Vec<String>>,
+	/// Other hostnames of this server, which can have their own users.
+	pub virtual_hosts: Option<Vec<SettingsVirtualHost>>,
 	pub port: usize,
 	pub admin_email: String,
 	pub token_lifetime_seconds: Option<u64>,
@@ -124,7 +134,27 @@ pub struct Settings {
 	pub logfile_path: String,
 	pub userfile_path: String,
 	pub data_path: String,
+	pub cors_allowed_origins: Option<Vec<String>>,
+	pub content_etags: Option<bool>,
+	/// Accepts bearer tokens in the `access_token` query parameter of GET and HEAD requests
+	/// (RFC 6750 section 2.3), so `<img>` or `<video>` tags can load private documents.
+	///
+	/// Tokens in URLs can leak in browser history or in logs, so it is disabled by default.
+	pub query_access_token: Option<bool>,
+	/// The maximum size of archives imported by `PUT /archive/<user>`, in bytes.
+	///
+	/// Bigger ones are refused with `413 Payload Too Large`, because they are held in memory while they are imported.
+	pub archive_max_bytes: Option<u64>,
+	pub web_authoring: Option<SettingsWebAuthoring>,
 	pub https: Option<SettingsHTTPS>,
+	/// Keeps deleted documents in a trash for a while, so their owners can restore them.
+	///
+	/// Deleted documents are lost at once if it is not set.
+	pub trash: Option<SettingsTrash>,
+	/// Regularly checks the content of all documents against their checksum, see [`scrub_data`][`crate::http_server::scrub_data`].
+	///
+	/// Damaged documents are only found when they are read if it is not set.
+	pub scrub: Option<SettingsScrub>,
 }
 impl Settings {
 	pub fn new(workspace_path: std::path::PathBuf) -> Self {
@@ -160,6 +190,9 @@ impl Settings {
 			force_https: None,
 			domain,
 			domain_suffix: Some(String::new()),
+			domain_aliases: None,
+			webfinger_hidden_users: None,
+			virtual_hosts: None,
 			port: random_port_generation(),
 			admin_email: String::new(),
 			token_lifetime_seconds: Some(60 * 60),
@@ -175,10 +208,92 @@ impl Settings {
 				.unwrap()
 				.display()
 				.to_string(),
+			cors_allowed_origins: Some(vec![String::from("*")]),
+			content_etags: Some(false),
+			query_access_token: Some(false),
+			archive_max_bytes: Some(256 * 1024 * 1024),
+			web_authoring: None,
 			https: Some(SettingsHTTPS::default()),
+			trash: None,
+			scrub: None,
 			oauth_wait_seconds: Some(2),
 		}
 	}
+
+	/// Checks if `domain` (optionally with a port) is the `domain` of this server,
+	/// one of its `domain_aliases`, or one of its `virtual_hosts`.
+	pub fn is_own_domain(&self, domain: &str) -> bool {
+		let domain = without_port(domain);
+		let localhost = String::from("localhost");
+
+		return std::iter::once(self.domain.as_ref().unwrap_or(&localhost))
+			.chain(self.domain_aliases.iter().flatten())
+			.chain(
+				self.virtual_hosts
+					.iter()
+					.flatten()
+					.map(|virtual_host| &virtual_host.domain),
+			)
+			.any(|own_domain| without_port(own_domain) == domain);
+	}
+
+	/// Returns the virtual host served on `host` (optionally with a port), if any.
+	pub fn virtual_host(&self, host: &str) -> Option<&SettingsVirtualHost> {
+		let host = without_port(host);
+
+		return self
+			.virtual_hosts
+			.iter()
+			.flatten()
+			.find(|virtual_host| without_port(&virtual_host.domain) == host);
+	}
+
+	/// These settings, as seen by requests on `host` (usually their `Host` header) :
+	/// if it is one of the domains of this server, it becomes its `domain`, so addresses
+	/// built with [`build_server_address`][`crate::http_server::build_server_address`] are on `host`.
+	///
+	/// Unknown hosts are ignored, because the `Host` header is chosen by clients.
+	pub fn for_host(&self, host: &str) -> Self {
+		let mut result = self.clone();
+
+		if let Some(virtual_host) = self.virtual_host(host) {
+			result.domain = Some(without_port(&virtual_host.domain));
+			if virtual_host.domain_suffix.is_some() {
+				result.domain_suffix = virtual_host.domain_suffix.clone();
+			}
+		} else if self.is_own_domain(host) {
+			result.domain = Some(without_port(host));
+		}
+
+		return result;
+	}
+
+	/// Checks if `username` is an account of `host`, according to the `users` of `virtual_hosts`.
+	pub fn has_user(&self, host: &str, username: &str) -> bool {
+		match self
+			.virtual_host(host)
+			.and_then(|virtual_host| virtual_host.users.as_ref())
+		{
+			Some(users) => users.iter().any(|user| user == username),
+			None => !self
+				.virtual_hosts
+				.iter()
+				.flatten()
+				.filter_map(|virtual_host| virtual_host.users.as_ref())
+				.flatten()
+				.any(|user| user == username),
+		}
+	}
+}
+
+/// Lowercase `domain`, without its port.
+fn without_port(domain: &str) -> String {
+	let domain = domain.trim().trim_end_matches('.').to_lowercase();
+
+	match domain.rsplit_once(':') {
+		Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => String::from(host),
+		_ => domain,
+	}
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -200,6 +315,69 @@ impl Default for SettingsHTTPS {
 	}
 }
 
+/// Another hostname of this server, see [`Settings::for_host`].
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct SettingsVirtualHost {
+	/// Like `storage.example.org`, as found in the `Host` header of requests.
+	pub domain: String,
+	/// Overrides the `domain_suffix` of the server on this host.
+	pub domain_suffix: Option<String>,
+	/// If set, only these users are found on this host (by webfinger or OAuth),
+	/// and they are not found on the other hosts.
+	pub users: Option<Vec<String>>,
+}
+
+/// See [`Trash`][`crate::database::Trash`].
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct SettingsTrash {
+	/// Trashed documents are purged after this count of days, they are never purged if it is too big to be represented as a date.
+	pub retention_days: u64,
+	/// Where trashed documents are saved, `trash` next to `data_path` if it is not set.
+	pub path: Option<String>,
+}
+
+/// See [`Settings::scrub`].
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct SettingsScrub {
+	/// Hours between the end of a scrubbing and the start of the next one.
+	pub interval_hours: u64,
+}
+
+/// Serves the `/storage/public/<user>/www/` folder of each user as a static website,
+/// see [`WebAuthoring`][`crate::http_server::middlewares::WebAuthoring`].
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct SettingsWebAuthoring {
+	/// Websites are served on subdomains of this domain, like `<user>.sites.example.com`.
+	pub domain: Option<String>,
+	/// Websites are served under this path of the server, like `/sites/<user>/`.
+	///
+	/// It should not collide with other paths of the server, like `storage` or `oauth`.
+	pub path: Option<String>,
+	/// The `max-age` directive of the `Cache-Control` header of served documents.
+	pub cache_max_age_seconds: Option<u64>,
+}
+impl SettingsWebAuthoring {
+	/// Where the website of `username` is published, as advertised by webfinger :
+	/// the fully qualified domain name of its subdomain, or else the URL of its path on `server_address`.
+	pub fn site_of(&self, username: &str, server_address: &str) -> Option<String> {
+		if let Some(domain) = &self.domain {
+			let domain = domain.trim().trim_matches('.');
+			if !domain.is_empty() {
+				return Some(format!("{}.{}", username, domain));
+			}
+		}
+
+		if let Some(path) = &self.path {
+			let path = path.trim().trim_matches('/');
+			if !path.is_empty() {
+				return Some(format!("{}{}/{}/", server_address, path, username));
+			}
+		}
+
+		return None;
+	}
+}
+
 fn random_port_generation() -> usize {
 	let mut rng = rand::thread_rng();
 
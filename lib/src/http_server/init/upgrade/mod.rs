@@ -0,0 +1,404 @@
+#[cfg(test)]
+mod tests;
+
+/// Upgrades the files of a workspace from one format version to the next one.
+type Upgrade = fn(&WorkspaceFiles) -> Result<(), String>;
+
+/// Each step upgrades a workspace from the format version at its index to the next one.
+///
+/// New steps are appended at the end, existing ones should never be changed.
+const UPGRADES: [Upgrade; 2] = [upgrade_from_0, upgrade_from_1];
+
+/// The format of workspaces written by this version, see [`upgrade_workspace`].
+pub const WORKSPACE_FORMAT_VERSION: u32 = UPGRADES.len() as u32;
+
+/// The file of the workspace which records its format version.
+const FORMAT_FILE_NAME: &str = "format.toml";
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+struct WorkspaceFormat {
+	version: u32,
+	/// ETags of all items were derived from their content, see [`has_content_etags`].
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	content_etags: bool,
+}
+
+/// The files of a workspace which can need to be upgraded.
+#[derive(Debug, Clone)]
+struct WorkspaceFiles {
+	settings: std::path::PathBuf,
+	users: std::path::PathBuf,
+	events: std::path::PathBuf,
+	data: std::path::PathBuf,
+}
+impl WorkspaceFiles {
+	/// Finds the files of the workspace at `workspace_path`, where its settings say they are.
+	fn new(workspace_path: &std::path::Path) -> Self {
+		let settings = workspace_path.join("settings.toml");
+
+		let settings_value = std::fs::read(&settings)
+			.ok()
+			.and_then(|content| toml::from_slice::<toml::Value>(&content).ok());
+		let setting = |name: &str, default: &str| {
+			settings_value
+				.as_ref()
+				.and_then(|settings| settings.get(name))
+				.and_then(|value| value.as_str())
+				.map(std::path::PathBuf::from)
+				.unwrap_or_else(|| workspace_path.join(default))
+		};
+
+		return Self {
+			users: setting("userfile_path", "users.bin"),
+			data: setting("data_path", "data"),
+			events: workspace_path.join("events.bin"),
+			settings,
+		};
+	}
+}
+
+/// Upgrades the settings, users, events and item metadata of the workspace at `workspace_path`
+/// to [`WORKSPACE_FORMAT_VERSION`], step by step, after saving them in its `backups` folder.
+///
+/// It should be called before loading anything from the workspace.
+/// Workspaces written by a newer version are refused, because their format is unknown.
+/// Workspaces without settings are new ones, so they get the current version.
+pub fn upgrade_workspace(
+	workspace_path: &std::path::Path,
+	logger: &mut charlie_buffalo::Logger,
+) -> Result<(), String> {
+	let files = WorkspaceFiles::new(workspace_path);
+	let format_path = workspace_path.join(FORMAT_FILE_NAME);
+
+	let mut format = match read_format(&format_path)? {
+		Some(format) => format,
+		None => WorkspaceFormat {
+			version: if files.settings.exists() {
+				// written before formats were versioned
+				0
+			} else {
+				WORKSPACE_FORMAT_VERSION
+			},
+			content_etags: false,
+		},
+	};
+	let version = format.version;
+
+	if version > WORKSPACE_FORMAT_VERSION {
+		return Err(format!(
+			"the workspace format version is {}, but this program only knows versions up to {}, please update it",
+			version, WORKSPACE_FORMAT_VERSION
+		));
+	}
+
+	if version < WORKSPACE_FORMAT_VERSION {
+		let backup_path = backup(workspace_path, &files, version)?;
+
+		logger.push(
+			vec![
+				(String::from("event"), String::from("setup")),
+				(String::from("module"), String::from("upgrade")),
+				(String::from("level"), String::from("INFO")),
+			],
+			Some(&format!(
+				"workspace saved in `{}` before upgrading its format from version {} to {}",
+				backup_path.to_string_lossy(),
+				version,
+				WORKSPACE_FORMAT_VERSION
+			)),
+		);
+
+		for (from, upgrade) in UPGRADES.iter().enumerate().skip(version as usize) {
+			upgrade(&files).map_err(|error| {
+				format!(
+					"can not upgrade the workspace format from version {} : {}",
+					from, error
+				)
+			})?;
+
+			// an interrupted upgrade starts again from the last successful step
+			format.version = from as u32 + 1;
+			write_format(&format_path, &format)?;
+		}
+
+		logger.push(
+			vec![
+				(String::from("event"), String::from("setup")),
+				(String::from("module"), String::from("upgrade")),
+				(String::from("level"), String::from("INFO")),
+			],
+			Some(&format!(
+				"workspace format upgraded to version {}",
+				WORKSPACE_FORMAT_VERSION
+			)),
+		);
+	} else if !format_path.exists() {
+		write_format(&format_path, &format)?;
+	}
+
+	return Ok(());
+}
+
+/// Tells if ETags of all items of the workspace at `workspace_path` were derived from their content,
+/// so they do not need to be derived again at each startup, see [`set_content_etags`].
+pub fn has_content_etags(workspace_path: &std::path::Path) -> bool {
+	return read_format(&workspace_path.join(FORMAT_FILE_NAME))
+		.ok()
+		.flatten()
+		.map(|format| format.content_etags)
+		.unwrap_or_default();
+}
+
+/// Records in the format file of the workspace at `workspace_path` if ETags of all its items
+/// are derived from their content, see [`has_content_etags`].
+///
+/// It should be called after [`upgrade_workspace`], which creates this file.
+pub fn set_content_etags(
+	workspace_path: &std::path::Path,
+	content_etags: bool,
+) -> Result<(), String> {
+	let format_path = workspace_path.join(FORMAT_FILE_NAME);
+
+	let mut format = read_format(&format_path)?.unwrap_or(WorkspaceFormat {
+		version: WORKSPACE_FORMAT_VERSION,
+		content_etags: false,
+	});
+	format.content_etags = content_etags;
+
+	return write_format(&format_path, &format);
+}
+
+/// Copies the settings, users, events and item metadata (but not the content of documents,
+/// which upgrades do not change) in a new folder of `<workspace>/backups/`, and returns its path.
+fn backup(
+	workspace_path: &std::path::Path,
+	files: &WorkspaceFiles,
+	version: u32,
+) -> Result<std::path::PathBuf, String> {
+	let backup_path = workspace_path.join("backups").join(format!(
+		"format_{}_{}",
+		version,
+		time::OffsetDateTime::now_utc().unix_timestamp()
+	));
+
+	for file in [&files.settings, &files.users, &files.events] {
+		if let Some(name) = file.file_name() {
+			if file.is_file() {
+				copy(file, &backup_path.join(name))?;
+			}
+		}
+	}
+
+	for itemdata in itemdata_files(&files.data)? {
+		if let Ok(relative) = itemdata.strip_prefix(&files.data) {
+			copy(&itemdata, &backup_path.join("data").join(relative))?;
+		}
+	}
+
+	return Ok(backup_path);
+}
+
+/// Replaces the former `Put` method of events by `Update`.
+///
+/// Item metadata gets the length of the content of documents, so listings do not need to read it.
+fn upgrade_from_0(files: &WorkspaceFiles) -> Result<(), String> {
+	if files.events.is_file() {
+		let events = std::fs::read_to_string(&files.events).map_err(|error| {
+			format!(
+				"can not read `{}` : {}",
+				files.events.to_string_lossy(),
+				error
+			)
+		})?;
+
+		std::fs::write(
+			&files.events,
+			events.replace(r#""method":"Put""#, r#""method":"Update""#),
+		)
+		.map_err(|error| {
+			format!(
+				"can not write `{}` : {}",
+				files.events.to_string_lossy(),
+				error
+			)
+		})?;
+	}
+
+	return update_documents(&files.data, |itemdata, document_path| {
+		if itemdata.content_length.is_none() {
+			if let Ok(metadata) = std::fs::metadata(document_path) {
+				itemdata.content_length = Some(metadata.len());
+				return Ok(true);
+			}
+		}
+
+		return Ok(false);
+	});
+}
+
+/// Item metadata gets the checksum of the content of documents, see [`DataDocument::checksum`][`crate::item::DataDocument::checksum`].
+///
+/// The current content is trusted, because it can not be verified before.
+fn upgrade_from_1(files: &WorkspaceFiles) -> Result<(), String> {
+	return update_documents(&files.data, |itemdata, document_path| {
+		if itemdata.checksum.is_none() {
+			let content = std::fs::read(document_path).map_err(|error| {
+				format!(
+					"can not read `{}` : {}",
+					document_path.to_string_lossy(),
+					error
+				)
+			})?;
+			itemdata.checksum = Some(crate::item::DataDocument::checksum_of(&content));
+
+			return Ok(true);
+		}
+
+		return Ok(false);
+	});
+}
+
+/// Applies `update` on the metadata of all documents inside the data folder at `data_path`,
+/// with the path of their content, and writes it again when `update` returns `true`.
+///
+/// Broken metadata and metadata without document are skipped, they are left to `FolderStorage::check`.
+fn update_documents(
+	data_path: &std::path::Path,
+	mut update: impl FnMut(&mut crate::item::DataDocument, &std::path::Path) -> Result<bool, String>,
+) -> Result<(), String> {
+	for itemdata_path in itemdata_files(data_path)? {
+		let file_name = itemdata_path
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_default();
+		let document_name = match file_name
+			.strip_prefix('.')
+			.and_then(|name| name.strip_suffix(".itemdata.toml"))
+		{
+			Some("folder") | None => continue,
+			Some(document_name) => document_name,
+		};
+		let document_path = itemdata_path.with_file_name(document_name);
+		if !document_path.is_file() {
+			continue;
+		}
+
+		let mut itemdata = match std::fs::read(&itemdata_path)
+			.ok()
+			.and_then(|content| toml::from_slice::<crate::item::DataDocument>(&content).ok())
+		{
+			Some(itemdata) => itemdata,
+			None => continue,
+		};
+
+		if update(&mut itemdata, &document_path)? {
+			itemdata.datastruct_version = String::from(env!("CARGO_PKG_VERSION"));
+
+			let content = toml::to_vec(&itemdata).map_err(|error| {
+				format!(
+					"can not serialize `{}` : {}",
+					itemdata_path.to_string_lossy(),
+					error
+				)
+			})?;
+			std::fs::write(&itemdata_path, content).map_err(|error| {
+				format!(
+					"can not write `{}` : {}",
+					itemdata_path.to_string_lossy(),
+					error
+				)
+			})?;
+		}
+	}
+
+	return Ok(());
+}
+
+/// All `*.itemdata.toml` files inside the data folder at `data_path`.
+fn itemdata_files(data_path: &std::path::Path) -> Result<Vec<std::path::PathBuf>, String> {
+	let mut result = vec![];
+
+	if data_path.is_dir() {
+		let entries = std::fs::read_dir(data_path).map_err(|error| {
+			format!("can not read `{}` : {}", data_path.to_string_lossy(), error)
+		})?;
+
+		for entry in entries.flatten() {
+			let path = entry.path();
+
+			if path.is_dir() {
+				result.append(&mut itemdata_files(&path)?);
+			} else if entry
+				.file_name()
+				.to_string_lossy()
+				.ends_with(".itemdata.toml")
+			{
+				result.push(path);
+			}
+		}
+	}
+
+	return Ok(result);
+}
+
+fn copy(from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+	if let Some(parent) = to.parent() {
+		std::fs::create_dir_all(parent).map_err(|error| {
+			format!("can not create `{}` : {}", parent.to_string_lossy(), error)
+		})?;
+	}
+
+	return std::fs::copy(from, to).map(|_| ()).map_err(|error| {
+		format!(
+			"can not copy `{}` in `{}` : {}",
+			from.to_string_lossy(),
+			to.to_string_lossy(),
+			error
+		)
+	});
+}
+
+/// Reads the format file at `format_path`, which does not exist in workspaces written before formats were versioned.
+fn read_format(format_path: &std::path::Path) -> Result<Option<WorkspaceFormat>, String> {
+	return match std::fs::read(format_path) {
+		Ok(content) => toml::from_slice::<WorkspaceFormat>(&content)
+			.map(Some)
+			.map_err(|error| {
+				format!(
+					"can not parse `{}` : {}",
+					format_path.to_string_lossy(),
+					error
+				)
+			}),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+		Err(error) => Err(format!(
+			"can not read `{}` : {}",
+			format_path.to_string_lossy(),
+			error
+		)),
+	};
+}
+
+fn write_format(format_path: &std::path::Path, format: &WorkspaceFormat) -> Result<(), String> {
+	let content = toml::to_vec(format)
+		.map_err(|error| format!("can not serialize the workspace format : {}", error))?;
+
+	// the workspace of a fresh install does not exist yet
+	if let Some(workspace_path) = format_path.parent() {
+		std::fs::create_dir_all(workspace_path).map_err(|error| {
+			format!(
+				"can not create `{}` : {}",
+				workspace_path.to_string_lossy(),
+				error
+			)
+		})?;
+	}
+
+	return std::fs::write(format_path, content).map_err(|error| {
+		format!(
+			"can not write `{}` : {}",
+			format_path.to_string_lossy(),
+			error
+		)
+	});
+}
@@ -0,0 +1,186 @@
+use super::{upgrade_workspace, WORKSPACE_FORMAT_VERSION};
+
+fn build_logger() -> charlie_buffalo::Logger {
+	return charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::from(move |log: charlie_buffalo::Log| {
+			println!("{:?} : {:?}", log.attributes, log.content);
+		})),
+		charlie_buffalo::new_dropper(Box::from(|_: &charlie_buffalo::Logger| {})),
+	);
+}
+
+#[test]
+fn t4mw8qz1xk6rdn3vbh() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	let workspace = tmp_folder.path();
+	let data = workspace.join("storage");
+
+	std::fs::write(
+		workspace.join("settings.toml"),
+		format!("data_path = {:?}\n", data.to_string_lossy()),
+	)
+	.unwrap();
+	std::fs::write(
+		workspace.join("events.bin"),
+		concat!(
+			r#"{"id":"1","method":"Put","path":"/storage/user/A/AA"},"#,
+			"\n"
+		),
+	)
+	.unwrap();
+	std::fs::create_dir_all(data.join("A")).unwrap();
+	std::fs::write(data.join("A").join("AA"), b"content").unwrap();
+	let former_itemdata =
+		"datastruct_version = \"0.9.0\"\netag = \"ETAG\"\ncontent_type = \"text/plain\"\n";
+	std::fs::write(data.join("A").join(".AA.itemdata.toml"), former_itemdata).unwrap();
+
+	upgrade_workspace(workspace, &mut build_logger()).unwrap();
+
+	assert_eq!(
+		std::fs::read_to_string(workspace.join("format.toml")).unwrap(),
+		format!("version = {}\n", WORKSPACE_FORMAT_VERSION)
+	);
+	assert!(std::fs::read_to_string(workspace.join("events.bin"))
+		.unwrap()
+		.contains(r#""method":"Update""#));
+	let itemdata: crate::item::DataDocument =
+		toml::from_slice(&std::fs::read(data.join("A").join(".AA.itemdata.toml")).unwrap())
+			.unwrap();
+	assert_eq!(itemdata.content_length, Some(7));
+	assert_eq!(
+		itemdata.checksum,
+		Some(crate::item::DataDocument::checksum_of(b"content"))
+	);
+	assert_eq!(itemdata.etag, crate::item::Etag::from("ETAG"));
+
+	let backups: Vec<std::path::PathBuf> = std::fs::read_dir(workspace.join("backups"))
+		.unwrap()
+		.map(|entry| entry.unwrap().path())
+		.collect();
+	assert_eq!(backups.len(), 1);
+	assert!(std::fs::read_to_string(backups[0].join("events.bin"))
+		.unwrap()
+		.contains(r#""method":"Put""#));
+	assert_eq!(
+		std::fs::read_to_string(backups[0].join("data").join("A").join(".AA.itemdata.toml"))
+			.unwrap(),
+		former_itemdata
+	);
+	assert!(!backups[0].join("data").join("A").join("AA").exists());
+
+	// nothing to do anymore
+	upgrade_workspace(workspace, &mut build_logger()).unwrap();
+	assert_eq!(
+		std::fs::read_dir(workspace.join("backups"))
+			.unwrap()
+			.count(),
+		1
+	);
+}
+
+#[test]
+fn h9rc2vx7nm5qtk1wzs() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	let workspace = tmp_folder.path();
+
+	// new workspace
+	upgrade_workspace(workspace, &mut build_logger()).unwrap();
+	assert_eq!(
+		std::fs::read_to_string(workspace.join("format.toml")).unwrap(),
+		format!("version = {}\n", WORKSPACE_FORMAT_VERSION)
+	);
+	assert!(!workspace.join("backups").exists());
+
+	// written by a newer version
+	std::fs::write(
+		workspace.join("format.toml"),
+		format!("version = {}\n", WORKSPACE_FORMAT_VERSION + 1),
+	)
+	.unwrap();
+	assert!(upgrade_workspace(workspace, &mut build_logger()).is_err());
+
+	// fresh install, before the workspace is created
+	let workspace = tmp_folder.path().join("database");
+	upgrade_workspace(&workspace, &mut build_logger()).unwrap();
+	assert_eq!(
+		std::fs::read_to_string(workspace.join("format.toml")).unwrap(),
+		format!("version = {}\n", WORKSPACE_FORMAT_VERSION)
+	);
+}
+
+#[test]
+fn q2nd7wk5vr9xmt4hcz() {
+	// events of workspaces which were never upgraded are still readable
+	assert!(matches!(
+		serde_json::from_str::<crate::http_server::DbEventMethod>(r#""Put""#).unwrap(),
+		crate::http_server::DbEventMethod::Update
+	));
+	assert_eq!(
+		serde_json::to_string(&crate::http_server::DbEventMethod::Update).unwrap(),
+		r#""Update""#
+	);
+}
+
+#[test]
+fn f5xq9wm2kt7vnz3hcd() {
+	let tmp_folder = tempfile::tempdir().unwrap();
+	let workspace = tmp_folder.path();
+	upgrade_workspace(workspace, &mut build_logger()).unwrap();
+
+	let mut settings = crate::http_server::Settings::new(workspace.to_path_buf());
+	let logger = std::sync::Arc::new(std::sync::Mutex::new(build_logger()));
+	let path = crate::item::ItemPath::from("A/AA");
+	let content_etag =
+		crate::item::Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"));
+
+	crate::http_server::load_or_create_database(&settings, workspace, logger.clone())
+		.put(
+			&path,
+			crate::item::Item::new_doc(b"AA", "text/plain"),
+			&[],
+			&[],
+		)
+		.unwrap();
+	assert!(!super::has_content_etags(workspace));
+
+	// first startup after enabling the setting
+	settings.content_etags = Some(true);
+	let database =
+		crate::http_server::load_or_create_database(&settings, workspace, logger.clone());
+	assert_eq!(
+		database.get(&path, &[], &[]).unwrap().get_etag(),
+		&content_etag
+	);
+	assert!(super::has_content_etags(workspace));
+
+	// next startups do not derive them again
+	let itemdata_path = workspace.join("data").join("A").join(".AA.itemdata.toml");
+	let mut itemdata: crate::item::DataDocument =
+		toml::from_slice(&std::fs::read(&itemdata_path).unwrap()).unwrap();
+	itemdata.etag = crate::item::Etag::from("ETAG");
+	std::fs::write(&itemdata_path, toml::to_vec(&itemdata).unwrap()).unwrap();
+	let database =
+		crate::http_server::load_or_create_database(&settings, workspace, logger.clone());
+	assert_eq!(
+		database.get(&path, &[], &[]).unwrap().get_etag(),
+		&crate::item::Etag::from("ETAG")
+	);
+
+	// but they are derived again after the setting was disabled
+	settings.content_etags = Some(false);
+	crate::http_server::load_or_create_database(&settings, workspace, logger.clone());
+	assert!(!super::has_content_etags(workspace));
+	settings.content_etags = Some(true);
+	let database = crate::http_server::load_or_create_database(&settings, workspace, logger);
+	assert_eq!(
+		database.get(&path, &[], &[]).unwrap().get_etag(),
+		&content_etag
+	);
+	assert_eq!(
+		std::fs::read_to_string(workspace.join("format.toml")).unwrap(),
+		format!(
+			"version = {}\ncontent_etags = true\n",
+			WORKSPACE_FORMAT_VERSION
+		)
+	);
+}
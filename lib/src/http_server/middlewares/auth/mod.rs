@@ -57,14 +57,13 @@ where
 	fn call(&self, service_request: actix_web::dev::ServiceRequest) -> Self::Future {
 		let request_method = service_request.method().clone();
 
-		match service_request.head().headers().get("Authorization") {
-			Some(auth_value) => {
-				let search_token = auth_value
-					.to_str()
-					.unwrap()
-					.strip_prefix("Bearer ")
-					.unwrap()
-					.trim();
+		let search_token = request_token(service_request.request());
+		let is_query_token = matches!(search_token, Some((_, true)));
+		let search_token = search_token.map(|(token, _)| token);
+
+		match search_token {
+			Some(search_token) => {
+				let search_token = search_token.as_str();
 				let tokens = service_request
 					.app_data::<actix_web::web::Data<
 						std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>>,
@@ -85,19 +84,31 @@ where
 
 				match tokens.iter().find(|e| e.get_name() == search_token) {
 					Some(token) => {
-						match token.is_allowed(
+						let max_token_lifetime_seconds =
 							settings.token_lifetime_seconds.unwrap_or_else(|| {
 								crate::http_server::Settings::new(std::path::PathBuf::from("."))
 									.token_lifetime_seconds
 									.unwrap()
-							}),
-							service_request.method(),
-							service_request.path(),
+							});
+
+						match needed_accesses(&service_request).into_iter().try_fold(
+							true,
+							|allowed, (method, path)| {
+								token
+									.is_allowed(max_token_lifetime_seconds, &method, path)
+									.map(|is_allowed| allowed && is_allowed)
+							},
 						) {
 							Ok(allowed) => {
 								if allowed {
 									let future = self.service.call(service_request);
-									Box::pin(async move { future.await })
+									Box::pin(async move {
+										let mut response = future.await?;
+										if is_query_token {
+											set_private(response.headers_mut());
+										}
+										Ok(response)
+									})
 								} else {
 									self.logger.lock().unwrap().push(
 										vec![
@@ -109,21 +120,9 @@ where
 									);
 
 									Box::pin(async move {
-										// TODO : check security issue about this ?
-										let all_origins =
-											actix_web::http::header::HeaderValue::from_bytes(b"*")
-												.unwrap();
-										let headers = service_request.headers().clone();
-										let origin = headers
-											.get(actix_web::http::header::ORIGIN)
-											.unwrap_or(&all_origins)
-											.to_str()
-											.unwrap();
-
 										Ok(actix_web::dev::ServiceResponse::new(
 											service_request.into_parts().0,
 											crate::database::build_http_json_response(
-												origin,
 												&request_method,
 												actix_web::http::StatusCode::FORBIDDEN,
 												None,
@@ -146,21 +145,9 @@ where
 								);
 
 								Box::pin(async move {
-									// TODO : check security issue about this ?
-									let all_origins =
-										actix_web::http::header::HeaderValue::from_bytes(b"*")
-											.unwrap();
-									let headers = service_request.headers().clone();
-									let origin = headers
-										.get(actix_web::http::header::ORIGIN)
-										.unwrap_or(&all_origins)
-										.to_str()
-										.unwrap();
-
 									Ok(actix_web::dev::ServiceResponse::new(
 										service_request.into_parts().0,
 										crate::database::build_http_json_response(
-											origin,
 											&request_method,
 											actix_web::http::StatusCode::FORBIDDEN,
 											None,
@@ -173,32 +160,19 @@ where
 							}
 						}
 					}
-					None => {
-						Box::pin(async move {
-							// TODO : check security issue about this ?
-							let all_origins =
-								actix_web::http::header::HeaderValue::from_bytes(b"*").unwrap();
-							let headers = service_request.headers().clone();
-							let origin = headers
-								.get(actix_web::http::header::ORIGIN)
-								.unwrap_or(&all_origins)
-								.to_str()
-								.unwrap();
-
-							Ok(actix_web::dev::ServiceResponse::new(
-								service_request.into_parts().0,
-								crate::database::build_http_json_response(
-									origin,
-									&request_method,
-									actix_web::http::StatusCode::UNAUTHORIZED,
-									None,
-									None,
-									None,
-									true,
-								),
-							))
-						})
-					}
+					None => Box::pin(async move {
+						Ok(actix_web::dev::ServiceResponse::new(
+							service_request.into_parts().0,
+							crate::database::build_http_json_response(
+								&request_method,
+								actix_web::http::StatusCode::UNAUTHORIZED,
+								None,
+								None,
+								None,
+								true,
+							),
+						))
+					}),
 				}
 			}
 			None => {
@@ -228,6 +202,12 @@ where
 				{
 					let future = self.service.call(service_request);
 					Box::pin(async move { future.await })
+				} else if service_request.path().starts_with("/connect/")
+					&& (service_request.method() == actix_web::http::Method::GET
+						|| service_request.method() == actix_web::http::Method::POST)
+				{
+					let future = self.service.call(service_request);
+					Box::pin(async move { future.await })
 				} else if service_request.path() == "/favicon.ico"
 					&& service_request.method() == actix_web::http::Method::GET
 				{
@@ -248,20 +228,9 @@ where
 					Box::pin(async move { future.await })
 				} else {
 					Box::pin(async move {
-						// TODO : check security issue about this ?
-						let all_origins =
-							actix_web::http::header::HeaderValue::from_bytes(b"*").unwrap();
-						let headers = service_request.headers().clone();
-						let origin = headers
-							.get(actix_web::http::header::ORIGIN)
-							.unwrap_or(&all_origins)
-							.to_str()
-							.unwrap();
-
 						Ok(actix_web::dev::ServiceResponse::new(
 							service_request.into_parts().0,
 							crate::database::build_http_json_response(
-								origin,
 								&request_method,
 								actix_web::http::StatusCode::UNAUTHORIZED,
 								None,
@@ -276,3 +245,128 @@ where
 		}
 	}
 }
+
+/// The accesses which the token of the request should allow, as `(method, path)`.
+///
+/// `MOVE` and `COPY` requests delete or read their source, and write their destination.
+/// Operations of `POST /batch` requests are checked one by one by [`post_batch`][`crate::http_server::post_batch`],
+/// trashed documents by [`get_trash`][`crate::http_server::get_trash`] and
+/// [`restore_trashed`][`crate::http_server::restore_trashed`], and archives by
+/// [`get_archive`][`crate::http_server::get_archive`] and [`put_archive`][`crate::http_server::put_archive`],
+/// and administration by [`get_scrub`][`crate::http_server::get_scrub`].
+fn needed_accesses(
+	service_request: &actix_web::dev::ServiceRequest,
+) -> Vec<(actix_web::http::Method, String)> {
+	let path = String::from(service_request.path());
+
+	if path == "/batch" && service_request.method() == actix_web::http::Method::POST {
+		return vec![];
+	}
+
+	if path.starts_with("/trash/")
+		&& (service_request.method() == actix_web::http::Method::GET
+			|| service_request.method() == actix_web::http::Method::POST)
+	{
+		return vec![];
+	}
+
+	if path.starts_with("/archive/")
+		&& (service_request.method() == actix_web::http::Method::GET
+			|| service_request.method() == actix_web::http::Method::PUT)
+	{
+		return vec![];
+	}
+
+	if path.starts_with("/admin/") && service_request.method() == actix_web::http::Method::GET {
+		return vec![];
+	}
+
+	let source_method = match service_request.method().as_str() {
+		"MOVE" => actix_web::http::Method::DELETE,
+		"COPY" => actix_web::http::Method::GET,
+		_ => {
+			return vec![(service_request.method().clone(), path)];
+		}
+	};
+
+	// an empty path is never allowed, when the destination is missing or incorrect
+	let destination =
+		crate::http_server::api::destination_of(service_request.headers()).unwrap_or_default();
+
+	return vec![
+		(source_method, path),
+		(actix_web::http::Method::PUT, destination),
+	];
+}
+
+/// The token given by `request`, and whether it was given in its query.
+///
+/// It is taken from the `Authorization` header, or from the `access_token` query parameter of `GET` and
+/// `HEAD` requests, if the `query_access_token` setting allows it. A malformed `Authorization` header
+/// gives an empty token, which never matches any token.
+pub(crate) fn request_token(request: &actix_web::HttpRequest) -> Option<(String, bool)> {
+	match request
+		.headers()
+		.get(actix_web::http::header::AUTHORIZATION)
+	{
+		Some(auth_value) => return Some((bearer_of(auth_value).unwrap_or_default(), false)),
+		None => {
+			let query_access_token =
+				request
+					.app_data::<actix_web::web::Data<
+						std::sync::Arc<std::sync::Mutex<crate::http_server::Settings>>,
+					>>()
+					.and_then(|settings| settings.lock().unwrap().query_access_token)
+					.unwrap_or_default();
+
+			if query_access_token
+				&& (request.method() == actix_web::http::Method::GET
+					|| request.method() == actix_web::http::Method::HEAD)
+			{
+				return access_token_of(request.query_string()).map(|token| (token, true));
+			} else {
+				return None;
+			}
+		}
+	}
+}
+
+/// The token of an `Authorization: Bearer <token>` header, if it is well-formed.
+fn bearer_of(auth_value: &actix_web::http::header::HeaderValue) -> Option<String> {
+	let (scheme, token) = auth_value.to_str().ok()?.trim().split_once(' ')?;
+
+	if scheme.eq_ignore_ascii_case("bearer") && !token.trim().is_empty() {
+		return Some(String::from(token.trim()));
+	} else {
+		return None;
+	}
+}
+
+/// Adds `private` to the `Cache-Control` header, so shared caches do not store responses to requests
+/// authenticated by their URI, as per RFC 6750 section 2.3.
+fn set_private(headers: &mut actix_web::http::header::HeaderMap) {
+	let value = match headers
+		.get(actix_web::http::header::CACHE_CONTROL)
+		.and_then(|value| value.to_str().ok())
+	{
+		Some(value) if !value.trim().is_empty() => format!("{}, private", value),
+		_ => String::from("private"),
+	};
+
+	if let Ok(value) = actix_web::http::header::HeaderValue::from_str(&value) {
+		headers.insert(actix_web::http::header::CACHE_CONTROL, value);
+	}
+}
+
+/// The `access_token` parameter of a query string, as per RFC 6750 section 2.3.
+fn access_token_of(query_string: &str) -> Option<String> {
+	return query_string.split('&').find_map(|parameter| {
+		let (name, value) = parameter.split_once('=')?;
+
+		if name == "access_token" && !value.is_empty() {
+			Some(pct_str::PctString::new(value).ok()?.decode())
+		} else {
+			None
+		}
+	});
+}
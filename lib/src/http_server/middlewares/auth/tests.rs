@@ -24,7 +24,8 @@ async fn hsv5femo2qgu80gbad0ov5() {
 			.service(crate::http_server::api::get_item)
 			.service(crate::http_server::webfinger_handle)
 			.service(crate::http_server::get_oauth)
-			.service(crate::http_server::post_oauth),
+			.service(crate::http_server::post_oauth)
+			.service(crate::http_server::get_connect),
 	)
 	.await;
 
@@ -38,6 +39,7 @@ async fn hsv5femo2qgu80gbad0ov5() {
 		(070, "/storage/public/user/folder/document", false),
 		(080, "/.well-known/webfinger", false),
 		(090, "/oauth", false),
+		(095, "/connect/user", false),
 		(100, "/favicon.ico", false),
 		(110, "/remotestorage.svg", false),
 		(120, "/", false),
@@ -93,6 +95,7 @@ async fn kp6m20xdwvw6v4t3yxq() {
 							crate::item::Item::Document {
 								etag: crate::item::Etag::new(),
 								content: Some(b"HELLO".to_vec()),
+								content_length: b"HELLO".len() as u64,
 								content_type: crate::item::ContentType::from("text/plain"),
 								last_modified: Some(time::OffsetDateTime::now_utc()),
 							},
@@ -105,6 +108,7 @@ async fn kp6m20xdwvw6v4t3yxq() {
 							crate::item::Item::Document {
 								etag: crate::item::Etag::new(),
 								content: Some(b"HELLO".to_vec()),
+								content_length: b"HELLO".len() as u64,
 								content_type: crate::item::ContentType::from("text/plain"),
 								last_modified: Some(time::OffsetDateTime::now_utc()),
 							},
@@ -120,6 +124,7 @@ async fn kp6m20xdwvw6v4t3yxq() {
 									crate::item::Item::Document {
 										etag: crate::item::Etag::new(),
 										content: Some(b"HELLO".to_vec()),
+										content_length: b"HELLO".len() as u64,
 										content_type: crate::item::ContentType::from("text/plain"),
 										last_modified: Some(time::OffsetDateTime::now_utc()),
 									},
@@ -132,6 +137,7 @@ async fn kp6m20xdwvw6v4t3yxq() {
 									crate::item::Item::Document {
 										etag: crate::item::Etag::new(),
 										content: Some(b"HELLO".to_vec()),
+										content_length: b"HELLO".len() as u64,
 										content_type: crate::item::ContentType::from("text/plain"),
 										last_modified: Some(time::OffsetDateTime::now_utc()),
 									},
@@ -142,7 +148,6 @@ async fn kp6m20xdwvw6v4t3yxq() {
 				]),
 			)]),
 		}));
-	let database = std::sync::Arc::new(std::sync::Mutex::new(database));
 
 	let settings = std::sync::Arc::new(std::sync::Mutex::new(crate::http_server::Settings::new(
 		tempfile::tempdir().unwrap().into_path(),
@@ -481,3 +486,254 @@ async fn kp6m20xdwvw6v4t3yxq() {
 		println!("OK");
 	}
 }
+
+#[actix_rt::test]
+async fn q8ct3vn0hx5mzr2kwb() {
+	let access_tokens: std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>> =
+		std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+	let token = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("pictures"),
+			right_type: crate::scope::ScopeRightType::Read,
+		}],
+		"test",
+		"user",
+	);
+	access_tokens.lock().unwrap().push(token.clone());
+
+	let database =
+		crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+			root_item: crate::item::Item::new_folder(vec![(
+				"user",
+				crate::item::Item::new_folder(vec![(
+					"pictures",
+					crate::item::Item::new_folder(vec![(
+						"a.png",
+						crate::item::Item::new_doc(b"PNG", "image/png"),
+					)]),
+				)]),
+			)]),
+		}));
+
+	let logger = Arc::new(Mutex::new(charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::new(|_| {})),
+		None,
+	)));
+
+	let encoded_token =
+		pct_str::PctString::encode(token.get_name().chars(), pct_str::URIReserved).to_string();
+
+	for query_access_token in [false, true] {
+		let mut settings =
+			crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+		settings.query_access_token = Some(query_access_token);
+		let settings = std::sync::Arc::new(std::sync::Mutex::new(settings));
+
+		let app = actix_web::test::init_service(
+			actix_web::App::new()
+				.app_data(actix_web::web::Data::new(database.clone()))
+				.app_data(actix_web::web::Data::new(access_tokens.clone()))
+				.app_data(actix_web::web::Data::new(settings.clone()))
+				.app_data(actix_web::web::Data::new(logger.clone()))
+				.wrap(super::Auth {
+					logger: logger.clone(),
+				})
+				.service(crate::http_server::api::get_item),
+		)
+		.await;
+
+		let tests = vec![
+			(
+				actix_web::http::header::HeaderValue::from_str(&format!(
+					"bearer {}",
+					token.get_name()
+				))
+				.unwrap(),
+				actix_web::http::StatusCode::OK,
+			),
+			(
+				actix_web::http::header::HeaderValue::from_static("Basic dXNlcjpwYXNzd29yZA=="),
+				actix_web::http::StatusCode::UNAUTHORIZED,
+			),
+			(
+				actix_web::http::header::HeaderValue::from_static("Bearer"),
+				actix_web::http::StatusCode::UNAUTHORIZED,
+			),
+			(
+				actix_web::http::header::HeaderValue::from_bytes(b"Bearer \xff").unwrap(),
+				actix_web::http::StatusCode::UNAUTHORIZED,
+			),
+		];
+
+		for (auth_value, expected_status) in tests {
+			let request = actix_web::test::TestRequest::get()
+				.uri("/storage/user/pictures/a.png")
+				.insert_header(("Authorization", auth_value))
+				.to_request();
+
+			let response = actix_web::test::call_service(&app, request).await;
+			assert_eq!(response.status(), expected_status);
+			assert!(!response
+				.headers()
+				.get(actix_web::http::header::CACHE_CONTROL)
+				.map(|value| value.to_str().unwrap().contains("private"))
+				.unwrap_or_default());
+		}
+
+		let request = actix_web::test::TestRequest::get()
+			.uri(&format!(
+				"/storage/user/pictures/a.png?access_token={}",
+				encoded_token
+			))
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+		if query_access_token {
+			assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+			assert!(response
+				.headers()
+				.get(actix_web::http::header::CACHE_CONTROL)
+				.unwrap()
+				.to_str()
+				.unwrap()
+				.split(',')
+				.any(|directive| directive.trim() == "private"));
+		} else {
+			assert_eq!(response.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+		}
+
+		let request = actix_web::test::TestRequest::get()
+			.uri("/storage/user/pictures/a.png?access_token=wrong")
+			.to_request();
+		assert_eq!(
+			actix_web::test::call_service(&app, request).await.status(),
+			actix_web::http::StatusCode::UNAUTHORIZED
+		);
+
+		let request = actix_web::test::TestRequest::put()
+			.uri(&format!(
+				"/storage/user/pictures/b.png?access_token={}",
+				encoded_token
+			))
+			.to_request();
+		assert_eq!(
+			actix_web::test::call_service(&app, request).await.status(),
+			actix_web::http::StatusCode::UNAUTHORIZED
+		);
+	}
+}
+
+#[actix_rt::test]
+async fn x7kd2mq9wz4hvt1cnp() {
+	let access_tokens: std::sync::Arc<std::sync::Mutex<Vec<crate::http_server::AccessBearer>>> =
+		std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+	let read_write = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("notes"),
+			right_type: crate::scope::ScopeRightType::ReadWrite,
+		}],
+		"test",
+		"user",
+	);
+	let read_only = crate::http_server::AccessBearer::new(
+		vec![crate::scope::Scope {
+			module: String::from("notes"),
+			right_type: crate::scope::ScopeRightType::Read,
+		}],
+		"test",
+		"user",
+	);
+	access_tokens.lock().unwrap().push(read_write.clone());
+	access_tokens.lock().unwrap().push(read_only.clone());
+
+	let settings = std::sync::Arc::new(std::sync::Mutex::new(crate::http_server::Settings::new(
+		tempfile::tempdir().unwrap().into_path(),
+	)));
+
+	let logger = Arc::new(Mutex::new(charlie_buffalo::Logger::new(
+		charlie_buffalo::new_dispatcher(Box::new(|_| {})),
+		None,
+	)));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(access_tokens.clone()))
+			.app_data(actix_web::web::Data::new(settings.clone()))
+			.wrap(super::Auth {
+				logger: logger.clone(),
+			})
+			.default_service(actix_web::web::to(|| async {
+				actix_web::HttpResponse::ImATeapot().finish()
+			})),
+	)
+	.await;
+
+	let tests = vec![
+		(
+			&read_write,
+			"MOVE",
+			"/storage/user/notes/a.txt",
+			"/storage/user/notes/b.txt",
+			actix_web::http::StatusCode::IM_A_TEAPOT,
+		),
+		(
+			&read_write,
+			"COPY",
+			"/storage/user/notes/a.txt",
+			"https://example.com/storage/user/notes/archives/a.txt",
+			actix_web::http::StatusCode::IM_A_TEAPOT,
+		),
+		(
+			&read_write,
+			"MOVE",
+			"/storage/user/notes/a.txt",
+			"/storage/user/pictures/a.txt",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+		(
+			&read_write,
+			"COPY",
+			"/storage/user/pictures/a.png",
+			"/storage/user/notes/a.png",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+		(
+			&read_write,
+			"MOVE",
+			"/storage/user/notes/a.txt",
+			"/storage/user/notes%2F..%2F..%2Fother%2Fnotes/a.txt",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+		(
+			&read_only,
+			"MOVE",
+			"/storage/user/notes/a.txt",
+			"/storage/user/notes/b.txt",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+		(
+			&read_only,
+			"COPY",
+			"/storage/user/notes/a.txt",
+			"/storage/user/notes/b.txt",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+	];
+
+	for (token, method, uri, destination, expected_status) in tests {
+		println!("{} {} to {}", method, uri, destination);
+
+		let request = actix_web::test::TestRequest::default()
+			.method(actix_web::http::Method::from_bytes(method.as_bytes()).unwrap())
+			.uri(uri)
+			.insert_header(("Authorization", format!("Bearer {}", token.get_name())))
+			.insert_header(("Destination", destination))
+			.to_request();
+
+		assert_eq!(
+			actix_web::test::call_service(&app, request).await.status(),
+			expected_status
+		);
+	}
+}
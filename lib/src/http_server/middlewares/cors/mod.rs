@@ -0,0 +1,346 @@
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod tests;
+
+/// Apply the CORS policy of the server, as required by the remoteStorage draft.
+///
+/// Preflight requests are answered here, according to the methods and headers
+/// allowed by each route (see [`route_policy`]).
+/// All other responses get the CORS headers, if the origin of the request is
+/// allowed by the `cors_allowed_origins` field of
+/// [`Settings`][`crate::http_server::Settings`].
+pub struct Cors;
+
+impl<S> actix_web::dev::Transform<S, actix_web::dev::ServiceRequest> for Cors
+where
+	S: actix_web::dev::Service<
+		actix_web::dev::ServiceRequest,
+		Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>,
+		Error = actix_web::Error,
+	>,
+	S::Future: 'static,
+{
+	type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+	type Error = actix_web::Error;
+	type InitError = ();
+	type Transform = CorsMiddleware<S>;
+	type Future = futures::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+	fn new_transform(&self, service: S) -> Self::Future {
+		futures::future::ok(Self::Transform { service })
+	}
+}
+
+pub struct CorsMiddleware<S> {
+	service: S,
+}
+
+impl<S> actix_web::dev::Service<actix_web::dev::ServiceRequest> for CorsMiddleware<S>
+where
+	S: actix_web::dev::Service<
+		actix_web::dev::ServiceRequest,
+		Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>,
+		Error = actix_web::Error,
+	>,
+	S::Future: 'static,
+{
+	type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+	type Error = actix_web::Error;
+	type Future =
+		futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	actix_web::dev::forward_ready!(service);
+
+	fn call(&self, service_request: actix_web::dev::ServiceRequest) -> Self::Future {
+		let origin = service_request
+			.headers()
+			.get(actix_web::http::header::ORIGIN)
+			.and_then(|origin| origin.to_str().ok())
+			.map(String::from);
+
+		let allowed_origins = service_request
+			.app_data::<actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>>()
+			.and_then(|settings| settings.lock().unwrap().cors_allowed_origins.clone());
+
+		let origin_is_allowed = match &origin {
+			Some(origin) => is_origin_allowed(origin, allowed_origins.as_deref()),
+			None => true,
+		};
+
+		let requested_method = service_request
+			.headers()
+			.get(actix_web::http::header::ACCESS_CONTROL_REQUEST_METHOD)
+			.and_then(|method| method.to_str().ok())
+			.map(String::from);
+
+		if let (Some(origin), Some(requested_method), Some(policy)) = (
+			&origin,
+			requested_method,
+			route_policy(service_request.path()),
+		) {
+			if service_request.method() == actix_web::http::Method::OPTIONS {
+				let requested_headers = service_request
+					.headers()
+					.get(actix_web::http::header::ACCESS_CONTROL_REQUEST_HEADERS)
+					.and_then(|headers| headers.to_str().ok())
+					.map(String::from)
+					.unwrap_or_default();
+
+				let response = preflight_response(
+					origin,
+					origin_is_allowed,
+					&requested_method,
+					&requested_headers,
+					&policy,
+				);
+
+				return Box::pin(async move {
+					Ok(actix_web::dev::ServiceResponse::new(
+						service_request.into_parts().0,
+						response,
+					))
+				});
+			}
+		}
+
+		let future = self.service.call(service_request);
+		Box::pin(async move {
+			let mut response = future.await?;
+
+			if origin_is_allowed {
+				let headers = response.headers_mut();
+
+				match origin {
+					Some(origin) => {
+						if let Ok(origin) = actix_web::http::header::HeaderValue::from_str(&origin)
+						{
+							headers.insert(
+								actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN,
+								origin,
+							);
+						}
+						headers.append(
+							actix_web::http::header::VARY,
+							actix_web::http::header::HeaderValue::from_static("Origin"),
+						);
+					}
+					None => {
+						headers.insert(
+							actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN,
+							actix_web::http::header::HeaderValue::from_static("*"),
+						);
+					}
+				}
+
+				headers.insert(
+					actix_web::http::header::ACCESS_CONTROL_EXPOSE_HEADERS,
+					actix_web::http::header::HeaderValue::from_static(EXPOSED_HEADERS),
+				);
+			}
+
+			Ok(response)
+		})
+	}
+}
+
+const EXPOSED_HEADERS: &str = "Content-Length, Content-Type, ETag, Last-Modified";
+const PREFLIGHT_MAX_AGE_SECONDS: u32 = 600;
+
+/// Methods and headers a route accepts from cross-origin requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsRoutePolicy {
+	pub methods: Vec<actix_web::http::Method>,
+	pub headers: Vec<&'static str>,
+}
+
+/// Returns the CORS policy of the route which handles `path`, if any.
+pub fn route_policy(path: &str) -> Option<CorsRoutePolicy> {
+	if path.starts_with("/storage/") {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+				actix_web::http::Method::HEAD,
+				actix_web::http::Method::PUT,
+				actix_web::http::Method::DELETE,
+				actix_web::http::Method::from_bytes(b"MOVE").unwrap(),
+				actix_web::http::Method::from_bytes(b"COPY").unwrap(),
+			],
+			headers: vec![
+				"Authorization",
+				"Content-Length",
+				"Content-Type",
+				"Origin",
+				"If-Match",
+				"If-None-Match",
+				"Destination",
+				"Overwrite",
+			],
+		})
+	} else if path == "/.well-known/webfinger" {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+				actix_web::http::Method::HEAD,
+			],
+			headers: vec!["Origin"],
+		})
+	} else if path == "/batch" {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::POST,
+			],
+			headers: vec!["Authorization", "Content-Length", "Content-Type", "Origin"],
+		})
+	} else if path.starts_with("/trash/") {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+				actix_web::http::Method::POST,
+			],
+			headers: vec!["Authorization", "Origin"],
+		})
+	} else if path.starts_with("/archive/") {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+				actix_web::http::Method::PUT,
+			],
+			headers: vec!["Authorization", "Content-Length", "Content-Type", "Origin"],
+		})
+	} else if path.starts_with("/admin/") {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+			],
+			headers: vec!["Authorization", "Origin"],
+		})
+	} else if path == "/events/all" {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+			],
+			headers: vec!["Authorization", "Last-Event-ID", "Origin"],
+		})
+	} else if path == "/favicon.ico" || path == "/remotestorage.svg" {
+		Some(CorsRoutePolicy {
+			methods: vec![
+				actix_web::http::Method::OPTIONS,
+				actix_web::http::Method::GET,
+			],
+			headers: vec!["Origin"],
+		})
+	} else {
+		None
+	}
+}
+
+/// Check if `origin` is part of `allowed_origins`.
+///
+/// If `allowed_origins` is [`None`][`Option::None`] or contains `*`, all origins are allowed.
+pub fn is_origin_allowed(origin: &str, allowed_origins: Option<&[String]>) -> bool {
+	match allowed_origins {
+		Some(allowed_origins) => {
+			let origin = origin.trim().trim_end_matches('/').to_lowercase();
+
+			allowed_origins.iter().any(|allowed_origin| {
+				let allowed_origin = allowed_origin.trim().trim_end_matches('/').to_lowercase();
+
+				allowed_origin == "*" || allowed_origin == origin
+			})
+		}
+		None => true,
+	}
+}
+
+fn preflight_response(
+	origin: &str,
+	origin_is_allowed: bool,
+	requested_method: &str,
+	requested_headers: &str,
+	policy: &CorsRoutePolicy,
+) -> actix_web::HttpResponse {
+	if !origin_is_allowed {
+		return crate::database::build_http_json_response(
+			&actix_web::http::Method::OPTIONS,
+			actix_web::http::StatusCode::FORBIDDEN,
+			None,
+			None,
+			Some(format!("the origin `{origin}` is not allowed")),
+			true,
+		);
+	}
+
+	let method_is_allowed = actix_web::http::Method::from_bytes(requested_method.trim().as_bytes())
+		.map(|requested_method| policy.methods.contains(&requested_method))
+		.unwrap_or(false);
+	if !method_is_allowed {
+		return crate::database::build_http_json_response(
+			&actix_web::http::Method::OPTIONS,
+			actix_web::http::StatusCode::METHOD_NOT_ALLOWED,
+			None,
+			None,
+			Some(format!(
+				"the method `{}` is not allowed on this route",
+				requested_method.trim()
+			)),
+			true,
+		);
+	}
+
+	if let Some(forbidden_header) = requested_headers
+		.split(',')
+		.map(str::trim)
+		.filter(|header| !header.is_empty())
+		.find(|header| {
+			!policy
+				.headers
+				.iter()
+				.any(|allowed| allowed.eq_ignore_ascii_case(header))
+		}) {
+		return crate::database::build_http_json_response(
+			&actix_web::http::Method::OPTIONS,
+			actix_web::http::StatusCode::FORBIDDEN,
+			None,
+			None,
+			Some(format!(
+				"the header `{forbidden_header}` is not allowed on this route"
+			)),
+			true,
+		);
+	}
+
+	let mut response = actix_web::HttpResponse::Ok();
+	response.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin));
+	response.insert_header((actix_web::http::header::VARY, "Origin"));
+	response.insert_header((
+		actix_web::http::header::ACCESS_CONTROL_ALLOW_METHODS,
+		policy
+			.methods
+			.iter()
+			.map(actix_web::http::Method::as_str)
+			.collect::<Vec<&str>>()
+			.join(", "),
+	));
+	response.insert_header((
+		actix_web::http::header::ACCESS_CONTROL_ALLOW_HEADERS,
+		policy.headers.join(", "),
+	));
+	response.insert_header((
+		actix_web::http::header::ACCESS_CONTROL_EXPOSE_HEADERS,
+		EXPOSED_HEADERS,
+	));
+	response.insert_header((
+		actix_web::http::header::ACCESS_CONTROL_MAX_AGE,
+		PREFLIGHT_MAX_AGE_SECONDS.to_string(),
+	));
+
+	return response.finish();
+}
@@ -0,0 +1,210 @@
+use std::sync::{Arc, Mutex};
+
+fn build_settings(
+	allowed_origins: Option<Vec<String>>,
+) -> Arc<Mutex<crate::http_server::Settings>> {
+	let mut settings = crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+	settings.cors_allowed_origins = allowed_origins;
+
+	return Arc::new(Mutex::new(settings));
+}
+
+#[test]
+fn ad2sy0qw9tzmnwtgeq() {
+	assert!(super::is_origin_allowed("https://example.com", None));
+	assert!(super::is_origin_allowed(
+		"https://example.com",
+		Some(&[String::from("*")])
+	));
+	assert!(super::is_origin_allowed(
+		"https://example.com",
+		Some(&[String::from("https://Example.com/")])
+	));
+	assert!(!super::is_origin_allowed(
+		"https://example.com",
+		Some(&[String::from("https://other.example.com")])
+	));
+	assert!(!super::is_origin_allowed("https://example.com", Some(&[])));
+}
+
+#[test]
+fn k1kcj0m1jrjg8fvz6pe() {
+	assert!(super::route_policy("/storage/user/document").is_some());
+	assert!(super::route_policy("/.well-known/webfinger").is_some());
+	assert!(super::route_policy("/events/all").is_some());
+	assert!(super::route_policy("/not/a/route").is_none());
+
+	let storage_policy = super::route_policy("/storage/user/document").unwrap();
+	assert!(storage_policy
+		.methods
+		.contains(&actix_web::http::Method::PUT));
+	assert!(storage_policy.headers.contains(&"If-None-Match"));
+
+	let webfinger_policy = super::route_policy("/.well-known/webfinger").unwrap();
+	assert!(!webfinger_policy
+		.methods
+		.contains(&actix_web::http::Method::PUT));
+}
+
+#[actix_rt::test]
+async fn jqr2utc5syr8kdm6ijd() {
+	let settings = build_settings(Some(vec![String::from("https://example.com")]));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(settings))
+			.wrap(super::Cors)
+			.service(crate::http_server::api::options_item),
+	)
+	.await;
+
+	let tests = vec![
+		(
+			10,
+			"https://example.com",
+			"PUT",
+			"Authorization, Content-Type, If-Match",
+			actix_web::http::StatusCode::OK,
+		),
+		(
+			20,
+			"https://example.com",
+			"PATCH",
+			"",
+			actix_web::http::StatusCode::METHOD_NOT_ALLOWED,
+		),
+		(
+			30,
+			"https://example.com",
+			"GET",
+			"X-Custom-Header",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+		(
+			40,
+			"https://evil.example.com",
+			"GET",
+			"",
+			actix_web::http::StatusCode::FORBIDDEN,
+		),
+	];
+
+	for test in tests {
+		print!(
+			"#{:03} : preflight from {} for {} ... ",
+			test.0, test.1, test.2
+		);
+
+		let mut request = actix_web::test::TestRequest::default()
+			.method(actix_web::http::Method::OPTIONS)
+			.uri("/storage/user/document")
+			.insert_header((actix_web::http::header::ORIGIN, test.1))
+			.insert_header((
+				actix_web::http::header::ACCESS_CONTROL_REQUEST_METHOD,
+				test.2,
+			));
+		if !test.3.is_empty() {
+			request = request.insert_header((
+				actix_web::http::header::ACCESS_CONTROL_REQUEST_HEADERS,
+				test.3,
+			));
+		}
+
+		let response = actix_web::test::call_service(&app, request.to_request()).await;
+		assert_eq!(response.status(), test.4);
+
+		if test.4 == actix_web::http::StatusCode::OK {
+			let headers = response.headers();
+			assert_eq!(
+				headers
+					.get(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+					.unwrap(),
+				test.1
+			);
+			assert_eq!(
+				headers.get(actix_web::http::header::VARY).unwrap(),
+				"Origin"
+			);
+			assert!(headers
+				.get(actix_web::http::header::ACCESS_CONTROL_ALLOW_METHODS)
+				.unwrap()
+				.to_str()
+				.unwrap()
+				.contains("PUT"));
+			assert!(headers
+				.get(actix_web::http::header::ACCESS_CONTROL_MAX_AGE)
+				.is_some());
+		} else {
+			assert!(response
+				.headers()
+				.get(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+				.is_none());
+		}
+
+		println!("OK");
+	}
+}
+
+#[actix_rt::test]
+async fn o8r4b6cs0hf1rjw3vql() {
+	let settings = build_settings(Some(vec![String::from("https://example.com")]));
+
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(settings))
+			.wrap(super::Cors)
+			.service(crate::http_server::get_favicon),
+	)
+	.await;
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/favicon.ico")
+		.insert_header((actix_web::http::header::ORIGIN, "https://example.com"))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+	assert_eq!(
+		response
+			.headers()
+			.get(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+			.unwrap(),
+		"https://example.com"
+	);
+	assert_eq!(
+		response
+			.headers()
+			.get(actix_web::http::header::VARY)
+			.unwrap(),
+		"Origin"
+	);
+	assert!(response
+		.headers()
+		.get(actix_web::http::header::ACCESS_CONTROL_EXPOSE_HEADERS)
+		.unwrap()
+		.to_str()
+		.unwrap()
+		.contains("ETag"));
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/favicon.ico")
+		.insert_header((actix_web::http::header::ORIGIN, "https://evil.example.com"))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+	assert!(response
+		.headers()
+		.get(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+		.is_none());
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/favicon.ico")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(
+		response
+			.headers()
+			.get(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+			.unwrap(),
+		"*"
+	);
+}
@@ -60,7 +60,7 @@ where
 			(String::from("path"), String::from(service_request.path())),
 			(
 				String::from("query"),
-				String::from(service_request.query_string()),
+				redacted_query(service_request.query_string()),
 			),
 		];
 		if let Some(peer_addr) = service_request.connection_info().realip_remote_addr() {
@@ -103,3 +103,15 @@ where
 		})
 	}
 }
+
+/// Hides the value of the `access_token` parameters of `query_string`, which are secrets.
+fn redacted_query(query_string: &str) -> String {
+	return query_string
+		.split('&')
+		.map(|parameter| match parameter.split_once('=') {
+			Some(("access_token", _)) => String::from("access_token=REDACTED"),
+			_ => String::from(parameter),
+		})
+		.collect::<Vec<String>>()
+		.join("&");
+}
@@ -1,7 +1,11 @@
 mod auth;
+mod cors;
 mod hsts;
 mod logger;
+mod web_authoring;
 
 pub use auth::*;
+pub use cors::*;
 pub use hsts::*;
 pub use logger::*;
+pub use web_authoring::*;
@@ -0,0 +1,303 @@
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod tests;
+
+/// Serves the `/storage/public/<user>/www/` folder of each user as a static website,
+/// if the `web_authoring` field of [`Settings`][`crate::http_server::Settings`] is set.
+///
+/// Websites are found on a subdomain (like `https://<user>.sites.example.com/`) and/or
+/// on a path of this server (like `https://example.com/sites/<user>/`), see [`site_request`].
+/// Their documents are public, so this middleware should wrap [`Auth`][`super::Auth`].
+pub struct WebAuthoring;
+
+impl<S> actix_web::dev::Transform<S, actix_web::dev::ServiceRequest> for WebAuthoring
+where
+	S: actix_web::dev::Service<
+		actix_web::dev::ServiceRequest,
+		Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>,
+		Error = actix_web::Error,
+	>,
+	S::Future: 'static,
+{
+	type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+	type Error = actix_web::Error;
+	type InitError = ();
+	type Transform = WebAuthoringMiddleware<S>;
+	type Future = futures::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+	fn new_transform(&self, service: S) -> Self::Future {
+		futures::future::ok(Self::Transform { service })
+	}
+}
+
+pub struct WebAuthoringMiddleware<S> {
+	service: S,
+}
+
+impl<S> actix_web::dev::Service<actix_web::dev::ServiceRequest> for WebAuthoringMiddleware<S>
+where
+	S: actix_web::dev::Service<
+		actix_web::dev::ServiceRequest,
+		Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>,
+		Error = actix_web::Error,
+	>,
+	S::Future: 'static,
+{
+	type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+	type Error = actix_web::Error;
+	type Future =
+		futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	actix_web::dev::forward_ready!(service);
+
+	fn call(&self, service_request: actix_web::dev::ServiceRequest) -> Self::Future {
+		let settings = service_request
+			.app_data::<actix_web::web::Data<Arc<Mutex<crate::http_server::Settings>>>>()
+			.and_then(|settings| settings.lock().unwrap().web_authoring.clone());
+
+		let is_read = service_request.method() == actix_web::http::Method::GET
+			|| service_request.method() == actix_web::http::Method::HEAD;
+
+		let site = match &settings {
+			Some(settings) if is_read => site_request(
+				settings,
+				service_request.connection_info().host(),
+				service_request.path(),
+			),
+			_ => None,
+		};
+
+		let (site, settings) = match (site, settings) {
+			(Some(site), Some(settings)) => (site, settings),
+			_ => {
+				return Box::pin(self.service.call(service_request));
+			}
+		};
+
+		let database = service_request
+			.app_data::<actix_web::web::Data<crate::database::Database>>()
+			.cloned();
+		let if_none_match =
+			crate::http_server::api::convert_actix_if_none_match(service_request.request());
+
+		Box::pin(async move {
+			let response = match database {
+				Some(database) => {
+					site_response(
+						&database,
+						&site,
+						service_request.path(),
+						service_request.method(),
+						if_none_match,
+						settings.cache_max_age_seconds,
+					)
+					.await
+				}
+				None => crate::database::build_http_json_response(
+					service_request.method(),
+					actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+					None,
+					None,
+					None,
+					true,
+				),
+			};
+
+			Ok(actix_web::dev::ServiceResponse::new(
+				service_request.into_parts().0,
+				response,
+			))
+		})
+	}
+}
+
+/// A request to a document of the website of a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteRequest {
+	pub username: String,
+	/// The decoded path of the document in the `www` folder of the user, without leading `/`.
+	///
+	/// It is empty or ends with `/` when the index document of a folder is requested.
+	pub path: String,
+	/// The request should be redirected to the same path with a trailing `/`
+	/// (like `/sites/<user>` to `/sites/<user>/`).
+	pub add_trailing_slash: bool,
+}
+
+/// Finds which website is requested by `host` and `path`, according to `settings`.
+///
+/// Returns [`None`][`Option::None`] if this request is not for a website (or if it
+/// tries to escape from the `www` folder of the user), so it should be handled as usual.
+pub fn site_request(
+	settings: &crate::http_server::SettingsWebAuthoring,
+	host: &str,
+	path: &str,
+) -> Option<SiteRequest> {
+	let path = pct_str::PctString::new(path).ok()?.decode();
+
+	let mut result = None;
+
+	if let Some(domain) = &settings.domain {
+		let domain = domain.trim().trim_matches('.').to_lowercase();
+		let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+		let host = host.trim_end_matches('.').to_lowercase();
+
+		if let Some(username) = host
+			.strip_suffix(&domain)
+			.and_then(|username| username.strip_suffix('.'))
+		{
+			if !domain.is_empty() && !username.is_empty() && !username.contains('.') {
+				result = Some(SiteRequest {
+					username: String::from(username),
+					path: String::from(path.trim_start_matches('/')),
+					add_trailing_slash: false,
+				});
+			}
+		}
+	}
+
+	if result.is_none() {
+		if let Some(prefix) = &settings.path {
+			let prefix = prefix.trim().trim_matches('/');
+
+			if let Some(rest) = path
+				.strip_prefix('/')
+				.and_then(|path| path.strip_prefix(prefix))
+				.and_then(|path| path.strip_prefix('/'))
+			{
+				let (username, path, add_trailing_slash) = match rest.split_once('/') {
+					Some((username, path)) => (username, path, false),
+					None => (rest, "", true),
+				};
+
+				if !prefix.is_empty() && !username.is_empty() {
+					result = Some(SiteRequest {
+						username: String::from(username),
+						path: String::from(path),
+						add_trailing_slash,
+					});
+				}
+			}
+		}
+	}
+
+	return result.filter(|site| {
+		!site.username.contains(['.', '\\'])
+			&& !site.path.contains('\\')
+			&& !site
+				.path
+				.split('/')
+				.any(|segment| segment == "." || segment == "..")
+	});
+}
+
+fn document_path(site: &SiteRequest, path: &str) -> crate::item::ItemPath {
+	return crate::item::ItemPath::from(format!("public/{}/www/{}", site.username, path).as_str());
+}
+
+async fn site_response(
+	database: &crate::database::Database,
+	site: &SiteRequest,
+	request_path: &str,
+	method: &actix_web::http::Method,
+	if_none_match: Vec<crate::item::Etag>,
+	cache_max_age_seconds: Option<u64>,
+) -> actix_web::HttpResponse {
+	if site.add_trailing_slash {
+		return redirection(request_path);
+	}
+
+	let path = if site.path.is_empty() || site.path.ends_with('/') {
+		format!("{}index.html", site.path)
+	} else {
+		site.path.clone()
+	};
+
+	match crate::database::AsyncDataSource::get(
+		database,
+		document_path(site, &path),
+		vec![],
+		if_none_match,
+		true,
+	)
+	.await
+	{
+		Ok(crate::item::Item::Document {
+			etag,
+			content: Some(content),
+			content_type,
+			last_modified,
+			..
+		}) => {
+			let etag: String = etag.into();
+			let content_type: String = content_type.into();
+
+			let mut response = actix_web::HttpResponse::Ok();
+			response.insert_header((actix_web::http::header::ETAG, etag));
+			if let Some(last_modified) = last_modified {
+				response.insert_header((
+					actix_web::http::header::LAST_MODIFIED,
+					last_modified
+						.format(&time::format_description::well_known::Rfc2822)
+						.unwrap_or_default(),
+				));
+			}
+			response.insert_header((
+				actix_web::http::header::CACHE_CONTROL,
+				match cache_max_age_seconds {
+					Some(max_age) => format!("public, max-age={}", max_age),
+					None => String::from("no-cache"),
+				},
+			));
+			response.content_type(content_type);
+
+			if method == actix_web::http::Method::HEAD {
+				response.insert_header((actix_web::http::header::CONTENT_LENGTH, content.len()));
+				return response.finish();
+			}
+
+			return response.body(content);
+		}
+		Err(
+			error @ (crate::database::DataSourceError::NotFound { .. }
+			| crate::database::DataSourceError::Conflict { .. }),
+		) => {
+			// `<path>` may be a folder which has an index document
+			if !site.path.is_empty() && !site.path.ends_with('/') {
+				let index = crate::database::AsyncDataSource::get(
+					database,
+					document_path(site, &format!("{}/index.html", site.path)),
+					vec![],
+					vec![],
+					false,
+				)
+				.await;
+
+				if let Ok(crate::item::Item::Document { .. }) = index {
+					return redirection(request_path);
+				}
+			}
+
+			return error.to_response(method, true);
+		}
+		Ok(_) => {
+			return crate::database::DataSourceError::NotFound {
+				item_path: document_path(site, &path),
+			}
+			.to_response(method, true);
+		}
+		Err(error) => {
+			return error.to_response(method, true);
+		}
+	}
+}
+
+fn redirection(request_path: &str) -> actix_web::HttpResponse {
+	return actix_web::HttpResponse::MovedPermanently()
+		.insert_header((
+			actix_web::http::header::LOCATION,
+			format!("{}/", request_path),
+		))
+		.finish();
+}
@@ -0,0 +1,285 @@
+use actix_web::http::StatusCode;
+use std::sync::{Arc, Mutex};
+
+fn build_settings(
+	web_authoring: Option<crate::http_server::SettingsWebAuthoring>,
+) -> Arc<Mutex<crate::http_server::Settings>> {
+	let mut settings = crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+	settings.web_authoring = web_authoring;
+
+	return Arc::new(Mutex::new(settings));
+}
+
+fn build_document(content: &[u8], content_type: &str) -> crate::item::Item {
+	return crate::item::Item::Document {
+		etag: crate::item::Etag::from_content(
+			content,
+			&crate::item::ContentType::from(content_type),
+		),
+		content: Some(content.to_vec()),
+		content_length: content.len() as u64,
+		content_type: crate::item::ContentType::from(content_type),
+		last_modified: Some(time::OffsetDateTime::now_utc()),
+	};
+}
+
+fn build_database() -> crate::database::Database {
+	return crate::database::Database::new(Box::new(crate::database::sources::MemoryStorage {
+		root_item: crate::item::Item::new_folder(vec![
+			(
+				"user",
+				crate::item::Item::new_folder(vec![(
+					"www",
+					crate::item::Item::new_folder(vec![(
+						"index.html",
+						build_document(b"PRIVATE", "text/html"),
+					)]),
+				)]),
+			),
+			(
+				"public",
+				crate::item::Item::new_folder(vec![(
+					"user",
+					crate::item::Item::new_folder(vec![
+						(
+							"www",
+							crate::item::Item::new_folder(vec![
+								("index.html", build_document(b"<h1>HOME</h1>", "text/html")),
+								("style.css", build_document(b"h1{}", "text/css")),
+								(
+									"blog",
+									crate::item::Item::new_folder(vec![(
+										"index.html",
+										build_document(b"<h1>BLOG</h1>", "text/html"),
+									)]),
+								),
+							]),
+						),
+						("notes.txt", build_document(b"NOTES", "text/plain")),
+					]),
+				)]),
+			),
+		]),
+	}));
+}
+
+#[test]
+fn w4kf9tq2zm7hxv1dcs() {
+	let both = crate::http_server::SettingsWebAuthoring {
+		domain: Some(String::from("sites.example.com")),
+		path: Some(String::from("/sites/")),
+		cache_max_age_seconds: None,
+	};
+
+	let site = |username: &str, path: &str, add_trailing_slash: bool| {
+		Some(super::SiteRequest {
+			username: String::from(username),
+			path: String::from(path),
+			add_trailing_slash,
+		})
+	};
+
+	assert_eq!(
+		super::site_request(&both, "user.sites.example.com", "/"),
+		site("user", "", false)
+	);
+	assert_eq!(
+		super::site_request(&both, "User.Sites.Example.com:8080", "/blog/post.html"),
+		site("user", "blog/post.html", false)
+	);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/sites/user/my%20page.html"),
+		site("user", "my page.html", false)
+	);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/sites/user"),
+		site("user", "", true)
+	);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/sites/user/"),
+		site("user", "", false)
+	);
+
+	assert_eq!(super::site_request(&both, "sites.example.com", "/"), None);
+	assert_eq!(
+		super::site_request(&both, "a.user.sites.example.com", "/"),
+		None
+	);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/storage/public/user/www/"),
+		None
+	);
+	assert_eq!(super::site_request(&both, "example.com", "/sites/"), None);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/sitesuser/"),
+		None
+	);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/sites/user/../../user/www/"),
+		None
+	);
+	assert_eq!(
+		super::site_request(&both, "example.com", "/sites/user/%2E%2E/notes.txt"),
+		None
+	);
+	assert_eq!(
+		super::site_request(&both, "user.sites.example.com", "/..%5C..%5Cnotes.txt"),
+		None
+	);
+
+	assert_eq!(
+		super::site_request(
+			&crate::http_server::SettingsWebAuthoring::default(),
+			"user.sites.example.com",
+			"/sites/user/"
+		),
+		None
+	);
+}
+
+#[actix_rt::test]
+async fn b7nq3xk0vr5mwz2hft() {
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(build_settings(Some(
+				crate::http_server::SettingsWebAuthoring {
+					domain: Some(String::from("sites.example.com")),
+					path: Some(String::from("sites")),
+					cache_max_age_seconds: Some(60),
+				},
+			))))
+			.app_data(actix_web::web::Data::new(build_database()))
+			.wrap(super::WebAuthoring)
+			.default_service(actix_web::web::to(|| async {
+				actix_web::HttpResponse::ImATeapot().finish()
+			})),
+	)
+	.await;
+
+	let tests = vec![
+		("user.sites.example.com", "/", StatusCode::OK, None),
+		("example.com", "/sites/user/", StatusCode::OK, None),
+		("example.com", "/sites/user/style.css", StatusCode::OK, None),
+		("example.com", "/sites/user/blog/", StatusCode::OK, None),
+		(
+			"example.com",
+			"/sites/user",
+			StatusCode::MOVED_PERMANENTLY,
+			Some("/sites/user/"),
+		),
+		(
+			"user.sites.example.com",
+			"/blog",
+			StatusCode::MOVED_PERMANENTLY,
+			Some("/blog/"),
+		),
+		(
+			"example.com",
+			"/sites/user/missing.html",
+			StatusCode::NOT_FOUND,
+			None,
+		),
+		("example.com", "/sites/other/", StatusCode::NOT_FOUND, None),
+		(
+			"example.com",
+			"/sites/user/../notes.txt",
+			StatusCode::IM_A_TEAPOT,
+			None,
+		),
+		(
+			"example.com",
+			"/storage/user/www/index.html",
+			StatusCode::IM_A_TEAPOT,
+			None,
+		),
+		("example.com", "/", StatusCode::IM_A_TEAPOT, None),
+	];
+
+	for (host, uri, expected_status, expected_location) in tests {
+		println!("GET {}{}", host, uri);
+
+		let request = actix_web::test::TestRequest::get()
+			.uri(uri)
+			.insert_header((actix_web::http::header::HOST, host))
+			.to_request();
+		let response = actix_web::test::call_service(&app, request).await;
+
+		assert_eq!(response.status(), expected_status);
+		assert_eq!(
+			response
+				.headers()
+				.get(actix_web::http::header::LOCATION)
+				.map(|value| value.to_str().unwrap()),
+			expected_location
+		);
+	}
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/sites/user/style.css")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::OK);
+	let headers = response.headers();
+	assert_eq!(
+		headers.get(actix_web::http::header::CONTENT_TYPE).unwrap(),
+		"text/css"
+	);
+	assert_eq!(
+		headers.get(actix_web::http::header::CACHE_CONTROL).unwrap(),
+		"public, max-age=60"
+	);
+	assert!(headers
+		.get(actix_web::http::header::LAST_MODIFIED)
+		.is_some());
+	let etag = headers
+		.get(actix_web::http::header::ETAG)
+		.unwrap()
+		.to_str()
+		.unwrap()
+		.to_string();
+	assert_eq!(actix_web::test::read_body(response).await, "h1{}");
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/sites/user/style.css")
+		.insert_header(actix_web::http::header::IfNoneMatch::Items(vec![
+			actix_web::http::header::EntityTag::new_strong(etag),
+		]))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+	let request = actix_web::test::TestRequest::default()
+		.method(actix_web::http::Method::HEAD)
+		.uri("/sites/user/")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::OK);
+	assert!(actix_web::test::read_body(response).await.is_empty());
+
+	let request = actix_web::test::TestRequest::put()
+		.uri("/sites/user/style.css")
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+}
+
+#[actix_rt::test]
+async fn p2hc6wz9tm4kqx8vrn() {
+	let app = actix_web::test::init_service(
+		actix_web::App::new()
+			.app_data(actix_web::web::Data::new(build_settings(None)))
+			.app_data(actix_web::web::Data::new(build_database()))
+			.wrap(super::WebAuthoring)
+			.default_service(actix_web::web::to(|| async {
+				actix_web::HttpResponse::ImATeapot().finish()
+			})),
+	)
+	.await;
+
+	let request = actix_web::test::TestRequest::get()
+		.uri("/sites/user/")
+		.insert_header((actix_web::http::header::HOST, "user.sites.example.com"))
+		.to_request();
+	let response = actix_web::test::call_service(&app, request).await;
+	assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+}
@@ -1,6 +1,8 @@
 mod api;
 mod init;
+mod scrub;
 mod tokens;
+mod trash;
 mod users;
 mod utils;
 mod webfinger;
@@ -14,7 +16,9 @@ pub mod middlewares;
 
 pub use api::*;
 pub use init::*;
+pub use scrub::*;
 pub use tokens::*;
+pub use trash::*;
 pub use users::Users;
 pub use webfinger::webfinger_handle;
 
@@ -36,13 +40,15 @@ pub struct DbEvent {
 #[derive(serde::Serialize, serde::Deserialize)]
 pub enum DbEventMethod {
 	Create,
+	/// Former events, not upgraded by [`upgrade_workspace`], name it `Put`.
+	#[serde(alias = "Put")]
 	Update,
 	Delete,
 }
 
 pub fn configure_server(
 	settings: Arc<Mutex<crate::http_server::Settings>>,
-	database: Arc<Mutex<crate::database::Database>>,
+	database: crate::database::Database,
 	access_tokens: Arc<Mutex<Vec<crate::http_server::AccessBearer>>>,
 	oauth_form_tokens: Arc<Mutex<Vec<crate::http_server::middlewares::OauthFormToken>>>,
 	users: Arc<Mutex<crate::http_server::Users>>,
@@ -73,12 +79,22 @@ pub fn configure_server(
 			.service(get_favicon)
 			.service(get_oauth)
 			.service(post_oauth)
+			.service(get_connect)
+			.service(post_connect)
 			.service(webfinger_handle)
 			.service(get_item)
 			.service(head_item)
 			.service(options_item)
 			.service(put_item)
 			.service(delete_item)
+			.service(move_item)
+			.service(copy_item)
+			.service(post_batch)
+			.service(get_trash)
+			.service(restore_trashed)
+			.service(get_archive)
+			.service(put_archive)
+			.service(get_scrub)
 			.service(remotestoragesvg)
 			.service(server_events)
 			.service(index);
@@ -89,17 +105,14 @@ pub fn configure_server(
 pub async fn options_favicon() -> impl actix_web::Responder {
 	let mut res = actix_web::HttpResponse::Ok();
 	res.insert_header((actix_web::http::header::ALLOW, "OPTIONS, GET"));
-	res.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"));
 
 	return res;
 }
 
 #[actix_web::get("/favicon.ico")]
 pub async fn get_favicon() -> impl actix_web::Responder {
-	let mut res = actix_web::HttpResponse::Ok();
-	res.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"));
-
-	return res.body(actix_web::web::Bytes::from_static(crate::assets::ICON));
+	return actix_web::HttpResponse::Ok()
+		.body(actix_web::web::Bytes::from_static(crate::assets::ICON));
 }
 
 #[actix_web::get("/remotestorage.svg")]
@@ -126,11 +139,8 @@ pub async fn server_events(
 		file_content_stream.append(&mut file_content);
 		file_content_stream.push(b']');
 
-		let file_content_stream = String::from_utf8_lossy(&file_content_stream);
-		let file_content_stream =
-			file_content_stream.replace(r#""method":"Put""#, r#""method":"Update""#); // backward compatibility
-
-		match serde_json::from_str(&file_content_stream) {
+		// former formats are upgraded by `upgrade_workspace`, or read through serde aliases
+		match serde_json::from_slice(&file_content_stream) {
 			Ok(events) => events,
 			Err(err) => {
 				logger.lock().unwrap().push(
@@ -163,7 +173,7 @@ pub async fn server_events(
 		.headers()
 		.iter()
 		.find(|(name, _)| name.as_str().trim().to_lowercase() == "authorization")
-		.map(|(_, value)| value.to_str().unwrap())
+		.and_then(|(_, value)| value.to_str().ok())
 		.map(|value| match value.strip_prefix("Bearer ") {
 			Some(value) => String::from(value),
 			None => String::from("error"),
@@ -232,7 +242,6 @@ pub async fn server_events(
 	}
 
 	let mut res = actix_web::HttpResponse::Ok();
-	res.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"));
 	res.insert_header((actix_web::http::header::CONTENT_TYPE, "text/event-stream"));
 	res.insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"));
 
@@ -253,4 +262,6 @@ pub async fn index() -> impl actix_web::Responder {
 #[derive(Debug, Clone, Default)]
 pub struct ProgramState {
 	pub https_mode: bool,
+	/// The report of the last [`scrub_data`], if any.
+	pub last_scrub: Option<ScrubReport>,
 }
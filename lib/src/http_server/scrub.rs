@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex};
+
+/// The result of a [`scrub_data`], kept in [`ProgramState`][`crate::http_server::ProgramState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrubReport {
+	/// When the scrubbing ended.
+	pub date: time::OffsetDateTime,
+	/// The documents whose content does not match their checksum.
+	pub damaged: Vec<crate::item::ItemPath>,
+}
+
+/// Checks the content of all documents in the `data_path` of `settings` against their checksum,
+/// logs the damaged ones, and saves the report in `program_state`.
+///
+/// The data folder is scrubbed without blocking the database, so documents which look damaged
+/// are read again through `database`, in case they were written in the meantime.
+pub fn scrub_data(
+	settings: &crate::http_server::Settings,
+	database: &crate::database::Database,
+	program_state: &Mutex<crate::http_server::ProgramState>,
+	logger: &Mutex<charlie_buffalo::Logger>,
+) -> Result<ScrubReport, crate::database::DataSourceError> {
+	let storage = crate::database::sources::FolderStorage {
+		root_folder_path: std::path::PathBuf::from(&settings.data_path),
+	};
+
+	let mut damaged = vec![];
+	for inconsistency in storage.scrub()? {
+		if let crate::database::sources::folder::Inconsistency::CorruptedContent {
+			item_path, ..
+		} = inconsistency
+		{
+			if let Err(crate::database::DataSourceError::InternalError { error }) =
+				database.get(&item_path, &[], &[])
+			{
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("event"), String::from("scrub")),
+						(String::from("path"), item_path.to_string()),
+						(String::from("level"), String::from("ERROR")),
+					],
+					Some(&error),
+				);
+
+				damaged.push(item_path);
+			}
+		}
+	}
+
+	logger.lock().unwrap().push(
+		vec![
+			(String::from("event"), String::from("scrub")),
+			(
+				String::from("level"),
+				String::from(if damaged.is_empty() {
+					"INFO"
+				} else {
+					"WARNING"
+				}),
+			),
+		],
+		Some(&format!(
+			"data scrubbed, {} damaged documents found",
+			damaged.len()
+		)),
+	);
+
+	let report = ScrubReport {
+		date: time::OffsetDateTime::now_utc(),
+		damaged,
+	};
+	program_state.lock().unwrap().last_scrub = Some(report.clone());
+
+	return Ok(report);
+}
+
+/// Runs [`scrub_data`] in a new thread, then again after each `interval_hours` of the `scrub` of `settings`.
+///
+/// It does nothing if this setting is not set.
+pub fn spawn_scrubbing(
+	settings: Arc<Mutex<crate::http_server::Settings>>,
+	database: crate::database::Database,
+	program_state: Arc<Mutex<crate::http_server::ProgramState>>,
+	logger: Arc<Mutex<charlie_buffalo::Logger>>,
+) {
+	let interval_hours = match &settings.lock().unwrap().scrub {
+		Some(scrub_settings) => scrub_settings.interval_hours.max(1),
+		None => return,
+	};
+
+	logger.lock().unwrap().push(
+		vec![
+			(String::from("event"), String::from("setup")),
+			(String::from("module"), String::from("scrub")),
+			(String::from("level"), String::from("INFO")),
+		],
+		Some(&format!("data is scrubbed every {} hours", interval_hours)),
+	);
+
+	std::thread::spawn(move || loop {
+		let settings = settings.lock().unwrap().clone();
+
+		if let Err(error) = scrub_data(&settings, &database, &program_state, &logger) {
+			logger.lock().unwrap().push(
+				vec![
+					(String::from("event"), String::from("scrub")),
+					(String::from("level"), String::from("ERROR")),
+				],
+				Some(&format!("can not scrub data : {}", error)),
+			);
+		}
+
+		std::thread::sleep(std::time::Duration::from_secs(interval_hours * 60 * 60));
+	});
+}
@@ -0,0 +1,46 @@
+use std::sync::{Arc, Mutex};
+
+/// How often [`spawn_trash_purging`] purges the trash.
+const PURGE_INTERVAL_SECONDS: u64 = 60 * 60;
+
+/// Runs [`Database::purge_trash`][`crate::database::Database::purge_trash`] in a new thread, then again every hour,
+/// so expired documents do not stay on disk while nobody lists or fills the trash.
+///
+/// It does nothing if `database` has no trash.
+pub fn spawn_trash_purging(
+	database: crate::database::Database,
+	logger: Arc<Mutex<charlie_buffalo::Logger>>,
+) {
+	if !database.has_trash() {
+		return;
+	}
+
+	std::thread::spawn(move || loop {
+		match database.purge_trash() {
+			Ok(0) => {}
+			Ok(count) => {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("event"), String::from("trash")),
+						(String::from("level"), String::from("INFO")),
+					],
+					Some(&format!(
+						"{} expired documents purged from the trash",
+						count
+					)),
+				);
+			}
+			Err(error) => {
+				logger.lock().unwrap().push(
+					vec![
+						(String::from("event"), String::from("trash")),
+						(String::from("level"), String::from("ERROR")),
+					],
+					Some(&format!("can not purge the trash : {}", error)),
+				);
+			}
+		}
+
+		std::thread::sleep(std::time::Duration::from_secs(PURGE_INTERVAL_SECONDS));
+	});
+}
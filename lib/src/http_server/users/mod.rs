@@ -68,6 +68,13 @@ impl Users {
 		return Ok(());
 	}
 
+	pub fn has_right(&self, username: &str, right: &UserRight) -> bool {
+		return self
+			.list
+			.iter()
+			.any(|user| user.name == username && user.rights.contains(right));
+	}
+
 	pub fn add_right(&mut self, username: &str, right: UserRight) -> Result<(), String> {
 		match self.list.iter_mut().find(|user| user.name == username) {
 			Some(user) => {
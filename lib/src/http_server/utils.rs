@@ -77,7 +77,10 @@ pub fn build_server_address(
 #[test]
 fn pbw1cgzctiqe163() {
 	let settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
-	let state = super::ProgramState { https_mode: false };
+	let state = super::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -95,7 +98,10 @@ fn pbw1cgzctiqe163() {
 fn ykf0gcnr7z2ko4wtx8uub() {
 	let mut settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
 	settings.domain_suffix = Some(String::from("test"));
-	let state = super::ProgramState { https_mode: false };
+	let state = super::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -113,7 +119,10 @@ fn ykf0gcnr7z2ko4wtx8uub() {
 fn wxpy6tncuwbbavvxi() {
 	let mut settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
 	settings.domain_suffix = Some(String::from("test/"));
-	let state = super::ProgramState { https_mode: false };
+	let state = super::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -131,7 +140,10 @@ fn wxpy6tncuwbbavvxi() {
 fn fpfxwrixa1jz7t() {
 	let settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
 
-	let state = super::ProgramState { https_mode: true };
+	let state = super::ProgramState {
+		https_mode: true,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -150,7 +162,10 @@ fn xtgfpc3x1zcmb() {
 	let domain = String::from("example.com");
 	let mut settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
 	settings.domain = Some(domain.clone());
-	let state = super::ProgramState { https_mode: false };
+	let state = super::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -168,7 +183,10 @@ fn ekkvpuijzifxc() {
 	let domain = String::from("example.com");
 	let mut settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
 	settings.domain = Some(domain.clone());
-	let state = super::ProgramState { https_mode: true };
+	let state = super::ProgramState {
+		https_mode: true,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -190,7 +208,10 @@ fn bj8n5zhu2oaaed55561ygk() {
 	if let Some(https) = &mut settings.https {
 		https.port = 443;
 	}
-	let state = super::ProgramState { https_mode: false };
+	let state = super::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
@@ -207,10 +228,49 @@ fn d434yaaxfqcnd4j() {
 	if let Some(https) = &mut settings.https {
 		https.port = 443;
 	}
-	let state = super::ProgramState { https_mode: true };
+	let state = super::ProgramState {
+		https_mode: true,
+		last_scrub: None,
+	};
 
 	assert_eq!(
 		build_server_address(&settings, &state),
 		format!("{}://{}/{}", "https", domain, "")
 	);
 }
+
+#[test]
+fn m5vq8rk2zt0hxw4cnb() {
+	let mut settings = super::Settings::new(tempfile::tempdir().unwrap().into_path());
+	settings.domain = Some(String::from("example.com"));
+	settings.domain_aliases = Some(vec![String::from("www.example.com")]);
+	settings.virtual_hosts = Some(vec![super::SettingsVirtualHost {
+		domain: String::from("storage.example.org"),
+		domain_suffix: Some(String::from("rs")),
+		users: Some(vec![String::from("alice")]),
+	}]);
+	settings.port = 80;
+	settings.force_https = Some(false);
+	let state = super::ProgramState {
+		https_mode: false,
+		last_scrub: None,
+	};
+
+	assert_eq!(
+		build_server_address(&settings.for_host("Storage.example.org:80"), &state),
+		"http://storage.example.org/rs/"
+	);
+	assert_eq!(
+		build_server_address(&settings.for_host("www.example.com"), &state),
+		"http://www.example.com/"
+	);
+	assert_eq!(
+		build_server_address(&settings.for_host("attacker.example.net"), &state),
+		"http://example.com/"
+	);
+
+	assert!(settings.has_user("storage.example.org", "alice"));
+	assert!(!settings.has_user("storage.example.org", "bob"));
+	assert!(!settings.has_user("example.com", "alice"));
+	assert!(settings.has_user("www.example.com", "bob"));
+}
@@ -1,8 +1,8 @@
 #[actix_web::get("/.well-known/webfinger")]
 pub async fn webfinger_handle(
-	request: actix_web::HttpRequest,
 	query: actix_web::web::Query<WebfingerQuery>,
 	settings: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<super::Settings>>>,
+	users: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<super::Users>>>,
 	program_state: actix_web::web::Data<std::sync::Arc<std::sync::Mutex<super::ProgramState>>>,
 ) -> impl actix_web::Responder {
 	let default_body = format!(
@@ -23,45 +23,70 @@ pub async fn webfinger_handle(
 			let items = resource.split('@').collect::<Vec<&str>>();
 			if items.len() == 2 {
 				let user = items.get(0).unwrap();
-				let _domain = items.get(1).unwrap();
-				// todo : check if user exists ?
-				// todo : check domain & host header ?
+				let domain = items.get(1).unwrap();
 
+				let user_exists = users
+					.lock()
+					.unwrap()
+					.get_usernames()
+					.into_iter()
+					.any(|name| name == user);
+				let user_is_hidden = settings
+					.lock()
+					.unwrap()
+					.webfinger_hidden_users
+					.iter()
+					.flatten()
+					.any(|name| name == user);
+
+				if !user_exists
+					|| user_is_hidden
+					|| !settings.lock().unwrap().is_own_domain(domain)
+					|| !settings.lock().unwrap().has_user(domain, user)
+				{
+					return actix_web::HttpResponse::NotFound().finish();
+				}
+
+				// links are for the domain of the account, whatever the host this request was sent to
 				let server_addr = super::build_server_address(
-					&settings.lock().unwrap(),
+					&settings.lock().unwrap().for_host(domain),
 					&program_state.lock().unwrap(),
 				);
+				let query_access_token = if settings
+					.lock()
+					.unwrap()
+					.query_access_token
+					.unwrap_or_default()
+				{
+					r#""true""#
+				} else {
+					"null"
+				};
+				let web_authoring = match settings
+					.lock()
+					.unwrap()
+					.web_authoring
+					.as_ref()
+					.and_then(|web_authoring| web_authoring.site_of(user, &server_addr))
+				{
+					Some(site) => serde_json::Value::from(site).to_string(),
+					None => String::from("null"),
+				};
 
-				// TODO : check security issue about this ?
-				let all_origins = actix_web::http::header::HeaderValue::from_bytes(b"*").unwrap();
-				let origin = request
-					.headers()
-					.get(actix_web::http::header::ORIGIN)
-					.unwrap_or(&all_origins)
-					.to_str()
-					.unwrap();
-
-				let mut response = actix_web::HttpResponse::Ok();
-				response
-					.insert_header((actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin));
-
-				if origin != "*" {
-					response.insert_header((actix_web::http::header::VARY, "Origin"));
-				}
-
-				response
+				actix_web::HttpResponse::Ok()
 					.content_type("application/ld+json")
 					.body(format!(
-						r#"{{"links":[{{"href":"{}storage/{}","rel":"{}","properties":{{"{}":"{}","{}":"{}oauth/{}","{}":{},"{}":{},"{}":{}}}}}]}}"#,
+						r#"{{"links":[{{"href":"{}storage/{}","rel":"{}","properties":{{"{}":"{}","{}":"{}oauth/{}","{}":{},"{}":{},"{}":{},"{}":{}}}}}]}}"#,
 						server_addr, user,
 						"http://tools.ietf.org/id/draft-dejong-remotestorage",
 						"http://remotestorage.io/spec/version",
 						"draft-dejong-remotestorage-19",
 						"http://tools.ietf.org/html/rfc6749#section-4.2",
 						server_addr, user,
-						"http://tools.ietf.org/html/rfc6750#section-2.3", "null",
+						"http://tools.ietf.org/html/rfc6750#section-2.3", query_access_token,
 						"http://tools.ietf.org/html/rfc7233", "null",
-						"http://remotestorage.io/spec/web-authoring", "null"
+						"http://remotestorage.io/spec/web-authoring", web_authoring,
+						"http://remotestorage.io/spec/paginated-listings", r#""true""#
 					))
 			} else {
 				actix_web::HttpResponse::Ok()
@@ -84,17 +109,219 @@ pub async fn webfinger_handle(
 		reject a request by the connecting application to obtain a bearer
 		token for a certain list of access scopes.
 	*/
-	/*
-	TODO :
-		Non-breaking examples that have been proposed so far, include a
-		"http://tools.ietf.org/html/rfc6750#section-2.3" property, set to
-		the string value "true" if the server supports passing the bearer
-		token in the URI query parameter as per section 2.3 of [BEARER],
-		instead of in the request header.
-	*/
 }
 
 #[derive(serde::Deserialize)]
 pub struct WebfingerQuery {
 	resource: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+	fn build_users() -> std::sync::Arc<std::sync::Mutex<crate::http_server::Users>> {
+		let mut users = crate::http_server::Users::new();
+		users.insert("user", &mut String::from("password")).unwrap();
+		users
+			.insert("hidden", &mut String::from("password"))
+			.unwrap();
+
+		return std::sync::Arc::new(std::sync::Mutex::new(users));
+	}
+
+	#[actix_rt::test]
+	async fn c2xv9kq7wm4hzt0bnr() {
+		for (query_access_token, expected) in [(false, "null"), (true, r#""true""#)] {
+			let mut settings =
+				crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+			settings.domain = Some(String::from("localhost"));
+			settings.query_access_token = Some(query_access_token);
+
+			let app = actix_web::test::init_service(
+				actix_web::App::new()
+					.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+						std::sync::Mutex::new(settings),
+					)))
+					.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+						std::sync::Mutex::new(crate::http_server::ProgramState {
+							https_mode: false,
+							last_scrub: None,
+						}),
+					)))
+					.app_data(actix_web::web::Data::new(build_users()))
+					.service(super::webfinger_handle),
+			)
+			.await;
+
+			let request = actix_web::test::TestRequest::get()
+				.uri("/.well-known/webfinger?resource=acct:user@localhost")
+				.to_request();
+			let body: serde_json::Value =
+				actix_web::test::call_and_read_body_json(&app, request).await;
+
+			assert_eq!(
+				body["links"][0]["properties"]["http://tools.ietf.org/html/rfc6750#section-2.3"],
+				serde_json::from_str::<serde_json::Value>(expected).unwrap()
+			);
+			assert_eq!(
+				body["links"][0]["properties"]["http://remotestorage.io/spec/paginated-listings"],
+				"true"
+			);
+		}
+	}
+
+	#[actix_rt::test]
+	async fn h5rm8zq1xw3kcv7dtn() {
+		for (web_authoring, expected) in [
+			(None, serde_json::Value::Null),
+			(
+				Some(crate::http_server::SettingsWebAuthoring {
+					domain: Some(String::from("sites.example.com")),
+					..Default::default()
+				}),
+				serde_json::Value::from("user.sites.example.com"),
+			),
+			(
+				Some(crate::http_server::SettingsWebAuthoring {
+					path: Some(String::from("/sites/")),
+					..Default::default()
+				}),
+				serde_json::Value::from("http://localhost/sites/user/"),
+			),
+		] {
+			let mut settings =
+				crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+			settings.domain = Some(String::from("localhost"));
+			settings.port = 80;
+			settings.force_https = Some(false);
+			settings.web_authoring = web_authoring;
+
+			let app = actix_web::test::init_service(
+				actix_web::App::new()
+					.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+						std::sync::Mutex::new(settings),
+					)))
+					.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+						std::sync::Mutex::new(crate::http_server::ProgramState {
+							https_mode: false,
+							last_scrub: None,
+						}),
+					)))
+					.app_data(actix_web::web::Data::new(build_users()))
+					.service(super::webfinger_handle),
+			)
+			.await;
+
+			let request = actix_web::test::TestRequest::get()
+				.uri("/.well-known/webfinger?resource=acct:user@localhost")
+				.to_request();
+			let body: serde_json::Value =
+				actix_web::test::call_and_read_body_json(&app, request).await;
+
+			assert_eq!(
+				body["links"][0]["properties"]["http://remotestorage.io/spec/web-authoring"],
+				expected
+			);
+		}
+	}
+
+	#[actix_rt::test]
+	async fn z3pt8wn5qk1hxm6vcy() {
+		let mut settings =
+			crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+		settings.domain = Some(String::from("example.com"));
+		settings.domain_aliases = Some(vec![String::from("www.example.com")]);
+		settings.webfinger_hidden_users = Some(vec![String::from("hidden")]);
+
+		let app = actix_web::test::init_service(
+			actix_web::App::new()
+				.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+					std::sync::Mutex::new(settings),
+				)))
+				.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+					std::sync::Mutex::new(crate::http_server::ProgramState {
+						https_mode: false,
+						last_scrub: None,
+					}),
+				)))
+				.app_data(actix_web::web::Data::new(build_users()))
+				.service(super::webfinger_handle),
+		)
+		.await;
+
+		let tests = vec![
+			("acct:user@example.com", actix_web::http::StatusCode::OK),
+			(
+				"acct:user@Example.com:8080",
+				actix_web::http::StatusCode::OK,
+			),
+			("acct:user@www.example.com", actix_web::http::StatusCode::OK),
+			(
+				"acct:other@example.com",
+				actix_web::http::StatusCode::NOT_FOUND,
+			),
+			(
+				"acct:user@example.org",
+				actix_web::http::StatusCode::NOT_FOUND,
+			),
+			(
+				"acct:hidden@example.com",
+				actix_web::http::StatusCode::NOT_FOUND,
+			),
+		];
+
+		for (resource, expected_status) in tests {
+			println!("{}", resource);
+
+			let request = actix_web::test::TestRequest::get()
+				.uri(&format!(
+					"/.well-known/webfinger?resource={}",
+					pct_str::PctString::encode(resource.chars(), pct_str::URIReserved)
+				))
+				.to_request();
+			let response = actix_web::test::call_service(&app, request).await;
+
+			assert_eq!(response.status(), expected_status);
+		}
+	}
+
+	#[actix_rt::test]
+	async fn n6kw2vx9qt4mzc8hrp() {
+		let mut settings =
+			crate::http_server::Settings::new(tempfile::tempdir().unwrap().into_path());
+		settings.domain = Some(String::from("example.com"));
+		settings.domain_aliases = Some(vec![String::from("www.example.com")]);
+		settings.port = 80;
+		settings.force_https = Some(false);
+
+		let app = actix_web::test::init_service(
+			actix_web::App::new()
+				.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+					std::sync::Mutex::new(settings),
+				)))
+				.app_data(actix_web::web::Data::new(std::sync::Arc::new(
+					std::sync::Mutex::new(crate::http_server::ProgramState {
+						https_mode: false,
+						last_scrub: None,
+					}),
+				)))
+				.app_data(actix_web::web::Data::new(build_users()))
+				.service(super::webfinger_handle),
+		)
+		.await;
+
+		let request = actix_web::test::TestRequest::get()
+			.uri("/.well-known/webfinger?resource=acct:user@www.example.com")
+			.insert_header((actix_web::http::header::HOST, "attacker.example.org"))
+			.to_request();
+		let body: serde_json::Value = actix_web::test::call_and_read_body_json(&app, request).await;
+
+		assert_eq!(
+			body["links"][0]["href"],
+			"http://www.example.com/storage/user"
+		);
+		assert_eq!(
+			body["links"][0]["properties"]["http://tools.ietf.org/html/rfc6749#section-4.2"],
+			"http://www.example.com/oauth/user"
+		);
+	}
+}
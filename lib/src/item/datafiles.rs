@@ -4,14 +4,42 @@ pub struct DataDocument {
 	pub datastruct_version: String,
 	pub etag: crate::item::Etag,
 	pub content_type: crate::item::ContentType,
+	/// The length of the content, in bytes.
+	///
+	/// It is [`None`][`Option::None`] in metadata written by older versions.
+	#[serde(default)]
+	pub content_length: Option<u64>,
+	/// The SHA-512 of the content, in hexadecimal, to detect its corruption.
+	///
+	/// It is [`None`][`Option::None`] in metadata written by older versions, so their content can not be verified.
+	#[serde(default)]
+	pub checksum: Option<String>,
 	pub last_modified: Option<time::OffsetDateTime>,
 }
+impl DataDocument {
+	/// The [`checksum`][`DataDocument::checksum`] of `content`.
+	pub fn checksum_of(content: &[u8]) -> String {
+		return hmac_sha512::Hash::hash(content)
+			.iter()
+			.map(|byte| format!("{:02x}", byte))
+			.collect();
+	}
+	/// Whether `content` is the one described by this metadata, or if it can not be known.
+	pub fn is_intact(&self, content: &[u8]) -> bool {
+		return match &self.checksum {
+			Some(checksum) => checksum == &Self::checksum_of(content),
+			None => true,
+		};
+	}
+}
 impl Default for DataDocument {
 	fn default() -> Self {
 		Self {
 			datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 			etag: crate::item::Etag::new(),
 			content_type: crate::item::ContentType::from("application/octet-stream"),
+			content_length: None,
+			checksum: None,
 			last_modified: Some(time::OffsetDateTime::now_utc()),
 		}
 	}
@@ -23,13 +51,16 @@ impl std::convert::TryFrom<crate::item::Item> for DataDocument {
 		match input {
 			crate::item::Item::Document {
 				etag,
+				content,
 				content_type,
+				content_length,
 				last_modified,
-				..
 			} => Ok(Self {
 				datastruct_version: String::from(env!("CARGO_PKG_VERSION")),
 				etag,
 				content_type,
+				content_length: Some(content_length),
+				checksum: content.as_deref().map(Self::checksum_of),
 				last_modified,
 			}),
 			_ => Err(String::from("input should be Item::Document and it is not")),
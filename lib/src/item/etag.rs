@@ -53,4 +53,70 @@ impl Etag {
 	pub fn to_uppercase(&self) -> Self {
 		Self(self.0.to_uppercase())
 	}
+	/// Tells if this is a weak validator, which starts with `W/`.
+	pub fn is_weak(&self) -> bool {
+		self.0.trim().starts_with("W/")
+	}
+	/// Tells if this is the `*` wildcard of `If-Match` and `If-None-Match` headers.
+	pub fn is_wildcard(&self) -> bool {
+		self.0.trim() == "*"
+	}
+	/// The value, without the `W/` prefix and the surrounding quotes.
+	pub fn opaque_tag(&self) -> &str {
+		let tag = self.0.trim();
+		let tag = tag.strip_prefix("W/").unwrap_or(tag);
+
+		tag.strip_prefix('"')
+			.and_then(|tag| tag.strip_suffix('"'))
+			.unwrap_or(tag)
+	}
+	/// The strong comparison of [RFC 7232, section 2.3.2](https://www.rfc-editor.org/rfc/rfc7232#section-2.3.2) :
+	/// both should not be weak, and their opaque tags should be the same.
+	pub fn strong_eq(&self, other: &Self) -> bool {
+		!self.is_weak() && !other.is_weak() && self.opaque_tag() == other.opaque_tag()
+	}
+	/// The weak comparison of [RFC 7232, section 2.3.2](https://www.rfc-editor.org/rfc/rfc7232#section-2.3.2) :
+	/// their opaque tags should be the same, whether they are weak or not.
+	pub fn weak_eq(&self, other: &Self) -> bool {
+		self.opaque_tag() == other.opaque_tag()
+	}
+	/// Derives an ETag from the binary content and the content type of a [`Document`][`crate::item::Item::Document`].
+	///
+	/// The same content and content type always give the same ETag, whatever the server or the client is,
+	/// and changing only the content type gives another ETag.
+	pub fn from_content(content: &[u8], content_type: &crate::item::ContentType) -> Self {
+		let mut hasher = hmac_sha512::Hash::new();
+		hasher.update(content_type.to_string().as_bytes());
+		hasher.update([0]);
+		hasher.update(content);
+
+		Self::from_hash(hasher.finalize())
+	}
+	/// Derives an ETag of a [`Folder`][`crate::item::Item::Folder`] from the names and ETags of its children,
+	/// like in a Merkle tree.
+	///
+	/// Names of folders should end with `/`, like in [`ItemPathPart`][`crate::item::ItemPathPart`].
+	/// The order of `children` does not matter.
+	pub fn from_children<'a>(children: impl IntoIterator<Item = (&'a str, &'a Self)>) -> Self {
+		let mut children: Vec<(&str, &Self)> = children.into_iter().collect();
+		children.sort_by_key(|(name, _)| *name);
+
+		let mut hasher = hmac_sha512::Hash::new();
+		for (name, etag) in children {
+			hasher.update(name.as_bytes());
+			hasher.update([0]);
+			hasher.update(etag.0.as_bytes());
+			hasher.update([b'\n']);
+		}
+
+		Self::from_hash(hasher.finalize())
+	}
+	fn from_hash(hash: [u8; 64]) -> Self {
+		Self(
+			hash.iter()
+				.take(32)
+				.map(|byte| format!("{:02x}", byte))
+				.collect(),
+		)
+	}
 }
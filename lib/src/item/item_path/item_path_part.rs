@@ -1,7 +1,7 @@
 /// It's the folder name, between an `/` and another `/`, or the filename.
 ///
 /// See also [`ItemPath`][`crate::item::ItemPath`].
-#[derive(PartialEq, Eq, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub enum ItemPathPart {
 	Folder(String),
 	Document(String),
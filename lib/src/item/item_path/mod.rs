@@ -8,7 +8,7 @@ pub use item_path_part::ItemPathPart;
 /// Used to describe path of an [`Item`][`crate::item::Item`] in database.
 ///
 /// It is a vector of [`ItemPathPart`][`crate::item::ItemPathPart`].
-#[derive(PartialEq, Eq, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub struct ItemPath(Vec<ItemPathPart>);
 
 impl ItemPath {
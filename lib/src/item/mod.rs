@@ -39,6 +39,10 @@ pub enum Item {
 		/// It can be [`None`][`Option::None`] if we don't need to fetch its content, for performances purposes.
 		content: Option<Vec<u8>>,
 		content_type: crate::item::ContentType,
+		/// The length of the binary content, in bytes, even if `content` is [`None`][`Option::None`].
+		///
+		/// It is stored in metadata, so folders can be listed without reading their documents.
+		content_length: u64,
 		last_modified: Option<time::OffsetDateTime>,
 	},
 }
@@ -62,6 +66,7 @@ impl Item {
 			etag: crate::item::Etag::new(),
 			content: Some(content.to_vec()),
 			content_type: crate::item::ContentType::from(content_type),
+			content_length: content.len() as u64,
 			last_modified: Some(time::OffsetDateTime::now_utc()),
 		};
 	}
@@ -76,11 +81,13 @@ impl Item {
 			Self::Document {
 				etag,
 				content_type,
+				content_length,
 				last_modified,
 				..
 			} => Self::Document {
 				etag: etag.clone(),
 				content_type: content_type.clone(),
+				content_length: *content_length,
 				last_modified: *last_modified,
 				content: None,
 			},
@@ -111,6 +118,58 @@ impl Item {
 		};
 	}
 
+	/// Replaces the [`Etag`][`crate::item::Etag`] of this [`Item`][`crate::item::Item`], and of
+	/// all its children, by ones derived from their content.
+	///
+	/// See [`Etag::from_content`][`crate::item::Etag::from_content`] and
+	/// [`Etag::from_children`][`crate::item::Etag::from_children`].
+	///
+	/// Items with `None` as `content` keep their current ETag.
+	pub fn rehash_etags(&mut self) {
+		match self {
+			Self::Folder {
+				etag,
+				content: Some(content),
+			} => {
+				for child in content.values_mut() {
+					child.rehash_etags();
+				}
+
+				*etag = Self::children_etag(content);
+			}
+			Self::Document {
+				etag,
+				content: Some(content),
+				content_type,
+				..
+			} => {
+				*etag = crate::item::Etag::from_content(content, content_type);
+			}
+			_ => {}
+		}
+	}
+
+	/// Derives the [`Etag`][`crate::item::Etag`] of a [`Folder`][`crate::item::Item::Folder`]
+	/// from the current ETags of its `content`.
+	pub fn children_etag(
+		content: &std::collections::HashMap<String, Box<crate::item::Item>>,
+	) -> crate::item::Etag {
+		let names: Vec<(String, &crate::item::Etag)> = content
+			.iter()
+			.map(|(name, child)| {
+				if child.is_folder() {
+					(format!("{}/", name), child.get_etag())
+				} else {
+					(name.clone(), child.get_etag())
+				}
+			})
+			.collect();
+
+		return crate::item::Etag::from_children(
+			names.iter().map(|(name, etag)| (name.as_str(), *etag)),
+		);
+	}
+
 	/// If this is an [`Document`][`crate::item::Item::Document`] and with
 	/// an [`Some`][`Option::Some`] as `content`, should returns the
 	/// binary content. Otherwise, returns [`None`][`Option::None`].
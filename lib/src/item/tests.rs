@@ -72,3 +72,69 @@ fn j5bmhdxhlgkhdk82rjio3ej6() {
 		None
 	);
 }
+
+#[test]
+fn r8wq3hzv2n5kxt0cbe1m() {
+	let build = |content: &[u8]| {
+		crate::item::Item::new_folder(vec![
+			(
+				"A",
+				crate::item::Item::new_folder(vec![(
+					"AA",
+					crate::item::Item::new_doc(content, "text/plain"),
+				)]),
+			),
+			("B", crate::item::Item::new_doc(b"B", "text/plain")),
+		])
+	};
+
+	let mut first = build(b"AA");
+	let mut second = build(b"AA");
+	let mut third = build(b"AB");
+	assert_ne!(first.get_etag(), second.get_etag());
+
+	first.rehash_etags();
+	second.rehash_etags();
+	third.rehash_etags();
+
+	for path in ["", "A/", "A/AA", "B"] {
+		let path = crate::item::ItemPath::from(path);
+		assert_eq!(
+			first.get_child(&path).unwrap().get_etag(),
+			second.get_child(&path).unwrap().get_etag()
+		);
+	}
+
+	assert_eq!(
+		first
+			.get_child(&crate::item::ItemPath::from("B"))
+			.unwrap()
+			.get_etag(),
+		third
+			.get_child(&crate::item::ItemPath::from("B"))
+			.unwrap()
+			.get_etag()
+	);
+	for path in ["", "A/", "A/AA"] {
+		let path = crate::item::ItemPath::from(path);
+		assert_ne!(
+			first.get_child(&path).unwrap().get_etag(),
+			third.get_child(&path).unwrap().get_etag()
+		);
+	}
+}
+
+#[test]
+fn v3kz8mq1tx6wnc4hrj() {
+	let mut plain = crate::item::Item::new_doc(b"AA", "text/plain");
+	let mut html = crate::item::Item::new_doc(b"AA", "text/html");
+
+	plain.rehash_etags();
+	html.rehash_etags();
+
+	assert_ne!(plain.get_etag(), html.get_etag());
+	assert_eq!(
+		plain.get_etag(),
+		&crate::item::Etag::from_content(b"AA", &crate::item::ContentType::from("text/plain"))
+	);
+}
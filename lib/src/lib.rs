@@ -5,7 +5,7 @@ pub mod scope;
 
 pub mod item;
 
-#[cfg(feature = "client")]
+#[cfg(any(feature = "client", feature = "client_native"))]
 pub mod client;
 
 #[cfg(feature = "server")]